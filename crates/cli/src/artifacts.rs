@@ -33,6 +33,14 @@ pub(crate) struct TestResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) cpu_state: Option<labwired_core::snapshot::CpuSnapshot>,
     pub(crate) firmware_hash: String,
+    /// Stable firmware identity: the ELF's GNU build-id (hex) when it has a
+    /// `.note.gnu.build-id`, otherwise the full-file `firmware_hash`.
+    #[serde(default)]
+    pub(crate) firmware_id: String,
+    /// What `firmware_id` holds: `"gnu_build_id"` or `"sha256"`. Empty when
+    /// the firmware could not be read.
+    #[serde(default)]
+    pub(crate) firmware_id_kind: String,
     pub(crate) config: TestConfig,
     /// Universal inspect block: final-state decoded register + artifact
     /// metadata for every peripheral (summary mode — framebuffer bytes omitted,
@@ -55,6 +63,18 @@ pub(crate) struct TestResult {
     /// prove-blink `gpio_edges`/`gpio_period`/`gpio_duty` clauses.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) logic_edges: Option<labwired_core::logic_capture::LogicEdgesResult>,
+    /// PC, offending address and kind of the CPU fault that stopped the run.
+    /// Absent when the run did not end on a fault.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) fault: Option<labwired_core::FaultInfo>,
+    /// Estimated energy the run spent, in joules (`Machine::energy_consumption`
+    /// under the default energy model). A rough figure for comparing builds;
+    /// absent when no machine ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) energy_joules: Option<f64>,
+    /// Set by `--dry-run`: the machine was loaded and reset but never stepped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) dry_run: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -62,6 +82,10 @@ pub(crate) struct StopReasonDetails {
     pub(crate) triggered_stop_condition: StopReason,
     pub(crate) triggered_limit: Option<NamedU64>,
     pub(crate) observed: Option<NamedU64>,
+    /// Instruction addresses of the short PC cycle that tripped
+    /// `loop_detect_repeats`; empty (and omitted) for every other stop.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) loop_addresses: Vec<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -74,6 +98,11 @@ pub(crate) struct NamedU64 {
 pub(crate) struct AssertionResult {
     pub(crate) assertion: TestAssertion,
     pub(crate) passed: bool,
+    /// Wall time spent evaluating this assertion. Reported as the JUnit
+    /// testcase `time`; kept out of `result.json` so the v1.0 contract and
+    /// its determinism digests are unchanged.
+    #[serde(skip)]
+    pub(crate) elapsed: std::time::Duration,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -180,6 +209,8 @@ pub(crate) enum Snapshot {
         limits: TestLimits,
         firmware_hash: String,
         config: TestConfig,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fault: Option<labwired_core::FaultInfo>,
     },
     ConfigError {
         message: String,
@@ -219,6 +250,16 @@ pub(crate) enum Snapshot {
     },
 }
 
+/// `(firmware_id, firmware_id_kind)` for `result.json`: the GNU build-id
+/// when the ELF carries one, else the full-file hash it was given.
+pub(crate) fn firmware_identity(firmware_bytes: &[u8], firmware_hash: &str) -> (String, String) {
+    match labwired_loader::gnu_build_id(firmware_bytes) {
+        Some(id) => (id, "gnu_build_id".to_string()),
+        None if firmware_hash.is_empty() => (String::new(), String::new()),
+        None => (firmware_hash.to_string(), "sha256".to_string()),
+    }
+}
+
 // snapshot_cortexm_cpu removed, use cpu.snapshot() directly
 
 pub(crate) struct InteractiveSnapshotInputs<'a> {
@@ -324,8 +324,8 @@ fn validate_chip(path: &PathBuf) -> ExitCode {
     }
 
     // 2. Memory Region Validation
-    let flash_size = labwired_config::parse_size(&chip.flash.size).unwrap_or(0);
-    let ram_size = labwired_config::parse_size(&chip.ram.size).unwrap_or(0);
+    let flash_size = chip.flash.size.bytes();
+    let ram_size = chip.ram.size.bytes();
 
     if flash_size == 0 {
         result.add_error(
@@ -0,0 +1,168 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! `labwired bench`: run a firmware for a fixed step budget or wall time and
+//! print one JSON performance record for regression tracking:
+//!
+//! ```text
+//! labwired bench --firmware app.elf --system system.yaml --duration-ms 5000 > bench.json
+//! ```
+
+use clap::Args;
+use labwired_core::bus::SystemBus;
+use labwired_core::memory::ProgramImage;
+use labwired_core::metrics::PerformanceMetrics;
+use labwired_core::{Cpu, DebugControl, Machine, StopReason};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{EXIT_CONFIG_ERROR, EXIT_PASS, EXIT_RUNTIME_ERROR};
+
+/// Steps handed to one `run` call; the wall-time limit is checked between them.
+const BENCH_CHUNK: u32 = 10_000;
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Firmware ELF to benchmark.
+    #[arg(short, long)]
+    pub firmware: PathBuf,
+
+    /// System manifest (`system.yaml`); the default bus when omitted.
+    #[arg(short, long)]
+    pub system: Option<PathBuf>,
+
+    /// Instructions to execute (default 1,000,000 when `--duration-ms` is not given).
+    #[arg(long, conflicts_with = "duration_ms")]
+    pub steps: Option<u64>,
+
+    /// Run for this many milliseconds of wall time instead of a step count.
+    #[arg(long)]
+    pub duration_ms: Option<u64>,
+
+    /// Also write the JSON record to this file.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Load the firmware ELF and build the bus for it, reporting failures as a
+/// config-error exit. Shared with `labwired dump`.
+pub(crate) fn load_program_and_bus(
+    firmware: &Path,
+    system: Option<&Path>,
+) -> Result<(ProgramImage, SystemBus), ExitCode> {
+    let program = labwired_loader::load_elf(firmware).map_err(|e| {
+        eprintln!("error: failed to load {}: {e:#}", firmware.display());
+        ExitCode::from(EXIT_CONFIG_ERROR)
+    })?;
+    let bus = labwired_core::system::builder::build_system_bus(system).map_err(|e| {
+        eprintln!("error: failed to build system bus: {e:#}");
+        ExitCode::from(EXIT_CONFIG_ERROR)
+    })?;
+    Ok((program, bus))
+}
+
+pub fn run_bench(args: BenchArgs) -> ExitCode {
+    let (program, mut bus) = match load_program_and_bus(&args.firmware, args.system.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(code) => return code,
+    };
+
+    let report = match program.arch {
+        labwired_core::Arch::Arm => {
+            let (cpu, _) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+            bench_machine(Machine::new(cpu, bus), &program, &args)
+        }
+        labwired_core::Arch::RiscV => {
+            let cpu = labwired_core::system::riscv::configure_riscv(&mut bus);
+            bench_machine(Machine::new(cpu, bus), &program, &args)
+        }
+        other => {
+            eprintln!("error: bench does not support {other:?} firmware");
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::from(EXIT_RUNTIME_ERROR);
+        }
+    };
+
+    let text = report.to_string();
+    println!("{text}");
+    if let Some(path) = &args.output {
+        if let Err(e) = std::fs::write(path, format!("{text}\n")) {
+            eprintln!("error: write {}: {e}", path.display());
+            return ExitCode::from(EXIT_RUNTIME_ERROR);
+        }
+    }
+    ExitCode::from(EXIT_PASS)
+}
+
+fn bench_machine<C: Cpu>(
+    mut machine: Machine<C>,
+    program: &ProgramImage,
+    args: &BenchArgs,
+) -> anyhow::Result<serde_json::Value> {
+    machine
+        .load_firmware(program)
+        .map_err(|e| anyhow::anyhow!("load firmware: {e}"))?;
+
+    // Installed after loading so `get_ips` measures the run alone.
+    let metrics = Arc::new(PerformanceMetrics::new());
+    machine.observers.push(metrics.clone());
+
+    let deadline = args.duration_ms.map(Duration::from_millis);
+    let mut remaining = match deadline {
+        Some(_) => u64::MAX,
+        None => args.steps.unwrap_or(1_000_000),
+    };
+    let start = Instant::now();
+    let mut stop = StopReason::MaxStepsReached;
+    while remaining > 0 && deadline.is_none_or(|d| start.elapsed() < d) {
+        let chunk = remaining.min(u64::from(BENCH_CHUNK)) as u32;
+        let before = machine.get_instruction_count();
+        stop = machine
+            .run(Some(chunk))
+            .map_err(|e| anyhow::anyhow!("simulation failed: {e}"))?;
+        remaining = remaining.saturating_sub(machine.get_instruction_count() - before);
+        if stop != StopReason::MaxStepsReached {
+            break;
+        }
+    }
+    let wall = start.elapsed();
+
+    let peripheral_cycles: serde_json::Map<String, serde_json::Value> = machine
+        .bus
+        .peripherals
+        .iter()
+        .map(|p| {
+            (
+                p.name.clone(),
+                metrics.get_peripheral_cycles(&p.name).into(),
+            )
+        })
+        .collect();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    Ok(serde_json::json!({
+        "firmware": args.firmware.display().to_string(),
+        "timestamp": timestamp,
+        "instructions": metrics.get_instructions(),
+        "cycles": metrics.get_cycles(),
+        "wall_time_ms": wall.as_secs_f64() * 1000.0,
+        "ips": metrics.get_ips(),
+        "stop_reason": format!("{stop:?}"),
+        "peripheral_cycles_total": metrics.get_peripheral_cycles_total(),
+        "peripheral_cycles": peripheral_cycles,
+    }))
+}
@@ -27,6 +27,38 @@ pub(crate) fn run_codegen(args: CodegenArgs) -> ExitCode {
         }
     };
 
+    let options = labwired_codegen::GeneratorOptions {
+        mmio_accessors: args.mmio,
+        raw: args.raw,
+        reserved_bits: args.reserved_bits,
+    };
+
+    if let Some(out_dir) = &args.out_dir {
+        return match labwired_codegen::PeripheralGenerator::write_device_tree(
+            &device, out_dir, options,
+        ) {
+            Ok(files) => {
+                info!(
+                    "Successfully wrote {} Rust files to {:?}",
+                    files.len(),
+                    out_dir
+                );
+                ExitCode::from(EXIT_PASS)
+            }
+            Err(e) => {
+                error!(
+                    "Failed to generate code for device {}: {:#}",
+                    device.name, e
+                );
+                ExitCode::from(EXIT_CONFIG_ERROR)
+            }
+        };
+    }
+    let Some(output) = &args.output else {
+        error!("Either --output or --out-dir is required");
+        return ExitCode::from(EXIT_CONFIG_ERROR);
+    };
+
     let mut output_code = String::new();
     output_code.push_str("// Generated by LabWired Codegen\n");
     output_code.push_str("#![allow(non_camel_case_types)]\n");
@@ -34,24 +66,22 @@ pub(crate) fn run_codegen(args: CodegenArgs) -> ExitCode {
     // output_code.push_str("use labwired_core::Peripheral;\n"); // Not strictly needed yet as we generate structs
     // output_code.push_str("use labwired_core::SimResult;\n\n");
 
-    for (name, peripheral) in &device.peripherals {
-        match labwired_codegen::PeripheralGenerator::generate(peripheral) {
-            Ok(code) => {
-                output_code.push_str(&code);
-                output_code.push_str("\n\n");
-            }
-            Err(e) => {
-                error!("Failed to generate code for peripheral {}: {}", name, e);
-                return ExitCode::from(EXIT_CONFIG_ERROR);
-            }
+    match labwired_codegen::PeripheralGenerator::generate_device_with_options(&device, options) {
+        Ok(code) => {
+            output_code.push_str(&code);
+            output_code.push('\n');
+        }
+        Err(e) => {
+            error!("Failed to generate code for device {}: {}", device.name, e);
+            return ExitCode::from(EXIT_CONFIG_ERROR);
         }
     }
 
-    if let Err(e) = std::fs::write(&args.output, output_code) {
+    if let Err(e) = std::fs::write(output, output_code) {
         error!("Failed to write output file: {}", e);
         return ExitCode::from(EXIT_CONFIG_ERROR);
     }
 
-    info!("Successfully wrote Rust code to {:?}", args.output);
+    info!("Successfully wrote Rust code to {:?}", output);
     ExitCode::from(EXIT_PASS)
 }
@@ -0,0 +1,136 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! `labwired dump`: run a firmware for N steps, then print the core register
+//! file and a hex dump of one memory region, for quick manual inspection
+//! without writing a test script:
+//!
+//! ```text
+//! labwired dump --firmware fw.elf --system sys.yaml --at-step 5000 --address 0x20000000 --len 64
+//! ```
+
+use clap::Args;
+use labwired_core::{DebugControl, Machine, StopReason};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use super::bench::load_program_and_bus;
+use crate::{parse_u32_addr, EXIT_CONFIG_ERROR, EXIT_PASS, EXIT_RUNTIME_ERROR};
+
+#[derive(Args, Debug)]
+pub struct DumpArgs {
+    /// Firmware ELF to run.
+    #[arg(short, long)]
+    pub firmware: PathBuf,
+
+    /// System manifest (`system.yaml`); the default bus when omitted.
+    #[arg(short, long)]
+    pub system: Option<PathBuf>,
+
+    /// Instructions to execute before dumping (0 dumps the reset state).
+    #[arg(long, default_value_t = 0)]
+    pub at_step: u32,
+
+    /// Start of the memory region to dump (hex with `0x` or decimal).
+    #[arg(long, value_parser = parse_u32_addr)]
+    pub address: u32,
+
+    /// Bytes to dump.
+    #[arg(long, default_value_t = 64)]
+    pub len: usize,
+}
+
+pub fn run_dump(args: DumpArgs) -> ExitCode {
+    let (program, mut bus) = match load_program_and_bus(&args.firmware, args.system.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(code) => return code,
+    };
+
+    let mut machine: Box<dyn DebugControl> = match program.arch {
+        labwired_core::Arch::Arm => {
+            let (cpu, _) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+            let mut machine = Machine::new(cpu, bus);
+            if let Err(e) = machine.load_firmware(&program) {
+                eprintln!("error: failed to load firmware: {e}");
+                return ExitCode::from(EXIT_RUNTIME_ERROR);
+            }
+            Box::new(machine)
+        }
+        labwired_core::Arch::RiscV => {
+            let cpu = labwired_core::system::riscv::configure_riscv(&mut bus);
+            let mut machine = Machine::new(cpu, bus);
+            if let Err(e) = machine.load_firmware(&program) {
+                eprintln!("error: failed to load firmware: {e}");
+                return ExitCode::from(EXIT_RUNTIME_ERROR);
+            }
+            Box::new(machine)
+        }
+        other => {
+            eprintln!("error: dump does not support {other:?} firmware");
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+
+    if args.at_step > 0 {
+        match machine.run(Some(args.at_step)) {
+            Ok(StopReason::MaxStepsReached) => {}
+            Ok(reason) => eprintln!(
+                "note: stopped early ({reason:?}) after {} steps",
+                machine.get_instruction_count()
+            ),
+            Err(e) => {
+                eprintln!("error: simulation failed: {e}");
+                return ExitCode::from(EXIT_RUNTIME_ERROR);
+            }
+        }
+    }
+
+    let data = match machine.read_memory(args.address, args.len) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!(
+                "error: cannot read {} bytes at {:#010x}: {e}",
+                args.len, args.address
+            );
+            return ExitCode::from(EXIT_RUNTIME_ERROR);
+        }
+    };
+
+    println!("step {}:", machine.get_instruction_count());
+    for name in machine.get_register_names() {
+        if let Ok(value) = machine.read_register_by_name(&name) {
+            println!("  {name:<4} = {value:#010x}");
+        }
+    }
+    println!();
+    print!("{}", hex_dump(args.address, &data));
+    ExitCode::from(EXIT_PASS)
+}
+
+/// `addr: xx xx .. |ascii|` lines, 16 bytes each.
+fn hex_dump(base: u32, data: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, line) in data.chunks(16).enumerate() {
+        let _ = write!(out, "{:#010x}:", base.wrapping_add(i as u32 * 16));
+        for byte in line {
+            let _ = write!(out, " {byte:02x}");
+        }
+        let pad = (16 - line.len()) * 3;
+        let ascii: String = line
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{:pad$}  |{ascii}|", "");
+    }
+    out
+}
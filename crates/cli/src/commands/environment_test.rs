@@ -294,6 +294,8 @@ fn default_environment_limits() -> TestLimits {
         stop_when_assertions_pass: false,
         stop_when_assertions_pass_settle_steps: 0,
         stop_when_assertions_pass_min_steps: 0,
+        loop_detect_repeats: None,
+        loop_detect_window: None,
     }
 }
 
@@ -331,7 +333,7 @@ fn unsupported_option_message(args: &TestArgs) -> Option<String> {
     if args.trace || args.vcd.is_some() || args.trace_max.is_some() {
         unsupported.push("--trace/--vcd/--trace-max");
     }
-    if args.coverage {
+    if args.coverage.is_some() {
         unsupported.push("--coverage");
     }
     if args.rom_boot || args.capture_app_entry.is_some() || args.resume_snapshot.is_some() {
@@ -343,6 +345,9 @@ fn unsupported_option_message(args: &TestArgs) -> Option<String> {
     if !args.watch_gpio.is_empty() {
         unsupported.push("--watch-gpio");
     }
+    if !args.filter_tag.is_empty() {
+        unsupported.push("--filter-tag");
+    }
     (!unsupported.is_empty()).then(|| {
         format!(
             "environment test scripts do not support {}; topology comes exclusively from inputs.env",
@@ -567,6 +572,7 @@ fn evaluate_assertions(assertions: &[TestAssertion], world: &World) -> Vec<Asser
     assertions
         .iter()
         .map(|assertion| {
+            let started = Instant::now();
             let passed = match assertion {
                 TestAssertion::MemoryValue(memory) => {
                     let node = memory.memory_value.node.as_deref().unwrap_or_default();
@@ -581,6 +587,7 @@ fn evaluate_assertions(assertions: &[TestAssertion], world: &World) -> Vec<Asser
             AssertionResult {
                 assertion: assertion.clone(),
                 passed,
+                elapsed: started.elapsed(),
             }
         })
         .collect()
@@ -590,12 +597,8 @@ fn memory_assertion_passes(
     machine: &dyn MachineTrait,
     assertion: &labwired_config::MemoryValueAssertion,
 ) -> bool {
-    let size = assertion.memory_value.size.unwrap_or(32);
-    let width = match size {
-        1 | 8 => 1,
-        2 | 16 => 2,
-        4 | 32 => 4,
-        _ => return false,
+    let Some(width) = assertion.memory_value.width() else {
+        return false;
     };
     let mut value = 0_u32;
     for offset in 0..width {
@@ -605,9 +608,7 @@ fn memory_assertion_passes(
         };
         value |= u32::from(byte) << (offset * 8);
     }
-    let mask = assertion.memory_value.mask.unwrap_or(0xffff_ffff) as u32;
-    let expected = assertion.memory_value.expected_value as u32;
-    (value & mask) == (expected & mask)
+    assertion.memory_value.matches(value)
 }
 
 fn max_cycles(world: &World) -> u64 {
@@ -880,6 +881,21 @@ fn write_environment_artifacts(
             tracing::error!("failed to write environment JUnit {:?}: {error}", path);
         }
     }
+    if let Some(path) = &args.tap {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(error) = crate::write_tap(
+            path,
+            &result.status,
+            &result.stop_reason,
+            &result.assertions,
+            result.message.as_deref(),
+            &result.stop_reason_details,
+        ) {
+            tracing::error!("failed to write environment TAP {:?}: {error}", path);
+        }
+    }
 }
 
 fn write_json<T: serde::Serialize>(path: &Path, value: &T) {
@@ -957,8 +973,9 @@ fn write_environment_junit(
             crate::assertion_short_name(&assertion.assertion)
         );
         cases.push_str(&format!(
-            "  <testcase classname=\"labwired\" name=\"{}\" time=\"0.000000\">\n",
-            crate::xml_escape(&name)
+            "  <testcase classname=\"labwired\" name=\"{}\" time=\"{:.6}\">\n",
+            crate::xml_escape(&name),
+            assertion.elapsed.as_secs_f64()
         ));
         if !assertion.passed {
             failures += 1;
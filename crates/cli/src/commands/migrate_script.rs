@@ -0,0 +1,142 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! `labwired migrate-script`: rewrite a deprecated `schema_version: 1` test
+//! script in the v1.0 `inputs`/`limits` form. Relative paths are rebased
+//! onto the output script's directory, so they keep naming the same files.
+//!
+//! ```text
+//! labwired migrate-script old.yaml new.yaml --firmware build/app.elf
+//! ```
+
+use clap::Args;
+use labwired_config::{load_test_script, LoadedTestScript};
+use std::path::{Component, Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::{EXIT_CONFIG_ERROR, EXIT_PASS, EXIT_RUNTIME_ERROR};
+
+#[derive(Args, Debug)]
+pub struct MigrateScriptArgs {
+    /// Legacy (`schema_version: 1`) test script to migrate.
+    pub input: PathBuf,
+
+    /// Where to write the v1.0 script.
+    pub output: PathBuf,
+
+    /// Firmware path for `inputs.firmware` when the legacy script relied on
+    /// `labwired test --firmware` instead of naming one. Relative to the
+    /// current directory, like `--firmware` itself.
+    #[arg(long)]
+    pub firmware: Option<String>,
+}
+
+pub fn run_migrate_script(args: MigrateScriptArgs) -> ExitCode {
+    let mut legacy = match load_test_script(&args.input) {
+        Ok(LoadedTestScript::LegacyV1(script)) => script,
+        Ok(_) => {
+            eprintln!(
+                "error: {} is not a legacy schema_version 1 script; nothing to migrate",
+                args.input.display()
+            );
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+        Err(err) => {
+            eprintln!("error: failed to load {}: {err:#}", args.input.display());
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+    // Script paths resolve against the script's directory; `--firmware`
+    // against the current one.
+    let input_dir = dir_of(&args.input);
+    let output_dir = dir_of(&args.output);
+    legacy.firmware = match legacy.firmware.take() {
+        Some(firmware) => Some(rebase(&firmware, &input_dir, &output_dir)),
+        None => args
+            .firmware
+            .map(|firmware| rebase(&firmware, Path::new(""), &output_dir)),
+    };
+    legacy.system = legacy
+        .system
+        .take()
+        .map(|system| rebase(&system, &input_dir, &output_dir));
+
+    let script = match legacy.migrate() {
+        Ok(script) => script,
+        Err(err) => {
+            eprintln!("error: cannot migrate {}: {err:#}", args.input.display());
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+
+    let yaml = match serde_yaml::to_string(&script) {
+        Ok(yaml) => yaml,
+        Err(err) => {
+            eprintln!("error: failed to serialize migrated script: {err}");
+            return ExitCode::from(EXIT_RUNTIME_ERROR);
+        }
+    };
+    if let Err(err) = std::fs::write(&args.output, yaml) {
+        eprintln!("error: failed to write {}: {err}", args.output.display());
+        return ExitCode::from(EXIT_RUNTIME_ERROR);
+    }
+    println!(
+        "Migrated {} -> {} (schema_version \"1.0\")",
+        args.input.display(),
+        args.output.display()
+    );
+    ExitCode::from(EXIT_PASS)
+}
+
+/// The directory `file` lives in (`""`, the current one, for a bare name).
+fn dir_of(file: &Path) -> PathBuf {
+    file.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Re-express `path`, relative to `from_dir`, relative to `to_dir`.
+/// Absolute paths are returned unchanged.
+fn rebase(path: &str, from_dir: &Path, to_dir: &Path) -> String {
+    if Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+    let target = absolute(&from_dir.join(path));
+    let base = absolute(to_dir);
+    let common = target
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        // Different roots (e.g. Windows drives): no relative form exists.
+        return target.display().to_string();
+    }
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    relative.extend(target.components().skip(common));
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative.display().to_string()
+}
+
+/// `path` made absolute against the current directory, with `.` and `..`
+/// resolved lexically (the files need not exist).
+fn absolute(path: &Path) -> PathBuf {
+    let joined = std::env::current_dir().unwrap_or_default().join(path);
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
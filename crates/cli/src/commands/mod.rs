@@ -1,15 +1,20 @@
 //! CLI subcommand handlers, split out of `main.rs`.
 
 pub mod asset;
+pub mod bench;
 pub mod codegen;
 pub mod cosim;
 pub mod coverage;
+pub mod dump;
 pub mod environment_test;
 pub mod esp32_boot_state;
 pub mod fuzz;
 pub mod machine;
+pub mod migrate_script;
 pub mod run;
+pub mod schema;
 pub mod snapshot;
 pub mod svd;
 pub mod test;
+pub mod test_suite;
 pub mod tier1;
@@ -115,8 +115,7 @@ pub(crate) fn run_firmware_riscv(args: RunArgs, _chip_yaml: String) -> ExitCode
         // stack pointer before jumping to the app, so SP=0 and the app's first
         // prologue store faults near 0xffffffff. Seed SP at the top of DRAM
         // (16-byte aligned, RISC-V ABI) so real IDF apps can boot.
-        let sp_top =
-            (chip.ram.base + labwired_config::parse_size(&chip.ram.size).unwrap_or(0)) as u32;
+        let sp_top = (chip.ram.base + chip.ram.size.bytes()) as u32;
         machine.cpu.set_sp(sp_top & !0xF);
         machine
     };
@@ -1103,10 +1102,10 @@ pub(crate) fn run_firmware_arm(args: &RunArgs, chip_yaml: &str) -> ExitCode {
     use std::io::Write;
 
     // Parse the chip descriptor.
-    let chip = match serde_yaml::from_str::<ChipDescriptor>(chip_yaml) {
+    let chip = match ChipDescriptor::from_yaml(chip_yaml) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("error: cannot parse chip YAML: {e}");
+            eprintln!("error: cannot parse chip YAML: {e:#}");
             return ExitCode::from(EXIT_CONFIG_ERROR);
         }
     };
@@ -1243,7 +1242,7 @@ pub(crate) fn run_interactive_arm(
         );
     }
 
-    report_metrics(&cli, &machine.cpu, &metrics);
+    report_metrics(&cli, &machine.cpu, &metrics, machine.energy_consumption());
     ExitCode::from(EXIT_PASS)
 }
 
@@ -1306,7 +1305,7 @@ pub(crate) fn run_interactive_riscv(
         );
     }
 
-    report_metrics(&cli, &machine.cpu, &metrics);
+    report_metrics(&cli, &machine.cpu, &metrics, machine.energy_consumption());
     ExitCode::from(EXIT_PASS)
 }
 
@@ -1364,6 +1363,6 @@ pub(crate) fn run_interactive_xtensa(
         );
     }
 
-    report_metrics(&cli, &machine.cpu, &metrics);
+    report_metrics(&cli, &machine.cpu, &metrics, machine.energy_consumption());
     ExitCode::from(EXIT_PASS)
 }
@@ -0,0 +1,61 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! `labwired schema`: print the JSON Schema for one of the YAML document
+//! kinds, for wiring into yaml-language-server:
+//!
+//! ```text
+//! labwired schema test-script > .schemas/test-script.json
+//! ```
+
+use clap::{Args, ValueEnum};
+use labwired_config::schema::{json_schema, SchemaKind};
+use std::process::ExitCode;
+
+use crate::{EXIT_PASS, EXIT_RUNTIME_ERROR};
+
+#[derive(Args, Debug)]
+pub struct SchemaArgs {
+    /// Which document's schema to print.
+    #[arg(value_enum)]
+    pub kind: SchemaType,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum SchemaType {
+    /// Chip descriptor (`configs/chips/*.yaml`).
+    Chip,
+    /// System manifest (`system.yaml`).
+    System,
+    /// Declarative peripheral descriptor.
+    Peripheral,
+    /// `labwired test` script.
+    TestScript,
+}
+
+impl From<SchemaType> for SchemaKind {
+    fn from(kind: SchemaType) -> Self {
+        match kind {
+            SchemaType::Chip => SchemaKind::Chip,
+            SchemaType::System => SchemaKind::System,
+            SchemaType::Peripheral => SchemaKind::Peripheral,
+            SchemaType::TestScript => SchemaKind::TestScript,
+        }
+    }
+}
+
+pub fn run_schema(args: SchemaArgs) -> ExitCode {
+    match serde_json::to_string_pretty(&json_schema(args.kind.into())) {
+        Ok(text) => {
+            println!("{text}");
+            ExitCode::from(EXIT_PASS)
+        }
+        Err(err) => {
+            eprintln!("error: failed to render schema: {err}");
+            ExitCode::from(EXIT_RUNTIME_ERROR)
+        }
+    }
+}
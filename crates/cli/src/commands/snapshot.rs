@@ -4,13 +4,75 @@
 // This software is released under the MIT License.
 // See the LICENSE file in the project root for full license information.
 
-//! `labwired snapshot` subcommands: capture and inspect machine snapshots.
+//! `labwired snapshot` subcommands: capture, inspect and diff machine snapshots.
 
 use crate::*;
 
 pub(crate) fn run_snapshot(args: SnapshotArgs) -> ExitCode {
     match args.command {
         SnapshotCommands::Capture(a) => run_snapshot_capture(a),
+        SnapshotCommands::Diff(a) => run_snapshot_diff(a),
+    }
+}
+
+/// Diff two `--snapshot` JSON files via [`MachineSnapshot::diff`].
+///
+/// [`MachineSnapshot::diff`]: labwired_core::snapshot::MachineSnapshot::diff
+pub(crate) fn run_snapshot_diff(args: SnapshotDiffArgs) -> ExitCode {
+    use labwired_core::snapshot::MachineSnapshot;
+
+    let load = |path: &std::path::Path| -> Result<MachineSnapshot, String> {
+        let raw =
+            std::fs::read_to_string(path).map_err(|e| format!("cannot read {path:?}: {e}"))?;
+        serde_json::from_str(&raw).map_err(|e| format!("cannot parse snapshot {path:?}: {e}"))
+    };
+    let (a, b) = match (load(&args.a), load(&args.b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+
+    let diff = a.diff(&b);
+    if args.json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(s) => println!("{s}"),
+            Err(e) => {
+                eprintln!("error: cannot serialize diff: {e}");
+                return ExitCode::from(EXIT_RUNTIME_ERROR);
+            }
+        }
+    } else if diff.is_empty() {
+        println!("snapshots match");
+    } else {
+        let hex = |v: Option<u64>| v.map_or_else(|| "-".to_string(), |v| format!("{v:#010x}"));
+        let json = |v: &Option<serde_json::Value>| {
+            v.as_ref()
+                .map_or_else(|| "-".to_string(), |v| v.to_string())
+        };
+        if !diff.registers.is_empty() {
+            println!("registers:");
+            for r in &diff.registers {
+                println!("  {:<10} {} -> {}", r.name, hex(r.old), hex(r.new));
+            }
+        }
+        if !diff.peripherals.is_empty() {
+            println!("peripherals:");
+            for p in &diff.peripherals {
+                println!("  {}:", p.name);
+                for c in &p.changes {
+                    let path = if c.path.is_empty() { "/" } else { &c.path };
+                    println!("    {path}: {} -> {}", json(&c.old), json(&c.new));
+                }
+            }
+        }
+    }
+
+    if diff.is_empty() {
+        ExitCode::from(EXIT_PASS)
+    } else {
+        ExitCode::from(EXIT_ASSERT_FAIL)
     }
 }
 
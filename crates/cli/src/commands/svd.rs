@@ -44,6 +44,11 @@ pub(crate) fn run_ingest_svd(args: IngestSvdArgs) -> ExitCode {
             .collect()
     });
 
+    let options = svd_ingestor::IngestOptions {
+        name_separator: args.name_separator.clone(),
+        keep_cluster_hierarchy: args.keep_cluster_hierarchy,
+    };
+
     let mut summary: Vec<serde_json::Value> = Vec::new();
     let mut errors = 0usize;
     for peripheral in &device.peripherals {
@@ -52,22 +57,30 @@ pub(crate) fn run_ingest_svd(args: IngestSvdArgs) -> ExitCode {
                 continue;
             }
         }
-        match svd_ingestor::process_peripheral(&device, peripheral) {
-            Ok(desc) => {
-                if let Err(e) = svd_ingestor::save_descriptor(&desc, &args.output_dir) {
-                    error!("Failed to save descriptor for {}: {}", peripheral.name, e);
-                    errors += 1;
-                    continue;
+        match svd_ingestor::expand_peripheral_with_options(&device, peripheral, &options) {
+            Ok(instances) => {
+                for instance in instances {
+                    let desc = instance.descriptor;
+                    let saved = if args.merge {
+                        svd_ingestor::save_descriptor_merged(&desc, &args.output_dir)
+                    } else {
+                        svd_ingestor::save_descriptor(&desc, &args.output_dir)
+                    };
+                    if let Err(e) = saved {
+                        error!("Failed to save descriptor for {}: {}", desc.peripheral, e);
+                        errors += 1;
+                        continue;
+                    }
+                    let path = args
+                        .output_dir
+                        .join(format!("{}.yaml", desc.peripheral.to_lowercase()));
+                    summary.push(serde_json::json!({
+                        "name": desc.peripheral,
+                        "descriptor_path": path.to_string_lossy(),
+                        "register_count": desc.registers.len(),
+                        "base_address": format!("0x{:08X}", instance.base_address),
+                    }));
                 }
-                let path = args
-                    .output_dir
-                    .join(format!("{}.yaml", desc.peripheral.to_lowercase()));
-                summary.push(serde_json::json!({
-                    "name": desc.peripheral,
-                    "descriptor_path": path.to_string_lossy(),
-                    "register_count": desc.registers.len(),
-                    "base_address": format!("0x{:08X}", peripheral.base_address),
-                }));
             }
             Err(e) => {
                 error!("Failed to process peripheral {}: {}", peripheral.name, e);
@@ -51,6 +51,35 @@ fn handle_faults(
     evidence
 }
 
+/// Apply the script's pre-run `setup` block. An unknown register/peripheral or
+/// an unmapped poke is a config error: the run never starts, so there is no
+/// half-initialized machine state to report.
+fn handle_setup<C: labwired_core::Cpu>(
+    args: &TestArgs,
+    machine: &mut labwired_core::Machine<C>,
+    setup: Option<&labwired_config::TestSetup>,
+    firmware_path: &Path,
+    system_path: Option<&PathBuf>,
+    firmware_bytes: &[u8],
+    limits: &TestLimits,
+) -> Result<(), ExitCode> {
+    let Some(setup) = setup else {
+        return Ok(());
+    };
+    labwired_cli::setup::apply_setup(machine, setup).map_err(|msg| {
+        error!("{}", msg);
+        write_config_error_outputs(
+            args,
+            Some(&firmware_path.to_path_buf()),
+            system_path,
+            Some(firmware_bytes),
+            Some(limits),
+            msg,
+        );
+        ExitCode::from(EXIT_CONFIG_ERROR)
+    })
+}
+
 /// Encode the public CLI exit contract for best-effort API metering.
 fn metering_exit_status(exit_code: &ExitCode) -> i32 {
     if *exit_code == ExitCode::from(EXIT_PASS) {
@@ -139,10 +168,14 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
         script_stop_when_assertions_pass,
         script_stop_when_assertions_pass_settle_steps,
         script_stop_when_assertions_pass_min_steps,
+        script_loop_detect_repeats,
+        script_loop_detect_window,
         assertions,
         faults,
         verdict,
         stimuli,
+        script_scenario,
+        setup,
     ) = match loaded {
         LoadedTestScript::V1_0(script) => (
             Some(script.inputs.firmware),
@@ -156,14 +189,18 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
             script.limits.stop_when_assertions_pass,
             script.limits.stop_when_assertions_pass_settle_steps,
             script.limits.stop_when_assertions_pass_min_steps,
+            script.limits.loop_detect_repeats,
+            script.limits.loop_detect_window,
             script.assertions,
             script.faults,
             script.verdict,
             script.stimuli,
+            script.scenario,
+            script.setup,
         ),
         LoadedTestScript::LegacyV1(script) => {
             tracing::warn!(
-                "Deprecated test script format detected (schema_version: 1). Please migrate to schema_version: \"1.0\" with inputs/limits nesting."
+                "Deprecated test script format detected (schema_version: 1). Please migrate to schema_version: \"1.0\" with inputs/limits nesting (`labwired migrate-script`)."
             );
             (
                 script.firmware,
@@ -177,10 +214,14 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                 false,
                 100_000,
                 0,
+                None,
+                None,
                 script.assertions,
                 Vec::new(),
                 None,
                 Vec::new(),
+                None,
+                None,
             )
         }
         LoadedTestScript::Env(script) => {
@@ -207,6 +248,22 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
         .map(|v| v.require_fault_fired)
         .unwrap_or(false);
     let mut assertions = assertions;
+    if !args.filter_tag.is_empty() {
+        let tagged = |a: &TestAssertion| a.tags().iter().any(|t| args.filter_tag.contains(t));
+        if !assertions.iter().any(tagged) {
+            let msg = format!(
+                "--filter-tag {} matched no assertions in {:?}",
+                args.filter_tag.join(","),
+                args.script
+            );
+            error!("{}", msg);
+            write_config_error_outputs(&args, None, args.system.as_ref(), None, None, msg);
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+        // How the run must end is not one of the filtered checks: keep every
+        // expected_stop_reason whatever its tags.
+        assertions.retain(|a| matches!(a, TestAssertion::ExpectedStopReason(_)) || tagged(a));
+    }
     if let Some(v) = &verdict {
         assertions.extend(v.safe_when.iter().cloned());
     }
@@ -226,6 +283,8 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
         stop_when_assertions_pass: script_stop_when_assertions_pass,
         stop_when_assertions_pass_settle_steps: script_stop_when_assertions_pass_settle_steps,
         stop_when_assertions_pass_min_steps: script_stop_when_assertions_pass_min_steps,
+        loop_detect_repeats: script_loop_detect_repeats,
+        loop_detect_window: script_loop_detect_window,
     };
 
     // Guard against accidentally huge runs from CI misconfiguration. The
@@ -306,6 +365,29 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
         }
     };
 
+    let scenario = match script_scenario
+        .as_deref()
+        .map(|s| resolve_script_path(&args.script, s))
+    {
+        Some(path) => match labwired_core::world::Scenario::from_file(&path) {
+            Ok(scenario) => Some(scenario),
+            Err(e) => {
+                let msg = format!("{:#}", e);
+                error!("{}", msg);
+                write_config_error_outputs(
+                    &args,
+                    Some(&firmware_path),
+                    system_path.as_ref(),
+                    None,
+                    Some(&resolved_limits),
+                    msg,
+                );
+                return ExitCode::from(EXIT_CONFIG_ERROR);
+            }
+        },
+        None => None,
+    };
+
     // For Xtensa/ESP32 system manifests, `SystemBus::from_config` (called
     // inside `build_system_bus`) will fail: it tries to attach external devices
     // (e.g. the SSD1680 e-paper panel) to `spi3`, but `spi3` is not in the
@@ -739,6 +821,27 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                 );
                 machine
             };
+            if let Err(code) = handle_setup(
+                &args,
+                &mut machine,
+                setup.as_ref(),
+                &firmware_path,
+                system_path.as_ref(),
+                &firmware_bytes,
+                &resolved_limits,
+            ) {
+                return code;
+            }
+            if args.dry_run {
+                return write_dry_run_outputs(
+                    &args,
+                    &machine.cpu,
+                    &firmware_path,
+                    system_path.as_ref(),
+                    &firmware_bytes,
+                    &resolved_limits,
+                );
+            }
             let fault_evidence = handle_faults(&mut machine.bus, &faults);
             let exit_code = execute_test_loop(
                 &args,
@@ -754,6 +857,7 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                 require_fault_fired,
                 fault_evidence,
                 &stimuli,
+                scenario.clone(),
                 // Xtensa (ESP32) path: never JIT-eligible (the RV32IMC JIT is
                 // RISC-V only), so keep the exact current observer-based metrics.
                 false,
@@ -940,6 +1044,27 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
             if !jit_eligible {
                 machine.observers.push(metrics.clone());
             }
+            if let Err(code) = handle_setup(
+                &args,
+                &mut machine,
+                setup.as_ref(),
+                &firmware_path,
+                system_path.as_ref(),
+                &firmware_bytes,
+                &resolved_limits,
+            ) {
+                return code;
+            }
+            if args.dry_run {
+                return write_dry_run_outputs(
+                    &args,
+                    &machine.cpu,
+                    &firmware_path,
+                    system_path.as_ref(),
+                    &firmware_bytes,
+                    &resolved_limits,
+                );
+            }
             let fault_evidence = handle_faults(&mut machine.bus, &faults);
             execute_test_loop(
                 &args,
@@ -955,6 +1080,7 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                 require_fault_fired,
                 fault_evidence,
                 &stimuli,
+                scenario.clone(),
                 jit_eligible,
             )
         }};
@@ -983,6 +1109,23 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                     e,
                 );
             }
+            let vector_issues = machine.boot_vector_issues();
+            for issue in &vector_issues {
+                warn!("Boot vector table: {}", issue);
+            }
+            if args.strict_vector && !vector_issues.is_empty() {
+                let msg = format!("--strict-vector: {}", vector_issues.join("; "));
+                error!("{}", msg);
+                write_config_error_outputs(
+                    &args,
+                    Some(&firmware_path),
+                    system_path.as_ref(),
+                    Some(&firmware_bytes),
+                    Some(&resolved_limits),
+                    msg,
+                );
+                return ExitCode::from(EXIT_CONFIG_ERROR);
+            }
             // RISC-V Arduino-ESP32 images enter at `call_start_cpu0`, which
             // assumes the second-stage bootloader already left a DRAM stack.
             // Cortex-M gets SP from the vector table in `load_firmware`; RISC-V
@@ -1020,21 +1163,19 @@ pub(crate) fn run_test(args: TestArgs) -> ExitCode {
                             .unwrap_or_else(|| std::path::Path::new("."))
                             .join(&manifest.chip);
                         if let Ok(chip) = labwired_config::ChipDescriptor::from_file(&chip_path) {
-                            if let Ok(ram_sz) = labwired_config::parse_size(&chip.ram.size) {
-                                let mut sp_top = (chip.ram.base + ram_sz) as u32;
-                                // ESP32-C3 boot stack placement:
-                                // - IDF `SOC_DRAM_HIGH` = 0x3FCE_0000; SP must
-                                //   be < that for `s_task_stack_is_sane_when_cache_frozen`.
-                                // - BROM `.data` occupies ~0x3FCD_E710..0x3FCE_0000
-                                //   (ets_ops / flash_guard tables). SP must sit
-                                //   below that so the boot stack does not stomp
-                                //   ROM globals (was: SP@0x3FCD_FFF0 → wild jalr).
-                                if chip.name == "esp32c3" {
-                                    const C3_BOOT_STACK_TOP: u32 = 0x3FCD_C000;
-                                    sp_top = C3_BOOT_STACK_TOP;
-                                }
-                                machine.cpu.set_sp(sp_top & !0xF);
+                            let mut sp_top = (chip.ram.base + chip.ram.size.bytes()) as u32;
+                            // ESP32-C3 boot stack placement:
+                            // - IDF `SOC_DRAM_HIGH` = 0x3FCE_0000; SP must
+                            //   be < that for `s_task_stack_is_sane_when_cache_frozen`.
+                            // - BROM `.data` occupies ~0x3FCD_E710..0x3FCE_0000
+                            //   (ets_ops / flash_guard tables). SP must sit
+                            //   below that so the boot stack does not stomp
+                            //   ROM globals (was: SP@0x3FCD_FFF0 → wild jalr).
+                            if chip.name == "esp32c3" {
+                                const C3_BOOT_STACK_TOP: u32 = 0x3FCD_C000;
+                                sp_top = C3_BOOT_STACK_TOP;
                             }
+                            machine.cpu.set_sp(sp_top & !0xF);
                             // Arduino-ESP32 `system_early_init` validates the
                             // ESP app image magic (0xE9) at the DROM-mapped
                             // flash header (`0x3C03_0000` on this C3 link).
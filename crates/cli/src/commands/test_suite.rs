@@ -0,0 +1,310 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! `labwired test-suite`: run many test scripts in one invocation.
+//!
+//! Each script goes through the unmodified `labwired test` path
+//! ([`super::test::run_test`]) with its own artifact directory; the per-script
+//! JUnit/TAP reports are then folded into one aggregate report with one
+//! `<testsuite>` (JUnit) or subtest (TAP) per script:
+//!
+//! ```text
+//! labwired test-suite --dir tests/hil --junit out/suite.xml --tap out/suite.tap
+//! ```
+
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tracing::{error, info};
+
+use crate::{
+    xml_escape, TestArgs, EXIT_ASSERT_FAIL, EXIT_CONFIG_ERROR, EXIT_PASS, EXIT_RUNTIME_ERROR,
+};
+
+#[derive(Args, Debug)]
+pub struct TestSuiteArgs {
+    /// Directory to discover test scripts in (`*.yaml` files with top-level
+    /// `schema_version` and `assertions`; system/chip manifests are skipped).
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+
+    /// Explicit test script (repeatable). Combined with `--dir` discoveries.
+    #[arg(short = 'c', long = "script")]
+    pub scripts: Vec<PathBuf>,
+
+    /// Directory for per-script artifacts (`<output-dir>/<script>/`) and the
+    /// aggregate `junit.xml`. Without it, per-script artifacts go to a
+    /// temporary directory that is removed after the run.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+
+    /// Path to write the aggregate JUnit XML report (one testsuite per script).
+    #[arg(long)]
+    pub junit: Option<PathBuf>,
+
+    /// Path to write the aggregate TAP report (one subtest per script).
+    #[arg(long)]
+    pub tap: Option<PathBuf>,
+
+    /// Disable UART stdout echo for every script.
+    #[arg(long)]
+    pub no_uart_stdout: bool,
+}
+
+/// Outcome of one script in the suite.
+struct SuiteRun {
+    /// Unique, filesystem-safe label (the script stem, de-duplicated).
+    label: String,
+    script: PathBuf,
+    exit_code: u8,
+    artifacts: PathBuf,
+}
+
+/// Map a runner `ExitCode` back onto the public exit contract.
+fn exit_code_value(code: &ExitCode) -> u8 {
+    [
+        EXIT_PASS,
+        EXIT_ASSERT_FAIL,
+        EXIT_CONFIG_ERROR,
+        EXIT_RUNTIME_ERROR,
+    ]
+    .into_iter()
+    .find(|v| *code == ExitCode::from(*v))
+    .unwrap_or(EXIT_CONFIG_ERROR)
+}
+
+/// True when `path` holds a test script rather than a system/chip/environment
+/// manifest that merely shares the directory.
+fn looks_like_test_script(path: &Path) -> bool {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(raw) = serde_yaml::from_str::<serde_yaml::Value>(&contents) else {
+        // Unparseable YAML is surfaced as that script's config error rather
+        // than silently dropped.
+        return true;
+    };
+    raw.get("schema_version").is_some() && raw.get("assertions").is_some()
+}
+
+/// Resolve the suite's script list: sorted `--dir` discoveries, then explicit
+/// `--script` entries in argument order.
+fn collect_scripts(args: &TestSuiteArgs) -> Result<Vec<PathBuf>, String> {
+    let mut scripts = Vec::new();
+    if let Some(dir) = &args.dir {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| format!("failed to read test-suite directory {:?}: {}", dir, e))?;
+        let mut discovered: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "yaml"))
+            .collect();
+        discovered.sort();
+        for path in discovered {
+            if looks_like_test_script(&path) {
+                scripts.push(path);
+            } else {
+                info!("test-suite: skipping non-script YAML {:?}", path);
+            }
+        }
+    }
+    scripts.extend(args.scripts.iter().cloned());
+    if scripts.is_empty() {
+        return Err("test-suite found no test scripts (use --dir and/or --script)".to_string());
+    }
+    Ok(scripts)
+}
+
+/// Script stem, suffixed `-2`, `-3`, … when two scripts share a stem.
+fn unique_label(script: &Path, taken: &[SuiteRun]) -> String {
+    let stem = script
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "script".to_string());
+    let mut label = stem.clone();
+    let mut n = 2;
+    while taken.iter().any(|r| r.label == label) {
+        label = format!("{}-{}", stem, n);
+        n += 1;
+    }
+    label
+}
+
+pub(crate) fn run_test_suite(args: TestSuiteArgs) -> ExitCode {
+    let scripts = match collect_scripts(&args) {
+        Ok(scripts) => scripts,
+        Err(msg) => {
+            error!("{}", msg);
+            return ExitCode::from(EXIT_CONFIG_ERROR);
+        }
+    };
+
+    let (work_root, is_temp) = match &args.output_dir {
+        Some(dir) => (dir.clone(), false),
+        None => (
+            std::env::temp_dir().join(format!("labwired-test-suite-{}", std::process::id())),
+            true,
+        ),
+    };
+
+    let mut runs: Vec<SuiteRun> = Vec::new();
+    for script in scripts {
+        let label = unique_label(&script, &runs);
+        let artifacts = work_root.join(&label);
+        info!("test-suite: running {:?}", script);
+        let exit = super::test::run_test(TestArgs {
+            firmware: None,
+            system: None,
+            script: script.clone(),
+            max_steps: None,
+            breakpoint: Vec::new(),
+            no_uart_stdout: args.no_uart_stdout,
+            output_dir: Some(artifacts.clone()),
+            junit: None,
+            tap: Some(artifacts.join("results.tap")),
+            filter_tag: Vec::new(),
+            max_cycles: None,
+            max_uart_bytes: None,
+            detect_stuck: None,
+            max_vcd_bytes: None,
+            trace: false,
+            vcd: None,
+            trace_max: None,
+            coverage: None,
+            rom_boot: false,
+            run_manifest: false,
+            capture_app_entry: None,
+            resume_snapshot: None,
+            no_key: false,
+            watch_gpio: Vec::new(),
+            dry_run: false,
+            strict_vector: false,
+        });
+        runs.push(SuiteRun {
+            label,
+            script,
+            exit_code: exit_code_value(&exit),
+            artifacts,
+        });
+    }
+
+    let mut junit_targets: Vec<PathBuf> = args.junit.iter().cloned().collect();
+    if !is_temp {
+        junit_targets.push(work_root.join("junit.xml"));
+    }
+    if !junit_targets.is_empty() {
+        let xml = aggregate_junit(&runs);
+        for path in &junit_targets {
+            write_report(path, &xml, "JUnit");
+        }
+    }
+    if let Some(path) = &args.tap {
+        write_report(path, &aggregate_tap(&runs), "TAP");
+    }
+
+    if is_temp {
+        let _ = std::fs::remove_dir_all(&work_root);
+    }
+
+    let failed = runs.iter().filter(|r| r.exit_code != EXIT_PASS).count();
+    info!(
+        "test-suite: {} of {} script(s) passed",
+        runs.len() - failed,
+        runs.len()
+    );
+    // The most severe per-script code wins, so a runtime/config error is
+    // never masked by an ordinary assertion failure elsewhere in the suite.
+    ExitCode::from(runs.iter().map(|r| r.exit_code).max().unwrap_or(EXIT_PASS))
+}
+
+fn write_report(path: &Path, contents: &str, kind: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = std::fs::write(path, contents) {
+        error!("Failed to write {} report {:?}: {}", kind, path, e);
+    }
+}
+
+/// Read a numeric attribute (`tests="3"`) from an XML start tag.
+fn xml_attr<T: std::str::FromStr>(tag: &str, name: &str) -> Option<T> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    tag[start..end].parse().ok()
+}
+
+/// Fold every script's `junit.xml` into one `<testsuites>` document. A script
+/// that produced no report (e.g. the runner could not create its artifact
+/// directory) still gets a testsuite with a single errored testcase.
+fn aggregate_junit(runs: &[SuiteRun]) -> String {
+    let (mut tests, mut failures, mut errors, mut time) = (0u64, 0u64, 0u64, 0f64);
+    let mut suites = String::new();
+    for run in runs {
+        let name = xml_escape(&run.label);
+        let report = std::fs::read_to_string(run.artifacts.join("junit.xml")).ok();
+        let suite = report.as_deref().and_then(|xml| {
+            let start = xml.find("<testsuite ")?;
+            Some(xml[start..].trim_end())
+        });
+        match suite {
+            Some(suite) => {
+                let open_tag = &suite[..suite.find('>').unwrap_or(suite.len())];
+                tests += xml_attr::<u64>(open_tag, "tests").unwrap_or(0);
+                failures += xml_attr::<u64>(open_tag, "failures").unwrap_or(0);
+                errors += xml_attr::<u64>(open_tag, "errors").unwrap_or(0);
+                time += xml_attr::<f64>(open_tag, "time").unwrap_or(0.0);
+                suites.push_str(&suite.replacen(
+                    "<testsuite name=\"labwired\"",
+                    &format!("<testsuite name=\"{}\"", name),
+                    1,
+                ));
+                suites.push('\n');
+            }
+            None => {
+                tests += 1;
+                errors += 1;
+                suites.push_str(&format!(
+                    "<testsuite name=\"{}\" tests=\"1\" failures=\"0\" errors=\"1\" time=\"0.000000\">\n  <testcase classname=\"labwired\" name=\"run\" time=\"0.000000\">\n    <error message=\"no report\">{}</error>\n  </testcase>\n</testsuite>\n",
+                    name,
+                    xml_escape(&format!(
+                        "script {} exited with code {} without writing junit.xml",
+                        run.script.display(),
+                        run.exit_code
+                    ))
+                ));
+            }
+        }
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"labwired\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">\n{}</testsuites>\n",
+        tests, failures, errors, time, suites
+    )
+}
+
+/// Nest every script's TAP stream as an indented subtest under a top-level
+/// plan with one test point per script.
+fn aggregate_tap(runs: &[SuiteRun]) -> String {
+    let mut tap = String::new();
+    tap.push_str("TAP version 13\n");
+    tap.push_str(&format!("1..{}\n", runs.len()));
+    for (idx, run) in runs.iter().enumerate() {
+        tap.push_str(&format!("# Subtest: {}\n", run.label));
+        if let Ok(inner) = std::fs::read_to_string(run.artifacts.join("results.tap")) {
+            for line in inner.lines().filter(|l| !l.starts_with("TAP version")) {
+                tap.push_str("    ");
+                tap.push_str(line);
+                tap.push('\n');
+            }
+        }
+        let verdict = if run.exit_code == EXIT_PASS {
+            "ok"
+        } else {
+            "not ok"
+        };
+        tap.push_str(&format!("{} {} - {}\n", verdict, idx + 1, run.label));
+    }
+    tap
+}
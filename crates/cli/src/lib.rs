@@ -7,6 +7,8 @@
 pub mod bus_vcd;
 pub mod coverage;
 pub mod faults;
+pub mod loop_detect;
 pub mod manifest;
 pub mod pc_coverage_report;
+pub mod setup;
 pub mod tier1;
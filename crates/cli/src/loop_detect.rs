@@ -0,0 +1,113 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Short-cycle hang detection for the test runner.
+//!
+//! `no_progress_steps` only fires when the PC stops changing, so a tight
+//! `loop { counter += 1 }` of two or three instructions runs to `max_steps`.
+//! [`LoopDetector`] watches the PC stream for any cycle of up to `window`
+//! instructions that repeats back-to-back `repeats` times.
+
+use std::collections::VecDeque;
+
+/// Default longest cycle looked for when a script sets only
+/// `loop_detect_repeats`.
+pub const DEFAULT_LOOP_WINDOW: u64 = 8;
+
+#[derive(Debug)]
+pub struct LoopDetector {
+    repeats: u64,
+    /// The last `window` PCs, oldest first.
+    history: VecDeque<u32>,
+    /// `runs[l - 1]`: consecutive steps whose PC equalled the PC `l` steps
+    /// earlier. A cycle of length `l` has repeated `n` times once this
+    /// reaches `l * (n - 1)`.
+    runs: Vec<u64>,
+}
+
+impl LoopDetector {
+    pub fn new(window: u64, repeats: u64) -> Self {
+        let window = window.max(1) as usize;
+        Self {
+            repeats: repeats.max(2),
+            history: VecDeque::with_capacity(window),
+            runs: vec![0; window],
+        }
+    }
+
+    /// Feed the PC after one step. Returns the loop's instruction addresses,
+    /// ascending, once the shortest repeating cycle crosses the threshold.
+    pub fn observe(&mut self, pc: u32) -> Option<Vec<u32>> {
+        let len = self.history.len();
+        let mut found = None;
+        for (i, run) in self.runs.iter_mut().enumerate() {
+            let period = i + 1;
+            if period <= len && self.history[len - period] == pc {
+                *run += 1;
+                if found.is_none() && *run >= period as u64 * (self.repeats - 1) {
+                    found = Some(period);
+                }
+            } else {
+                *run = 0;
+            }
+        }
+
+        if self.history.len() == self.runs.len() {
+            self.history.pop_front();
+        }
+        self.history.push_back(pc);
+
+        found.map(|period| {
+            let mut body: Vec<u32> = self.history.iter().rev().take(period).copied().collect();
+            body.sort_unstable();
+            body.dedup();
+            body
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_a_three_instruction_busy_loop() {
+        let mut detector = LoopDetector::new(DEFAULT_LOOP_WINDOW, 4);
+        let body = [0x2000_0100, 0x2000_0102, 0x2000_0104];
+        let mut hit = None;
+        let mut steps = 0;
+        for pc in body.iter().cycle().take(30) {
+            steps += 1;
+            if let Some(addrs) = detector.observe(*pc) {
+                hit = Some(addrs);
+                break;
+            }
+        }
+        assert_eq!(hit, Some(body.to_vec()));
+        // The first pass establishes the cycle; three more confirm it.
+        assert_eq!(steps, 12);
+    }
+
+    #[test]
+    fn straight_line_code_and_long_loops_do_not_fire() {
+        let mut detector = LoopDetector::new(4, 3);
+        for pc in (0..1000u32).map(|i| 0x100 + 2 * i) {
+            assert_eq!(detector.observe(pc), None);
+        }
+        // A six-instruction cycle is longer than the window.
+        for pc in (0..60u32).map(|i| 0x200 + 2 * (i % 6)) {
+            assert_eq!(detector.observe(pc), None);
+        }
+    }
+
+    #[test]
+    fn a_single_pc_spin_is_a_one_instruction_cycle() {
+        let mut detector = LoopDetector::new(DEFAULT_LOOP_WINDOW, 3);
+        assert_eq!(detector.observe(0x400), None);
+        assert_eq!(detector.observe(0x400), None);
+        assert_eq!(detector.observe(0x400), Some(vec![0x400]));
+    }
+}
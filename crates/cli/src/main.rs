@@ -10,6 +10,7 @@ mod wifi_frames;
 use clap::{Parser, Subcommand};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::str::FromStr;
@@ -27,9 +28,13 @@ mod gpio_observer;
 mod size_limited_writer;
 mod vcd_trace;
 
-use artifacts::{AssertionResult, NamedU64, Snapshot, StopReasonDetails, TestConfig, TestResult};
+use artifacts::{
+    firmware_identity, AssertionResult, NamedU64, Snapshot, StopReasonDetails, TestConfig,
+    TestResult,
+};
 use labwired_config::{
-    load_test_script, LoadedTestScript, StopReason, TestAssertion, TestLimits, UdsTesterDetails,
+    load_test_script, LoadedTestScript, StopReason, TestAssertion, TestLimits, UartTextOptions,
+    UdsTesterDetails,
 };
 
 pub(crate) const EXIT_PASS: u8 = 0;
@@ -39,7 +44,7 @@ pub(crate) const EXIT_RUNTIME_ERROR: u8 = 3;
 
 const RESULT_SCHEMA_VERSION: &str = "1.0";
 
-fn parse_u32_addr(s: &str) -> Result<u32, String> {
+pub(crate) fn parse_u32_addr(s: &str) -> Result<u32, String> {
     let trimmed = s.trim();
     if let Some(hex) = trimmed
         .strip_prefix("0x")
@@ -120,6 +125,11 @@ enum Commands {
     /// Deterministic, CI-friendly runner mode driven by a test script (YAML).
     Test(TestArgs),
 
+    /// Run every test script in a directory (and/or each `--script`) and
+    /// aggregate the results into one JUnit/TAP report. Exits non-zero if any
+    /// script failed.
+    TestSuite(commands::test_suite::TestSuiteArgs),
+
     /// Machine control operations (load, etc.)
     Machine(MachineArgs),
 
@@ -163,6 +173,22 @@ enum Commands {
     /// findings, not emulation false positives. Exits non-zero if a crash is
     /// found (CI-friendly).
     Fuzz(FuzzArgs),
+
+    /// Rewrite a deprecated `schema_version: 1` test script in the v1.0
+    /// `inputs`/`limits` form.
+    MigrateScript(commands::migrate_script::MigrateScriptArgs),
+
+    /// Print the JSON Schema for a YAML document kind (chip, system,
+    /// peripheral, test-script) for editor validation.
+    Schema(commands::schema::SchemaArgs),
+
+    /// Run a firmware for a fixed step count or wall time and print a JSON
+    /// performance record (IPS, cycles, per-peripheral tick cycles).
+    Bench(commands::bench::BenchArgs),
+
+    /// Run a firmware for N steps, then print the register file and a hex
+    /// dump of one memory region.
+    Dump(commands::dump::DumpArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -263,6 +289,24 @@ pub struct SnapshotArgs {
 pub enum SnapshotCommands {
     /// Boot a firmware, step N times, write a runtime snapshot blob.
     Capture(SnapshotCaptureArgs),
+
+    /// Compare two JSON machine snapshots (`--snapshot` output): changed CPU
+    /// registers and per-peripheral state differences. Exits 0 when they
+    /// match, 1 when they differ.
+    Diff(SnapshotDiffArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotDiffArgs {
+    /// Baseline snapshot JSON.
+    pub a: PathBuf,
+
+    /// Snapshot JSON to compare against the baseline.
+    pub b: PathBuf,
+
+    /// Print the diff as JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -407,6 +451,18 @@ pub struct IngestSvdArgs {
     /// instead of a human table. Used by the MCP agent surface.
     #[arg(long)]
     pub json: bool,
+    /// Merge into existing descriptors instead of overwriting them: registers are
+    /// refreshed from the SVD, hand-added `timing` and `on_read`/`on_write` hooks kept.
+    #[arg(long)]
+    pub merge: bool,
+    /// Separator between cluster and register names in flattened register ids.
+    #[arg(long, default_value = "_")]
+    pub name_separator: String,
+
+    /// Keep the SVD cluster hierarchy as a dotted path with array indexes
+    /// (`CH[0].CR`) instead of flattening it with `--name-separator`.
+    #[arg(long)]
+    pub keep_cluster_hierarchy: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -416,8 +472,25 @@ pub struct CodegenArgs {
     pub input: PathBuf,
 
     /// Path to the output Rust file
-    #[arg(short, long)]
-    pub output: PathBuf,
+    #[arg(short, long, required_unless_present = "out_dir")]
+    pub output: Option<PathBuf>,
+
+    /// Write a formatted source tree instead: `lib.rs` plus one file per
+    /// peripheral
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    pub out_dir: Option<PathBuf>,
+
+    /// Also emit volatile `read_from`/`write_to` register accessors
+    #[arg(long)]
+    pub mmio: bool,
+
+    /// Skip `prettyplease` formatting and write the raw token text
+    #[arg(long)]
+    pub raw: bool,
+
+    /// Emit per-register `RESERVED_MASK` constants and `write_masked`
+    #[arg(long)]
+    pub reserved_bits: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -584,6 +657,19 @@ struct TestArgs {
     #[arg(long)]
     junit: Option<PathBuf>,
 
+    /// Optional path to write a TAP (Test Anything Protocol, v13) report:
+    /// one `ok`/`not ok` line per assertion, with a YAML diagnostic block on
+    /// each failure.
+    #[arg(long)]
+    tap: Option<PathBuf>,
+
+    /// Only evaluate assertions carrying this tag (repeatable; an assertion
+    /// matching any given tag is kept). `expected_stop_reason` assertions are
+    /// always kept. Requires schema_version 1.1 tags; a filter that matches no
+    /// assertion is a config error.
+    #[arg(long = "filter-tag", value_name = "TAG")]
+    filter_tag: Vec<String>,
+
     /// Override max cycles limit
     #[arg(long)]
     max_cycles: Option<u64>,
@@ -613,10 +699,11 @@ struct TestArgs {
     trace_max: Option<usize>,
 
     /// Collect firmware statement coverage. Writes coverage.info (LCOV) and
-    /// coverage.json into --output-dir. Distinct from `labwired coverage`,
+    /// coverage.json into --output-dir, and the LCOV report to PATH too when
+    /// one is given (`--coverage out.info`). Distinct from `labwired coverage`,
     /// which measures chip-model register faithfulness.
-    #[arg(long)]
-    coverage: bool,
+    #[arg(long, value_name = "PATH")]
+    coverage: Option<Option<PathBuf>>,
 
     /// Boot from the real ROM reset vector instead of fast-booting the ELF
     /// (ESP32-C3: mask ROM → 2nd-stage bootloader → app, exactly like
@@ -668,6 +755,20 @@ struct TestArgs {
     /// browser logic analyzer uses. No watch → zero overhead, no block emitted.
     #[arg(long = "watch-gpio", value_name = "PERIPHERAL:PIN")]
     watch_gpio: Vec<String>,
+
+    /// Validate the setup without running it: load the script, read the
+    /// firmware, build the bus, load and reset the machine, then stop with
+    /// status `pass`, zero steps and `dry_run: true` in result.json. Config
+    /// errors are reported exactly as in a real run. Single-machine scripts
+    /// only; environment scripts run as usual.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Fail with a config error (exit 2) when the Cortex-M vector table the
+    /// firmware boots from is implausible: initial SP outside RAM or reset
+    /// handler outside flash. Without it these are only logged as warnings.
+    #[arg(long)]
+    strict_vector: bool,
 }
 
 /// Unified error response for agent consumption
@@ -729,6 +830,7 @@ fn main() -> ExitCode {
 
     match cli.command {
         Some(Commands::Test(args)) => commands::test::run_test(args),
+        Some(Commands::TestSuite(args)) => commands::test_suite::run_test_suite(args),
         Some(Commands::Machine(args)) => run_machine(args),
         Some(Commands::Asset(args)) => run_asset(args),
         Some(Commands::Run(args)) => commands::run::run_firmware(args),
@@ -737,6 +839,10 @@ fn main() -> ExitCode {
         Some(Commands::Tier1Matrix(args)) => commands::tier1::run_tier1_matrix(args),
         Some(Commands::CosimStep(args)) => commands::cosim::run_cosim_step(args),
         Some(Commands::Fuzz(args)) => commands::fuzz::run_fuzz(args),
+        Some(Commands::MigrateScript(args)) => commands::migrate_script::run_migrate_script(args),
+        Some(Commands::Schema(args)) => commands::schema::run_schema(args),
+        Some(Commands::Bench(args)) => commands::bench::run_bench(args),
+        Some(Commands::Dump(args)) => commands::dump::run_dump(args),
         None => commands::run::run_interactive(cli),
     }
 }
@@ -1044,6 +1150,7 @@ fn report_metrics<C: labwired_core::Cpu>(
     cli: &Cli,
     cpu: &C,
     metrics: &labwired_core::metrics::PerformanceMetrics,
+    energy_joules: f64,
 ) {
     if cli.json {
         let report = serde_json::json!({
@@ -1052,6 +1159,7 @@ fn report_metrics<C: labwired_core::Cpu>(
             "total_instructions": metrics.get_instructions(),
             "total_cycles": metrics.get_cycles(),
             "average_ips": metrics.get_ips(),
+            "energy_joules": energy_joules,
         });
         println!("{}", serde_json::to_string(&report).unwrap());
     } else {
@@ -1060,6 +1168,7 @@ fn report_metrics<C: labwired_core::Cpu>(
         info!("Total Instructions: {}", metrics.get_instructions());
         info!("Total Cycles: {}", metrics.get_cycles());
         info!("Average IPS: {:.2}", metrics.get_ips());
+        info!("Estimated Energy: {:.3} uJ", energy_joules * 1e6);
     }
 }
 
@@ -1105,7 +1214,7 @@ pub(crate) fn build_stop_reason_details(
                 value: uart_bytes,
             }),
         ),
-        StopReason::NoProgress => (
+        StopReason::NoProgress | StopReason::SleepDeadlock => (
             limits.no_progress_steps.map(|v| NamedU64 {
                 name: "no_progress_steps".to_string(),
                 value: v,
@@ -1140,13 +1249,15 @@ pub(crate) fn build_stop_reason_details(
         | StopReason::DecodeError
         | StopReason::Halt
         | StopReason::Exception
-        | StopReason::ConfigError => (None, None),
+        | StopReason::ConfigError
+        | StopReason::DryRun => (None, None),
     };
 
     StopReasonDetails {
         triggered_stop_condition: stop_reason.clone(),
         triggered_limit,
         observed,
+        loop_addresses: Vec::new(),
     }
 }
 
@@ -1195,6 +1306,8 @@ fn handle_load_error<C: labwired_core::Cpu>(
         &[],
         None,
         None,
+        None,
+        None,
     );
     ExitCode::from(EXIT_RUNTIME_ERROR)
 }
@@ -1205,28 +1318,26 @@ fn assertion_currently_passes(
     machine: &labwired_core::Machine<impl labwired_core::Cpu>,
 ) -> bool {
     match assertion {
-        TestAssertion::UartContains(a) => uart_text.contains(&a.uart_contains),
-        TestAssertion::UartRegex(a) => simple_regex_is_match(&a.uart_regex, uart_text),
+        // The streamed text is decoded lossily, so `strict_utf8` is left to
+        // the final verdict, which sees the raw bytes.
+        TestAssertion::UartContains(_) | TestAssertion::UartRegex(_) => {
+            uart_assertion_passes(assertion, uart_text, None)
+        }
         TestAssertion::ExpectedStopReason(_) => true,
         TestAssertion::MemoryValue(a) => {
-            let size = a.memory_value.size.unwrap_or(32);
-            let result = match size {
-                1 | 8 => machine
+            let result = match a.memory_value.width() {
+                Some(1) => machine
                     .bus
                     .read_u8(a.memory_value.address)
                     .map(|v| v as u32),
-                2 | 16 => machine
+                Some(2) => machine
                     .bus
                     .read_u16(a.memory_value.address)
                     .map(|v| v as u32),
-                4 | 32 => machine.bus.read_u32(a.memory_value.address),
+                Some(4) => machine.bus.read_u32(a.memory_value.address),
                 _ => return false,
             };
-            result.is_ok_and(|val| {
-                let mask = a.memory_value.mask.unwrap_or(0xFFFFFFFF) as u32;
-                let expected = a.memory_value.expected_value as u32;
-                (val & mask) == (expected & mask)
-            })
+            result.is_ok_and(|val| a.memory_value.matches(val))
         }
         TestAssertion::UdsTester(a) => {
             evaluate_uds_tester(&machine.bus.can_uds_testers, &a.uds_tester).is_ok()
@@ -1262,12 +1373,13 @@ fn riscv_jit_test_eligible<C: labwired_core::Cpu>(
     // want to accelerate.
     matches!(arch, labwired_core::Arch::RiscV)
         && !args.trace
-        && !args.coverage
+        && args.coverage.is_none()
         && args.vcd.is_none()
         && args.breakpoint.is_empty()
         && args.watch_gpio.is_empty()
         && args.capture_app_entry.is_none()
         && limits.no_progress_steps.is_none()
+        && limits.loop_detect_repeats.is_none()
         && !limits.stop_when_assertions_pass
         && !machine.bus.requires_cycle_accurate()
         && !machine.logic_poll_active()
@@ -1312,6 +1424,9 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     require_fault_fired: bool,
     mut fault_evidence: Vec<labwired_cli::faults::FaultEvidence>,
     stimuli: &[labwired_config::StimulusSpec],
+    // Timed external events from the script's `scenario:` file, fired like
+    // `after_cycles` stimuli at the top of each loop iteration.
+    mut scenario: Option<labwired_core::world::Scenario>,
     // True when this run qualifies for the RV32IMC wasm-JIT fast path (decided
     // by `riscv_jit_test_eligible` in the caller): RiscV arch, batch mode, and
     // NONE of the per-instruction-visibility features that gate the JIT off.
@@ -1324,6 +1439,16 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     let max_cycles = resolved_limits.max_cycles;
     let max_uart_bytes = resolved_limits.max_uart_bytes;
     let detect_stuck = resolved_limits.no_progress_steps;
+    let mut loop_detector = resolved_limits.loop_detect_repeats.map(|repeats| {
+        labwired_cli::loop_detect::LoopDetector::new(
+            resolved_limits
+                .loop_detect_window
+                .unwrap_or(labwired_cli::loop_detect::DEFAULT_LOOP_WINDOW),
+            repeats,
+        )
+    });
+    // The PCs of the short cycle the loop detector stopped on, if it did.
+    let mut loop_addresses: Vec<u32> = Vec::new();
     let script_wall_time_ms = resolved_limits.wall_time_ms;
 
     let start = std::time::Instant::now();
@@ -1340,7 +1465,7 @@ fn execute_test_loop<C: labwired_core::Cpu>(
         None
     };
 
-    let coverage_observer = if args.coverage {
+    let coverage_observer = if args.coverage.is_some() {
         let obs = Arc::new(labwired_core::pc_coverage::PcCoverageObserver::new());
         machine.observers.push(obs.clone());
         Some(obs)
@@ -1355,6 +1480,9 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     }
 
     let mut sim_error_happened = false;
+    // A scenario event that could not be applied (unknown peripheral, bus
+    // fault on a poke) invalidates the run whatever the assertions say.
+    let mut scenario_failed = false;
     let mut prev_pc = machine.cpu.get_pc();
     let mut stuck_counter: u64 = 0;
 
@@ -1463,6 +1591,7 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     let batch_size = if machine.config.batch_mode_enabled
         && args.breakpoint.is_empty()
         && detect_stuck.is_none()
+        && loop_detector.is_none()
         && !resolved_limits.stop_when_assertions_pass
         // Cycle-tight GPIO-timing devices (e.g. HC-SR04 ECHO pulse) only behave
         // correctly when peripherals tick between every instruction; instruction
@@ -1517,6 +1646,11 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     // instead of certifying as passed. A regression (assertions stop passing)
     // resets it, so the pass must be durable.
     let mut assertions_first_passed_at: Option<u64> = None;
+    // UART text the early-stop check evaluates against. Only the bytes
+    // captured since the previous step are decoded and appended, so the log is
+    // no longer copied each step; the assertions still search the whole text.
+    let mut streamed_uart_text = String::new();
+    let mut streamed_uart_bytes = 0usize;
 
     // ── --capture-app-entry: cache a genuine faithful-boot state ─────────
     // While the REAL rom-boot runs, snapshot the machine the instant control
@@ -1613,6 +1747,16 @@ fn execute_test_loop<C: labwired_core::Cpu>(
                 }
             }
         }
+        if let Some(scenario) = scenario.as_mut() {
+            if let Err(e) = scenario.fire_due(machine) {
+                error!("{:#}", e);
+                scenario_failed = true;
+                sim_error_happened = true;
+                stop_reason = StopReason::Exception;
+                steps_executed = step;
+                break;
+            }
+        }
         if !args.breakpoint.is_empty() && args.breakpoint.contains(&machine.cpu.get_pc()) {
             stop_reason = StopReason::Halt;
             steps_executed = step;
@@ -1662,6 +1806,11 @@ fn execute_test_loop<C: labwired_core::Cpu>(
                 }
             }
         }
+        if let Some(next) = scenario.as_ref().and_then(|s| s.next_cycle()) {
+            if next > current_cycle {
+                limit = limit.min(next - current_cycle);
+            }
+        }
         if let Some(cycle_limit) = max_cycles {
             if cycle_limit > current_cycle {
                 limit = limit.min(cycle_limit - current_cycle);
@@ -1697,11 +1846,19 @@ fn execute_test_loop<C: labwired_core::Cpu>(
             if current_pc == prev_pc {
                 stuck_counter += 1;
                 if stuck_counter >= limit {
-                    stop_reason = StopReason::NoProgress;
-                    error!(
-                        "No progress (PC stuck at {:#x}) for {} steps",
-                        prev_pc, limit
-                    );
+                    if machine.is_sleep_deadlocked() {
+                        stop_reason = StopReason::SleepDeadlock;
+                        error!(
+                            "Deadlock: sleeping at {:#x} with no enabled IRQ for {} steps",
+                            prev_pc, limit
+                        );
+                    } else {
+                        stop_reason = StopReason::NoProgress;
+                        error!(
+                            "No progress (PC stuck at {:#x}) for {} steps",
+                            prev_pc, limit
+                        );
+                    }
                     break;
                 }
             } else {
@@ -1710,19 +1867,44 @@ fn execute_test_loop<C: labwired_core::Cpu>(
             }
         }
 
+        // Check no_progress (short PC cycle) - a multi-instruction busy loop
+        // moves the PC every step, so the stuck check above never sees it.
+        if let Some(detector) = loop_detector.as_mut() {
+            if let Some(addrs) = detector.observe(machine.cpu.get_pc()) {
+                if machine.is_sleep_deadlocked() {
+                    stop_reason = StopReason::SleepDeadlock;
+                    error!(
+                        "Deadlock: sleeping in {:#x?} with no enabled IRQ for {} repetitions",
+                        addrs,
+                        resolved_limits.loop_detect_repeats.unwrap_or_default()
+                    );
+                } else {
+                    stop_reason = StopReason::NoProgress;
+                    error!(
+                        "No progress (PC looping over {:#x?}) for {} repetitions",
+                        addrs,
+                        resolved_limits.loop_detect_repeats.unwrap_or_default()
+                    );
+                }
+                loop_addresses = addrs;
+                break;
+            }
+        }
+
         if resolved_limits.stop_when_assertions_pass {
             let has_runtime_assertions = assertions
                 .iter()
                 .any(|a| !matches!(a, TestAssertion::ExpectedStopReason(_)));
             if has_runtime_assertions {
-                let uart_text = {
-                    let bytes = uart_tx.lock().map(|g| g.clone()).unwrap_or_default();
-                    String::from_utf8_lossy(&bytes).to_string()
-                };
+                if let Ok(captured) = uart_tx.lock() {
+                    if let Some(fresh) = captured.get(streamed_uart_bytes..) {
+                        streamed_uart_bytes += append_utf8_lossy(&mut streamed_uart_text, fresh);
+                    }
+                }
                 let all_pass = assertions
                     .iter()
                     .filter(|a| !matches!(a, TestAssertion::ExpectedStopReason(_)))
-                    .all(|a| assertion_currently_passes(a, &uart_text, machine));
+                    .all(|a| assertion_currently_passes(a, &streamed_uart_text, machine));
                 if all_pass {
                     // Latch the first all-pass step, but not before the absolute
                     // minimum-steps floor: assertions that satisfy trivially early
@@ -1773,9 +1955,10 @@ fn execute_test_loop<C: labwired_core::Cpu>(
         }
     }
 
-    let uart_text = {
+    let (uart_text, invalid_utf8_at) = {
         let bytes = uart_tx.lock().map(|g| g.clone()).unwrap_or_default();
-        String::from_utf8_lossy(&bytes).to_string()
+        let invalid_utf8_at = std::str::from_utf8(&bytes).err().map(|e| e.valid_up_to());
+        (String::from_utf8_lossy(&bytes).to_string(), invalid_utf8_at)
     };
 
     let mut assertion_results = Vec::new();
@@ -1783,26 +1966,30 @@ fn execute_test_loop<C: labwired_core::Cpu>(
     let mut expected_stop_reason_matched = false;
 
     for assertion in assertions {
+        let started = std::time::Instant::now();
         let passed = match &assertion {
-            TestAssertion::UartContains(a) => uart_text.contains(&a.uart_contains),
-            TestAssertion::UartRegex(a) => simple_regex_is_match(&a.uart_regex, &uart_text),
+            TestAssertion::UartContains(_) | TestAssertion::UartRegex(_) => {
+                uart_assertion_passes(assertion, &uart_text, invalid_utf8_at)
+            }
             TestAssertion::ExpectedStopReason(a) => a.expected_stop_reason == stop_reason,
             TestAssertion::MemoryValue(a) => {
                 // `size` is the value width. Accept either bytes (1/2/4) or
                 // bits (8/16/32) — both name the same u8/u16/u32 reads — so a
                 // natural "4 bytes" guess for a u32 RAM word works as well as
                 // the historical bit-width form. Defaults to a 32-bit (u32) word.
+                // A `kind` (u8..i32) sets the width instead and sign-extends
+                // signed reads before the compare.
                 let size = a.memory_value.size.unwrap_or(32);
-                let result = match size {
-                    1 | 8 => machine
+                let result = match a.memory_value.width() {
+                    Some(1) => machine
                         .bus
                         .read_u8(a.memory_value.address)
                         .map(|v| v as u32),
-                    2 | 16 => machine
+                    Some(2) => machine
                         .bus
                         .read_u16(a.memory_value.address)
                         .map(|v| v as u32),
-                    4 | 32 => machine.bus.read_u32(a.memory_value.address),
+                    Some(4) => machine.bus.read_u32(a.memory_value.address),
                     _ => {
                         error!(
                             "Unsupported memory assertion size: {} — use 1/2/4 (bytes) or 8/16/32 (bits)",
@@ -1816,11 +2003,12 @@ fn execute_test_loop<C: labwired_core::Cpu>(
                     Ok(val) => {
                         let mask = a.memory_value.mask.unwrap_or(0xFFFFFFFF) as u32;
                         let expected = a.memory_value.expected_value as u32;
-                        let matched = (val & mask) == (expected & mask);
+                        let matched = a.memory_value.matches(val);
                         if !matched {
+                            let got = a.memory_value.kind.map_or(val, |k| k.extend(val));
                             error!(
                                 "Memory assertion failed at {:#x} (size {}): expected {:#x}, got {:#x} (mask {:#x})",
-                                a.memory_value.address, size, expected, val, mask
+                                a.memory_value.address, size, expected, got, mask
                             );
                         }
                         matched
@@ -1861,15 +2049,21 @@ fn execute_test_loop<C: labwired_core::Cpu>(
         assertion_results.push(AssertionResult {
             assertion: assertion.clone(),
             passed,
+            elapsed: started.elapsed(),
         });
     }
 
     let stop_requires_assertion = matches!(
         stop_reason,
-        StopReason::WallTime | StopReason::MaxUartBytes | StopReason::NoProgress
+        StopReason::WallTime
+            | StopReason::MaxUartBytes
+            | StopReason::NoProgress
+            | StopReason::SleepDeadlock
     );
 
-    let status = if !all_passed || (stop_requires_assertion && !expected_stop_reason_matched) {
+    let status = if scenario_failed {
+        "error"
+    } else if !all_passed || (stop_requires_assertion && !expected_stop_reason_matched) {
         "fail"
     } else if sim_error_happened && !expected_stop_reason_matched {
         "error"
@@ -1879,7 +2073,7 @@ fn execute_test_loop<C: labwired_core::Cpu>(
 
     let duration = start.elapsed();
     let uart_bytes = uart_tx.lock().map(|g| g.len() as u64).unwrap_or(0);
-    let stop_reason_details = build_stop_reason_details(
+    let mut stop_reason_details = build_stop_reason_details(
         &stop_reason,
         resolved_limits,
         steps_executed,
@@ -1889,6 +2083,15 @@ fn execute_test_loop<C: labwired_core::Cpu>(
         duration,
         0, // vcd_bytes - will be updated below
     );
+    if !loop_addresses.is_empty() {
+        stop_reason_details.triggered_limit =
+            resolved_limits.loop_detect_repeats.map(|v| NamedU64 {
+                name: "loop_detect_repeats".to_string(),
+                value: v,
+            });
+        stop_reason_details.observed = None;
+        stop_reason_details.loop_addresses = loop_addresses;
+    }
     // Finalise runtime-observed fault outcomes (e.g. missing_clock fires only
     // when the firmware actually accessed the unclocked peripheral) and enforce
     // the require_fault_fired gate: a fault that never took effect makes the run
@@ -1950,9 +2153,13 @@ fn execute_test_loop<C: labwired_core::Cpu>(
         &fault_evidence,
         Some(inspect_block),
         logic_edges,
+        machine.last_fault,
+        Some(machine.energy_consumption()),
     );
 
-    if !all_passed
+    if scenario_failed {
+        ExitCode::from(EXIT_RUNTIME_ERROR)
+    } else if !all_passed
         || fault_gate_failed
         || (stop_requires_assertion && !expected_stop_reason_matched)
     {
@@ -1985,10 +2192,13 @@ fn write_outputs<C: labwired_core::Cpu>(
     fault_evidence: &[labwired_cli::faults::FaultEvidence],
     inspect: Option<labwired_core::inspect::MachineInspect>,
     logic_edges: Option<labwired_core::logic_capture::LogicEdgesResult>,
+    fault: Option<labwired_core::FaultInfo>,
+    energy_joules: Option<f64>,
 ) {
     let mut hasher = Sha256::new();
     hasher.update(firmware_bytes);
     let firmware_hash = format!("{:x}", hasher.finalize());
+    let (firmware_id, firmware_id_kind) = firmware_identity(firmware_bytes, &firmware_hash);
 
     // Drain the coverage-gap log for THIS run. `write_outputs` is called
     // synchronously at the tail of `execute_test_loop`, on the very thread that
@@ -1998,6 +2208,7 @@ fn write_outputs<C: labwired_core::Cpu>(
     let fidelity = labwired_core::fidelity::take().to_gaps();
 
     let assertions_for_junit = assertions.clone();
+    let uart_bytes = uart_tx.lock().map(|g| g.clone()).unwrap_or_default();
     let result = TestResult {
         result_schema_version: RESULT_SCHEMA_VERSION.to_string(),
         status: status.to_string(),
@@ -2011,6 +2222,8 @@ fn write_outputs<C: labwired_core::Cpu>(
         assertions,
         cpu_state: Some(cpu.snapshot()),
         firmware_hash,
+        firmware_id,
+        firmware_id_kind,
         config: TestConfig {
             firmware: firmware_path.to_path_buf(),
             system: system_path.cloned(),
@@ -2019,8 +2232,23 @@ fn write_outputs<C: labwired_core::Cpu>(
         inspect,
         fidelity,
         logic_edges,
+        fault,
+        energy_joules,
+        dry_run: false,
     };
 
+    let coverage_report = coverage_observer
+        .as_ref()
+        .and_then(|cov| build_coverage_report(cov, firmware_path));
+    if let (Some(report), Some(Some(lcov_path))) = (&coverage_report, &args.coverage) {
+        if let Some(parent) = lcov_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(lcov_path, report.to_lcov()) {
+            error!("Failed to write LCOV report {:?}: {}", lcov_path, e);
+        }
+    }
+
     if let Some(output_dir) = &args.output_dir {
         if let Err(e) = std::fs::create_dir_all(output_dir) {
             error!("Failed to create output directory {:?}: {}", output_dir, e);
@@ -2065,72 +2293,26 @@ fn write_outputs<C: labwired_core::Cpu>(
 
             // coverage.info (LCOV) + coverage.json
             let mut coverage_summary: Option<labwired_cli::manifest::CoverageSummary> = None;
-            if let Some(cov) = coverage_observer {
-                match labwired_loader::SymbolProvider::new(firmware_path) {
-                    Ok(symbols) => {
-                        let mut report = labwired_cli::pc_coverage_report::CoverageReport::build(
-                            symbols.statement_rows(),
-                            |addr| cov.was_executed(addr as u32),
-                        );
-                        // Resolve each observed branch site to its source line.
-                        let branch_cov = cov
-                            .branch_sites()
-                            .into_iter()
-                            .filter_map(|(src, counts)| {
-                                symbols.lookup(src as u64).and_then(|loc| {
-                                    loc.line.map(|line| {
-                                        // statement_rows uses the line-program
-                                        // file basename; lookup() returns the
-                                        // full path. Normalise to the basename
-                                        // so branches attach to the right SF.
-                                        let file = loc
-                                            .file
-                                            .rsplit('/')
-                                            .next()
-                                            .unwrap_or(&loc.file)
-                                            .to_string();
-                                        labwired_cli::pc_coverage_report::BranchCoverage {
-                                            file,
-                                            line,
-                                            taken: counts.taken,
-                                            not_taken: counts.not_taken,
-                                        }
-                                    })
-                                })
-                            })
-                            .collect();
-                        report.set_branches(branch_cov);
-                        let info_path = output_dir.join("coverage.info");
-                        if let Err(e) = std::fs::write(&info_path, report.to_lcov()) {
-                            error!("Failed to write coverage.info: {}", e);
-                        }
-                        let cov_json_path = output_dir.join("coverage.json");
-                        match std::fs::File::create(&cov_json_path) {
-                            Ok(f) => {
-                                if let Err(e) = serde_json::to_writer_pretty(f, &report) {
-                                    error!("Failed to write coverage.json: {}", e);
-                                }
-                            }
-                            Err(e) => error!("Failed to create coverage.json: {}", e),
+            if let Some(report) = &coverage_report {
+                let info_path = output_dir.join("coverage.info");
+                if let Err(e) = std::fs::write(&info_path, report.to_lcov()) {
+                    error!("Failed to write coverage.info: {}", e);
+                }
+                let cov_json_path = output_dir.join("coverage.json");
+                match std::fs::File::create(&cov_json_path) {
+                    Ok(f) => {
+                        if let Err(e) = serde_json::to_writer_pretty(f, report) {
+                            error!("Failed to write coverage.json: {}", e);
                         }
-                        info!(
-                            "Coverage: {}/{} statements ({:.1}%), {}/{} branches ({:.1}%)",
-                            report.covered_statements,
-                            report.total_statements,
-                            report.statement_percent(),
-                            report.covered_branches,
-                            report.total_branches,
-                            report.branch_percent()
-                        );
-                        coverage_summary = Some(labwired_cli::manifest::CoverageSummary {
-                            statements_total: report.total_statements,
-                            statements_covered: report.covered_statements,
-                            branches_total: report.total_branches,
-                            branches_covered: report.covered_branches,
-                        });
                     }
-                    Err(e) => error!("Failed to load symbols for coverage: {}", e),
+                    Err(e) => error!("Failed to create coverage.json: {}", e),
                 }
+                coverage_summary = Some(labwired_cli::manifest::CoverageSummary {
+                    statements_total: report.total_statements,
+                    statements_covered: report.covered_statements,
+                    branches_total: report.total_branches,
+                    branches_covered: report.covered_branches,
+                });
             }
 
             // run-manifest.json (signable, reproducible)
@@ -2215,6 +2397,7 @@ fn write_outputs<C: labwired_core::Cpu>(
                     system: result.config.system.clone(),
                     script: result.config.script.clone(),
                 },
+                fault: result.fault,
             };
             match std::fs::File::create(&snapshot_path) {
                 Ok(f) => {
@@ -2227,8 +2410,7 @@ fn write_outputs<C: labwired_core::Cpu>(
 
             // uart.log
             let uart_path = output_dir.join("uart.log");
-            let bytes = uart_tx.lock().map(|g| g.clone()).unwrap_or_default();
-            if let Err(e) = std::fs::write(&uart_path, bytes) {
+            if let Err(e) = std::fs::write(&uart_path, &uart_bytes) {
                 error!("Failed to write uart.log: {}", e);
             }
 
@@ -2248,6 +2430,7 @@ fn write_outputs<C: labwired_core::Cpu>(
                 result.instructions,
                 &result.limits,
                 &result.stop_reason_details,
+                &uart_bytes,
             ) {
                 error!("Failed to write junit.xml: {}", e);
             }
@@ -2272,10 +2455,27 @@ fn write_outputs<C: labwired_core::Cpu>(
             result.instructions,
             &result.limits,
             &result.stop_reason_details,
+            &uart_bytes,
         ) {
             error!("Failed to write JUnit report {:?}: {}", junit_path, e);
         }
     }
+
+    if let Some(tap_path) = &args.tap {
+        if let Some(parent) = tap_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = write_tap(
+            tap_path,
+            status,
+            &result.stop_reason,
+            &assertions_for_junit,
+            result.message.as_deref(),
+            &result.stop_reason_details,
+        ) {
+            error!("Failed to write TAP report {:?}: {}", tap_path, e);
+        }
+    }
 }
 
 pub(crate) fn write_config_error_outputs(
@@ -2295,6 +2495,8 @@ pub(crate) fn write_config_error_outputs(
         }
         None => String::new(),
     };
+    let (firmware_id, firmware_id_kind) =
+        firmware_identity(firmware_bytes.unwrap_or_default(), &firmware_hash);
 
     let resolved_limits = limits.cloned().unwrap_or(TestLimits {
         max_steps: 0,
@@ -2306,6 +2508,8 @@ pub(crate) fn write_config_error_outputs(
         stop_when_assertions_pass: false,
         stop_when_assertions_pass_settle_steps: 0,
         stop_when_assertions_pass_min_steps: 0,
+        loop_detect_repeats: None,
+        loop_detect_window: None,
     });
 
     let stop_reason = StopReason::ConfigError;
@@ -2333,6 +2537,8 @@ pub(crate) fn write_config_error_outputs(
         assertions: vec![],
         cpu_state: None,
         firmware_hash,
+        firmware_id,
+        firmware_id_kind,
         config: TestConfig {
             firmware: firmware_path.cloned().unwrap_or_default(),
             system: system_path.cloned(),
@@ -2343,6 +2549,9 @@ pub(crate) fn write_config_error_outputs(
         fidelity: Vec::new(),
         // Nor any logic-analyzer edges — capture never armed.
         logic_edges: None,
+        fault: None,
+        energy_joules: None,
+        dry_run: false,
     };
 
     if let Some(output_dir) = &args.output_dir {
@@ -2399,6 +2608,7 @@ pub(crate) fn write_config_error_outputs(
                 0,
                 &result.limits,
                 &result.stop_reason_details,
+                &[],
             ) {
                 error!("Failed to write junit.xml: {}", e);
             }
@@ -2423,10 +2633,156 @@ pub(crate) fn write_config_error_outputs(
             0,
             &result.limits,
             &result.stop_reason_details,
+            &[],
         ) {
             error!("Failed to write JUnit report {:?}: {}", junit_path, e);
         }
     }
+
+    if let Some(tap_path) = &args.tap {
+        if let Some(parent) = tap_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = write_tap(
+            tap_path,
+            "error",
+            &result.stop_reason,
+            &[],
+            result.message.as_deref(),
+            &result.stop_reason_details,
+        ) {
+            error!("Failed to write TAP report {:?}: {}", tap_path, e);
+        }
+    }
+}
+
+/// Map the observed PCs onto the firmware's DWARF statement rows and branch
+/// sites. `None` (after logging) when the ELF's symbols cannot be loaded.
+fn build_coverage_report(
+    cov: &labwired_core::pc_coverage::PcCoverageObserver,
+    firmware_path: &Path,
+) -> Option<labwired_cli::pc_coverage_report::CoverageReport> {
+    let symbols = match labwired_loader::SymbolProvider::new(firmware_path) {
+        Ok(symbols) => symbols,
+        Err(e) => {
+            error!("Failed to load symbols for coverage: {}", e);
+            return None;
+        }
+    };
+    let mut report =
+        labwired_cli::pc_coverage_report::CoverageReport::build(symbols.statement_rows(), |addr| {
+            cov.was_executed(addr as u32)
+        });
+    // Resolve each observed branch site to its source line.
+    let branch_cov = cov
+        .branch_sites()
+        .into_iter()
+        .filter_map(|(src, counts)| {
+            symbols.lookup(src as u64).and_then(|loc| {
+                loc.line.map(|line| {
+                    // statement_rows uses the line-program file basename;
+                    // lookup() returns the full path. Normalise to the basename
+                    // so branches attach to the right SF.
+                    let file = loc.file.rsplit('/').next().unwrap_or(&loc.file).to_string();
+                    labwired_cli::pc_coverage_report::BranchCoverage {
+                        file,
+                        line,
+                        taken: counts.taken,
+                        not_taken: counts.not_taken,
+                    }
+                })
+            })
+        })
+        .collect();
+    report.set_branches(branch_cov);
+    info!(
+        "Coverage: {}/{} statements ({:.1}%), {}/{} branches ({:.1}%)",
+        report.covered_statements,
+        report.total_statements,
+        report.statement_percent(),
+        report.covered_branches,
+        report.total_branches,
+        report.branch_percent()
+    );
+    Some(report)
+}
+
+/// `--dry-run` epilogue: the machine is built, loaded and reset, so report a
+/// pass without stepping it. Only result.json and an empty uart.log are
+/// written — there is no run for the other artifacts to describe.
+pub(crate) fn write_dry_run_outputs<C: labwired_core::Cpu>(
+    args: &TestArgs,
+    cpu: &C,
+    firmware_path: &Path,
+    system_path: Option<&PathBuf>,
+    firmware_bytes: &[u8],
+    limits: &TestLimits,
+) -> ExitCode {
+    let mut hasher = Sha256::new();
+    hasher.update(firmware_bytes);
+    let firmware_hash = format!("{:x}", hasher.finalize());
+    let (firmware_id, firmware_id_kind) = firmware_identity(firmware_bytes, &firmware_hash);
+
+    let stop_reason = StopReason::DryRun;
+    let stop_reason_details = build_stop_reason_details(
+        &stop_reason,
+        limits,
+        0,
+        0,
+        0,
+        0,
+        std::time::Duration::from_secs(0),
+        0, // vcd_bytes
+    );
+    let result = TestResult {
+        result_schema_version: RESULT_SCHEMA_VERSION.to_string(),
+        status: "pass".to_string(),
+        steps_executed: 0,
+        cycles: 0,
+        instructions: 0,
+        stop_reason,
+        stop_reason_details,
+        limits: limits.clone(),
+        message: None,
+        assertions: vec![],
+        cpu_state: Some(cpu.snapshot()),
+        firmware_hash,
+        firmware_id,
+        firmware_id_kind,
+        config: TestConfig {
+            firmware: firmware_path.to_path_buf(),
+            system: system_path.cloned(),
+            script: args.script.clone(),
+        },
+        inspect: None,
+        fidelity: Vec::new(),
+        logic_edges: None,
+        fault: None,
+        energy_joules: None,
+        dry_run: true,
+    };
+    info!("Dry run: setup is valid, simulation not started");
+
+    if let Some(output_dir) = &args.output_dir {
+        if let Err(e) = std::fs::create_dir_all(output_dir) {
+            error!("Failed to create output directory {:?}: {}", output_dir, e);
+        } else {
+            let result_path = output_dir.join("result.json");
+            match std::fs::File::create(&result_path) {
+                Ok(f) => {
+                    if let Err(e) = serde_json::to_writer_pretty(f, &result) {
+                        error!("Failed to write result.json: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to create result.json: {}", e),
+            }
+            if let Err(e) = std::fs::write(output_dir.join("uart.log"), b"") {
+                error!("Failed to write uart.log: {}", e);
+            }
+        }
+    }
+
+    ExitCode::from(EXIT_PASS)
 }
 
 fn resolve_script_path(script_path: &Path, value: &str) -> PathBuf {
@@ -2448,6 +2804,36 @@ fn xml_escape(s: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// Cap on the UART text attached to the JUnit "run" testcase's
+/// `<system-out>`. Chatty firmware can emit megabytes; CI UIs only need the
+/// tail to make a failure debuggable.
+const JUNIT_MAX_SYSTEM_OUT_BYTES: usize = 64 * 1024;
+
+/// Cap on the UART slice attached to each failing UART assertion.
+const JUNIT_MAX_ASSERTION_OUT_BYTES: usize = 4 * 1024;
+
+/// Render the tail of the captured UART as XML-safe text of at most
+/// `max_bytes` (plus a truncation marker). Control characters that XML 1.0
+/// cannot carry (NUL, ANSI ESC, …) are replaced with U+FFFD so a stray byte
+/// never makes the whole report unparseable.
+fn junit_uart_excerpt(uart: &[u8], max_bytes: usize) -> String {
+    let text = String::from_utf8_lossy(uart);
+    let mut start = text.len().saturating_sub(max_bytes);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let mut out = String::new();
+    if start > 0 {
+        out.push_str(&format!("[... {} bytes truncated ...]\n", start));
+    }
+    out.extend(text[start..].chars().map(|c| match c {
+        '\t' | '\n' | '\r' => c,
+        c if c.is_control() => '\u{FFFD}',
+        c => c,
+    }));
+    xml_escape(&out)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn write_junit_xml(
     path: &Path,
@@ -2463,6 +2849,7 @@ pub(crate) fn write_junit_xml(
     instructions: u64,
     limits: &TestLimits,
     stop_reason_details: &StopReasonDetails,
+    uart: &[u8],
 ) -> std::io::Result<()> {
     let any_assertion_failed = assertions.iter().any(|a| !a.passed);
     let any_expected_stop_reason_matched = assertions
@@ -2470,7 +2857,10 @@ pub(crate) fn write_junit_xml(
         .any(|a| matches!(a.assertion, TestAssertion::ExpectedStopReason(_)) && a.passed);
     let stop_requires_assertion = matches!(
         stop_reason,
-        StopReason::WallTime | StopReason::MaxUartBytes | StopReason::NoProgress
+        StopReason::WallTime
+            | StopReason::MaxUartBytes
+            | StopReason::NoProgress
+            | StopReason::SleepDeadlock
     );
 
     let mut details = String::new();
@@ -2573,6 +2963,12 @@ pub(crate) fn write_junit_xml(
             xml_escape(&details)
         ));
     }
+    if !uart.is_empty() {
+        testcases.push_str(&format!(
+            "    <system-out>{}</system-out>\n",
+            junit_uart_excerpt(uart, JUNIT_MAX_SYSTEM_OUT_BYTES)
+        ));
+    }
     testcases.push_str("  </testcase>\n");
 
     // One testcase per assertion so CI UIs show exactly which assertion failed.
@@ -2584,8 +2980,9 @@ pub(crate) fn write_junit_xml(
             assertion_short_name(&a.assertion)
         );
         testcases.push_str(&format!(
-            "  <testcase classname=\"labwired\" name=\"{}\" time=\"0.000000\">\n",
-            xml_escape(&name)
+            "  <testcase classname=\"labwired\" name=\"{}\" time=\"{:.6}\">\n",
+            xml_escape(&name),
+            a.elapsed.as_secs_f64()
         ));
         if !a.passed {
             failures += 1;
@@ -2593,6 +2990,18 @@ pub(crate) fn write_junit_xml(
                 "    <failure message=\"assertion failed\">{}</failure>\n",
                 xml_escape(&format!("{}\n\n{}", name, details))
             ));
+            // A failing UART assertion is only debuggable next to what the
+            // firmware actually printed, so attach the tail of the capture.
+            let uart_assertion = matches!(
+                a.assertion,
+                TestAssertion::UartContains(_) | TestAssertion::UartRegex(_)
+            );
+            if uart_assertion && !uart.is_empty() {
+                testcases.push_str(&format!(
+                    "    <system-out>{}</system-out>\n",
+                    junit_uart_excerpt(uart, JUNIT_MAX_ASSERTION_OUT_BYTES)
+                ));
+            }
         }
         testcases.push_str("  </testcase>\n");
     }
@@ -2622,25 +3031,106 @@ pub(crate) fn write_junit_xml(
     std::fs::write(path, xml)
 }
 
+/// Write a TAP version 13 report: the plan covers exactly one point per
+/// assertion, and every `not ok` carries a YAML diagnostic block with the stop
+/// reason and the triggered limit / observed value. Run-level failures that no
+/// assertion accounts for (runtime/config errors, a safety stop without
+/// `expected_stop_reason`) end the stream with `Bail out!` so TAP consumers
+/// never read them as a pass.
+pub(crate) fn write_tap(
+    path: &Path,
+    status: &str,
+    stop_reason: &StopReason,
+    assertions: &[AssertionResult],
+    message: Option<&str>,
+    stop_reason_details: &StopReasonDetails,
+) -> std::io::Result<()> {
+    // JSON strings are valid YAML scalars, which sidesteps YAML quoting rules.
+    fn yaml_str(s: &str) -> String {
+        serde_json::to_string(s).unwrap_or_else(|_| "\"\"".to_string())
+    }
+    let stop_reason_str = serde_json::to_value(stop_reason)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", stop_reason));
+
+    let mut tap = String::new();
+    tap.push_str("TAP version 13\n");
+    tap.push_str(&format!("1..{}\n", assertions.len()));
+    for (idx, a) in assertions.iter().enumerate() {
+        // `#` would start a TAP directive (SKIP/TODO) inside the description.
+        let description = assertion_short_name(&a.assertion)
+            .replace('\n', " ")
+            .replace('#', "\\#");
+        if a.passed {
+            tap.push_str(&format!("ok {} - {}\n", idx + 1, description));
+            continue;
+        }
+        tap.push_str(&format!("not ok {} - {}\n", idx + 1, description));
+        tap.push_str("  ---\n");
+        tap.push_str("  message: \"assertion failed\"\n");
+        tap.push_str(&format!("  stop_reason: {}\n", yaml_str(&stop_reason_str)));
+        if let Some(t) = &stop_reason_details.triggered_limit {
+            tap.push_str("  triggered_limit:\n");
+            tap.push_str(&format!("    name: {}\n", yaml_str(&t.name)));
+            tap.push_str(&format!("    value: {}\n", t.value));
+        }
+        if let Some(o) = &stop_reason_details.observed {
+            tap.push_str("  observed:\n");
+            tap.push_str(&format!("    name: {}\n", yaml_str(&o.name)));
+            tap.push_str(&format!("    value: {}\n", o.value));
+        }
+        tap.push_str("  ...\n");
+    }
+
+    let any_assertion_failed = assertions.iter().any(|a| !a.passed);
+    if status == "error" {
+        let kind = if *stop_reason == StopReason::ConfigError {
+            "config error"
+        } else {
+            "runtime error"
+        };
+        match message {
+            Some(msg) => tap.push_str(&format!("Bail out! {}: {}\n", kind, msg.replace('\n', " "))),
+            None => tap.push_str(&format!("Bail out! {} ({})\n", kind, stop_reason_str)),
+        }
+    } else if status == "fail" && !any_assertion_failed {
+        tap.push_str(&format!(
+            "Bail out! stop condition {} requires expected_stop_reason assertion\n",
+            stop_reason_str
+        ));
+    }
+
+    std::fs::write(path, tap)
+}
+
+/// Report label for an assertion: its schema 1.1 `name` when set, else a
+/// summary of what it checks, followed by any tags (`boot banner [smoke]`).
 fn assertion_short_name(assertion: &TestAssertion) -> String {
     const MAX_LEN: usize = 120;
-    let s = match assertion {
-        TestAssertion::UartContains(a) => format!("uart_contains: {}", a.uart_contains),
-        TestAssertion::UartRegex(a) => format!("uart_regex: {}", a.uart_regex),
-        TestAssertion::ExpectedStopReason(a) => {
+    let s = match (assertion.name(), assertion) {
+        (Some(name), _) => name.to_string(),
+        (None, TestAssertion::UartContains(a)) => format!("uart_contains: {}", a.uart_contains),
+        (None, TestAssertion::UartRegex(a)) => format!("uart_regex: {}", a.uart_regex),
+        (None, TestAssertion::ExpectedStopReason(a)) => {
             format!("expected_stop_reason: {:?}", a.expected_stop_reason)
         }
-        TestAssertion::MemoryValue(a) => format!(
+        (None, TestAssertion::MemoryValue(a)) => format!(
             "memory_value: @{:#x}={:#x}",
             a.memory_value.address, a.memory_value.expected_value
         ),
-        TestAssertion::UdsTester(a) => {
+        (None, TestAssertion::UdsTester(a)) => {
             format!(
                 "uds_tester: {} result={:?}",
                 a.uds_tester.id, a.uds_tester.result
             )
         }
     };
+    let s = if assertion.tags().is_empty() {
+        s
+    } else {
+        format!("{} [{}]", s, assertion.tags().join(", "))
+    };
 
     if s.len() <= MAX_LEN {
         return s;
@@ -2669,6 +3159,145 @@ pub(crate) fn evaluate_uds_tester(
     }
 }
 
+/// Decode `bytes` onto `text`, returning how many were consumed. Invalid
+/// bytes become U+FFFD; only a multi-byte sequence cut off at the end is left
+/// for the next call, so a stream decoded in chunks reads the same as the
+/// whole buffer decoded at once.
+fn append_utf8_lossy(text: &mut String, mut bytes: &[u8]) -> usize {
+    let len = bytes.len();
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                text.push_str(valid);
+                return len;
+            }
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                text.push_str(&String::from_utf8_lossy(valid));
+                match e.error_len() {
+                    Some(invalid) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        bytes = &rest[invalid..];
+                    }
+                    None => return len - rest.len(),
+                }
+            }
+        }
+    }
+}
+
+/// `text` with ANSI escape sequences removed: CSI (`ESC [ ... final`), OSC
+/// (`ESC ] ... BEL` or `ESC ] ... ESC \\`) and other `ESC` escapes, whose
+/// intermediate bytes (0x20-0x2F, e.g. the `(` of `ESC ( B`) are followed by
+/// one final byte (0x30-0x7E). Borrows when there is no `ESC` to strip.
+fn strip_ansi_escapes(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                }
+            }
+            Some(c) if ('\x20'..='\x2f').contains(&c) => {
+                while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+                chars.next_if(|c| ('\x30'..='\x7e').contains(c));
+            }
+            // A lone final byte: already consumed.
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// The captured UART text as a `uart_contains`/`uart_regex` assertion sees
+/// it: ANSI escapes removed first, then `\r\n` folded to `\n`, each only when
+/// the assertion asks for it.
+fn uart_assertion_text(text: &str, options: UartTextOptions) -> Cow<'_, str> {
+    let mut text = if options.strip_ansi {
+        strip_ansi_escapes(text)
+    } else {
+        Cow::Borrowed(text)
+    };
+    if options.normalize_newlines && text.contains("\r\n") {
+        text = Cow::Owned(text.replace("\r\n", "\n"));
+    }
+    text
+}
+
+/// Evaluate a `uart_contains`/`uart_regex` assertion (`false` for any other
+/// kind). `invalid_utf8_at` is the offset of the first invalid byte in the raw
+/// capture, if any; it fails `strict_utf8` assertions outright.
+fn uart_assertion_passes(
+    assertion: &TestAssertion,
+    uart_text: &str,
+    invalid_utf8_at: Option<usize>,
+) -> bool {
+    let (pattern, is_regex) = match assertion {
+        TestAssertion::UartContains(a) => (a.uart_contains.as_str(), false),
+        TestAssertion::UartRegex(a) => (a.uart_regex.as_str(), true),
+        _ => return false,
+    };
+    let options = assertion.uart_text_options();
+    if options.strict_utf8 {
+        if let Some(offset) = invalid_utf8_at {
+            error!(
+                "UART output is not valid UTF-8 (first invalid byte at offset {}); strict_utf8 assertion fails",
+                offset
+            );
+            return false;
+        }
+    }
+    let text = uart_assertion_text(uart_text, options);
+    let (after, before) = assertion.uart_window();
+    uart_window(&text, after, before).is_some_and(|w| {
+        if is_regex {
+            simple_regex_is_match(pattern, w)
+        } else {
+            w.contains(pattern)
+        }
+    })
+}
+
+/// The part of the UART output a windowed assertion matches against: the text
+/// after the first `after` marker, cut at the first `before` marker that
+/// follows it. `None` when `after` is set but never appeared, so the assertion
+/// fails rather than matching an empty window.
+fn uart_window<'a>(text: &'a str, after: Option<&str>, before: Option<&str>) -> Option<&'a str> {
+    let mut window = text;
+    if let Some(marker) = after {
+        let start = window.find(marker)? + marker.len();
+        window = &window[start..];
+    }
+    if let Some(marker) = before {
+        if let Some(end) = window.find(marker) {
+            window = &window[..end];
+        }
+    }
+    Some(window)
+}
+
 // Minimal regex matcher supporting: '^' anchor, '$' anchor, '.' and '*' (Kleene star).
 // This is intentionally small to avoid introducing new deps; it does not implement full PCRE/Rust regex.
 pub(crate) fn simple_regex_is_match(pattern: &str, text: &str) -> bool {
@@ -2777,6 +3406,138 @@ mod tests {
         assert!(err.contains("ghost-tester"), "missing id in: {err}");
     }
 
+    #[test]
+    fn strip_ansi_lets_a_coloured_pass_match_plain_text() {
+        let uart = "\x1b[1;32mPASS\x1b[0m: 3 tests\r\n\x1b]0;title\x07done\x1b(B\n";
+        assert!(!uart.contains("PASS: 3 tests"));
+
+        let plain = strip_ansi_escapes(uart);
+        assert_eq!(plain, "PASS: 3 tests\r\ndone\n");
+        assert!(plain.contains("PASS: 3 tests"));
+        // Intermediate bytes run up to one final byte; `ESC M` has none.
+        assert_eq!(strip_ansi_escapes("a\x1b#8b\x1b%@c\x1bMd"), "abcd");
+        assert!(matches!(strip_ansi_escapes("no escapes"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn script_strip_ansi_decides_whether_a_coloured_pass_matches() {
+        let script: labwired_config::TestScript = serde_yaml::from_str(
+            r#"
+schema_version: "1.2"
+inputs:
+  firmware: "fw.elf"
+limits:
+  max_steps: 1000
+assertions:
+  - uart_contains: "PASS: 3 tests"
+  - uart_contains: "PASS: 3 tests"
+    strip_ansi: true
+"#,
+        )
+        .unwrap();
+        script.validate().unwrap();
+        let uart = "\x1b[1;32mPASS\x1b[0m: 3 tests\r\n";
+
+        let [raw, stripped] = &script.assertions[..] else {
+            panic!("expected two assertions");
+        };
+        assert!(!uart_assertion_passes(raw, uart, None));
+        assert!(uart_assertion_passes(stripped, uart, None));
+    }
+
+    #[test]
+    fn normalize_newlines_lets_crlf_output_match_lf_patterns() {
+        let plain: TestAssertion = serde_yaml::from_str("uart_contains: \"BOOT\\nOK\"").unwrap();
+        let normalized: TestAssertion =
+            serde_yaml::from_str("uart_contains: \"BOOT\\nOK\"\nnormalize_newlines: true").unwrap();
+        let uart = "BOOT\r\nOK\r\n";
+
+        assert!(!uart_assertion_passes(&plain, uart, None));
+        assert!(uart_assertion_passes(&normalized, uart, None));
+        assert_eq!(
+            uart_assertion_text(uart, normalized.uart_text_options()),
+            "BOOT\nOK\n"
+        );
+    }
+
+    #[test]
+    fn strict_utf8_fails_on_invalid_uart_bytes() {
+        let raw = b"PASS \xff\n";
+        let invalid_utf8_at = std::str::from_utf8(raw).err().map(|e| e.valid_up_to());
+        assert_eq!(invalid_utf8_at, Some(5));
+        let uart = String::from_utf8_lossy(raw);
+
+        let lenient: TestAssertion = serde_yaml::from_str("uart_contains: PASS").unwrap();
+        let strict: TestAssertion =
+            serde_yaml::from_str("uart_contains: PASS\nstrict_utf8: true").unwrap();
+        assert!(uart_assertion_passes(&lenient, &uart, invalid_utf8_at));
+        assert!(!uart_assertion_passes(&strict, &uart, invalid_utf8_at));
+        assert!(uart_assertion_passes(&strict, "PASS\n", None));
+    }
+
+    #[test]
+    fn append_utf8_lossy_holds_back_a_split_sequence() {
+        let stream = "DONE \u{2713}\n".as_bytes();
+        // Cut inside the three-byte check mark.
+        let head = &stream[..stream.len() - 2];
+        let mut text = String::new();
+        let consumed = append_utf8_lossy(&mut text, head);
+        assert_eq!(text, "DONE ");
+        let consumed = consumed + append_utf8_lossy(&mut text, &stream[consumed..]);
+        assert_eq!(consumed, stream.len());
+        assert_eq!(text, "DONE \u{2713}\n");
+    }
+
+    #[test]
+    fn append_utf8_lossy_replaces_an_invalid_byte_before_a_split_sequence() {
+        let mut stream = b"OK \xFF ".to_vec();
+        stream.extend_from_slice("\u{2713}".as_bytes());
+        // The 0xFF is replaced; the check mark cut after its first byte waits.
+        let head = &stream[..stream.len() - 2];
+        let mut text = String::new();
+        let consumed = append_utf8_lossy(&mut text, head);
+        assert_eq!(consumed, head.len() - 1);
+        assert_eq!(text, "OK \u{FFFD} ");
+        let consumed = consumed + append_utf8_lossy(&mut text, &stream[consumed..]);
+        assert_eq!(consumed, stream.len());
+        assert_eq!(text, String::from_utf8_lossy(&stream));
+    }
+
+    #[test]
+    fn uart_window_restricts_matches_to_the_marked_phase() {
+        let uart = "BOOT\nphase1 OK\nRESET\nphase2 FAIL\nDONE\n";
+
+        // "OK" was printed, but only before the RESET banner.
+        assert!(uart.contains("OK"));
+        let after_reset = uart_window(uart, Some("RESET"), None).unwrap();
+        assert!(!after_reset.contains("OK"));
+        assert!(after_reset.contains("phase2 FAIL"));
+
+        let before_reset = uart_window(uart, None, Some("RESET")).unwrap();
+        assert!(before_reset.contains("phase1 OK"));
+        assert!(!before_reset.contains("phase2"));
+
+        let between = uart_window(uart, Some("RESET"), Some("DONE")).unwrap();
+        assert_eq!(between, "\nphase2 FAIL\n");
+        assert!(simple_regex_is_match("^.phase2", between));
+
+        // A missing `after` marker fails; a missing `before` leaves it open.
+        assert_eq!(uart_window(uart, Some("REBOOT"), None), None);
+        assert_eq!(uart_window(uart, None, Some("HALT")), Some(uart));
+    }
+
+    #[test]
+    fn junit_uart_excerpt_keeps_tail_and_sanitizes() {
+        let uart = b"first line\nsecond <line>\x1b[0m\n";
+        let full = junit_uart_excerpt(uart, 1024);
+        assert_eq!(full, "first line\nsecond &lt;line&gt;\u{FFFD}[0m\n");
+
+        let tail = junit_uart_excerpt(uart, 8);
+        assert!(tail.starts_with("[... "), "{tail}");
+        assert!(tail.ends_with("[0m\n"), "{tail}");
+        assert!(!tail.contains("first line"), "{tail}");
+    }
+
     #[test]
     fn config_error_snapshot_keeps_serde_tag() {
         let snapshot = crate::artifacts::Snapshot::ConfigError {
@@ -2785,6 +3546,7 @@ mod tests {
                 triggered_stop_condition: StopReason::ConfigError,
                 triggered_limit: None,
                 observed: None,
+                loop_addresses: Vec::new(),
             },
             limits: TestLimits {
                 max_steps: 1,
@@ -2796,6 +3558,8 @@ mod tests {
                 stop_when_assertions_pass: false,
                 stop_when_assertions_pass_settle_steps: 0,
                 stop_when_assertions_pass_min_steps: 0,
+                loop_detect_repeats: None,
+                loop_detect_window: None,
             },
             config: crate::artifacts::TestConfig {
                 firmware: std::path::PathBuf::from("firmware.elf"),
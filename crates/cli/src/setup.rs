@@ -0,0 +1,57 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Apply a test script's `setup` block (schema_version 1.1) to a built machine.
+//!
+//! Runs after `load_firmware` and before the first step, so register writes
+//! override the reset state (vector-table SP/PC included) and memory pokes
+//! land on top of the loaded image. Unknown registers/peripherals are errors:
+//! a typo must not silently leave the machine in its default state.
+
+use labwired_config::TestSetup;
+use labwired_core::{Bus, Cpu, Machine};
+
+/// Apply registers, then memory writes, then interrupt pends, in that order.
+pub fn apply_setup<C: Cpu>(machine: &mut Machine<C>, setup: &TestSetup) -> Result<(), String> {
+    for (name, value) in &setup.registers {
+        let id = machine.cpu.index_of_register(name).ok_or_else(|| {
+            format!(
+                "setup.registers: unknown register '{}' (known: {})",
+                name,
+                machine.cpu.get_register_names().join(", ")
+            )
+        })?;
+        machine.cpu.set_register(id, *value);
+    }
+
+    for (i, write) in setup.memory.iter().enumerate() {
+        for (offset, byte) in write.bytes.iter().enumerate() {
+            let addr = write.address + offset as u64;
+            machine
+                .bus
+                .write_u8(addr, *byte)
+                .map_err(|e| format!("setup.memory[{}]: write at {:#x} failed: {}", i, addr, e))?;
+        }
+    }
+
+    let mut fallthrough = Vec::new();
+    for name in &setup.inject_irq {
+        let idx = machine
+            .bus
+            .find_peripheral_index_by_name(name)
+            .ok_or_else(|| format!("setup.inject_irq: peripheral '{}' not found", name))?;
+        let irq = machine.bus.peripherals[idx]
+            .irq
+            .ok_or_else(|| format!("setup.inject_irq: peripheral '{}' has no irq line", name))?;
+        // Same delivery as an event-raised IRQ: pended in the NVIC, and made
+        // CPU-visible immediately when the line is already enabled.
+        machine.bus.pend_irq_for_event(irq, &mut fallthrough);
+    }
+    for exc in fallthrough {
+        machine.cpu.set_exception_pending(exc);
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::process::Command;
+
+#[test]
+fn bench_reports_ips_and_timestamp_as_json() {
+    let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .canonicalize()
+        .unwrap();
+    let elf_path = root.join("tests/fixtures/uart-ok-thumbv7m.elf");
+    let out_path = std::env::temp_dir().join(format!("labwired-bench-{}.json", std::process::id()));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args(["bench", "--firmware", elf_path.to_str().unwrap()])
+        .args([
+            "--duration-ms",
+            "200",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run labwired bench");
+    assert!(
+        output.status.success(),
+        "labwired bench failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .rfind(|l| l.starts_with('{'))
+        .unwrap_or_else(|| panic!("no JSON record in stdout: {stdout}"));
+    let json: serde_json::Value = serde_json::from_str(line).expect("bench output is JSON");
+    assert!(json["ips"].as_f64().unwrap() > 0.0, "{json}");
+    assert!(json["instructions"].as_u64().unwrap() > 0, "{json}");
+    assert!(json["timestamp"].as_u64().unwrap() > 0, "{json}");
+    assert!(json["peripheral_cycles"].is_object(), "{json}");
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&out_path).unwrap()).unwrap();
+    assert_eq!(written, json);
+    std::fs::remove_file(&out_path).ok();
+}
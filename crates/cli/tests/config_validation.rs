@@ -103,8 +103,8 @@ fn test_stm32f401cdu6_chip_loads_with_i2c1() {
     let chip = ChipDescriptor::from_file(&chip_path).expect("F401CDU6 chip yaml failed to parse");
 
     assert_eq!(chip.name, "stm32f401cdu6");
-    assert_eq!(chip.flash.size, "384KB");
-    assert_eq!(chip.ram.size, "96KB");
+    assert_eq!(chip.flash.size.to_string(), "384KB");
+    assert_eq!(chip.ram.size.to_string(), "96KB");
     assert!(
         chip.peripherals
             .iter()
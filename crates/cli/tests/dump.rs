@@ -0,0 +1,35 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::process::Command;
+
+#[test]
+fn dump_after_steps_shows_bytes_written_by_firmware() {
+    let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .canonicalize()
+        .unwrap();
+    let elf_path = root.join("tests/fixtures/uart-ok-thumbv7m.elf");
+
+    // By step 20 `main` has run `push {r7, lr}`, leaving the return address
+    // 0x00000427 at the top of the stack.
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args(["dump", "--firmware", elf_path.to_str().unwrap()])
+        .args(["--at-step", "20", "--address", "0x2001FFF8", "--len", "8"])
+        .output()
+        .expect("Failed to run labwired dump");
+    assert!(
+        output.status.success(),
+        "labwired dump failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0x2001fff8:"), "{stdout}");
+    assert!(stdout.contains("27 04 00 00"), "{stdout}");
+    assert!(stdout.contains("PC"), "{stdout}");
+    assert!(stdout.contains("SP   = 0x2001fff8"), "{stdout}");
+}
@@ -0,0 +1,91 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const FIXTURE: &str = "../../tests/fixtures/uart-ok-thumbv7m.elf";
+
+#[test]
+fn pc_coverage_includes_main() {
+    let fw_bytes = std::fs::read(FIXTURE).unwrap();
+    let main = labwired_loader::resolve_symbol_in_elf(&fw_bytes, "main")
+        .expect("fixture exports main")
+        & !1;
+
+    let program = labwired_loader::load_elf(Path::new(FIXTURE)).unwrap();
+    let mut bus = labwired_core::bus::SystemBus::new();
+    let (cpu, _nvic) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+    let mut machine = labwired_core::Machine::new(cpu, bus);
+    machine.load_firmware(&program).unwrap();
+    let cov = Arc::new(labwired_core::pc_coverage::PcCoverageObserver::new());
+    machine.observers.push(cov.clone());
+
+    for _ in 0..10_000 {
+        if machine.step().is_err() {
+            break;
+        }
+    }
+
+    assert!(cov.was_executed(main), "main @ {main:#x} never executed");
+    // `main` is 26 bytes in the fixture; more than its first instruction ran.
+    let in_main = cov
+        .covered_addresses()
+        .into_iter()
+        .filter(|&a| (main..main + 26).contains(&a))
+        .count();
+    assert!(in_main > 1, "only {in_main} address(es) of main covered");
+}
+
+#[test]
+fn coverage_flag_writes_lcov_to_the_given_path() {
+    let fw_abs = std::fs::canonicalize(FIXTURE).unwrap();
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("labwired-tests-coverage-{}", nonce));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("script.yaml");
+    std::fs::write(
+        &script,
+        format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 1000
+assertions: []
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+    let lcov = dir.join("reports").join("out.info");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--coverage",
+            lcov.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(lcov.exists(), "no LCOV report at {lcov:?}");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
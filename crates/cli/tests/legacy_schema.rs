@@ -56,3 +56,153 @@ assertions: []
 
     assert_eq!(output.status.code(), Some(0));
 }
+
+#[test]
+fn migrate_script_rewrites_legacy_v1_as_valid_v1_0() {
+    let legacy = write_temp_file(
+        "legacy-migrate-in",
+        r#"
+schema_version: 1
+firmware: "tests/fixtures/uart-ok-thumbv7m.elf"
+max_steps: 4321
+wall_time_ms: 750
+assertions:
+  - uart_contains: "OK"
+  - expected_stop_reason: max_steps
+"#,
+    );
+    let migrated = legacy.with_extension("migrated.yaml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "migrate-script",
+            legacy.to_str().unwrap(),
+            migrated.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute labwired");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let loaded = labwired_config::load_test_script(&migrated).expect("migrated script loads");
+    let labwired_config::LoadedTestScript::V1_0(script) = loaded else {
+        panic!("migrated script must load as schema_version 1.0");
+    };
+    assert_eq!(script.schema_version, "1.0");
+    assert_eq!(
+        script.inputs.firmware,
+        "tests/fixtures/uart-ok-thumbv7m.elf"
+    );
+    assert_eq!(script.limits.max_steps, 4321);
+    assert_eq!(script.limits.wall_time_ms, Some(750));
+    assert_eq!(script.assertions.len(), 2);
+}
+
+#[test]
+fn migrate_script_requires_firmware_when_legacy_omits_it() {
+    let legacy = write_temp_file(
+        "legacy-migrate-nofw",
+        r#"
+schema_version: 1
+max_steps: 10
+"#,
+    );
+    let migrated = legacy.with_extension("migrated.yaml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "migrate-script",
+            legacy.to_str().unwrap(),
+            migrated.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute labwired");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!migrated.exists());
+}
+
+#[test]
+fn migrate_script_rebases_relative_paths_onto_the_output_directory() {
+    let legacy = write_temp_file(
+        "legacy-migrate-rebase",
+        r#"
+schema_version: 1
+firmware: "fw/app.elf"
+system: "boards/system.yaml"
+max_steps: 10
+"#,
+    );
+    let dir = legacy.parent().unwrap().to_path_buf();
+    let out_dir = dir.join(legacy.file_stem().unwrap()).join("out");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let migrated = out_dir.join("migrated.yaml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "migrate-script",
+            legacy.to_str().unwrap(),
+            migrated.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute labwired");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let loaded = labwired_config::load_test_script(&migrated).expect("migrated script loads");
+    let labwired_config::LoadedTestScript::V1_0(script) = loaded else {
+        panic!("migrated script must load as schema_version 1.0");
+    };
+    assert_eq!(PathBuf::from(&script.inputs.firmware), PathBuf::from("../../fw/app.elf"));
+    assert_eq!(
+        script.inputs.system.map(PathBuf::from),
+        Some(PathBuf::from("../../boards/system.yaml"))
+    );
+}
+
+#[test]
+fn migrate_script_rebases_firmware_flag_from_the_current_directory() {
+    let legacy = write_temp_file(
+        "legacy-migrate-flag",
+        r#"
+schema_version: 1
+max_steps: 10
+"#,
+    );
+    // Canonical, so it matches the child's view of its current directory.
+    let dir = legacy.parent().unwrap().canonicalize().unwrap();
+    let out_dir = dir.join(legacy.file_stem().unwrap());
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .current_dir(&dir)
+        .args([
+            "migrate-script",
+            legacy.to_str().unwrap(),
+            out_dir.join("migrated.yaml").to_str().unwrap(),
+            "--firmware",
+            "build/app.elf",
+        ])
+        .output()
+        .expect("Failed to execute labwired");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let loaded = labwired_config::load_test_script(out_dir.join("migrated.yaml"))
+        .expect("migrated script loads");
+    let labwired_config::LoadedTestScript::V1_0(script) = loaded else {
+        panic!("migrated script must load as schema_version 1.0");
+    };
+    assert_eq!(PathBuf::from(&script.inputs.firmware), PathBuf::from("../build/app.elf"));
+}
@@ -587,3 +587,427 @@ assertions:
         2
     );
 }
+
+#[test]
+fn test_cli_test_mode_junit_attaches_uart_system_out() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let fw_abs = std::fs::canonicalize(root.join("tests/fixtures/uart-ok-thumbv7m.elf")).unwrap();
+    let sys_abs =
+        std::fs::canonicalize(root.join("configs/systems/ci-fixture-uart1.yaml")).unwrap();
+    let script = write_temp_file(
+        "script-junit-system-out",
+        &format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+  system: "{}"
+limits:
+  max_steps: 100000
+assertions:
+  - uart_contains: "OK"
+  - uart_contains: "NEVER_PRESENT"
+"#,
+            fw_abs.to_str().unwrap(),
+            sys_abs.to_str().unwrap()
+        ),
+    );
+
+    let junit_path = std::env::temp_dir().join("labwired-junit-system-out.xml");
+    let _ = std::fs::remove_file(&junit_path);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--junit",
+            junit_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let junit = std::fs::read_to_string(&junit_path).unwrap();
+
+    // The "run" testcase carries the full (capped) capture, and the failing
+    // UART assertion carries its own slice — passing assertions carry none.
+    assert_eq!(junit.matches("<system-out>").count(), 2, "{junit}");
+    let run_case = junit
+        .split("<testcase")
+        .find(|case| case.contains("name=\"run\""))
+        .unwrap();
+    assert!(run_case.contains("<system-out>OK"), "{run_case}");
+    let failing_case = junit
+        .split("<testcase")
+        .find(|case| case.contains("NEVER_PRESENT"))
+        .unwrap();
+    assert!(failing_case.contains("<system-out>OK"), "{failing_case}");
+}
+
+#[test]
+fn test_cli_test_mode_tap_report() {
+    let fw_abs = std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap();
+    let script = write_temp_file(
+        "script-tap",
+        &format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - uart_contains: "NEVER_PRESENT"
+  - expected_stop_reason: max_steps
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    );
+
+    let tap_path = std::env::temp_dir().join("labwired-tap-report.tap");
+    let _ = std::fs::remove_file(&tap_path);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--tap",
+            tap_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let tap = std::fs::read_to_string(&tap_path).unwrap();
+    let lines: Vec<&str> = tap.lines().collect();
+
+    assert_eq!(lines[0], "TAP version 13");
+    assert_eq!(lines[1], "1..2", "plan must match the assertion count");
+    assert_eq!(lines[2], "not ok 1 - uart_contains: NEVER_PRESENT");
+    assert!(tap.contains("  stop_reason: \"max_steps\""), "{tap}");
+    assert!(
+        tap.contains("  triggered_limit:\n    name: \"max_steps\"\n    value: 10"),
+        "{tap}"
+    );
+    assert!(
+        tap.contains("ok 2 - expected_stop_reason: MaxSteps"),
+        "{tap}"
+    );
+    assert!(!tap.contains("Bail out!"), "{tap}");
+}
+
+#[test]
+fn test_cli_test_mode_named_assertions_and_filter_tag() {
+    let fw_abs = std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap();
+    let script = write_temp_file(
+        "script-filter-tag",
+        &format!(
+            r#"
+schema_version: "1.1"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - expected_stop_reason: max_steps
+    name: "runs to the step limit"
+    tags: [smoke]
+  - uart_contains: "NEVER_PRESENT"
+    name: "slow banner"
+    tags: [nightly]
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    );
+
+    let tap_path = std::env::temp_dir().join("labwired-filter-tag.tap");
+    let junit_path = std::env::temp_dir().join("labwired-filter-tag.xml");
+    let _ = std::fs::remove_file(&tap_path);
+    let _ = std::fs::remove_file(&junit_path);
+
+    // Only the `smoke` assertion runs, so the failing `nightly` one is skipped.
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--filter-tag",
+            "smoke",
+            "--tap",
+            tap_path.to_str().unwrap(),
+            "--junit",
+            junit_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let tap = std::fs::read_to_string(&tap_path).unwrap();
+    assert!(tap.contains("1..1\n"), "{tap}");
+    assert!(
+        tap.contains("ok 1 - runs to the step limit [smoke]"),
+        "{tap}"
+    );
+    assert!(!tap.contains("slow banner"), "{tap}");
+
+    let junit = std::fs::read_to_string(&junit_path).unwrap();
+    assert!(
+        junit.contains("name=\"assertion 1: runs to the step limit [smoke]\""),
+        "{junit}"
+    );
+
+    // A filter matching nothing is a config error, never a vacuous pass.
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--filter-tag",
+            "does-not-exist",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_cli_test_mode_filter_tag_keeps_expected_stop_reason() {
+    let fw_abs = std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap();
+    let script = write_temp_file(
+        "script-filter-tag-stop-reason",
+        &format!(
+            r#"
+schema_version: "1.1"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - expected_stop_reason: halt
+    name: "halts on its own"
+  - memory_value:
+      address: 0x20000000
+      expected_value: 0
+      mask: 0
+    name: "always true"
+    tags: [smoke]
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    );
+
+    let tap_path = std::env::temp_dir().join("labwired-filter-tag-stop-reason.tap");
+    let _ = std::fs::remove_file(&tap_path);
+
+    // The untagged stop-reason check still runs, and fails at max_steps.
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--filter-tag",
+            "smoke",
+            "--tap",
+            tap_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+
+    let tap = std::fs::read_to_string(&tap_path).unwrap();
+    assert!(tap.contains("1..2\n"), "{tap}");
+    assert!(tap.contains("not ok 1 - halts on its own"), "{tap}");
+    assert!(tap.contains("ok 2 - always true [smoke]"), "{tap}");
+}
+
+#[test]
+fn test_cli_test_mode_dry_run() {
+    let fw_abs = std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap();
+    let script = write_temp_file(
+        "script-dry-run",
+        &format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - uart_contains: "NEVER_PRESENT"
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    );
+
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let output_dir = std::env::temp_dir().join(format!("labwired-tests-dry-run-{}", nonce));
+
+    // The assertion would fail a real run; a dry run never evaluates it.
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--dry-run",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let result_content = std::fs::read_to_string(output_dir.join("result.json")).unwrap();
+    let result: serde_json::Value = serde_json::from_str(&result_content).unwrap();
+    assert_eq!(result["status"], "pass");
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(result["steps_executed"], 0);
+    assert_eq!(result["stop_reason"], "dry_run");
+
+    let _ = std::fs::remove_dir_all(&output_dir);
+}
+
+#[test]
+fn test_cli_test_mode_dry_run_missing_firmware_is_config_error() {
+    let script = write_temp_file(
+        "script-dry-run-missing-fw",
+        r#"
+schema_version: "1.0"
+inputs:
+  firmware: "does-not-exist.elf"
+limits:
+  max_steps: 10
+assertions: []
+"#,
+    );
+
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let output_dir = std::env::temp_dir().join(format!("labwired-tests-dry-run-missing-{}", nonce));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--dry-run",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2)); // EXIT_CONFIG_ERROR
+
+    let result_content = std::fs::read_to_string(output_dir.join("result.json")).unwrap();
+    let result: serde_json::Value = serde_json::from_str(&result_content).unwrap();
+    assert_eq!(result["status"], "error");
+    assert_eq!(result["stop_reason"], "config_error");
+    assert!(result.get("dry_run").is_none());
+
+    let _ = std::fs::remove_dir_all(&output_dir);
+}
+
+#[test]
+fn test_cli_strict_vector_rejects_sp_outside_ram() {
+    // The fixture's initial SP targets 0x2000_xxxx; this chip puts RAM at
+    // 0x3000_0000, so the booted vector table is implausible.
+    let fw_abs = std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap();
+    let base_dir = std::env::temp_dir()
+        .join("labwired-tests")
+        .join(format!("strict-vector-{}", std::process::id()));
+    let _ = std::fs::create_dir_all(&base_dir);
+
+    std::fs::write(
+        base_dir.join("chip.yaml"),
+        r#"
+name: "ram-elsewhere"
+arch: "cortex-m3"
+flash:
+  base: 0x0
+  size: "64KB"
+ram:
+  base: 0x30000000
+  size: "20KB"
+peripherals: []
+"#,
+    )
+    .unwrap();
+    let system_path = base_dir.join("system.yaml");
+    std::fs::write(
+        &system_path,
+        r#"
+name: "ram-elsewhere-system"
+chip: "chip.yaml"
+"#,
+    )
+    .unwrap();
+    let script = write_temp_file(
+        "script-strict-vector",
+        &format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions: []
+"#,
+            fw_abs.to_str().unwrap()
+        ),
+    );
+
+    let run = |strict: bool, output_dir: &PathBuf| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_labwired"));
+        cmd.args([
+            "test",
+            "--system",
+            system_path.to_str().unwrap(),
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--dry-run",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+        ]);
+        if strict {
+            cmd.arg("--strict-vector");
+        }
+        cmd.output().expect("Failed to execute command")
+    };
+
+    // Without the flag the problem is only a warning.
+    let lenient = run(false, &base_dir.join("lenient"));
+    assert_eq!(lenient.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&lenient.stderr);
+    assert!(stderr.contains("initial SP"), "stderr: {stderr}");
+
+    let strict_dir = base_dir.join("strict");
+    let strict = run(true, &strict_dir);
+    assert_eq!(strict.status.code(), Some(2)); // EXIT_CONFIG_ERROR
+    let result_content = std::fs::read_to_string(strict_dir.join("result.json")).unwrap();
+    let result: serde_json::Value = serde_json::from_str(&result_content).unwrap();
+    assert_eq!(result["stop_reason"], "config_error");
+    assert!(result["message"]
+        .as_str()
+        .is_some_and(|m| m.contains("outside RAM")));
+
+    let _ = std::fs::remove_dir_all(&base_dir);
+}
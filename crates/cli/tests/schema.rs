@@ -0,0 +1,61 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::process::Command;
+
+fn schema_json(kind: &str) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args(["schema", kind])
+        .output()
+        .expect("Failed to run labwired schema");
+    assert!(
+        output.status.success(),
+        "labwired schema {kind} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("schema output is JSON")
+}
+
+/// Follows a local `{"$ref": "#/$defs/Name"}` to its definition.
+fn resolve<'a>(root: &'a serde_json::Value, node: &'a serde_json::Value) -> &'a serde_json::Value {
+    match node.get("$ref").and_then(|r| r.as_str()) {
+        Some(pointer) => root
+            .pointer(pointer.trim_start_matches('#'))
+            .unwrap_or_else(|| panic!("dangling $ref {pointer}")),
+        None => node,
+    }
+}
+
+fn required(node: &serde_json::Value) -> Vec<&str> {
+    node["required"]
+        .as_array()
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_script_schema_requires_limits_max_steps() {
+    let schema = schema_json("test-script");
+    assert!(required(&schema).contains(&"limits"));
+
+    let limits = resolve(&schema, &schema["properties"]["limits"]);
+    assert!(
+        required(limits).contains(&"max_steps"),
+        "TestLimits schema must require max_steps: {limits}"
+    );
+    assert!(!required(limits).contains(&"max_cycles"));
+}
+
+#[test]
+fn chip_schema_accepts_hex_string_addresses() {
+    let schema = schema_json("chip");
+    let flash = resolve(&schema, &schema["properties"]["flash"]);
+    let base = flash["properties"]["base"]["anyOf"]
+        .as_array()
+        .expect("lax address schema is anyOf");
+    assert!(base.iter().any(|s| s["type"] == "string"));
+    assert!(base.iter().any(|s| s["type"] == "integer"));
+}
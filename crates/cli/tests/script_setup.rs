@@ -0,0 +1,176 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn nonce() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+fn fixture_elf() -> PathBuf {
+    std::fs::canonicalize("../../tests/fixtures/uart-ok-thumbv7m.elf").unwrap()
+}
+
+/// Entry PC after `load_firmware`; a breakpoint there stops the run at step 0.
+fn entry_pc(fw: &Path) -> u32 {
+    let program = labwired_loader::load_elf(fw).unwrap();
+    let mut bus = labwired_core::bus::SystemBus::new();
+    let (cpu, _nvic) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+    let mut machine = labwired_core::Machine::new(cpu, bus);
+    machine.load_firmware(&program).unwrap();
+    machine.cpu.pc
+}
+
+fn run_at_step_zero(prefix: &str, body: &str) -> (Output, PathBuf) {
+    let fw = fixture_elf();
+    let dir = std::env::temp_dir().join(format!("labwired-tests-{}-{}", prefix, nonce()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("script.yaml");
+    std::fs::write(
+        &script,
+        format!(
+            "schema_version: \"1.1\"\ninputs:\n  firmware: \"{}\"\nlimits:\n  max_steps: 1000\n{}",
+            fw.display(),
+            body
+        ),
+    )
+    .unwrap();
+    let output_dir = dir.join("out");
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test",
+            "--script",
+            script.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--breakpoint",
+            &format!("0x{:x}", entry_pc(&fw)),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    (output, output_dir)
+}
+
+#[test]
+fn test_setup_register_write_visible_at_step_zero() {
+    let (output, output_dir) = run_at_step_zero(
+        "setup-registers",
+        r#"
+setup:
+  registers:
+    R4: 0xCAFEF00D
+assertions:
+  - expected_stop_reason: halt
+"#,
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join("result.json")).unwrap())
+            .unwrap();
+    assert_eq!(result["steps_executed"], 0);
+
+    let snapshot: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join("snapshot.json")).unwrap())
+            .unwrap();
+    assert_eq!(snapshot["cpu"]["registers"][4], 0xCAFE_F00Du64);
+}
+
+#[test]
+fn test_setup_memory_poke_reads_back() {
+    let (output, output_dir) = run_at_step_zero(
+        "setup-memory",
+        r#"
+setup:
+  memory:
+    - address: 0x20000100
+      bytes: [0x78, 0x56, 0x34, 0x12]
+assertions:
+  - memory_value:
+      address: 0x20000100
+      expected_value: 0x12345678
+  - memory_value:
+      address: 0x20000102
+      expected_value: 0x34
+      size: 1
+"#,
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join("result.json")).unwrap())
+            .unwrap();
+    assert_eq!(result["status"], "pass");
+}
+
+#[test]
+fn test_memory_value_kind_i16_compares_negative_value() {
+    let (output, output_dir) = run_at_step_zero(
+        "setup-memory-i16",
+        r#"
+setup:
+  memory:
+    - address: 0x20000100
+      bytes: [0xFB, 0xFF, 0x00, 0x00]
+assertions:
+  - memory_value:
+      address: 0x20000100
+      expected_value: -5
+      kind: i16
+  - memory_value:
+      address: 0x20000100
+      expected_value: 0xFFFB
+      kind: u16
+"#,
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join("result.json")).unwrap())
+            .unwrap();
+    assert_eq!(result["status"], "pass");
+}
+
+#[test]
+fn test_setup_unknown_register_is_config_error() {
+    let (output, output_dir) = run_at_step_zero(
+        "setup-unknown-register",
+        r#"
+setup:
+  registers:
+    NOT_A_REGISTER: 1
+assertions:
+  - expected_stop_reason: halt
+"#,
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let result: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_dir.join("result.json")).unwrap())
+            .unwrap();
+    assert!(result["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown register 'NOT_A_REGISTER'"));
+}
@@ -113,3 +113,44 @@ fn test_cli_snapshot_save_and_load() {
 
     let _ = std::fs::remove_file(&snapshot_path);
 }
+
+#[test]
+fn test_cli_snapshot_diff_reports_changed_register() {
+    let snapshot = |r3: u32| {
+        let registers: Vec<u32> = (0..16).map(|i| if i == 3 { r3 } else { i }).collect();
+        serde_json::json!({
+            "schema_version": 1,
+            "cpu": {
+                "type": "arm",
+                "registers": registers,
+                "pc": 15,
+                "xpsr": 0x0100_0000,
+                "primask": false,
+                "pending_exceptions": 0,
+                "vtor": 0
+            },
+            "peripherals": { "uart1": { "sr": 192 } }
+        })
+    };
+    let a = get_temp_path("snapshot-a", "json");
+    let b = get_temp_path("snapshot-b", "json");
+    std::fs::write(&a, snapshot(3).to_string()).unwrap();
+    std::fs::write(&b, snapshot(0xDEAD_BEEF).to_string()).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args(["snapshot", "diff", a.to_str().unwrap(), b.to_str().unwrap()])
+        .arg("--json")
+        .output()
+        .expect("Failed to execute snapshot diff");
+
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).expect("diff JSON");
+    assert_eq!(
+        diff["registers"],
+        serde_json::json!([{ "name": "r3", "old": 3, "new": 0xDEAD_BEEFu32 }])
+    );
+    assert_eq!(diff["peripherals"], serde_json::json!([]));
+
+    let _ = std::fs::remove_file(&a);
+    let _ = std::fs::remove_file(&b);
+}
@@ -13,6 +13,10 @@ fn get_labwired_bin() -> PathBuf {
 }
 
 fn run_test(name: &str, yaml_content: &str) -> Value {
+    run_test_with_exit_code(name, yaml_content).0
+}
+
+fn run_test_with_exit_code(name: &str, yaml_content: &str) -> (Value, Option<i32>) {
     let temp_dir = std::env::temp_dir().join(format!("labwired-stop-{}", name));
     let _ = std::fs::remove_dir_all(&temp_dir);
     std::fs::create_dir_all(&temp_dir).unwrap();
@@ -86,7 +90,7 @@ fn run_test(name: &str, yaml_content: &str) -> Value {
         println!("Stdout: {}", String::from_utf8_lossy(&output.stdout));
         println!("Stderr: {}", String::from_utf8_lossy(&output.stderr));
     }
-    val
+    (val, output.status.code())
 }
 
 #[test]
@@ -219,6 +223,134 @@ assertions:
     );
 }
 
+#[test]
+fn test_stop_when_assertions_pass_stops_right_after_marker() {
+    // With no settling window the UART check is the only cost per step, and the
+    // run ends on the step the marker lands — far short of the step budget.
+    let script = r#"
+schema_version: "1.0"
+inputs:
+  firmware: "__FIRMWARE__"
+  system: "__SYSTEM__"
+limits:
+  max_steps: 1000000
+  stop_when_assertions_pass: true
+  stop_when_assertions_pass_settle_steps: 0
+assertions:
+  - uart_contains: "OK"
+"#;
+    let result = run_test("assertions_passed_no_settle", script);
+    assert_eq!(result["status"], "pass");
+    assert_eq!(result["stop_reason"], "assertions_passed");
+    let steps = result["steps_executed"].as_u64().unwrap();
+    assert!(
+        steps < 100000,
+        "runner should stop as soon as the marker is printed, ran {steps} steps"
+    );
+}
+
+/// A scenario event that cannot be applied fails the run as a runtime
+/// error, even though the script has no assertion it could break.
+#[test]
+fn test_failing_scenario_event_is_a_runtime_error() {
+    let scenario_path = std::env::temp_dir().join("labwired-stop-scenario-error.yaml");
+    std::fs::write(
+        &scenario_path,
+        "events:\n  - at_cycle: 10\n    event: { type: irq, peripheral: no_such_peripheral }\n",
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+schema_version: "1.2"
+inputs:
+  firmware: "__FIRMWARE__"
+  system: "__SYSTEM__"
+limits:
+  max_steps: 1000
+scenario: "{}"
+assertions: []
+"#,
+        scenario_path.display()
+    );
+
+    let (result, exit_code) = run_test_with_exit_code("scenario_error", &script);
+    assert_eq!(result["status"], "error");
+    assert_eq!(result["stop_reason"], "exception");
+    assert!(result["steps_executed"].as_u64().unwrap() < 1000);
+    assert_eq!(exit_code, Some(3));
+}
+
+/// A three-instruction busy loop planted in RAM (`adds r0, #1; nop; b loop`):
+/// the PC moves every step, so the PC-stuck check alone runs to `max_steps`,
+/// while the loop detector stops it with `no_progress` and names the loop.
+#[test]
+fn test_loop_detector_catches_busy_loop_pc_stuck_misses() {
+    let script = |extra_limits: &str| {
+        format!(
+            r#"
+schema_version: "1.2"
+inputs:
+  firmware: "__FIRMWARE__"
+  system: "__SYSTEM__"
+limits:
+  max_steps: 5000
+  no_progress_steps: 100
+{extra_limits}
+setup:
+  registers:
+    PC: 0x20000100
+  memory:
+    - address: 0x20000100
+      bytes: [0x01, 0x30, 0x00, 0xbf, 0xfc, 0xe7]
+assertions: []
+"#
+        )
+    };
+
+    let result = run_test("loop_pc_stuck_only", &script(""));
+    assert_eq!(result["stop_reason"], "max_steps");
+
+    let result = run_test("loop_detected", &script("  loop_detect_repeats: 10"));
+    assert_eq!(result["stop_reason"], "no_progress");
+    assert!(result["steps_executed"].as_u64().unwrap() < 100);
+    let details = &result["stop_reason_details"];
+    assert_eq!(details["triggered_limit"]["name"], "loop_detect_repeats");
+    assert_eq!(details["triggered_limit"]["value"], 10);
+    assert_eq!(
+        details["loop_addresses"],
+        serde_json::json!([0x2000_0100u32, 0x2000_0102u32, 0x2000_0104u32])
+    );
+}
+
+/// `wfi; b .-2` planted in RAM with no IRQ enabled: the loop detector fires,
+/// and because the core is asleep with nothing able to wake it the runner
+/// reports `sleep_deadlock` rather than a generic `no_progress`.
+#[test]
+fn test_wfi_without_enabled_irq_stops_with_sleep_deadlock() {
+    let script = r#"
+schema_version: "1.2"
+inputs:
+  firmware: "__FIRMWARE__"
+  system: "__SYSTEM__"
+limits:
+  max_steps: 5000
+  no_progress_steps: 100
+  loop_detect_repeats: 10
+setup:
+  registers:
+    PC: 0x20000100
+  memory:
+    - address: 0x20000100
+      bytes: [0x30, 0xbf, 0xfd, 0xe7]
+assertions:
+  - expected_stop_reason: sleep_deadlock
+"#;
+    let result = run_test("sleep_deadlock", script);
+    assert_eq!(result["stop_reason"], "sleep_deadlock");
+    assert_eq!(result["status"], "pass");
+    assert!(result["steps_executed"].as_u64().unwrap() < 5000);
+}
+
 /// Regression guard for the print-then-crash false-pass hole.
 ///
 /// The `uart-then-bkpt-thumbv7m.elf` fixture emits the acceptance token
@@ -0,0 +1,138 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn fixture_elf() -> PathBuf {
+    std::fs::canonicalize(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/uart-ok-thumbv7m.elf"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_suite_aggregates_pass_and_fail_scripts() {
+    let dir = std::env::temp_dir().join("labwired-test-suite-dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let fw = fixture_elf();
+
+    std::fs::write(
+        dir.join("a-pass.yaml"),
+        format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - expected_stop_reason: max_steps
+"#,
+            fw.display()
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("b-fail.yaml"),
+        format!(
+            r#"
+schema_version: "1.0"
+inputs:
+  firmware: "{}"
+limits:
+  max_steps: 10
+assertions:
+  - uart_contains: "NEVER_PRESENT"
+  - expected_stop_reason: max_steps
+"#,
+            fw.display()
+        ),
+    )
+    .unwrap();
+    // Not a test script: discovery must skip it rather than fail the suite.
+    std::fs::write(
+        dir.join("system.yaml"),
+        "name: \"board\"\nchip: \"chip.yaml\"\n",
+    )
+    .unwrap();
+
+    let out = std::env::temp_dir().join("labwired-test-suite-out");
+    let _ = std::fs::remove_dir_all(&out);
+    let tap_path = out.join("suite.tap");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args([
+            "test-suite",
+            "--dir",
+            dir.to_str().unwrap(),
+            "--no-uart-stdout",
+            "--output-dir",
+            out.to_str().unwrap(),
+            "--tap",
+            tap_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Per-script artifacts land in their own directories.
+    assert!(out.join("a-pass/result.json").is_file());
+    assert!(out.join("b-fail/result.json").is_file());
+
+    let junit = std::fs::read_to_string(out.join("junit.xml")).unwrap();
+    // a-pass: run + 1 assertion; b-fail: run + 2 assertions (one failing).
+    assert!(
+        junit.contains("<testsuites name=\"labwired\" tests=\"5\" failures=\"1\" errors=\"0\""),
+        "{junit}"
+    );
+    assert_eq!(junit.matches("<testsuite ").count(), 2, "{junit}");
+    assert!(junit.contains("<testsuite name=\"a-pass\""), "{junit}");
+    assert!(junit.contains("<testsuite name=\"b-fail\""), "{junit}");
+
+    let tap = std::fs::read_to_string(&tap_path).unwrap();
+    let top_level: Vec<&str> = tap.lines().filter(|l| !l.starts_with(' ')).collect();
+    assert_eq!(
+        top_level,
+        [
+            "TAP version 13",
+            "1..2",
+            "# Subtest: a-pass",
+            "ok 1 - a-pass",
+            "# Subtest: b-fail",
+            "not ok 2 - b-fail",
+        ]
+    );
+    assert!(
+        tap.contains("    not ok 1 - uart_contains: NEVER_PRESENT"),
+        "{tap}"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&out);
+}
+
+#[test]
+fn test_suite_without_scripts_is_config_error() {
+    let dir = std::env::temp_dir().join("labwired-test-suite-empty");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_labwired"))
+        .args(["test-suite", "--dir", dir.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let _ = std::fs::remove_dir_all(&dir);
+}
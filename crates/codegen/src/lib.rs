@@ -1,12 +1,221 @@
-use anyhow::Result;
-use labwired_ir::{IrField, IrPeripheral, IrRegister};
-use proc_macro2::TokenStream;
+use anyhow::{Context, Result};
+use labwired_ir::{IrDevice, IrField, IrPeripheral, IrRegister};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use std::path::{Path, PathBuf};
 
 pub struct PeripheralGenerator;
 
+/// Knobs for [`PeripheralGenerator::generate_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeneratorOptions {
+    /// Emit volatile `read_from`/`write_to` accessors (and `read`/`write`
+    /// at the peripheral's `BASE_ADDR`) on every register, so the output
+    /// works as a HAL shim for firmware running on the simulator.
+    pub mmio_accessors: bool,
+    /// Return the raw `TokenStream` text (one long line) instead of running
+    /// it through `prettyplease`. Faster for large devices whose output is
+    /// only compiled, never read.
+    pub raw: bool,
+    /// Emit a `RESERVED_MASK` constant per register (bits no declared field
+    /// covers) and a `write_masked` that leaves those bits untouched.
+    pub reserved_bits: bool,
+}
+
 impl PeripheralGenerator {
     pub fn generate(peripheral: &IrPeripheral) -> Result<String> {
+        Self::generate_with_options(peripheral, GeneratorOptions::default())
+    }
+
+    pub fn generate_with_options(
+        peripheral: &IrPeripheral,
+        options: GeneratorOptions,
+    ) -> Result<String> {
+        let mod_name = format_ident!("{}", peripheral.name.to_lowercase());
+        Self::render(
+            Self::generate_module(peripheral, &mod_name, options)?,
+            options,
+        )
+    }
+
+    pub fn generate_device(device: &IrDevice) -> Result<String> {
+        Self::generate_device_with_options(device, GeneratorOptions::default())
+    }
+
+    /// One module per peripheral plus an `Interrupt` enum built from
+    /// `interrupt_mapping`. The output only uses `core`, so it drops into a
+    /// `#![no_std]` crate.
+    pub fn generate_device_with_options(
+        device: &IrDevice,
+        options: GeneratorOptions,
+    ) -> Result<String> {
+        let mut modules = Vec::new();
+        for (mod_name, peripheral) in Self::device_modules(device) {
+            modules.push(Self::generate_module(peripheral, &mod_name, options)?);
+        }
+
+        let interrupts = Self::generate_interrupt_enum(device);
+        let expanded = quote! {
+            #(#modules)*
+            #interrupts
+        };
+        Self::render(expanded, options)
+    }
+
+    /// Write `device` as a crate source tree under `out_dir`: a `lib.rs`
+    /// with the `Interrupt` enum and one `<module>.rs` per peripheral, each
+    /// rendered like [`Self::generate_with_options`]. Returns the written
+    /// paths, `lib.rs` first.
+    pub fn write_device_tree(
+        device: &IrDevice,
+        out_dir: &Path,
+        options: GeneratorOptions,
+    ) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(out_dir)
+            .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+        let mut written = vec![out_dir.join("lib.rs")];
+        let mut mod_decls = Vec::new();
+        for (mod_name, peripheral) in Self::device_modules(device) {
+            let items = Self::generate_module_items(peripheral, options)?;
+            let path = out_dir.join(format!("{}.rs", mod_name));
+            Self::write_rendered(&path, items, options)?;
+            mod_decls.push(quote! { pub mod #mod_name; });
+            written.push(path);
+        }
+
+        let description = device.description.as_deref().unwrap_or(&device.name);
+        let interrupts = Self::generate_interrupt_enum(device);
+        let lib = quote! {
+            #![doc = #description]
+            #![no_std]
+            #![allow(non_camel_case_types)]
+            #![allow(non_snake_case)]
+
+            #(#mod_decls)*
+
+            #interrupts
+        };
+        Self::write_rendered(&written[0], lib, options)?;
+        Ok(written)
+    }
+
+    fn write_rendered(path: &Path, tokens: TokenStream, options: GeneratorOptions) -> Result<()> {
+        let code = Self::render(tokens, options)
+            .with_context(|| format!("Failed to render {}", path.display()))?;
+        std::fs::write(path, code).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn render(tokens: TokenStream, options: GeneratorOptions) -> Result<String> {
+        if options.raw {
+            return Ok(tokens.to_string());
+        }
+        let file: syn::File = syn::parse2(tokens).context("Generated code does not parse")?;
+        Ok(prettyplease::unparse(&file))
+    }
+
+    /// Module name for every peripheral, in `peripherals` order. Peripherals
+    /// whose names differ only by case would lowercase onto the same module;
+    /// keep them distinct.
+    fn device_modules(device: &IrDevice) -> Vec<(Ident, &IrPeripheral)> {
+        let mut taken = std::collections::HashSet::new();
+        let mut modules = Vec::new();
+        for peripheral in device.peripherals.values() {
+            let base = peripheral.name.to_lowercase();
+            let mut mod_name = base.clone();
+            let mut n = 1;
+            while !taken.insert(mod_name.clone()) {
+                mod_name = if n == 1 {
+                    format!("{}_peripheral", base)
+                } else {
+                    format!("{}_peripheral{}", base, n)
+                };
+                n += 1;
+            }
+            modules.push((format_ident!("{}", mod_name), peripheral));
+        }
+        modules
+    }
+
+    fn generate_interrupt_enum(device: &IrDevice) -> TokenStream {
+        if device.interrupt_mapping.is_empty() {
+            return quote! {};
+        }
+        let mut by_number: Vec<(&String, u32)> = device
+            .interrupt_mapping
+            .iter()
+            .map(|(name, value)| (name, *value))
+            .collect();
+        by_number.sort_by_key(|&(name, value)| (value, name.clone()));
+
+        // Enum discriminants must be unique: the first name for a vector is
+        // the variant, later names sharing it become associated aliases.
+        let mut variants = Vec::new();
+        let mut aliases = Vec::new();
+        let mut primary: Option<(u32, Ident)> = None;
+        for (name, value) in by_number {
+            let ident = format_ident!("{}", Self::interrupt_ident(name));
+            let number = Literal::u32_unsuffixed(value);
+            match &primary {
+                Some((v, first)) if *v == value => {
+                    aliases.push(quote! { pub const #ident: Self = Self::#first; });
+                }
+                _ => {
+                    variants.push(quote! { #ident = #number });
+                    primary = Some((value, ident));
+                }
+            }
+        }
+
+        quote! {
+            /// Device interrupt vectors.
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            #[repr(u32)]
+            pub enum Interrupt {
+                #(#variants,)*
+            }
+
+            impl Interrupt {
+                #(#aliases)*
+
+                pub const fn number(self) -> u32 {
+                    self as u32
+                }
+            }
+        }
+    }
+
+    /// Interrupt names come straight from vendor SVDs; map anything that is
+    /// not a valid identifier character to `_`.
+    fn interrupt_ident(name: &str) -> String {
+        let mut ident: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            ident.insert(0, '_');
+        }
+        ident
+    }
+
+    fn generate_module(
+        peripheral: &IrPeripheral,
+        mod_name: &Ident,
+        options: GeneratorOptions,
+    ) -> Result<TokenStream> {
+        let items = Self::generate_module_items(peripheral, options)?;
+        Ok(quote! {
+            pub mod #mod_name {
+                #items
+            }
+        })
+    }
+
+    /// The items of a peripheral's module, without the `mod` wrapper.
+    fn generate_module_items(
+        peripheral: &IrPeripheral,
+        options: GeneratorOptions,
+    ) -> Result<TokenStream> {
         let original_name = &peripheral.name;
         let mut name = format_ident!("{}", original_name);
 
@@ -30,31 +239,32 @@ impl PeripheralGenerator {
 
         for reg in &peripheral.registers {
             if seen_registers.insert(reg.name.clone()) {
-                registers_code.push(Self::generate_register(reg)?);
+                registers_code.push(Self::generate_register(reg, &name, options)?);
             }
         }
 
-        let mod_name = format_ident!("{}", peripheral.name.to_lowercase());
-
         let expanded = quote! {
-            pub mod #mod_name {
-                #[doc = #description]
-                pub struct #name;
-
-                impl #name {
-                    pub const BASE_ADDR: u64 = #base_address;
-                }
+            #[doc = #description]
+            pub struct #name;
 
-                #(#registers_code)*
+            impl #name {
+                pub const BASE_ADDR: u64 = #base_address;
             }
+
+            #(#registers_code)*
         };
 
-        Ok(expanded.to_string())
+        Ok(expanded)
     }
 
-    fn generate_register(reg: &IrRegister) -> Result<TokenStream> {
+    fn generate_register(
+        reg: &IrRegister,
+        peripheral: &Ident,
+        options: GeneratorOptions,
+    ) -> Result<TokenStream> {
         let struct_name = format_ident!("{}", reg.name);
         let reset_value = reg.reset_value;
+        let offset = reg.offset;
         let description = reg.description.as_deref().unwrap_or("No description");
 
         let mut field_methods = Vec::new();
@@ -62,11 +272,37 @@ impl PeripheralGenerator {
             field_methods.push(Self::generate_field(field)?);
         }
 
+        let mmio = if options.mmio_accessors {
+            Self::generate_mmio_accessors(reg.size, peripheral)
+        } else {
+            quote! {}
+        };
+
+        let reserved = if options.reserved_bits {
+            let mask = syn::LitInt::new(
+                &format!("{:#010x}", Self::reserved_mask(reg)),
+                proc_macro2::Span::call_site(),
+            );
+            quote! {
+                /// Bits of this register that no declared field covers.
+                pub const RESERVED_MASK: u32 = #mask;
+
+                /// Set every declared field from `value`, keeping the current
+                /// reserved bits.
+                pub fn write_masked(&mut self, value: u32) {
+                    self.0 = (self.0 & Self::RESERVED_MASK) | (value & !Self::RESERVED_MASK);
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         let expanded = quote! {
             #[doc = #description]
             pub struct #struct_name(u32);
 
             impl #struct_name {
+                pub const OFFSET: u64 = #offset;
                 pub const RESET_VALUE: u32 = #reset_value as u32;
 
                 pub fn new() -> Self {
@@ -86,12 +322,70 @@ impl PeripheralGenerator {
                 pub fn set_raw(&mut self, value: u32) {
                     self.0 = value;
                 }
+
+                #reserved
+
+                #mmio
             }
         };
 
         Ok(expanded)
     }
 
+    /// Bits within the register's width that no field declares. A register
+    /// without fields is modelled as a single opaque value, so nothing in it
+    /// counts as reserved.
+    fn reserved_mask(reg: &IrRegister) -> u32 {
+        if reg.fields.is_empty() {
+            return 0;
+        }
+        let width = reg.size.min(32);
+        let register_mask = ((1u64 << width) - 1) as u32;
+        let covered = reg.fields.iter().fold(0u64, |acc, f| {
+            let width = f.bit_width.min(32) as u64;
+            acc | (((1u64 << width) - 1) << f.bit_offset.min(63))
+        });
+        register_mask & !(covered as u32)
+    }
+
+    /// Volatile accessors for a register of `size` bits. `read_from` and
+    /// `write_to` take the peripheral base so one register type serves every
+    /// instance; `read`/`write` use the generated `BASE_ADDR`.
+    fn generate_mmio_accessors(size: u32, peripheral: &Ident) -> TokenStream {
+        let width = match size {
+            8 => format_ident!("u8"),
+            16 => format_ident!("u16"),
+            _ => format_ident!("u32"),
+        };
+        quote! {
+            /// # Safety
+            /// `base` must be the address of a mapped instance of this peripheral.
+            pub unsafe fn read_from(base: usize) -> Self {
+                let ptr = (base + Self::OFFSET as usize) as *const #width;
+                Self(core::ptr::read_volatile(ptr) as u32)
+            }
+
+            /// # Safety
+            /// `base` must be the address of a mapped instance of this peripheral.
+            pub unsafe fn write_to(&self, base: usize) {
+                let ptr = (base + Self::OFFSET as usize) as *mut #width;
+                core::ptr::write_volatile(ptr, self.0 as #width);
+            }
+
+            /// # Safety
+            /// The peripheral must be mapped at its `BASE_ADDR`.
+            pub unsafe fn read() -> Self {
+                Self::read_from(#peripheral::BASE_ADDR as usize)
+            }
+
+            /// # Safety
+            /// The peripheral must be mapped at its `BASE_ADDR`.
+            pub unsafe fn write(&self) {
+                self.write_to(#peripheral::BASE_ADDR as usize)
+            }
+        }
+    }
+
     fn sanitize_name(name: &str) -> String {
         let keywords = [
             "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
@@ -111,11 +405,12 @@ impl PeripheralGenerator {
     fn generate_field(field: &IrField) -> Result<TokenStream> {
         let name_str = field.name.to_lowercase();
         // Avoid collision with register methods
-        let name_str = if ["reset", "new", "raw", "set_raw"].contains(&name_str.as_str()) {
-            format!("{}_field", name_str)
-        } else {
-            name_str
-        };
+        let name_str =
+            if ["reset", "new", "raw", "set_raw", "write_masked"].contains(&name_str.as_str()) {
+                format!("{}_field", name_str)
+            } else {
+                name_str
+            };
 
         let name = format_ident!("{}", Self::sanitize_name(&name_str));
         let set_name = format_ident!("set_{}", name_str);
@@ -165,7 +460,7 @@ impl PeripheralGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use labwired_ir::{IrAccess, IrField, IrPeripheral, IrRegister};
+    use labwired_ir::{IrAccess, IrDevice, IrField, IrPeripheral, IrRegister};
 
     #[test]
     fn test_codegen_basic() {
@@ -214,6 +509,247 @@ mod tests {
         assert!(result.contains("fn set_ue"));
     }
 
+    #[test]
+    fn test_codegen_mmio_accessors() {
+        let peripheral = IrPeripheral {
+            name: "GPIOA".to_string(),
+            base_address: 0x4001_0800,
+            description: None,
+            registers: vec![
+                IrRegister {
+                    name: "ODR".to_string(),
+                    offset: 0x0C,
+                    size: 32,
+                    access: IrAccess::ReadWrite,
+                    reset_value: 0,
+                    description: None,
+                    fields: vec![],
+                    side_effects: None,
+                },
+                IrRegister {
+                    name: "LCK".to_string(),
+                    offset: 0x18,
+                    size: 16,
+                    access: IrAccess::ReadWrite,
+                    reset_value: 0,
+                    description: None,
+                    fields: vec![],
+                    side_effects: None,
+                },
+            ],
+            interrupts: vec![],
+            timing: vec![],
+        };
+
+        let plain = PeripheralGenerator::generate(&peripheral).unwrap();
+        assert!(plain.contains("pub const OFFSET: u64 = 12u64;"));
+        assert!(!plain.contains("read_volatile"));
+
+        let options = GeneratorOptions {
+            mmio_accessors: true,
+            ..Default::default()
+        };
+        let result = PeripheralGenerator::generate_with_options(&peripheral, options).unwrap();
+        assert!(result.contains("pub const OFFSET: u64 = 24u64;"));
+        assert!(result.contains("pub unsafe fn read_from(base: usize) -> Self"));
+        assert!(result.contains("pub unsafe fn write_to(&self, base: usize)"));
+        assert!(result.contains("core::ptr::read_volatile"));
+        assert!(result.contains("core::ptr::write_volatile"));
+        assert!(result.contains("as *const u16"));
+        assert!(result.contains("Self::read_from(GPIOA::BASE_ADDR as usize)"));
+    }
+
+    #[test]
+    fn test_codegen_device_modules_and_interrupt_enum() {
+        let peripheral = |name: &str, base_address| IrPeripheral {
+            name: name.to_string(),
+            base_address,
+            description: None,
+            registers: vec![IrRegister {
+                name: "CR".to_string(),
+                offset: 0,
+                size: 32,
+                access: IrAccess::ReadWrite,
+                reset_value: 0,
+                description: None,
+                fields: vec![],
+                side_effects: None,
+            }],
+            interrupts: vec![],
+            timing: vec![],
+        };
+        let device = IrDevice {
+            name: "TESTCHIP".to_string(),
+            arch: "arm".to_string(),
+            description: None,
+            peripherals: [
+                ("USART1".to_string(), peripheral("USART1", 0x4001_3800)),
+                // Differs from the enum only by case, which is no clash.
+                (
+                    "INTERRUPT".to_string(),
+                    peripheral("INTERRUPT", 0x4001_0400),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            interrupt_mapping: [("USART1".to_string(), 37), ("EXTI0".to_string(), 6)]
+                .into_iter()
+                .collect(),
+            memory_regions: Default::default(),
+        };
+
+        let result = PeripheralGenerator::generate_device(&device).unwrap();
+        assert!(result.contains("pub mod usart1 {"));
+        assert!(result.contains("pub mod interrupt {"));
+        assert!(!result.contains("interrupt_peripheral"));
+        assert!(result.contains("pub enum Interrupt {"));
+        assert!(result.contains("EXTI0 = 6,"));
+        assert!(result.contains("USART1 = 37,"));
+        assert!(result.contains("pub const fn number(self) -> u32"));
+        assert!(!result.contains("std::"));
+    }
+
+    #[test]
+    fn test_codegen_device_tree_parses() {
+        let uart = IrPeripheral {
+            name: "UART".to_string(),
+            base_address: 0x4000_1000,
+            description: None,
+            registers: vec![IrRegister {
+                name: "CR1".to_string(),
+                offset: 0,
+                size: 32,
+                access: IrAccess::ReadWrite,
+                reset_value: 0,
+                description: None,
+                fields: vec![IrField {
+                    name: "UE".to_string(),
+                    bit_offset: 0,
+                    bit_width: 1,
+                    access: None,
+                    description: None,
+                }],
+                side_effects: None,
+            }],
+            interrupts: vec![],
+            timing: vec![],
+        };
+        let device = IrDevice {
+            name: "TESTCHIP".to_string(),
+            arch: "arm".to_string(),
+            description: None,
+            peripherals: [("UART".to_string(), uart)].into_iter().collect(),
+            interrupt_mapping: [("UART".to_string(), 37)].into_iter().collect(),
+            memory_regions: Default::default(),
+        };
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("labwired-codegen-tree-{}", nonce));
+
+        let options = GeneratorOptions {
+            mmio_accessors: true,
+            ..Default::default()
+        };
+        let files = PeripheralGenerator::write_device_tree(&device, &dir, options).unwrap();
+        assert_eq!(files, vec![dir.join("lib.rs"), dir.join("uart.rs")]);
+        for path in &files {
+            let src = std::fs::read_to_string(path).unwrap();
+            syn::parse_file(&src).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        }
+        let lib = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(lib.contains("pub mod uart;"));
+        assert!(lib.contains("pub enum Interrupt"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_codegen_output_is_formatted() {
+        let register = |name: &str, offset| IrRegister {
+            name: name.to_string(),
+            offset,
+            size: 32,
+            access: IrAccess::ReadWrite,
+            reset_value: 0,
+            description: None,
+            fields: vec![IrField {
+                name: "EN".to_string(),
+                bit_offset: 0,
+                bit_width: 1,
+                access: None,
+                description: None,
+            }],
+            side_effects: None,
+        };
+        let peripheral = IrPeripheral {
+            name: "TIM".to_string(),
+            base_address: 0x4000_0000,
+            description: None,
+            registers: vec![register("CR1", 0), register("CR2", 4), register("SR", 0x10)],
+            interrupts: vec![],
+            timing: vec![],
+        };
+
+        let result = PeripheralGenerator::generate(&peripheral).unwrap();
+        assert!(result.lines().count() > 30, "{result}");
+        assert!(result.starts_with("pub mod tim {\n"));
+        assert!(result.contains("\n    pub struct CR2(u32);\n"));
+        assert!(result.contains("\n        pub fn set_en(&mut self, value: u32) {\n"));
+
+        let raw = PeripheralGenerator::generate_with_options(
+            &peripheral,
+            GeneratorOptions {
+                raw: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!raw.contains('\n'));
+        assert!(raw.contains("pub struct CR2 (u32) ;"));
+    }
+
+    #[test]
+    fn test_codegen_reserved_mask_covers_field_gaps() {
+        let field = |name: &str, bit_offset, bit_width| IrField {
+            name: name.to_string(),
+            bit_offset,
+            bit_width,
+            access: None,
+            description: None,
+        };
+        let peripheral = IrPeripheral {
+            name: "SPI".to_string(),
+            base_address: 0x4001_3000,
+            description: None,
+            registers: vec![IrRegister {
+                name: "CR1".to_string(),
+                offset: 0,
+                size: 16,
+                access: IrAccess::ReadWrite,
+                reset_value: 0,
+                description: None,
+                // Bits 4..=7 are a gap between the two fields.
+                fields: vec![field("MODE", 0, 4), field("BR", 8, 8)],
+                side_effects: None,
+            }],
+            interrupts: vec![],
+            timing: vec![],
+        };
+
+        let plain = PeripheralGenerator::generate(&peripheral).unwrap();
+        assert!(!plain.contains("RESERVED_MASK"));
+
+        let options = GeneratorOptions {
+            reserved_bits: true,
+            ..Default::default()
+        };
+        let result = PeripheralGenerator::generate_with_options(&peripheral, options).unwrap();
+        assert!(result.contains("pub const RESERVED_MASK: u32 = 0x000000f0;"));
+        assert!(result.contains("pub fn write_masked(&mut self, value: u32)"));
+    }
+
     #[test]
     fn test_codegen_keywords() {
         let peripheral = IrPeripheral {
@@ -14,6 +14,10 @@ use std::path::Path;
 /// a documented Phase 2 stub).
 pub mod canonical;
 
+/// JSON Schema export for the YAML config types (`schema` feature).
+#[cfg(feature = "schema")]
+pub mod schema;
+
 fn deserialize_u64_lax<'de, D>(deserializer: D) -> Result<u64, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -42,15 +46,35 @@ where
     }
 }
 
+fn deserialize_opt_u64_lax<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+    #[derive(Deserialize)]
+    struct Lax(#[serde(deserialize_with = "deserialize_u64_lax")] u64);
+
+    Ok(Option::<Lax>::deserialize(deserializer)?.map(|Lax(v)| v))
+}
+
 /// Default schema version for YAML configs
 fn default_schema_version() -> String {
     "1.0".to_string()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Arch {
-    #[serde(alias = "cortex-m3", alias = "cortex-m4", alias = "cortex-m7")]
+    #[serde(
+        alias = "cortex-m0",
+        alias = "cortex-m0+",
+        alias = "cortex-m0plus",
+        alias = "cortex-m1",
+        alias = "cortex-m3",
+        alias = "cortex-m4",
+        alias = "cortex-m7"
+    )]
     Arm,
     #[serde(alias = "riscv32", alias = "rv32i", alias = "rv32imac")]
     RiscV,
@@ -60,10 +84,12 @@ pub enum Arch {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MemoryRange {
     #[serde(deserialize_with = "deserialize_u64_lax")]
+    #[cfg_attr(feature = "schema", schemars(schema_with = "schema::lax_u64"))]
     pub base: u64,
-    pub size: String, // e.g. "128KB"
+    pub size: ByteSize, // e.g. "128KB"
 }
 
 /// An additional named RAM/ROM-backed memory window beyond the primary
@@ -71,9 +97,11 @@ pub struct MemoryRange {
 /// (e.g. the ESP32-C3's separate IRAM `0x4037C000` and flash-DROM `0x3C000000`
 /// views), which real firmware links code/rodata into.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NamedMemoryRange {
     pub name: String,
     #[serde(deserialize_with = "deserialize_u64_lax")]
+    #[cfg_attr(feature = "schema", schemars(schema_with = "schema::lax_u64"))]
     pub base: u64,
     pub size: String,
     /// Optional env var naming a path to a raw binary loaded into this region at
@@ -81,6 +109,11 @@ pub struct NamedMemoryRange {
     /// that can't be committed — the region stays zero-filled if unset/missing.
     #[serde(default)]
     pub image_env: Option<String>,
+    /// Back the region with pages allocated on first write instead of one
+    /// up-front buffer, for large mostly-unused windows (64-bit maps, big
+    /// external flash). Not combinable with `image_env`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sparse: bool,
 }
 
 /// Optional RCC clock-gate declaration for a peripheral. When present, the bus
@@ -93,6 +126,7 @@ pub struct NamedMemoryRange {
 /// "ahbenr", "ahb2enr"); the bus maps it to the chip family's actual RCC offset
 /// at build time, so the same name resolves correctly on F1 vs L4.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClockGate {
     /// Symbolic RCC enable-register name, e.g. "apb1enr" / "apb2enr" / "ahbenr".
     pub reg: String,
@@ -101,10 +135,12 @@ pub struct ClockGate {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeripheralConfig {
     pub id: String,
     pub r#type: String, // "uart", "timer", "gpio", etc.
     #[serde(deserialize_with = "deserialize_u64_lax")]
+    #[cfg_attr(feature = "schema", schemars(schema_with = "schema::lax_u64"))]
     pub base_address: u64,
     #[serde(default)]
     pub size: Option<String>,
@@ -114,6 +150,10 @@ pub struct PeripheralConfig {
     #[serde(default)]
     pub clock: Option<ClockGate>,
     #[serde(default)]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "HashMap<String, serde_json::Value>")
+    )]
     pub config: HashMap<String, serde_yaml::Value>,
 }
 
@@ -124,12 +164,14 @@ pub struct PeripheralConfig {
 /// correctly. Extra YAML fields (e.g. `functions:`, consumed by the app codegen)
 /// are ignored here.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PinLoc {
     pub gpio: String,
     pub bit: u8,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChipDescriptor {
     #[serde(default = "default_schema_version")]
     pub schema_version: String,
@@ -153,7 +195,20 @@ pub struct ChipDescriptor {
     /// stage-2 blob (flash is directly mapped); it uses this offset to find
     /// the real reset vector when the flash-base vectors are not valid.
     #[serde(default, deserialize_with = "deserialize_u64_lax")]
+    #[cfg_attr(feature = "schema", schemars(schema_with = "schema::lax_u64"))]
     pub reset_vector_offset: u64,
+    /// Absolute address of the vector table the core boots from, written to
+    /// VTOR at firmware load. Set it when the image is an application linked
+    /// behind a bootloader (e.g. `0x08004000`) or running its table from RAM;
+    /// the reset (SP, PC) pair and every exception vector come from there.
+    /// Unset (the default) keeps the flash-base / `reset_vector_offset` rules.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_u64_lax"
+    )]
+    #[cfg_attr(feature = "schema", schemars(schema_with = "schema::lax_u64"))]
+    pub vtor: Option<u64>,
     /// RP2040-style atomic register aliases. When true, every 0x1000-strided
     /// alias of a peripheral register in the APB window decodes as an atomic
     /// op on the base register: `+0x0000` normal, `+0x1000` XOR, `+0x2000`
@@ -163,6 +218,13 @@ pub struct ChipDescriptor {
     /// first `hw_set_bits`. Default `false` (other Cortex-M parts).
     #[serde(default)]
     pub atomic_register_aliases: bool,
+    /// Whether plain CPU/DMA stores may modify the primary `flash` region
+    /// (and its boot alias at 0x0). `false` write-protects it: any store
+    /// faults with a memory violation, catching firmware that scribbles over
+    /// its own image. Default `true` so firmware that self-programs through
+    /// the flash controller keeps working.
+    #[serde(default = "default_true")]
+    pub flash_writable: bool,
     /// Extra CPU-visible memory windows beyond `flash`/`ram` (e.g. ESP32 IRAM
     /// and flash-DROM). Empty for chips with a simple two-region map.
     #[serde(default)]
@@ -176,6 +238,7 @@ pub struct ChipDescriptor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExternalDevice {
     pub id: String,
     pub r#type: String,
@@ -191,10 +254,15 @@ pub struct ExternalDevice {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub route: BTreeMap<String, String>,
     #[serde(default)]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "HashMap<String, serde_json::Value>")
+    )]
     pub config: HashMap<String, serde_yaml::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CosimAdapter {
     ExternalProcess,
@@ -207,6 +275,7 @@ fn default_cosim_step_ns() -> u64 {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CosimModelConfig {
     pub id: String,
     pub adapter: CosimAdapter,
@@ -219,10 +288,15 @@ pub struct CosimModelConfig {
     #[serde(default)]
     pub outputs: HashMap<String, String>,
     #[serde(default)]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "HashMap<String, serde_json::Value>")
+    )]
     pub config: HashMap<String, serde_yaml::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum BoardIoKind {
     Led,
@@ -235,6 +309,7 @@ pub enum BoardIoKind {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum BoardIoSignal {
     #[default]
@@ -247,6 +322,7 @@ fn default_true() -> bool {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BoardIoBinding {
     pub id: String,
     pub kind: BoardIoKind,
@@ -263,6 +339,7 @@ pub struct BoardIoBinding {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SystemManifest {
     #[serde(default = "default_schema_version")]
     pub schema_version: String,
@@ -575,27 +652,250 @@ fn optional_nonempty_interconnect_string<'a>(
     Ok(Some(value))
 }
 
+/// Set to `1` to downgrade unknown keys in chip descriptors, system
+/// manifests and peripheral descriptors from load errors to warnings (for
+/// configs written against a newer LabWired).
+pub const ALLOW_UNKNOWN_CONFIG_KEYS_ENV: &str = "LABWIRED_ALLOW_UNKNOWN_CONFIG_KEYS";
+
+/// Keys LabWired deliberately ignores, as `serde_ignored` paths with `*`
+/// standing for any sequence index or map key. Everything else a field does
+/// not read is an unknown key.
+const INFORMATIONAL_KEYS: &[&str] = &[
+    // Emitted by the chip generators.
+    "id",
+    "registers_count",
+    "pins.*.functions",
+    // Bus name (`apb1`, `ahb`, ...) from SVD onboarding.
+    "peripherals.*.bus",
+    // Board clock consumed by the canonical board tooling.
+    "cpu_hz",
+];
+
+fn is_informational_key(path: &str) -> bool {
+    INFORMATIONAL_KEYS.iter().any(|pattern| {
+        let mut want = pattern.split('.');
+        let mut got = path.split('.');
+        loop {
+            match (want.next(), got.next()) {
+                (None, None) => return true,
+                (Some(w), Some(g)) if w == "*" || w == g => {}
+                _ => return false,
+            }
+        }
+    })
+}
+
+/// Deserialize a chip descriptor, system manifest, peripheral descriptor or
+/// Strict IR document, reporting every key no field reads at any depth (open
+/// maps such as a peripheral's `config:` consume theirs). Unknown keys fail
+/// the load, naming their paths, so a typo like `base_adress` cannot leave a
+/// default behind; [`ALLOW_UNKNOWN_CONFIG_KEYS_ENV`] downgrades them to a
+/// warning.
+fn deserialize_config<'de, T, D>(what: &str, deserializer: D) -> Result<T>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+    D::Error: std::error::Error + Send + Sync + 'static,
+{
+    deserialize_config_with(what, deserializer, allow_unknown_config_keys())
+}
+
+fn deserialize_config_with<'de, T, D>(what: &str, deserializer: D, allow_unknown: bool) -> Result<T>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+    D::Error: std::error::Error + Send + Sync + 'static,
+{
+    let mut unknown = Vec::new();
+    let parsed: std::result::Result<T, D::Error> =
+        serde_ignored::deserialize(deserializer, |path| {
+            let path = path.to_string();
+            if !is_informational_key(&path) {
+                unknown.push(path);
+            }
+        });
+    if !unknown.is_empty() {
+        let list = unknown.join(", ");
+        if allow_unknown {
+            tracing::warn!("{what}: ignoring unknown key(s) {list}");
+        } else {
+            // A misspelled required key also fails to parse; the unknown key
+            // is the root cause, so lead with it.
+            let cause = match &parsed {
+                Err(e) => format!("; {e}"),
+                Ok(_) => String::new(),
+            };
+            anyhow::bail!(
+                "{what}: unknown key(s) {list} (set {ALLOW_UNKNOWN_CONFIG_KEYS_ENV}=1 to ignore them){cause}"
+            );
+        }
+    }
+    parsed.with_context(|| format!("Failed to parse {what}"))
+}
+
+fn allow_unknown_config_keys() -> bool {
+    std::env::var(ALLOW_UNKNOWN_CONFIG_KEYS_ENV).is_ok_and(|v| v == "1")
+}
+
+/// Serialize a config value to `path`: JSON for a `.json` extension, YAML
+/// otherwise. Fields are written in declaration order.
+fn write_config_file<T: Serialize>(value: &T, path: &Path, what: &str) -> Result<()> {
+    let content = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::to_string_pretty(value)
+            .with_context(|| format!("Failed to serialize {what} as JSON"))?
+    } else {
+        serde_yaml::to_string(value)
+            .with_context(|| format!("Failed to serialize {what} as YAML"))?
+    };
+    std::fs::write(path, content).with_context(|| format!("Failed to write {what} to {:?}", path))
+}
+
 impl ChipDescriptor {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)?;
 
         if path.extension().is_some_and(|ext| ext == "json") {
-            let ir: labwired_ir::IrDevice = serde_json::from_str(&content)
+            let raw: serde_json::Value = serde_json::from_str(&content)
                 .with_context(|| format!("Failed to parse Strict IR from {:?}", path))?;
-            Ok(Self::from(ir))
+            let ir: labwired_ir::IrDevice =
+                deserialize_config(&format!("Strict IR {:?}", path), raw)?;
+            let chip = Self::from(ir);
+            chip.validate()
+                .with_context(|| format!("Invalid chip descriptor {:?}", path))?;
+            Ok(chip)
+        } else {
+            let chip = Self::from_yaml_named(&content, &format!("chip descriptor {:?}", path))?;
+            chip.validate()
+                .with_context(|| format!("Invalid chip descriptor {:?}", path))?;
+            Ok(chip)
+        }
+    }
+
+    /// Parse a chip descriptor from YAML text, rejecting unknown keys the
+    /// same way [`Self::from_file`] does. Unlike `from_file` this does not
+    /// run [`Self::validate`].
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Self::from_yaml_named(yaml, "chip descriptor")
+    }
+
+    fn from_yaml_named(yaml: &str, what: &str) -> Result<Self> {
+        let raw: serde_yaml::Value =
+            serde_yaml::from_str(yaml).context("Failed to parse Chip Descriptor YAML")?;
+        let mut chip: Self = deserialize_config(what, &raw)?;
+        // `arch: cortex-m0` collapses to `Arch::Arm`; keep the alias as the
+        // core name so core-specific behavior (ARMv6-M decode subset,
+        // strict alignment) still applies when `core:` is omitted.
+        if chip.core.is_none() {
+            chip.core = raw
+                .get("arch")
+                .and_then(|a| a.as_str())
+                .filter(|a| a.starts_with("cortex-"))
+                .map(str::to_string);
+        }
+        Ok(chip)
+    }
+
+    /// Width of the chip's physical address space: 32 bits for Cortex-M cores
+    /// and Xtensa, 64 bits otherwise. The onboarding boards (RV64 SiFive
+    /// parts, Cortex-A/R, x86) map RAM above 4 GiB and declare no Cortex-M
+    /// `core`, so they must not be held to the 32-bit bus.
+    pub fn address_bits(&self) -> u32 {
+        let cortex_m = self
+            .core
+            .as_deref()
+            .is_some_and(|c| c.trim().to_ascii_lowercase().starts_with("cortex-m"));
+        if cortex_m || self.arch == Arch::Xtensa {
+            32
         } else {
-            serde_yaml::from_str(&content).context("Failed to parse Chip Descriptor YAML")
+            64
         }
     }
+
+    /// Write the descriptor to `path` (see [`write_config_file`]). A `.json`
+    /// file holds the descriptor itself, not Strict IR, so it reloads with
+    /// `serde_json` rather than [`Self::from_file`].
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        write_config_file(self, path.as_ref(), "Chip Descriptor")
+    }
+
+    /// Check what serde cannot: every size string parses, every memory window
+    /// and peripheral fits in the chip's address space (see
+    /// [`Self::address_bits`]), and peripheral ids are unique. Errors name the
+    /// offending field, e.g. `peripherals[3].size`.
+    pub fn validate(&self) -> Result<()> {
+        let bits = self.address_bits();
+        let check_window = |field: &str, base: u64, size: u64| -> Result<()> {
+            let fits = match base.checked_add(size) {
+                Some(end) => bits == 64 || end <= 1 << bits,
+                None => false,
+            };
+            if !fits {
+                anyhow::bail!(
+                    "chip '{}': {field} window {base:#x}+{size:#x} exceeds the {bits}-bit address space",
+                    self.name
+                );
+            }
+            Ok(())
+        };
+        let size_of = |field: &str, size: &str| -> Result<u64> {
+            parse_size(size).with_context(|| {
+                format!("chip '{}': {field} '{size}' is not a valid size", self.name)
+            })
+        };
+
+        check_window("flash", self.flash.base, self.flash.size.bytes())?;
+        check_window("ram", self.ram.base, self.ram.size.bytes())?;
+        for (i, region) in self.memory_regions.iter().enumerate() {
+            let field = format!("memory_regions[{i}] ('{}')", region.name);
+            let size = size_of(&format!("{field}.size"), &region.size)?;
+            check_window(&field, region.base, size)?;
+        }
+
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for (i, p) in self.peripherals.iter().enumerate() {
+            if let Some(first) = seen.insert(p.id.as_str(), i) {
+                anyhow::bail!(
+                    "chip '{}': peripherals[{i}].id '{}' duplicates peripherals[{first}]",
+                    self.name,
+                    p.id
+                );
+            }
+            let field = format!("peripherals[{i}] ('{}')", p.id);
+            let size = match &p.size {
+                Some(size) => size_of(&format!("{field}.size"), size)?,
+                None => 0,
+            };
+            check_window(&field, p.base_address, size)?;
+        }
+        Ok(())
+    }
 }
 
 impl SystemManifest {
+    /// Write the manifest to `path`: JSON for a `.json` extension, YAML
+    /// otherwise.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        write_config_file(self, path.as_ref(), "System Manifest")
+    }
+
+    /// Parse a manifest from YAML text, rejecting unknown keys the same way
+    /// [`Self::from_file`] does. `can-player` `path:` entries are left as-is;
+    /// only `from_file` can resolve them.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Self::from_yaml_named(yaml, "system manifest")
+    }
+
+    fn from_yaml_named(yaml: &str, what: &str) -> Result<Self> {
+        let raw: serde_yaml::Value =
+            serde_yaml::from_str(yaml).context("Failed to parse System Manifest")?;
+        deserialize_config(what, &raw)
+    }
+
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
-        let f = std::fs::File::open(path)?;
-        let mut manifest: SystemManifest =
-            serde_yaml::from_reader(f).context("Failed to parse System Manifest")?;
+        let content = std::fs::read_to_string(path)?;
+        let mut manifest = Self::from_yaml_named(&content, &format!("system manifest {:?}", path))?;
         // can-player accepts `path:` as a CLI convenience; core itself only
         // ever sees `data:` (keeps std::fs out of the sim core → wasm-safe).
         let base = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
@@ -658,6 +958,7 @@ impl SystemManifest {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Access {
     #[serde(alias = "R/W", alias = "rw")]
@@ -668,22 +969,38 @@ pub enum Access {
     WriteOnly,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FieldDescriptor {
     pub name: String,
     pub bit_range: [u8; 2], // [msb, lsb]
     #[serde(default)]
     pub description: Option<String>,
+    /// Field-specific access, when it differs from the register's. A
+    /// `read-only` field inside a writable register keeps its value on writes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<Access>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReadAction {
     None,
     Clear,
+    /// Reading sets every bit of the register (SVD `set`).
+    Set,
+    /// Reading modifies the register in an unspecified way (SVD `modify`);
+    /// carried for fidelity, the simulator leaves the value unchanged.
+    Modify,
+    /// Reading has an external side effect (SVD `modifyExternal`); the
+    /// register value itself is unchanged.
+    #[serde(alias = "modifyExternal")]
+    ModifyExternal,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WriteAction {
     None,
@@ -691,9 +1008,22 @@ pub enum WriteAction {
     WriteOneToClear,
     #[serde(alias = "zeroToClear")]
     WriteZeroToClear,
+    #[serde(alias = "oneToSet")]
+    WriteOneToSet,
+    #[serde(alias = "zeroToSet")]
+    WriteZeroToSet,
+    #[serde(alias = "oneToToggle", alias = "toggle")]
+    WriteOneToToggle,
+    #[serde(alias = "zeroToToggle")]
+    WriteZeroToToggle,
+    /// Any write clears every bit (SVD `clear`).
+    Clear,
+    /// Any write sets every bit (SVD `set`).
+    Set,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SideEffectsDescriptor {
     #[serde(default)]
     pub read_action: Option<ReadAction>,
@@ -706,6 +1036,7 @@ pub struct SideEffectsDescriptor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TimingTrigger {
     Write {
@@ -721,17 +1052,27 @@ pub enum TimingTrigger {
     Periodic {
         period_cycles: u64,
     },
+    /// Fires when the CPU takes the IRQ named in the peripheral's
+    /// `interrupts` map (its NVIC pending bit is acknowledged on entry).
+    OnInterruptAck {
+        interrupt: String,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TimingAction {
     SetBits { register: String, bits: u32 },
     ClearBits { register: String, bits: u32 },
     WriteValue { register: String, value: u32 },
+    ToggleBits { register: String, bits: u32 },
+    PendInterrupt { name: String },
+    ClearInterrupt { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TimingDescriptor {
     pub id: String,
     pub trigger: TimingTrigger,
@@ -741,7 +1082,19 @@ pub struct TimingDescriptor {
     pub interrupt: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// One interrupt raised by a peripheral, with the SVD description that the
+/// name→number `interrupts` map cannot carry.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InterruptDescriptor {
+    pub name: String,
+    pub value: u32,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RegisterDescriptor {
     pub id: String,
     pub address_offset: u64,
@@ -754,7 +1107,8 @@ pub struct RegisterDescriptor {
     pub side_effects: Option<SideEffectsDescriptor>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PeripheralDescriptor {
     pub peripheral: String,
     pub version: String,
@@ -763,6 +1117,10 @@ pub struct PeripheralDescriptor {
     pub interrupts: Option<std::collections::HashMap<String, u32>>,
     #[serde(default)]
     pub timing: Option<Vec<TimingDescriptor>>,
+    /// Per-interrupt detail (descriptions), ordered by IRQ number. Optional:
+    /// `interrupts` stays the source of truth for name→number resolution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interrupt_descriptors: Vec<InterruptDescriptor>,
 }
 
 /// Declarative descriptor for a GPIO / pin-timing external device — the family
@@ -1543,16 +1901,165 @@ impl PeripheralDescriptor {
     }
 
     pub fn from_yaml(yaml: &str) -> Result<Self> {
-        serde_yaml::from_str(yaml).context("Failed to parse Peripheral Descriptor")
+        let raw: serde_yaml::Value =
+            serde_yaml::from_str(yaml).context("Failed to parse Peripheral Descriptor")?;
+        deserialize_config("peripheral descriptor", &raw)
     }
+
+    /// Reject register layouts a `GenericPeripheral` cannot serve unambiguously:
+    /// duplicate register ids, and registers whose byte ranges
+    /// (`address_offset .. address_offset + size/8`) partially overlap.
+    /// Registers at the same offset with the same size are SVD alternate
+    /// registers (aliases of one storage location) and are allowed.
+    pub fn validate(&self) -> Result<()> {
+        let mut ids = HashSet::with_capacity(self.registers.len());
+        for reg in &self.registers {
+            if !ids.insert(reg.id.as_str()) {
+                anyhow::bail!(
+                    "Peripheral '{}' has duplicate register id '{}'",
+                    self.peripheral,
+                    reg.id
+                );
+            }
+        }
+
+        let span = |r: &RegisterDescriptor| {
+            (
+                r.address_offset,
+                r.address_offset + (r.size as u64 / 8).max(1),
+            )
+        };
+        let mut regs: Vec<&RegisterDescriptor> = self.registers.iter().collect();
+        regs.sort_by_key(|r| span(r));
+        for (i, a) in regs.iter().enumerate() {
+            let (a_start, a_end) = span(a);
+            for b in regs[i + 1..].iter().take_while(|b| span(b).0 < a_end) {
+                if span(b) != (a_start, a_end) {
+                    anyhow::bail!(
+                        "Peripheral '{}': registers '{}' (0x{:X}..0x{:X}) and '{}' (0x{:X}..0x{:X}) overlap",
+                        self.peripheral,
+                        a.id,
+                        a_start,
+                        a_end,
+                        b.id,
+                        span(b).0,
+                        span(b).1
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every interrupt of this peripheral, ordered by IRQ number: the entries of
+    /// `interrupt_descriptors`, plus any `interrupts` name without one (with no
+    /// description).
+    pub fn interrupt_list(&self) -> Vec<InterruptDescriptor> {
+        let mut list = self.interrupt_descriptors.clone();
+        for (name, &value) in self.interrupts.iter().flatten() {
+            if !list.iter().any(|i| &i.name == name) {
+                list.push(InterruptDescriptor {
+                    name: name.clone(),
+                    value,
+                    description: None,
+                });
+            }
+        }
+        list.sort_by(|a, b| a.value.cmp(&b.value).then_with(|| a.name.cmp(&b.name)));
+        list
+    }
+
+    /// Bytes from the peripheral base to the end of its highest register.
+    pub fn register_span(&self) -> u64 {
+        self.registers
+            .iter()
+            .map(|r| r.address_offset + (r.size as u64 / 8).max(1))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Register access for an IR access mode. The clear-on-access modes are
+/// readable and writable; their side effect travels in
+/// [`ir_side_effects`]. Write-once locking is not modelled: `WriteOnce`
+/// keeps its write-only direction and `ReadWriteOnce` behaves as read/write.
+fn ir_register_access(access: labwired_ir::IrAccess) -> Access {
+    use labwired_ir::IrAccess;
+    match access {
+        IrAccess::ReadOnly => Access::ReadOnly,
+        IrAccess::WriteOnly | IrAccess::WriteOnce => Access::WriteOnly,
+        IrAccess::ReadWrite
+        | IrAccess::Write1ToClear
+        | IrAccess::ReadToClear
+        | IrAccess::ReadWriteOnce
+        | IrAccess::Unknown => Access::ReadWrite,
+    }
+}
+
+/// Side effects for an IR register: explicit `side_effects` strings win,
+/// and `Write1ToClear` / `ReadToClear` access modes fill in the matching
+/// write or read action when none was given.
+fn ir_side_effects(
+    access: labwired_ir::IrAccess,
+    side_effects: Option<labwired_ir::IrSideEffects>,
+) -> Option<SideEffectsDescriptor> {
+    let mut desc = side_effects.map(|se| SideEffectsDescriptor {
+        read_action: se.read_action.and_then(|s| match s.as_str() {
+            "clear" => Some(ReadAction::Clear),
+            "set" => Some(ReadAction::Set),
+            "modify" => Some(ReadAction::Modify),
+            "modify_external" | "modifyExternal" => Some(ReadAction::ModifyExternal),
+            "none" => Some(ReadAction::None),
+            _ => None,
+        }),
+        write_action: se.write_action.and_then(|s| match s.as_str() {
+            "one_to_clear" | "oneToClear" | "w1c" => Some(WriteAction::WriteOneToClear),
+            "zero_to_clear" | "zeroToClear" | "w0c" => Some(WriteAction::WriteZeroToClear),
+            "one_to_set" | "oneToSet" | "w1s" => Some(WriteAction::WriteOneToSet),
+            "zero_to_set" | "zeroToSet" | "w0s" => Some(WriteAction::WriteZeroToSet),
+            "one_to_toggle" | "oneToToggle" | "w1t" => Some(WriteAction::WriteOneToToggle),
+            "zero_to_toggle" | "zeroToToggle" | "w0t" => Some(WriteAction::WriteZeroToToggle),
+            "clear" => Some(WriteAction::Clear),
+            "set" => Some(WriteAction::Set),
+            "none" => Some(WriteAction::None),
+            _ => None,
+        }),
+        on_read: None,
+        on_write: None,
+    });
+    let implied = match access {
+        labwired_ir::IrAccess::Write1ToClear => (None, Some(WriteAction::WriteOneToClear)),
+        labwired_ir::IrAccess::ReadToClear => (Some(ReadAction::Clear), None),
+        _ => return desc,
+    };
+    let d = desc.get_or_insert(SideEffectsDescriptor {
+        read_action: None,
+        write_action: None,
+        on_read: None,
+        on_write: None,
+    });
+    if d.read_action.is_none() {
+        d.read_action = implied.0;
+    }
+    if d.write_action.is_none() {
+        d.write_action = implied.1;
+    }
+    desc
 }
 
 impl From<labwired_ir::IrPeripheral> for PeripheralDescriptor {
     fn from(ir: labwired_ir::IrPeripheral) -> Self {
         let mut interrupts = std::collections::HashMap::new();
+        let mut interrupt_descriptors = Vec::new();
         for int in ir.interrupts {
-            interrupts.insert(int.name, int.value);
+            interrupts.insert(int.name.clone(), int.value);
+            interrupt_descriptors.push(InterruptDescriptor {
+                name: int.name,
+                value: int.value,
+                description: int.description,
+            });
         }
+        interrupt_descriptors.sort_by_key(|i| i.value);
 
         Self {
             peripheral: ir.name,
@@ -1564,11 +2071,7 @@ impl From<labwired_ir::IrPeripheral> for PeripheralDescriptor {
                     id: r.name,
                     address_offset: r.offset,
                     size: r.size as u8,
-                    access: match r.access {
-                        labwired_ir::IrAccess::ReadOnly => Access::ReadOnly,
-                        labwired_ir::IrAccess::WriteOnly => Access::WriteOnly,
-                        _ => Access::ReadWrite,
-                    },
+                    access: ir_register_access(r.access),
                     reset_value: r.reset_value as u32,
                     fields: r
                         .fields
@@ -1577,27 +2080,10 @@ impl From<labwired_ir::IrPeripheral> for PeripheralDescriptor {
                             name: f.name,
                             bit_range: [(f.bit_offset + f.bit_width - 1) as u8, f.bit_offset as u8],
                             description: f.description,
+                            access: f.access.map(ir_register_access),
                         })
                         .collect(),
-                    side_effects: r.side_effects.map(|se| SideEffectsDescriptor {
-                        read_action: se.read_action.and_then(|s| match s.as_str() {
-                            "clear" => Some(ReadAction::Clear),
-                            "none" => Some(ReadAction::None),
-                            _ => None,
-                        }),
-                        write_action: se.write_action.and_then(|s| match s.as_str() {
-                            "one_to_clear" | "oneToClear" | "w1c" => {
-                                Some(WriteAction::WriteOneToClear)
-                            }
-                            "zero_to_clear" | "zeroToClear" | "w0c" => {
-                                Some(WriteAction::WriteZeroToClear)
-                            }
-                            "none" => Some(WriteAction::None),
-                            _ => None,
-                        }),
-                        on_read: None,
-                        on_write: None,
-                    }),
+                    side_effects: ir_side_effects(r.access, r.side_effects),
                 })
                 .collect(),
             interrupts: if interrupts.is_empty() {
@@ -1605,6 +2091,7 @@ impl From<labwired_ir::IrPeripheral> for PeripheralDescriptor {
             } else {
                 Some(interrupts)
             },
+            interrupt_descriptors,
             timing: if ir.timing.is_empty() {
                 None
             } else {
@@ -1656,11 +2143,11 @@ impl From<labwired_ir::IrDevice> for ChipDescriptor {
             .get("FLASH")
             .map(|r| MemoryRange {
                 base: r.base,
-                size: format!("{}B", r.size),
+                size: ByteSize(r.size),
             })
             .unwrap_or(MemoryRange {
                 base: 0,
-                size: "0".to_string(),
+                size: ByteSize(0),
             });
 
         let ram = ir
@@ -1668,11 +2155,11 @@ impl From<labwired_ir::IrDevice> for ChipDescriptor {
             .get("RAM")
             .map(|r| MemoryRange {
                 base: r.base,
-                size: format!("{}B", r.size),
+                size: ByteSize(r.size),
             })
             .unwrap_or(MemoryRange {
                 base: 0,
-                size: "0".to_string(),
+                size: ByteSize(0),
             });
 
         Self {
@@ -1683,7 +2170,9 @@ impl From<labwired_ir::IrDevice> for ChipDescriptor {
             flash,
             ram,
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: ir
                 .peripherals
@@ -1711,6 +2200,7 @@ impl From<labwired_ir::IrDevice> for ChipDescriptor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct TestInputs {
     pub firmware: String,
@@ -1726,6 +2216,7 @@ pub struct EnvTestInputs {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct TestLimits {
     pub max_steps: u64,
@@ -1752,6 +2243,16 @@ pub struct TestLimits {
     /// before this many steps have executed.
     #[serde(default)]
     pub stop_when_assertions_pass_min_steps: u64,
+    /// Stop with `no_progress` once the same cycle of PCs (of up to
+    /// `loop_detect_window` instructions) repeats this many times back-to-back.
+    /// Catches tight multi-instruction hangs that `no_progress_steps` misses
+    /// (schema_version 1.2+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_detect_repeats: Option<u64>,
+    /// Longest PC cycle, in instructions, the loop detector looks for.
+    /// Defaults to 8; requires `loop_detect_repeats` (schema_version 1.2+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_detect_window: Option<u64>,
 }
 
 fn default_stop_settle_steps() -> u64 {
@@ -1759,6 +2260,7 @@ fn default_stop_settle_steps() -> u64 {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum StopReason {
     /// Runner failed before simulation started (e.g. script parse/validation error).
@@ -1768,35 +2270,111 @@ pub enum StopReason {
     MaxUartBytes,
     MaxVcdBytes,
     NoProgress,
+    /// A `no_progress` stop where the core was asleep in WFI/WFE with no
+    /// enabled interrupt that could wake it.
+    SleepDeadlock,
     WallTime,
     AssertionsPassed,
     MemoryViolation,
     DecodeError,
     Halt,
     Exception,
+    /// `labwired test --dry-run`: the machine was built, loaded and reset,
+    /// then stopped before executing an instruction.
+    DryRun,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct UartContainsAssertion {
     pub uart_contains: String,
+    /// Optional display name used in JUnit/TAP reports (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels for `labwired test --filter-tag` (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Only match output after the first occurrence of this marker
+    /// (schema_version 1.2+). A marker that never appears fails the assertion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Only match output before the first occurrence of this marker (searched
+    /// after `after`, when both are set; schema_version 1.2+). A marker that
+    /// never appears leaves the window open to the end of the output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Remove ANSI escape sequences (colours, cursor moves) from the captured
+    /// output before matching (schema_version 1.2+). `uart.log` keeps the raw
+    /// bytes either way.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip_ansi: bool,
+    /// Convert `\r\n` to `\n` in the captured output before matching
+    /// (schema_version 1.2+), so patterns can be written without `\r`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_newlines: bool,
+    /// Fail instead of matching when the captured output is not valid UTF-8
+    /// (schema_version 1.2+). By default invalid bytes become U+FFFD.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_utf8: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct UartRegexAssertion {
     pub uart_regex: String,
+    /// Optional display name used in JUnit/TAP reports (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels for `labwired test --filter-tag` (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Only match output after the first occurrence of this marker
+    /// (schema_version 1.2+). A marker that never appears fails the assertion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Only match output before the first occurrence of this marker (searched
+    /// after `after`, when both are set; schema_version 1.2+). A marker that
+    /// never appears leaves the window open to the end of the output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Remove ANSI escape sequences (colours, cursor moves) from the captured
+    /// output before matching (schema_version 1.2+). `uart.log` keeps the raw
+    /// bytes either way.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip_ansi: bool,
+    /// Convert `\r\n` to `\n` in the captured output before matching
+    /// (schema_version 1.2+), so patterns can be written without `\r`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_newlines: bool,
+    /// Fail instead of matching when the captured output is not valid UTF-8
+    /// (schema_version 1.2+). By default invalid bytes become U+FFFD.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_utf8: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct StopReasonAssertion {
     pub expected_stop_reason: StopReason,
+    /// Optional display name used in JUnit/TAP reports (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels for `labwired test --filter-tag` (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(deny_unknown_fields))]
 pub struct MemoryValueDetails {
     pub address: u64,
+    /// Negative literals are accepted (for signed `kind`s) and stored
+    /// two's-complement.
+    #[cfg_attr(feature = "schema", schemars(with = "i64"))]
     pub expected_value: u64,
     pub mask: Option<u64>,
     /// Value width to read at `address`. Accepts bytes (1/2/4) or the
@@ -1806,14 +2384,57 @@ pub struct MemoryValueDetails {
     /// Target node for a multi-node environment assertion. Single-node scripts
     /// leave this unset and continue to use the existing machine path.
     pub node: Option<String>,
+    /// Typed read: sets the width and, for signed kinds, sign-extends the
+    /// value before comparing, so `kind: i16` with `expected_value: -5`
+    /// matches a stored `0xFFFB`. Overrides `size` (which must agree if set).
+    pub kind: Option<MemoryValueKind>,
+}
+
+/// Typed interpretation of a `memory_value` read.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryValueKind {
+    U8,
+    U16,
+    U32,
+    I8,
+    I16,
+    I32,
+}
+
+impl MemoryValueKind {
+    /// Read width in bytes.
+    pub fn width(self) -> u64 {
+        match self {
+            Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 => 4,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, Self::I8 | Self::I16 | Self::I32)
+    }
+
+    /// Widen a raw little-endian read of [`Self::width`] bytes to 32 bits,
+    /// sign-extending for the signed kinds.
+    pub fn extend(self, raw: u32) -> u32 {
+        match self {
+            Self::I8 => raw as u8 as i8 as i32 as u32,
+            Self::I16 => raw as u16 as i16 as i32 as u32,
+            _ => raw,
+        }
+    }
 }
 
 // `MemoryValueDetails` is public and callers historically construct it with a
-// struct literal. Keep that field shape intact while retaining the distinction
-// between an omitted `node` and parsed `node: null`: the latter is an invalid
-// explicit qualifier in single-node scripts and must survive a serde round
-// trip. This reserved private sentinel is created only while deserializing a
-// `node: null` field.
+// struct literal, so every new field is a breaking change recorded under
+// "Changed" in CHANGELOG.md (`kind` was the last). Keep the existing field
+// shape intact while retaining the distinction between an omitted `node` and
+// parsed `node: null`: the latter is an invalid explicit qualifier in
+// single-node scripts and must survive a serde round trip. This reserved
+// private sentinel is created only while deserializing a `node: null` field.
 const EXPLICIT_NULL_NODE_SENTINEL: &str = "\u{0}labwired:explicit-null-node";
 
 fn is_explicit_null_node(node: Option<&str>) -> bool {
@@ -1832,18 +2453,52 @@ impl MemoryValueDetails {
             mask: None,
             size: None,
             node: None,
+            kind: None,
         }
     }
+
+    /// Bytes to read: from `kind` when set, else `size` (1/2/4 bytes or
+    /// 8/16/32 bits, default 32 bits). `None` for an unsupported `size`.
+    pub fn width(&self) -> Option<u64> {
+        if let Some(kind) = self.kind {
+            return Some(kind.width());
+        }
+        match self.size.unwrap_or(32) {
+            1 | 8 => Some(1),
+            2 | 16 => Some(2),
+            4 | 32 => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Compare a raw read of [`Self::width`] bytes against `expected_value`,
+    /// after sign-extending per `kind` and applying `mask` (32-bit compare).
+    pub fn matches(&self, raw: u32) -> bool {
+        let value = self.kind.map_or(raw, |kind| kind.extend(raw));
+        let mask = self.mask.unwrap_or(0xFFFF_FFFF) as u32;
+        (value & mask) == (self.expected_value as u32 & mask)
+    }
+}
+
+/// `expected_value` on the wire: negative literals are accepted for signed
+/// kinds and stored two's-complement in the `u64` field.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ExpectedValueWire {
+    Unsigned(u64),
+    Signed(i64),
 }
 
 #[derive(Serialize)]
 struct SerializableMemoryValueDetails<'a> {
     address: u64,
-    expected_value: u64,
+    expected_value: ExpectedValueWire,
     mask: Option<u64>,
     size: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     node: Option<Option<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<MemoryValueKind>,
 }
 
 impl Serialize for MemoryValueDetails {
@@ -1853,9 +2508,14 @@ impl Serialize for MemoryValueDetails {
     {
         SerializableMemoryValueDetails {
             address: self.address,
-            expected_value: self.expected_value,
+            expected_value: if self.kind.is_some_and(MemoryValueKind::is_signed) {
+                ExpectedValueWire::Signed(self.expected_value as i64)
+            } else {
+                ExpectedValueWire::Unsigned(self.expected_value)
+            },
             mask: self.mask,
             size: self.size,
+            kind: self.kind,
             node: if is_explicit_null_node(self.node.as_deref()) {
                 Some(None)
             } else {
@@ -1870,13 +2530,15 @@ impl Serialize for MemoryValueDetails {
 #[serde(deny_unknown_fields)]
 struct MemoryValueDetailsWire {
     address: u64,
-    expected_value: u64,
+    expected_value: ExpectedValueWire,
     #[serde(default)]
     mask: Option<u64>,
     #[serde(default)]
     size: Option<u8>,
     #[serde(default)]
     node: FieldPresence<String>,
+    #[serde(default)]
+    kind: Option<MemoryValueKind>,
 }
 
 impl<'de> Deserialize<'de> for MemoryValueDetails {
@@ -1885,11 +2547,24 @@ impl<'de> Deserialize<'de> for MemoryValueDetails {
         D: serde::Deserializer<'de>,
     {
         let wire = MemoryValueDetailsWire::deserialize(deserializer)?;
+        if let (Some(kind), Some(size)) = (wire.kind, wire.size) {
+            if u64::from(size) != kind.width() && u64::from(size) != kind.width() * 8 {
+                let name = format!("{kind:?}").to_lowercase();
+                return Err(serde::de::Error::custom(format!(
+                    "memory_value kind '{name}' conflicts with size {size}"
+                )));
+            }
+        }
+        let expected_value = match wire.expected_value {
+            ExpectedValueWire::Unsigned(value) => value,
+            ExpectedValueWire::Signed(value) => value as u64,
+        };
         Ok(Self {
             address: wire.address,
-            expected_value: wire.expected_value,
+            expected_value,
             mask: wire.mask,
             size: wire.size,
+            kind: wire.kind,
             node: match wire.node {
                 FieldPresence::Absent => None,
                 FieldPresence::Present(Some(node)) => Some(node),
@@ -1900,18 +2575,27 @@ impl<'de> Deserialize<'de> for MemoryValueDetails {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct MemoryValueAssertion {
     pub memory_value: MemoryValueDetails,
+    /// Optional display name used in JUnit/TAP reports (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels for `labwired test --filter-tag` (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum UdsTesterResult {
     Done,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct UdsTesterDetails {
     pub id: String,
@@ -1919,12 +2603,20 @@ pub struct UdsTesterDetails {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct UdsTesterAssertion {
     pub uds_tester: UdsTesterDetails,
+    /// Optional display name used in JUnit/TAP reports (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels for `labwired test --filter-tag` (schema_version 1.1+).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TestAssertion {
     UartContains(UartContainsAssertion),
@@ -1934,10 +2626,75 @@ pub enum TestAssertion {
     UdsTester(UdsTesterAssertion),
 }
 
+impl TestAssertion {
+    /// The assertion's optional report name (schema_version 1.1+).
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            TestAssertion::UartContains(a) => a.name.as_deref(),
+            TestAssertion::UartRegex(a) => a.name.as_deref(),
+            TestAssertion::ExpectedStopReason(a) => a.name.as_deref(),
+            TestAssertion::MemoryValue(a) => a.name.as_deref(),
+            TestAssertion::UdsTester(a) => a.name.as_deref(),
+        }
+    }
+
+    /// The assertion's tags (schema_version 1.1+); empty when untagged.
+    pub fn tags(&self) -> &[String] {
+        match self {
+            TestAssertion::UartContains(a) => &a.tags,
+            TestAssertion::UartRegex(a) => &a.tags,
+            TestAssertion::ExpectedStopReason(a) => &a.tags,
+            TestAssertion::MemoryValue(a) => &a.tags,
+            TestAssertion::UdsTester(a) => &a.tags,
+        }
+    }
+
+    /// The `(after, before)` markers bounding a UART assertion's match window
+    /// (schema_version 1.2+); both `None` for unbounded and non-UART assertions.
+    pub fn uart_window(&self) -> (Option<&str>, Option<&str>) {
+        match self {
+            TestAssertion::UartContains(a) => (a.after.as_deref(), a.before.as_deref()),
+            TestAssertion::UartRegex(a) => (a.after.as_deref(), a.before.as_deref()),
+            _ => (None, None),
+        }
+    }
+
+    /// How a UART assertion preprocesses the captured output before matching
+    /// (schema_version 1.2+); all off for non-UART assertions.
+    pub fn uart_text_options(&self) -> UartTextOptions {
+        match self {
+            TestAssertion::UartContains(a) => UartTextOptions {
+                strip_ansi: a.strip_ansi,
+                normalize_newlines: a.normalize_newlines,
+                strict_utf8: a.strict_utf8,
+            },
+            TestAssertion::UartRegex(a) => UartTextOptions {
+                strip_ansi: a.strip_ansi,
+                normalize_newlines: a.normalize_newlines,
+                strict_utf8: a.strict_utf8,
+            },
+            _ => UartTextOptions::default(),
+        }
+    }
+}
+
+/// The text-preprocessing flags of a `uart_contains`/`uart_regex` assertion.
+/// The raw capture written to `uart.log` is never affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UartTextOptions {
+    /// Remove ANSI escape sequences before matching.
+    pub strip_ansi: bool,
+    /// Convert `\r\n` to `\n` before matching.
+    pub normalize_newlines: bool,
+    /// Fail when the capture is not valid UTF-8 instead of matching lossily.
+    pub strict_utf8: bool,
+}
+
 /// Where a fault is applied. Either a peripheral (by `id`, optionally narrowed
 /// to a `register` and `bit`) or a raw memory `address`. Resolved against the
 /// built chip when the run starts.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct FaultTarget {
     #[serde(default)]
@@ -1952,6 +2709,7 @@ pub struct FaultTarget {
 
 /// The access mode a `permission_flip` fault forces a register into.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AccessMode {
     ReadOnly,
@@ -1960,6 +2718,7 @@ pub enum AccessMode {
 
 /// The access direction a `permission_violation` fault denies.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AccessDirection {
     Read,
@@ -1969,6 +2728,7 @@ pub enum AccessDirection {
 /// When a fault takes effect. Mirrors the declarative peripheral trigger
 /// vocabulary so peripheral-class faults reuse the same evaluator.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum FaultTrigger {
     /// Applied while the bus is built, before the firmware runs.
@@ -1991,6 +2751,7 @@ pub enum FaultTrigger {
 /// The taxonomy of injectable faults. Each maps to a documented silicon failure
 /// mode; see the per-kind required parameters enforced in [`TestScript::validate`].
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum FaultKind {
     MissingClock,
@@ -2009,6 +2770,7 @@ pub enum FaultKind {
 /// A single injected fault. `kind`-specific parameters are the optional fields;
 /// which are required is enforced structurally by [`TestScript::validate`].
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct FaultSpec {
     pub id: String,
@@ -2049,6 +2811,7 @@ pub struct FaultSpec {
 /// The safe-behaviour judgment for a fault-injection run. `safe_when` reuses the
 /// ordinary assertion vocabulary; the firmware passes iff every entry holds.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct Verdict {
     #[serde(default)]
@@ -2067,6 +2830,7 @@ pub struct Verdict {
 /// resolution is by unique channel key and an ambiguous channel is a run-time
 /// error rather than silently picking one.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct StimulusTarget {
     /// Optional owning peripheral name / sensor id to disambiguate `channel`.
@@ -2083,6 +2847,7 @@ pub struct StimulusTarget {
 /// via the generic `Machine::set_input` path, so it works for any input device
 /// without per-type wiring.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct StimulusSpec {
     pub target: StimulusTarget,
@@ -2092,7 +2857,35 @@ pub struct StimulusSpec {
     pub value: f64,
 }
 
+/// One `setup.memory` poke: `bytes` written in order starting at `address`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SetupMemoryWrite {
+    pub address: u64,
+    pub bytes: Vec<u8>,
+}
+
+/// Pre-run machine state (schema_version 1.1+), applied after the firmware is
+/// loaded and before the first step. Lets a script drive an ISR or start from
+/// a specific state without building dedicated firmware for every scenario.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TestSetup {
+    /// CPU registers by name, as listed by the core (e.g. `R0`, `SP`, `PC`).
+    #[serde(default)]
+    pub registers: BTreeMap<String, u32>,
+    /// Raw memory/MMIO writes, applied in order after the registers.
+    #[serde(default)]
+    pub memory: Vec<SetupMemoryWrite>,
+    /// Peripherals whose `irq` line is pended before the first step.
+    #[serde(default)]
+    pub inject_irq: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct TestScript {
     pub schema_version: String,
@@ -2109,6 +2902,15 @@ pub struct TestScript {
     /// Input stimuli to drive during the run (schema_version 1.2+).
     #[serde(default)]
     pub stimuli: Vec<StimulusSpec>,
+    /// Path (relative to the script) of a scenario YAML of timed external
+    /// events — button presses, UART RX, IRQs, register pokes — replayed
+    /// during the run (schema_version 1.2+). See `labwired_core::world::Scenario`.
+    #[serde(default)]
+    pub scenario: Option<String>,
+    /// Registers, memory and pending interrupts to apply before the run
+    /// (schema_version 1.1+).
+    #[serde(default)]
+    pub setup: Option<TestSetup>,
 }
 
 fn reject_explicit_memory_nodes(assertions: &[TestAssertion], script_kind: &str) -> Result<()> {
@@ -2124,6 +2926,19 @@ fn reject_explicit_memory_nodes(assertions: &[TestAssertion], script_kind: &str)
     Ok(())
 }
 
+/// Assertion `name`/`tags` arrived with schema_version 1.1; older script
+/// kinds reject them so a typo'd version can't silently drop the metadata.
+fn reject_assertion_metadata(assertions: &[TestAssertion], script_kind: &str) -> Result<()> {
+    for (index, assertion) in assertions.iter().enumerate() {
+        if assertion.name().is_some() || !assertion.tags().is_empty() {
+            anyhow::bail!(
+                "{script_kind} test scripts do not support 'name'/'tags' on assertions (assertions[{index}]); use schema_version '1.1'"
+            );
+        }
+    }
+    Ok(())
+}
+
 impl TestScript {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let f = std::fs::File::open(&path)
@@ -2134,6 +2949,12 @@ impl TestScript {
         Ok(script)
     }
 
+    /// Write the script to `path`: JSON for a `.json` extension, YAML
+    /// otherwise.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        write_config_file(self, path.as_ref(), "Test Script")
+    }
+
     pub fn validate(&self) -> Result<()> {
         if !matches!(self.schema_version.as_str(), "1.0" | "1.1" | "1.2") {
             anyhow::bail!(
@@ -2152,6 +2973,45 @@ impl TestScript {
 
         reject_explicit_memory_nodes(&self.assertions, "single-node")?;
 
+        // Named/tagged assertions require schema_version 1.1+.
+        if self.schema_version == "1.0" {
+            reject_assertion_metadata(&self.assertions, "schema_version '1.0'")?;
+        }
+        for (index, assertion) in self.assertions.iter().enumerate() {
+            if assertion.name().is_some_and(|n| n.trim().is_empty()) {
+                anyhow::bail!("assertions[{index}]: 'name' cannot be empty");
+            }
+            let (after, before) = assertion.uart_window();
+            if after.is_some() || before.is_some() {
+                // UART match windows require schema_version 1.2+.
+                if matches!(self.schema_version.as_str(), "1.0" | "1.1") {
+                    anyhow::bail!(
+                        "assertions[{index}]: 'after'/'before' require schema_version '1.2' (got '{}')",
+                        self.schema_version
+                    );
+                }
+                if after.is_some_and(str::is_empty) || before.is_some_and(str::is_empty) {
+                    anyhow::bail!("assertions[{index}]: 'after'/'before' markers cannot be empty");
+                }
+            }
+            let options = assertion.uart_text_options();
+            for (field, set) in [
+                ("strip_ansi", options.strip_ansi),
+                ("normalize_newlines", options.normalize_newlines),
+                ("strict_utf8", options.strict_utf8),
+            ] {
+                if set && matches!(self.schema_version.as_str(), "1.0" | "1.1") {
+                    anyhow::bail!(
+                        "assertions[{index}]: '{field}' requires schema_version '1.2' (got '{}')",
+                        self.schema_version
+                    );
+                }
+            }
+            if assertion.tags().iter().any(|t| t.trim().is_empty()) {
+                anyhow::bail!("assertions[{index}]: tags cannot be empty");
+            }
+        }
+
         // Fault injection requires schema_version 1.1+.
         if self.schema_version == "1.0" && (!self.faults.is_empty() || self.verdict.is_some()) {
             anyhow::bail!(
@@ -2160,6 +3020,54 @@ impl TestScript {
             );
         }
 
+        if let Some(setup) = &self.setup {
+            if self.schema_version == "1.0" {
+                anyhow::bail!(
+                    "'setup' requires schema_version '1.1' (got '{}')",
+                    self.schema_version
+                );
+            }
+            if setup.registers.keys().any(|r| r.trim().is_empty()) {
+                anyhow::bail!("setup.registers: register names cannot be empty");
+            }
+            for (i, write) in setup.memory.iter().enumerate() {
+                if write.bytes.is_empty() {
+                    anyhow::bail!("setup.memory[{}]: 'bytes' cannot be empty", i);
+                }
+                if write
+                    .address
+                    .checked_add(write.bytes.len() as u64)
+                    .is_none()
+                {
+                    anyhow::bail!("setup.memory[{}]: write overflows the address space", i);
+                }
+            }
+            if setup.inject_irq.iter().any(|p| p.trim().is_empty()) {
+                anyhow::bail!("setup.inject_irq: peripheral names cannot be empty");
+            }
+        }
+
+        let limits = &self.limits;
+        if limits.loop_detect_repeats.is_some() || limits.loop_detect_window.is_some() {
+            // Loop detection requires schema_version 1.2+.
+            if matches!(self.schema_version.as_str(), "1.0" | "1.1") {
+                anyhow::bail!(
+                    "'limits.loop_detect_repeats'/'limits.loop_detect_window' require schema_version '1.2' (got '{}')",
+                    self.schema_version
+                );
+            }
+            match limits.loop_detect_repeats {
+                None => anyhow::bail!("'limits.loop_detect_window' requires 'loop_detect_repeats'"),
+                Some(repeats) if repeats < 2 => {
+                    anyhow::bail!("Limit 'loop_detect_repeats' must be at least 2")
+                }
+                Some(_) => {}
+            }
+            if limits.loop_detect_window == Some(0) {
+                anyhow::bail!("Limit 'loop_detect_window' must be greater than zero");
+            }
+        }
+
         // Input stimuli require schema_version 1.2+.
         if !self.stimuli.is_empty() && matches!(self.schema_version.as_str(), "1.0" | "1.1") {
             anyhow::bail!(
@@ -2188,6 +3096,18 @@ impl TestScript {
             }
         }
 
+        if let Some(scenario) = &self.scenario {
+            if matches!(self.schema_version.as_str(), "1.0" | "1.1") {
+                anyhow::bail!(
+                    "'scenario' requires schema_version '1.2' (got '{}')",
+                    self.schema_version
+                );
+            }
+            if scenario.trim().is_empty() {
+                anyhow::bail!("'scenario' path cannot be empty");
+            }
+        }
+
         // Structural fault-compiler guardrails. Deeper checks that need the
         // built chip (target resolution, bit-within-register) run when the bus
         // is available; these catch malformed specs up front.
@@ -2487,6 +3407,8 @@ impl EnvTestLimits {
                 .stop_when_assertions_pass_min_steps
                 .into_value()
                 .unwrap_or_default(),
+            loop_detect_repeats: None,
+            loop_detect_window: None,
         };
         (limits, explicit_limits)
     }
@@ -2605,6 +3527,8 @@ impl EnvTestScript {
             anyhow::bail!("Environment test scripts do not support 'stimuli'");
         }
 
+        reject_assertion_metadata(&self.assertions, "environment")?;
+
         if self.assertions.is_empty() {
             anyhow::bail!("Environment test scripts require at least one assertion");
         }
@@ -2767,8 +3691,50 @@ impl LegacyTestScriptV1 {
             );
         }
         reject_explicit_memory_nodes(&self.assertions, "legacy")?;
+        reject_assertion_metadata(&self.assertions, "legacy")?;
         Ok(())
     }
+
+    /// Re-express this script in the v1.0 `inputs`/`limits` form. Assertions
+    /// and `wall_time_ms` carry over; every limit v1 lacked takes its v1.0
+    /// default. Fails when the result is not a valid v1.0 script — e.g. no
+    /// `firmware` (legacy scripts could take it from the command line) or a
+    /// zero `max_steps`.
+    pub fn migrate(self) -> Result<TestScript> {
+        let Some(firmware) = self.firmware else {
+            anyhow::bail!("Legacy script has no 'firmware'; v1.0 requires inputs.firmware");
+        };
+        let script = TestScript {
+            schema_version: "1.0".to_string(),
+            inputs: TestInputs {
+                firmware,
+                system: self.system,
+            },
+            limits: TestLimits {
+                max_steps: self.max_steps,
+                max_cycles: None,
+                max_uart_bytes: None,
+                no_progress_steps: None,
+                wall_time_ms: self.wall_time_ms,
+                max_vcd_bytes: None,
+                stop_when_assertions_pass: false,
+                stop_when_assertions_pass_settle_steps: default_stop_settle_steps(),
+                stop_when_assertions_pass_min_steps: 0,
+                loop_detect_repeats: None,
+                loop_detect_window: None,
+            },
+            assertions: self.assertions,
+            faults: Vec::new(),
+            verdict: None,
+            stimuli: Vec::new(),
+            scenario: None,
+            setup: None,
+        };
+        script
+            .validate()
+            .context("Migrated script is not a valid schema_version \"1.0\" script")?;
+        Ok(script)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -2861,6 +3827,64 @@ pub fn parse_size(size_str: &str) -> Result<u64> {
     Ok(bytes.value() as u64)
 }
 
+/// A byte count written as a human size ("128KB", "1MB") or a bare integer.
+/// Parsed once at deserialize time with [`parse_size`], so a bad size fails
+/// the config load instead of every consumer; serializes back to the
+/// canonical [`Display`](std::fmt::Display) form (largest exact KB/MB unit,
+/// else bytes), which `parse_size` reads back to the same value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+impl std::str::FromStr for ByteSize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_size(s).map(ByteSize)
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const KB: u64 = 1024;
+        const MB: u64 = 1024 * KB;
+        match self.0 {
+            0 => write!(f, "0B"),
+            n if n % MB == 0 => write!(f, "{}MB", n / MB),
+            n if n % KB == 0 => write!(f, "{}KB", n / KB),
+            n => write!(f, "{n}B"),
+        }
+    }
+}
+
+impl Serialize for ByteSize {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bytes(u64),
+            Human(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Bytes(n) => Ok(ByteSize(n)),
+            Raw::Human(s) => s
+                .parse()
+                .map_err(|e| serde::de::Error::custom(format!("invalid size '{s}': {e}"))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod parse_size_tests {
     use super::parse_size;
@@ -2883,6 +3907,24 @@ mod parse_size_tests {
     fn garbage_still_errors() {
         assert!(parse_size("not-a-size").is_err());
     }
+
+    #[test]
+    fn memory_range_sizes_round_trip_canonically() {
+        use super::{ByteSize, MemoryRange};
+        for (text, bytes) in [("1MB", 1 << 20), ("128KB", 128 << 10)] {
+            let yaml = format!("base: 0x20000000\nsize: \"{text}\"\n");
+            let range: MemoryRange = serde_yaml::from_str(&yaml).unwrap();
+            assert_eq!(range.size, ByteSize(bytes));
+            let out = serde_yaml::to_string(&range).unwrap();
+            assert!(out.contains(&format!("size: {text}")), "{out}");
+            let back: MemoryRange = serde_yaml::from_str(&out).unwrap();
+            assert_eq!(back.size.bytes(), bytes);
+        }
+        // Non-canonical spellings normalize; bad sizes fail at load time.
+        let range: MemoryRange = serde_yaml::from_str("base: 0\nsize: 1048576\n").unwrap();
+        assert_eq!(range.size.to_string(), "1MB");
+        assert!(serde_yaml::from_str::<MemoryRange>("base: 0\nsize: \"lots\"\n").is_err());
+    }
 }
 
 #[cfg(test)]
@@ -2969,6 +4011,97 @@ limits:
             .to_string()
             .contains("not yet supported for stimuli"));
     }
+
+    #[test]
+    fn scenario_path_requires_schema_1_2() {
+        let s: TestScript = serde_yaml::from_str(&script("1.2", "scenario: press.yaml\n")).unwrap();
+        s.validate().unwrap();
+        assert_eq!(s.scenario.as_deref(), Some("press.yaml"));
+
+        let s: TestScript = serde_yaml::from_str(&script("1.1", "scenario: press.yaml\n")).unwrap();
+        let err = s.validate().unwrap_err().to_string();
+        assert!(err.contains("requires schema_version '1.2'"), "{err}");
+    }
+
+    #[test]
+    fn loop_detect_limits_validate() {
+        let s: TestScript =
+            serde_yaml::from_str(&script("1.2", "  loop_detect_repeats: 5\n")).unwrap();
+        s.validate().unwrap();
+        assert_eq!(s.limits.loop_detect_repeats, Some(5));
+
+        for (schema, block, needle) in [
+            (
+                "1.1",
+                "  loop_detect_repeats: 5\n",
+                "require schema_version '1.2'",
+            ),
+            (
+                "1.2",
+                "  loop_detect_window: 4\n",
+                "requires 'loop_detect_repeats'",
+            ),
+            ("1.2", "  loop_detect_repeats: 1\n", "at least 2"),
+        ] {
+            let s: TestScript = serde_yaml::from_str(&script(schema, block)).unwrap();
+            let err = s.validate().unwrap_err().to_string();
+            assert!(err.contains(needle), "{err}");
+        }
+    }
+
+    #[test]
+    fn uart_window_markers_require_schema_1_2() {
+        let block = "assertions:\n  - uart_contains: \"OK\"\n    after: \"RESET\"\n";
+        let s: TestScript = serde_yaml::from_str(&script("1.2", block)).unwrap();
+        s.validate().unwrap();
+        assert_eq!(s.assertions[0].uart_window(), (Some("RESET"), None));
+
+        let s: TestScript = serde_yaml::from_str(&script("1.1", block)).unwrap();
+        let err = s.validate().unwrap_err().to_string();
+        assert!(err.contains("require schema_version '1.2'"), "{err}");
+
+        let empty = "assertions:\n  - uart_regex: \"OK\"\n    before: \"\"\n";
+        let s: TestScript = serde_yaml::from_str(&script("1.2", empty)).unwrap();
+        let err = s.validate().unwrap_err().to_string();
+        assert!(err.contains("markers cannot be empty"), "{err}");
+    }
+
+    #[test]
+    fn uart_strip_ansi_requires_schema_1_2() {
+        let block = "assertions:\n  - uart_contains: \"PASS\"\n    strip_ansi: true\n";
+        let s: TestScript = serde_yaml::from_str(&script("1.2", block)).unwrap();
+        s.validate().unwrap();
+        assert!(s.assertions[0].uart_text_options().strip_ansi);
+
+        let s: TestScript = serde_yaml::from_str(&script("1.1", block)).unwrap();
+        let err = s.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("'strip_ansi' requires schema_version '1.2'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn uart_newline_and_utf8_options_require_schema_1_2() {
+        let block = "assertions:\n  - uart_regex: \"^OK$\"\n    normalize_newlines: true\n    strict_utf8: true\n";
+        let s: TestScript = serde_yaml::from_str(&script("1.2", block)).unwrap();
+        s.validate().unwrap();
+        assert_eq!(
+            s.assertions[0].uart_text_options(),
+            UartTextOptions {
+                strip_ansi: false,
+                normalize_newlines: true,
+                strict_utf8: true,
+            }
+        );
+
+        let s: TestScript = serde_yaml::from_str(&script("1.1", block)).unwrap();
+        let err = s.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("'normalize_newlines' requires schema_version '1.2'"),
+            "{err}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -3084,6 +4217,96 @@ faults:
         assert!(err.to_string().contains("Duplicate fault id"));
     }
 
+    #[test]
+    fn test_named_tagged_assertions_v1_1() {
+        let yaml = r#"
+schema_version: "1.1"
+inputs:
+  firmware: "fw.elf"
+limits:
+  max_steps: 100
+assertions:
+  - uart_contains: "BOOT OK"
+    name: "boot banner"
+    tags: [smoke, uart]
+  - memory_value:
+      address: 0x20000000
+      expected_value: 1
+    tags: [state]
+  - expected_stop_reason: max_steps
+"#;
+        let script: TestScript = serde_yaml::from_str(yaml).unwrap();
+        script.validate().expect("valid 1.1 named-assertion script");
+        assert_eq!(script.assertions[0].name(), Some("boot banner"));
+        assert_eq!(script.assertions[0].tags(), ["smoke", "uart"]);
+        assert!(matches!(
+            script.assertions[1],
+            TestAssertion::MemoryValue(_)
+        ));
+        assert_eq!(script.assertions[1].name(), None);
+        assert_eq!(script.assertions[1].tags(), ["state"]);
+        assert!(script.assertions[2].tags().is_empty());
+
+        // Untagged assertions serialize exactly as before.
+        let json = serde_json::to_value(&script.assertions[2]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "expected_stop_reason": "max_steps" })
+        );
+    }
+
+    #[test]
+    fn test_named_assertions_require_v1_1() {
+        let yaml = r#"
+schema_version: "1.0"
+inputs:
+  firmware: "fw.elf"
+limits:
+  max_steps: 100
+assertions:
+  - uart_contains: "OK"
+    name: "banner"
+"#;
+        let script: TestScript = serde_yaml::from_str(yaml).unwrap();
+        let err = script.validate().unwrap_err();
+        assert!(err.to_string().contains("'name'/'tags'"), "{err}");
+    }
+
+    #[test]
+    fn test_setup_block_v1_1() {
+        let yaml = r#"
+schema_version: "1.1"
+inputs:
+  firmware: "fw.elf"
+limits:
+  max_steps: 100
+setup:
+  registers:
+    R0: 0x1234
+  memory:
+    - address: 0x20000000
+      bytes: [0xDE, 0xAD]
+  inject_irq: ["usart1"]
+"#;
+        let script: TestScript = serde_yaml::from_str(yaml).unwrap();
+        script.validate().unwrap();
+        let setup = script.setup.unwrap();
+        assert_eq!(setup.registers.get("R0"), Some(&0x1234));
+        assert_eq!(
+            setup.memory,
+            vec![SetupMemoryWrite {
+                address: 0x2000_0000,
+                bytes: vec![0xDE, 0xAD],
+            }]
+        );
+        assert_eq!(setup.inject_irq, vec!["usart1".to_string()]);
+
+        let v1_0 = yaml.replace("\"1.1\"", "\"1.0\"");
+        let script: TestScript = serde_yaml::from_str(&v1_0).unwrap();
+        let err = script.validate().unwrap_err();
+        assert!(err.to_string().contains("'setup' requires"), "{err}");
+    }
+
     #[test]
     fn test_v1_0_script_still_valid_without_faults() {
         let yaml = r#"
@@ -3984,6 +5207,167 @@ registers:
         );
     }
 
+    #[test]
+    fn unknown_keys_are_reported_at_any_depth() {
+        let doc: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+name: "c"
+arch: "arm"
+registers_count: 3
+flash: { base: 0, size: "64K", sise: "128K" }
+ram: { base: 0x20000000, size: "16K" }
+peripherals:
+  - id: "uart1"
+    type: "uart"
+    base_address: 0x40013800
+    bus: "apb2"
+    clock: { reg: "apb2enr", bitt: 14 }
+    config: { anything: 1 }
+"#,
+        )
+        .unwrap();
+        let err = deserialize_config_with::<ChipDescriptor, _>("chip", &doc, false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("unknown key(s) flash.sise, peripherals.0.clock.?.bitt"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn unknown_config_keys_can_be_downgraded_to_warnings() {
+        let doc: serde_yaml::Value = serde_yaml::from_str(
+            "peripheral: \"X\"\nversion: \"1\"\nregisters: []\nregister: []\n",
+        )
+        .unwrap();
+        let err = deserialize_config_with::<PeripheralDescriptor, _>("desc", &doc, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown key(s) register"), "{err}");
+        assert!(deserialize_config_with::<PeripheralDescriptor, _>("desc", &doc, true).is_ok());
+    }
+
+    #[test]
+    fn strict_ir_json_rejects_unknown_keys() {
+        let doc = serde_json::json!({
+            "name": "d",
+            "arch": "arm",
+            "description": null,
+            "peripherals": {},
+            "interrupt_mapping": {},
+            "memory_region": {}
+        });
+        let err = deserialize_config_with::<labwired_ir::IrDevice, _>("ir", doc, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown key(s) memory_region"), "{err}");
+    }
+
+    #[test]
+    fn peripheral_descriptor_yaml_round_trip_is_equal() {
+        let yaml = r#"
+peripheral: "USART"
+version: "1.0"
+registers:
+  - id: "SR"
+    address_offset: 0x00
+    size: 32
+    access: "R/W"
+    reset_value: 0x00C0
+    fields:
+      - name: "TXE"
+        bit_range: [7, 7]
+        description: "Transmit data register empty"
+    side_effects:
+      read_action: clear
+      write_action: write_one_to_clear
+      on_read: "clear_rxne"
+interrupts:
+  USART1: 37
+timing:
+  - id: "tx_done"
+    trigger: { write: { register: "DR" } }
+    delay_cycles: 100
+    action: { set_bits: { register: "SR", bits: 0x40 } }
+"#;
+        let desc = PeripheralDescriptor::from_yaml(yaml).unwrap();
+        let round_tripped =
+            PeripheralDescriptor::from_yaml(&serde_yaml::to_string(&desc).unwrap()).unwrap();
+        assert_eq!(desc, round_tripped);
+    }
+
+    #[test]
+    fn peripheral_descriptor_validate_rejects_overlapping_registers() {
+        let yaml = r#"
+peripheral: "SPI"
+version: "1.0"
+registers:
+  - { id: "CR1", address_offset: 0x00, size: 32, access: "R/W", reset_value: 0 }
+  - { id: "CR2", address_offset: 0x02, size: 32, access: "R/W", reset_value: 0 }
+"#;
+        let err = PeripheralDescriptor::from_yaml(yaml)
+            .unwrap()
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'CR1'") && err.contains("'CR2'"), "{err}");
+
+        // Same offset and size is an SVD alternate register, not an overlap.
+        let aliased = yaml.replace("0x02", "0x00");
+        PeripheralDescriptor::from_yaml(&aliased)
+            .unwrap()
+            .validate()
+            .unwrap();
+
+        let duplicate = yaml.replace("0x02", "0x04").replace("\"CR2\"", "\"CR1\"");
+        let err = PeripheralDescriptor::from_yaml(&duplicate)
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("duplicate register id 'CR1'"));
+    }
+
+    #[test]
+    fn ir_clear_on_access_modes_become_side_effects() {
+        let reg = |name: &str, access| labwired_ir::IrRegister {
+            name: name.to_string(),
+            offset: 0,
+            size: 32,
+            access,
+            reset_value: 0,
+            fields: Vec::new(),
+            side_effects: None,
+            description: None,
+        };
+        let ir = labwired_ir::IrPeripheral {
+            name: "uart".to_string(),
+            base_address: 0x4000_0000,
+            description: None,
+            registers: vec![
+                reg("DR", labwired_ir::IrAccess::ReadToClear),
+                reg("SR", labwired_ir::IrAccess::Write1ToClear),
+                reg("KEY", labwired_ir::IrAccess::WriteOnce),
+                reg("CR", labwired_ir::IrAccess::ReadWrite),
+            ],
+            interrupts: Vec::new(),
+            timing: Vec::new(),
+        };
+        let desc = PeripheralDescriptor::from(ir);
+
+        let dr = desc.registers[0].side_effects.as_ref().unwrap();
+        assert_eq!(desc.registers[0].access, Access::ReadWrite);
+        assert_eq!(dr.read_action, Some(ReadAction::Clear));
+        assert_eq!(dr.write_action, None);
+
+        let sr = desc.registers[1].side_effects.as_ref().unwrap();
+        assert_eq!(sr.write_action, Some(WriteAction::WriteOneToClear));
+        assert_eq!(sr.read_action, None);
+
+        assert_eq!(desc.registers[2].access, Access::WriteOnly);
+        assert!(desc.registers[3].side_effects.is_none());
+    }
+
     #[test]
     fn uds_tester_assertion_parses_result_done() {
         let yaml = r#"
@@ -0,0 +1,64 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! JSON Schema for the hand-written YAML documents — chip descriptors, system
+//! manifests, peripheral descriptors and test scripts — so editors running
+//! yaml-language-server can validate them as they are typed.
+//!
+//! The schemas are derived from the serde types themselves; fields whose
+//! deserializer is more lenient than their Rust type (hex-string addresses,
+//! human sizes, free-form `config:` maps) override their schema here.
+
+use crate::{ByteSize, ChipDescriptor, PeripheralDescriptor, SystemManifest, TestScript};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// A YAML document kind with an exported schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    Chip,
+    System,
+    Peripheral,
+    TestScript,
+}
+
+/// The JSON Schema (draft 2020-12) for `kind`.
+pub fn json_schema(kind: SchemaKind) -> serde_json::Value {
+    let schema = match kind {
+        SchemaKind::Chip => schemars::schema_for!(ChipDescriptor),
+        SchemaKind::System => schemars::schema_for!(SystemManifest),
+        SchemaKind::Peripheral => schemars::schema_for!(PeripheralDescriptor),
+        SchemaKind::TestScript => schemars::schema_for!(TestScript),
+    };
+    schema.to_value()
+}
+
+/// Schema for fields read with `deserialize_u64_lax`: an integer, or a
+/// decimal / `0x` hex string with optional `_` separators.
+pub(crate) fn lax_u64(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "anyOf": [
+            { "type": "integer", "minimum": 0 },
+            { "type": "string", "pattern": "^\\s*(0[xX][0-9a-fA-F_]+|[0-9_]+)\\s*$" }
+        ]
+    })
+}
+
+impl JsonSchema for ByteSize {
+    fn schema_name() -> Cow<'static, str> {
+        "ByteSize".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A byte count: an integer, or a human size such as \"128KB\" or \"1MB\".",
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string" }
+            ]
+        })
+    }
+}
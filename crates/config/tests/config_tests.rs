@@ -29,6 +29,183 @@ peripherals:
     assert_eq!(desc.peripherals[0].irq, None);
 }
 
+#[test]
+fn chip_from_file_keeps_cortex_arch_alias_as_core() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("m0.yaml");
+    std::fs::write(
+        &path,
+        r#"
+name: "m0-chip"
+arch: "cortex-m0+"
+flash:
+  base: 0x0
+  size: "32KB"
+ram:
+  base: 0x20000000
+  size: "8KB"
+peripherals: []
+"#,
+    )
+    .unwrap();
+    let desc = ChipDescriptor::from_file(&path).unwrap();
+    assert_eq!(desc.arch, labwired_config::Arch::Arm);
+    assert_eq!(desc.core.as_deref(), Some("cortex-m0+"));
+}
+
+#[test]
+fn chip_to_file_round_trips() {
+    let chip = validation_chip(
+        r#"  - id: "uart1"
+    type: "uart"
+    base_address: 0x40013800
+    size: "1KB"
+    irq: 37
+    config:
+      baud: 115200"#,
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chip.yaml");
+    chip.to_file(&path).unwrap();
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        text.find("name:").unwrap() < text.find("flash:").unwrap(),
+        "fields keep declaration order:\n{text}"
+    );
+    let reloaded = ChipDescriptor::from_file(&path).unwrap();
+    assert_eq!(reloaded.name, chip.name);
+    assert_eq!(reloaded.core, chip.core);
+    assert_eq!(reloaded.ram.size, chip.ram.size);
+    assert_eq!(reloaded.peripherals[0].irq, Some(37));
+    assert_eq!(
+        serde_yaml::to_string(&reloaded).unwrap(),
+        serde_yaml::to_string(&chip).unwrap()
+    );
+
+    let json = dir.path().join("chip.json");
+    chip.to_file(&json).unwrap();
+    let from_json: ChipDescriptor =
+        serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    assert_eq!(from_json.peripherals[0].base_address, 0x4001_3800);
+}
+
+#[test]
+fn chip_from_file_rejects_misspelled_base_address() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("typo.yaml");
+    std::fs::write(
+        &path,
+        validation_chip_yaml(
+            r#"  - id: "uart1"
+    type: "uart"
+    base_adress: 0x40013800"#,
+            "16KB",
+        ),
+    )
+    .unwrap();
+    let err = format!("{:#}", ChipDescriptor::from_file(&path).unwrap_err());
+    assert!(
+        err.contains("unknown key(s) peripherals.0.base_adress"),
+        "{err}"
+    );
+    assert!(err.contains("LABWIRED_ALLOW_UNKNOWN_CONFIG_KEYS"), "{err}");
+}
+
+fn validation_chip_yaml(peripherals: &str, ram_size: &str) -> String {
+    format!(
+        r#"
+name: "bad-chip"
+arch: "cortex-m4"
+core: "cortex-m4"
+flash:
+  base: 0x08000000
+  size: "64KB"
+ram:
+  base: 0x20000000
+  size: "{ram_size}"
+peripherals:
+{peripherals}
+"#
+    )
+}
+
+fn validation_chip(peripherals: &str) -> ChipDescriptor {
+    serde_yaml::from_str(&validation_chip_yaml(peripherals, "16KB")).unwrap()
+}
+
+#[test]
+fn chip_validate_rejects_duplicate_peripheral_ids() {
+    let chip = validation_chip(
+        r#"  - id: "uart1"
+    type: "uart"
+    base_address: 0x40013800
+  - id: "uart1"
+    type: "uart"
+    base_address: 0x40004400"#,
+    );
+    let err = chip.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("peripherals[1].id 'uart1' duplicates peripherals[0]"),
+        "{err}"
+    );
+}
+
+#[test]
+fn chip_validate_rejects_unparseable_size() {
+    // Memory sizes are `ByteSize`, so a bad one fails at parse time already.
+    let err = serde_yaml::from_str::<ChipDescriptor>(&validation_chip_yaml("  []", "lots"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("invalid size 'lots'"), "{err}");
+
+    let chip = validation_chip(
+        r#"  - id: "gpioa"
+    type: "gpio"
+    base_address: 0x40020000
+    size: "lots""#,
+    );
+    let err = chip.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("peripherals[0] ('gpioa').size 'lots' is not a valid size"),
+        "{err}"
+    );
+
+    let chip = validation_chip(
+        r#"  - id: "gpioa"
+    type: "gpio"
+    base_address: 0xFFFFFC00
+    size: "2KB""#,
+    );
+    let err = chip.validate().unwrap_err().to_string();
+    assert!(err.contains("peripherals[0] ('gpioa')"), "{err}");
+    assert!(validation_chip("  []").validate().is_ok());
+}
+
+#[test]
+fn chip_validate_allows_64_bit_windows_without_a_cortex_m_core() {
+    // sifive-fu740 onboarding shape: 8 GiB of RAM at 2 GiB.
+    let yaml = r#"
+name: "rv64-board"
+arch: "arm"
+flash:
+  base: 0x01000000
+  size: "8KB"
+ram:
+  base: 0x80000000
+  size: "8589934592B"
+peripherals: []
+"#;
+    let chip: ChipDescriptor = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(chip.address_bits(), 64);
+    chip.validate().unwrap();
+
+    let mut m4 = chip.clone();
+    m4.core = Some("cortex-m4".to_string());
+    let err = m4.validate().unwrap_err().to_string();
+    assert!(err.contains("exceeds the 32-bit address space"), "{err}");
+}
+
 #[test]
 fn test_new_fields_parse() {
     let yaml = r#"
@@ -105,6 +282,26 @@ fn memory_value_details_constructor_is_externally_constructible_and_sparse() {
     );
 }
 
+#[test]
+fn memory_value_kind_sign_extends_and_round_trips_negative_expected() {
+    let details: MemoryValueDetails =
+        serde_yaml::from_str("{ address: 0x20000100, expected_value: -5, kind: i16 }").unwrap();
+    assert_eq!(details.width(), Some(2));
+    assert!(details.matches(0xFFFB));
+    assert!(!details.matches(0x7FFB));
+
+    let yaml = serde_yaml::to_string(&details).unwrap();
+    assert!(yaml.contains("expected_value: -5"), "{yaml}");
+    let back: MemoryValueDetails = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(back.expected_value, details.expected_value);
+
+    let err = serde_yaml::from_str::<MemoryValueDetails>(
+        "{ address: 0x20000100, expected_value: 1, kind: i16, size: 4 }",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("conflicts with size 4"), "{err}");
+}
+
 #[test]
 fn system_manifest_rejects_incomplete_cosim_model() {
     let yaml = r#"
@@ -143,13 +340,16 @@ external_devices: []
 #[test]
 fn memory_value_details_public_fields_remain_struct_literal_constructible() {
     // This is compiled as a downstream crate. Keep the public struct shape
-    // usable by callers that construct a memory assertion directly.
+    // usable by callers that construct a memory assertion directly. A field
+    // added here breaks those callers: record it under "Changed" in
+    // CHANGELOG.md, as was done for `kind`.
     let details = MemoryValueDetails {
         address: 0x2001_0000,
         expected_value: 1,
         mask: None,
         size: None,
         node: None,
+        kind: None,
     };
 
     let serialized = serde_yaml::to_string(&details).unwrap();
@@ -9,6 +9,14 @@ use super::*;
 use crate::{SimResult, SimulationError};
 use std::sync::atomic::Ordering;
 
+#[cfg(test)]
+thread_local! {
+    /// `write_u8` calls on this thread, so tests can see how many stores
+    /// [`SystemBus::write_bytes`] dispatched byte by byte.
+    pub(crate) static BYTE_WRITE_DISPATCHES: std::cell::Cell<u64> =
+        const { std::cell::Cell::new(0) };
+}
+
 impl SystemBus {
     /// Side-effect-free byte read used by the universal inspect `peek`.
     ///
@@ -30,6 +38,9 @@ impl SystemBus {
                 return Some(val);
             }
         }
+        if let Some(val) = self.sparse_mem.iter().find_map(|m| m.read_u8(addr)) {
+            return Some(val);
+        }
         // Cortex-M boot alias: 0x0 mirrors flash start on many STM32 parts.
         if self.flash.base_addr != 0 && addr < self.flash.data.len() as u64 {
             if let Some(val) = self.flash.read_u8(self.flash.base_addr + addr) {
@@ -42,14 +53,264 @@ impl SystemBus {
         }
         None
     }
-}
 
-impl crate::Bus for SystemBus {
-    fn logic_tap(&self) -> Option<crate::logic_capture::LogicTap> {
-        Some(self.logic_tap.clone())
+    /// True when a bus store at `addr` targets write-protected flash, either
+    /// directly or through the boot alias at 0x0 (RAM always wins the alias).
+    fn flash_write_protected(&self, addr: u64) -> bool {
+        if self.flash.writable || self.ram.read_u8(addr).is_some() {
+            return false;
+        }
+        let len = self.flash.data.len() as u64;
+        let direct = addr >= self.flash.base_addr && addr - self.flash.base_addr < len;
+        let alias = self.flash.base_addr != 0 && addr < len;
+        direct || alias
     }
 
-    fn read_u8(&self, addr: u64) -> SimResult<u8> {
+    /// True when strict alignment is on and a `size`-byte access at `addr`
+    /// is not naturally aligned; latches the address for
+    /// [`SystemBus::take_alignment_fault`].
+    fn misaligned(&self, addr: u64, size: u64) -> bool {
+        if self.config.strict_alignment && addr % size != 0 {
+            self.alignment_fault.set(Some(addr));
+            return true;
+        }
+        false
+    }
+
+    /// Take the address of the last access rejected by strict alignment.
+    pub fn take_alignment_fault(&self) -> Option<u64> {
+        self.alignment_fault.take()
+    }
+
+    /// Store `data` at `addr`. A range wholly inside RAM, writable flash or one
+    /// `extra_mem` window is copied into the backing slice in one go (used by
+    /// debugger memory writes such as GDB `load`); anything else, including
+    /// flash behind the H5 program write-buffer gate, falls back to `write_u8`
+    /// per byte. Observers see the same per-byte events either way.
+    pub fn write_bytes(&mut self, addr: u64, data: &[u8]) -> SimResult<()> {
+        let old: Option<Vec<u8>> = (!self.observers.is_empty()).then(|| {
+            (0..data.len() as u64)
+                .map(|i| self.observed_old_u8(addr + i))
+                .collect()
+        });
+        let flash_ok = self.flash_error_flags_idx.is_none() && !self.flash_write_protected(addr);
+        let wrote = self.ram.write_bytes(addr, data)
+            || (flash_ok && self.flash.write_bytes(addr, data))
+            || self.extra_mem.iter_mut().any(|m| m.write_bytes(addr, data));
+        if !wrote {
+            for (i, &byte) in data.iter().enumerate() {
+                self.write_u8_lane(addr + i as u64, byte)?;
+            }
+            return Ok(());
+        }
+        if let Some(old) = old {
+            for observer in &self.observers {
+                for (i, (&o, &n)) in old.iter().zip(data).enumerate() {
+                    observer.on_memory_write(addr + i as u64, o, n);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert between a little-endian lane value and the target's data byte
+    /// order. Byte-invariant: the same bytes land at the same addresses for
+    /// either order, only their significance in the word changes.
+    fn data_u16(&self, v: u16) -> u16 {
+        match self.config.endianness {
+            crate::Endianness::Little => v,
+            crate::Endianness::Big => v.swap_bytes(),
+        }
+    }
+
+    /// 32-bit counterpart of [`SystemBus::data_u16`].
+    fn data_u32(&self, v: u32) -> u32 {
+        match self.config.endianness {
+            crate::Endianness::Little => v,
+            crate::Endianness::Big => v.swap_bytes(),
+        }
+    }
+
+    /// Halfword read from RAM/flash/extra windows/peripherals, little-endian
+    /// lane order. `read_u16` applies the target byte order on top.
+    fn read_u16_lanes(&self, addr: u64) -> SimResult<u16> {
+        if let Some(val) = self.ram.read_u16(addr) {
+            return Ok(val);
+        }
+        // See read_u32_lanes: with optimized_bus_access off, peripherals (FlashXip)
+        // win over the plain flash region at the same XIP address. extra_mem
+        // always gets a word path (IRAM etc. never conflict with XIP windows).
+        let flash_and_alias = |s: &Self| -> Option<u16> {
+            if let Some(val) = s.flash.read_u16(addr) {
+                return Some(val);
+            }
+            if s.flash.base_addr != 0 && addr + 1 < s.flash.data.len() as u64 {
+                return s.flash.read_u16(s.flash.base_addr + addr);
+            }
+            None
+        };
+        let extra_mem_half = |s: &Self| -> Option<u16> {
+            for mem in &s.extra_mem {
+                if let Some(val) = mem.read_u16(addr) {
+                    return Some(val);
+                }
+            }
+            None
+        };
+        if self.config.optimized_bus_access {
+            // extra_mem (bootrom, IRAM, …) must win over the low-address flash
+            // alias: `flash_and_alias` maps addr < flash.size into
+            // flash.base+addr, which would shadow RP2040/ESP mask ROM at 0x0
+            // with XIP contents at flash.base+addr (e.g. 0xa10 → 0x10000a10).
+            if let Some(val) = extra_mem_half(self) {
+                return Ok(val);
+            }
+            if let Some(val) = flash_and_alias(self) {
+                return Ok(val);
+            }
+            if let Some(idx) = self.find_peripheral_index(addr) {
+                if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
+                    return Ok(0);
+                }
+                let off = addr - self.peripherals[idx].base;
+                self.note_mmio_activity(idx, off);
+                return self.peripherals[idx].dev.read_u16(off);
+            }
+        } else {
+            if let Some(idx) = self.find_peripheral_index(addr) {
+                if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
+                    return Ok(0);
+                }
+                let off = addr - self.peripherals[idx].base;
+                self.note_mmio_activity(idx, off);
+                return self.peripherals[idx].dev.read_u16(off);
+            }
+            if let Some(val) = extra_mem_half(self) {
+                return Ok(val);
+            }
+            if let Some(val) = flash_and_alias(self) {
+                return Ok(val);
+            }
+        }
+        let b0 = self.read_u8_raw(addr)? as u16;
+        let b1 = self.read_u8_raw(addr + 1)? as u16;
+        Ok(b0 | (b1 << 8))
+    }
+
+    /// Word counterpart of [`SystemBus::read_u16_lanes`]; bit-band and atomic
+    /// alias redirection happen in `read_u32` before this.
+    fn read_u32_lanes(&self, addr: u64) -> SimResult<u32> {
+        if let Some(val) = self.ram.read_u32(addr) {
+            return Ok(val);
+        }
+        // Flash region + boot alias, and peripherals. With optimized_bus_access
+        // off (C3 rom-boot) peripherals win over the plain flash region so an
+        // MMU-translating FlashXip window overrides the zero-filled flash at the
+        // same XIP address — otherwise a 4-byte instruction fetch at an XIP
+        // address reads 0, misdecodes as 2-byte and the PC drifts (mirrors the
+        // read_u8 fix). Linear extra_mem (IRAM/ROM/RTC) never conflicts with
+        // those XIP windows, so it always gets a word-sized fast path — the
+        // previous fall-through did 4× find_peripheral via read_u8.
+        let flash_and_alias = |s: &Self| -> Option<u32> {
+            if let Some(val) = s.flash.read_u32(addr) {
+                return Some(val);
+            }
+            if s.flash.base_addr != 0 && addr + 3 < s.flash.data.len() as u64 {
+                return s.flash.read_u32(s.flash.base_addr + addr);
+            }
+            None
+        };
+        let extra_mem_word = |s: &Self| -> Option<u32> {
+            for mem in &s.extra_mem {
+                if let Some(val) = mem.read_u32(addr) {
+                    return Some(val);
+                }
+            }
+            None
+        };
+        if self.config.optimized_bus_access {
+            // Same ordering as read_u16_lanes: bootrom/IRAM in extra_mem must win
+            // over the low-address flash alias (RP2040 mask ROM @ 0x0).
+            if let Some(val) = extra_mem_word(self) {
+                return Ok(val);
+            }
+            if let Some(val) = flash_and_alias(self) {
+                return Ok(val);
+            }
+            if let Some(idx) = self.find_peripheral_index(addr) {
+                if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
+                    return Ok(0);
+                }
+                let off = addr - self.peripherals[idx].base;
+                self.note_mmio_activity(idx, off);
+                return self.peripherals[idx].dev.read_u32(off);
+            }
+        } else {
+            if let Some(idx) = self.find_peripheral_index(addr) {
+                if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
+                    return Ok(0);
+                }
+                let off = addr - self.peripherals[idx].base;
+                self.note_mmio_activity(idx, off);
+                return self.peripherals[idx].dev.read_u32(off);
+            }
+            // IRAM / ROM / RTC after peripherals so XIP FlashXip still wins on
+            // 0x4200_0000 / 0x3C00_0000 over zero-filled extra_mem twins.
+            if let Some(val) = extra_mem_word(self) {
+                return Ok(val);
+            }
+            if let Some(val) = flash_and_alias(self) {
+                return Ok(val);
+            }
+        }
+        let b0 = self.read_u8_raw(addr)? as u32;
+        let b1 = self.read_u8_raw(addr + 1)? as u32;
+        let b2 = self.read_u8_raw(addr + 2)? as u32;
+        let b3 = self.read_u8_raw(addr + 3)? as u32;
+        Ok(b0 | (b1 << 8) | (b2 << 16) | (b3 << 24))
+    }
+
+    /// The pre-store byte reported to `on_memory_write`. Backing memory is
+    /// read directly and peripherals only through `peek`, since their reads
+    /// may carry side effects.
+    fn observed_old_u8(&self, addr: u64) -> u8 {
+        let flash_alias_old = if self.flash.base_addr != 0 && addr < self.flash.data.len() as u64 {
+            self.flash.read_u8(self.flash.base_addr + addr)
+        } else {
+            None
+        };
+        self.ram
+            .read_u8(addr)
+            .or_else(|| self.flash.read_u8(addr))
+            .or(flash_alias_old)
+            .or_else(|| self.extra_mem.iter().find_map(|m| m.read_u8(addr)))
+            .or_else(|| self.sparse_mem.iter().find_map(|m| m.read_u8(addr)))
+            .or_else(|| {
+                self.find_peripheral_index(addr).and_then(|idx| {
+                    let p = &self.peripherals[idx];
+                    p.dev.peek(addr - p.base)
+                })
+            })
+            .unwrap_or(0)
+    }
+
+    /// Old lane bytes for a wide store, or `None` when nobody is observing.
+    fn observed_old_lanes<const N: usize>(&self, addr: u64) -> Option<[u8; N]> {
+        if self.observers.is_empty() {
+            return None;
+        }
+        Some(std::array::from_fn(|i| {
+            self.observed_old_u8(addr + i as u64)
+        }))
+    }
+
+    /// Byte read without the `on_memory_read` notification; the wide read
+    /// paths fall back to this and report their lanes themselves.
+    fn read_u8_raw(&self, addr: u64) -> SimResult<u8> {
         // RAM is always first (hot path, never overlaps a peripheral window).
         if let Some(val) = self.ram.read_u8(addr) {
             return Ok(val);
@@ -75,11 +336,15 @@ impl crate::Bus for SystemBus {
                     return Ok(val);
                 }
             }
+            if let Some(val) = self.sparse_mem.iter().find_map(|m| m.read_u8(addr)) {
+                return Ok(val);
+            }
             if let Some(val) = flash_alias(self) {
                 return Ok(val);
             }
             if let Some(idx) = self.find_peripheral_index(addr) {
                 if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
                     return Ok(0); // unclocked peripheral reads 0 (silicon gating)
                 }
                 let p = &self.peripherals[idx];
@@ -93,6 +358,7 @@ impl crate::Bus for SystemBus {
             // extra_mem remain the fallback for addresses no peripheral covers.
             if let Some(idx) = self.find_peripheral_index(addr) {
                 if !self.is_peripheral_clocked(idx) {
+                    self.unclocked_access(idx, addr, "read")?;
                     return Ok(0); // unclocked peripheral reads 0 (silicon gating)
                 }
                 let p = &self.peripherals[idx];
@@ -108,6 +374,9 @@ impl crate::Bus for SystemBus {
                     return Ok(val);
                 }
             }
+            if let Some(val) = self.sparse_mem.iter().find_map(|m| m.read_u8(addr)) {
+                return Ok(val);
+            }
             if let Some(val) = flash_alias(self) {
                 return Ok(val);
             }
@@ -120,27 +389,144 @@ impl crate::Bus for SystemBus {
         Err(SimulationError::MemoryViolation(addr))
     }
 
-    fn write_u8(&mut self, addr: u64, value: u8) -> SimResult<()> {
-        let flash_alias_old = if self.flash.base_addr != 0 && addr < self.flash.data.len() as u64 {
-            self.flash.read_u8(self.flash.base_addr + addr)
-        } else {
-            None
+    /// Report a completed read of `width` bytes to `on_memory_read`.
+    fn notify_read(&self, addr: u64, width: u8, value: u32) {
+        for observer in &self.observers {
+            observer.on_memory_read(addr, width, value);
+        }
+    }
+
+    /// Report a completed store of `width` bytes to `on_memory_store`.
+    fn notify_store(&self, addr: u64, width: u8, value: u32) {
+        for observer in &self.observers {
+            observer.on_memory_store(addr, width, value);
+        }
+    }
+
+    /// Report a wide store that bypassed `write_u8` one lane at a time, so
+    /// observers see the same events as for the equivalent byte stores.
+    fn notify_wide_write<const N: usize>(&self, addr: u64, old: Option<[u8; N]>, new: [u8; N]) {
+        let Some(old) = old else {
+            return;
         };
+        for observer in &self.observers {
+            for (i, (o, n)) in old.into_iter().zip(new).enumerate() {
+                observer.on_memory_write(addr + i as u64, o, n);
+            }
+        }
+    }
 
-        // Avoid calling `read_u8` here since peripheral reads may carry side effects.
-        let old_value = self
-            .ram
-            .read_u8(addr)
-            .or_else(|| self.flash.read_u8(addr))
-            .or(flash_alias_old)
-            .or_else(|| self.extra_mem.iter().find_map(|m| m.read_u8(addr)))
-            .or_else(|| {
-                self.find_peripheral_index(addr).and_then(|idx| {
-                    let p = &self.peripherals[idx];
-                    p.dev.peek(addr - p.base)
-                })
-            })
-            .unwrap_or(0);
+    /// Halfword store in little-endian lane order; see `read_u16_lanes`.
+    fn write_u16_lanes(&mut self, addr: u64, value: u16) -> SimResult<()> {
+        let old = self.observed_old_lanes::<2>(addr);
+        let mut wrote = self.ram.write_u16(addr, value) || self.flash.write_u16(addr, value);
+        if !wrote && self.flash.base_addr != 0 && addr + 1 < self.flash.data.len() as u64 {
+            wrote = self.flash.write_u16(self.flash.base_addr + addr, value);
+        }
+        if wrote {
+            self.notify_wide_write(addr, old, value.to_le_bytes());
+            return Ok(());
+        }
+        if let Some(idx) = self.find_peripheral_index(addr) {
+            if !self.is_peripheral_clocked(idx) {
+                // unclocked peripheral: write dropped (gating)
+                return self.unclocked_access(idx, addr, "write");
+            }
+            let off = addr - self.peripherals[idx].base;
+            self.note_mmio_activity(idx, off);
+            #[cfg(feature = "event-scheduler")]
+            self.sync_scheduler_peripheral(idx);
+            self.maybe_latch_dc(idx);
+            let c3_io_mux_capture = self.begin_esp32c3_io_mux_write(idx);
+            let r = {
+                let p = &mut self.peripherals[idx];
+                p.ticks_remaining = 0;
+                p.dev.write_u16(off, value)
+            };
+            if r.is_ok() {
+                self.finish_esp32c3_io_mux_write(c3_io_mux_capture);
+            }
+            self.maybe_arm_hcsr04(idx);
+            self.maybe_start_dht22(idx);
+            self.maybe_clock_tm1637(idx);
+            self.maybe_clock_hx711(idx);
+            self.maybe_sample_seven_segment(idx);
+            #[cfg(feature = "event-scheduler")]
+            self.collect_scheduled_events(idx);
+            if r.is_ok() {
+                let base = self.peripherals[idx].base;
+                self.sync_esp32c3_irq_cache_write(idx, addr - base);
+                self.refresh_legacy_tick_index(idx);
+                self.refresh_bus_tick_index(idx);
+                self.notify_wide_write(addr, old, value.to_le_bytes());
+            }
+            return r;
+        }
+        self.write_u8_lane(addr, (value & 0xFF) as u8)?;
+        self.write_u8_lane(addr + 1, ((value >> 8) & 0xFF) as u8)?;
+        Ok(())
+    }
+
+    /// Word store in little-endian lane order; see `read_u32_lanes`.
+    fn write_u32_lanes(&mut self, addr: u64, value: u32) -> SimResult<()> {
+        let old = self.observed_old_lanes::<4>(addr);
+        let mut wrote = self.ram.write_u32(addr, value) || self.flash.write_u32(addr, value);
+        if !wrote && self.flash.base_addr != 0 && addr + 3 < self.flash.data.len() as u64 {
+            wrote = self.flash.write_u32(self.flash.base_addr + addr, value);
+        }
+        if wrote {
+            self.notify_wide_write(addr, old, value.to_le_bytes());
+            return Ok(());
+        }
+        if let Some(idx) = self.find_peripheral_index(addr) {
+            if !self.is_peripheral_clocked(idx) {
+                // unclocked peripheral: write dropped (gating)
+                return self.unclocked_access(idx, addr, "write");
+            }
+            let off = addr - self.peripherals[idx].base;
+            self.note_mmio_activity(idx, off);
+            #[cfg(feature = "event-scheduler")]
+            self.sync_scheduler_peripheral(idx);
+            self.maybe_latch_dc(idx);
+            let c3_io_mux_capture = self.begin_esp32c3_io_mux_write(idx);
+            let r = {
+                let p = &mut self.peripherals[idx];
+                p.ticks_remaining = 0;
+                p.dev.write_u32(off, value)
+            };
+            if r.is_ok() {
+                self.finish_esp32c3_io_mux_write(c3_io_mux_capture);
+            }
+            self.maybe_arm_hcsr04(idx);
+            self.maybe_start_dht22(idx);
+            self.maybe_clock_tm1637(idx);
+            self.maybe_clock_hx711(idx);
+            self.maybe_sample_seven_segment(idx);
+            #[cfg(feature = "event-scheduler")]
+            self.collect_scheduled_events(idx);
+            if r.is_ok() {
+                let base = self.peripherals[idx].base;
+                self.sync_esp32c3_irq_cache_write(idx, addr - base);
+                self.refresh_legacy_tick_index(idx);
+                self.refresh_bus_tick_index(idx);
+                self.notify_wide_write(addr, old, value.to_le_bytes());
+            }
+            return r;
+        }
+        self.write_u8_lane(addr, (value & 0xFF) as u8)?;
+        self.write_u8_lane(addr + 1, ((value >> 8) & 0xFF) as u8)?;
+        self.write_u8_lane(addr + 2, ((value >> 16) & 0xFF) as u8)?;
+        self.write_u8_lane(addr + 3, ((value >> 24) & 0xFF) as u8)?;
+        Ok(())
+    }
+
+    /// Byte store behind `write_u8`, without the `on_memory_store`
+    /// notification; the wide store paths fall back to this per lane so a
+    /// halfword or word store is still reported once.
+    fn write_u8_lane(&mut self, addr: u64, value: u8) -> SimResult<()> {
+        #[cfg(test)]
+        BYTE_WRITE_DISPATCHES.with(|n| n.set(n.get() + 1));
+        let old_value = self.observed_old_u8(addr);
 
         // OPT-IN H5 program write-buffer fidelity gate. When a FLASH peripheral
         // has the gate enabled, a flash-region write feeds the silicon-true
@@ -198,6 +584,9 @@ impl crate::Bus for SystemBus {
             }
         }
 
+        if self.flash_write_protected(addr) {
+            return Err(SimulationError::MemoryViolation(addr));
+        }
         let flash_alias_write = self.flash.base_addr != 0
             && addr < self.flash.data.len() as u64
             && self.flash.write_u8(self.flash.base_addr + addr, value);
@@ -206,6 +595,7 @@ impl crate::Bus for SystemBus {
             || self.flash.write_u8(addr, value)
             || flash_alias_write
             || self.extra_mem.iter_mut().any(|m| m.write_u8(addr, value))
+            || self.sparse_mem.iter_mut().any(|m| m.write_u8(addr, value))
         {
             Ok(())
         } else {
@@ -215,7 +605,7 @@ impl crate::Bus for SystemBus {
                     // Unclocked peripheral: the write is dropped on real silicon
                     // (the bus access never reaches the gated block), so status
                     // bits never change and the firmware visibly stalls.
-                    return Ok(());
+                    return self.unclocked_access(idx, addr, "write");
                 }
                 let off = addr - self.peripherals[idx].base;
                 self.note_mmio_activity(idx, off);
@@ -269,72 +659,38 @@ impl crate::Bus for SystemBus {
 
         res
     }
+}
+
+impl crate::Bus for SystemBus {
+    fn logic_tap(&self) -> Option<crate::logic_capture::LogicTap> {
+        Some(self.logic_tap.clone())
+    }
+
+    fn read_u8(&self, addr: u64) -> SimResult<u8> {
+        let val = self.read_u8_raw(addr)?;
+        self.notify_read(addr, 1, val as u32);
+        Ok(val)
+    }
+
+    fn write_u8(&mut self, addr: u64, value: u8) -> SimResult<()> {
+        self.write_u8_lane(addr, value)?;
+        self.notify_store(addr, 1, value as u32);
+        Ok(())
+    }
 
     fn read_u16(&self, addr: u64) -> SimResult<u16> {
-        if let Some(val) = self.ram.read_u16(addr) {
-            return Ok(val);
+        if self.misaligned(addr, 2) {
+            return Err(SimulationError::MemoryViolation(addr));
         }
-        // See read_u32: with optimized_bus_access off, peripherals (FlashXip)
-        // win over the plain flash region at the same XIP address. extra_mem
-        // always gets a word path (IRAM etc. never conflict with XIP windows).
-        let flash_and_alias = |s: &Self| -> Option<u16> {
-            if let Some(val) = s.flash.read_u16(addr) {
-                return Some(val);
-            }
-            if s.flash.base_addr != 0 && addr + 1 < s.flash.data.len() as u64 {
-                return s.flash.read_u16(s.flash.base_addr + addr);
-            }
-            None
-        };
-        let extra_mem_half = |s: &Self| -> Option<u16> {
-            for mem in &s.extra_mem {
-                if let Some(val) = mem.read_u16(addr) {
-                    return Some(val);
-                }
-            }
-            None
-        };
-        if self.config.optimized_bus_access {
-            // extra_mem (bootrom, IRAM, …) must win over the low-address flash
-            // alias: `flash_and_alias` maps addr < flash.size into
-            // flash.base+addr, which would shadow RP2040/ESP mask ROM at 0x0
-            // with XIP contents at flash.base+addr (e.g. 0xa10 → 0x10000a10).
-            if let Some(val) = extra_mem_half(self) {
-                return Ok(val);
-            }
-            if let Some(val) = flash_and_alias(self) {
-                return Ok(val);
-            }
-            if let Some(idx) = self.find_peripheral_index(addr) {
-                if !self.is_peripheral_clocked(idx) {
-                    return Ok(0);
-                }
-                let off = addr - self.peripherals[idx].base;
-                self.note_mmio_activity(idx, off);
-                return self.peripherals[idx].dev.read_u16(off);
-            }
-        } else {
-            if let Some(idx) = self.find_peripheral_index(addr) {
-                if !self.is_peripheral_clocked(idx) {
-                    return Ok(0);
-                }
-                let off = addr - self.peripherals[idx].base;
-                self.note_mmio_activity(idx, off);
-                return self.peripherals[idx].dev.read_u16(off);
-            }
-            if let Some(val) = extra_mem_half(self) {
-                return Ok(val);
-            }
-            if let Some(val) = flash_and_alias(self) {
-                return Ok(val);
-            }
-        }
-        let b0 = self.read_u8(addr)? as u16;
-        let b1 = self.read_u8(addr + 1)? as u16;
-        Ok(b0 | (b1 << 8))
+        let val = self.data_u16(self.read_u16_lanes(addr)?);
+        self.notify_read(addr, 2, val as u32);
+        Ok(val)
     }
 
     fn read_u32(&self, addr: u64) -> SimResult<u32> {
+        if self.misaligned(addr, 4) {
+            return Err(SimulationError::MemoryViolation(addr));
+        }
         // Debug (env-gated): trace the driver's reads of a freshly-injected RX
         // buffer, to RE the rx-control header format the RX callback parses.
         if crate::peripherals::esp32c3::wifi_mac::rxbuf_trace_enabled() {
@@ -366,130 +722,32 @@ impl crate::Bus for SystemBus {
             }
         }
 
-        if let Some(val) = self.ram.read_u32(addr) {
-            return Ok(val);
-        }
-        // Flash region + boot alias, and peripherals. With optimized_bus_access
-        // off (C3 rom-boot) peripherals win over the plain flash region so an
-        // MMU-translating FlashXip window overrides the zero-filled flash at the
-        // same XIP address — otherwise a 4-byte instruction fetch at an XIP
-        // address reads 0, misdecodes as 2-byte and the PC drifts (mirrors the
-        // read_u8 fix). Linear extra_mem (IRAM/ROM/RTC) never conflicts with
-        // those XIP windows, so it always gets a word-sized fast path — the
-        // previous fall-through did 4× find_peripheral via read_u8.
-        let flash_and_alias = |s: &Self| -> Option<u32> {
-            if let Some(val) = s.flash.read_u32(addr) {
-                return Some(val);
-            }
-            if s.flash.base_addr != 0 && addr + 3 < s.flash.data.len() as u64 {
-                return s.flash.read_u32(s.flash.base_addr + addr);
-            }
-            None
-        };
-        let extra_mem_word = |s: &Self| -> Option<u32> {
-            for mem in &s.extra_mem {
-                if let Some(val) = mem.read_u32(addr) {
-                    return Some(val);
-                }
-            }
-            None
-        };
-        if self.config.optimized_bus_access {
-            // Same ordering as read_u16: bootrom/IRAM in extra_mem must win
-            // over the low-address flash alias (RP2040 mask ROM @ 0x0).
-            if let Some(val) = extra_mem_word(self) {
-                return Ok(val);
-            }
-            if let Some(val) = flash_and_alias(self) {
-                return Ok(val);
-            }
-            if let Some(idx) = self.find_peripheral_index(addr) {
-                if !self.is_peripheral_clocked(idx) {
-                    return Ok(0);
-                }
-                let off = addr - self.peripherals[idx].base;
-                self.note_mmio_activity(idx, off);
-                return self.peripherals[idx].dev.read_u32(off);
-            }
-        } else {
-            if let Some(idx) = self.find_peripheral_index(addr) {
-                if !self.is_peripheral_clocked(idx) {
-                    return Ok(0);
-                }
-                let off = addr - self.peripherals[idx].base;
-                self.note_mmio_activity(idx, off);
-                return self.peripherals[idx].dev.read_u32(off);
-            }
-            // IRAM / ROM / RTC after peripherals so XIP FlashXip still wins on
-            // 0x4200_0000 / 0x3C00_0000 over zero-filled extra_mem twins.
-            if let Some(val) = extra_mem_word(self) {
-                return Ok(val);
-            }
-            if let Some(val) = flash_and_alias(self) {
-                return Ok(val);
-            }
-        }
-        let b0 = self.read_u8(addr)? as u32;
-        let b1 = self.read_u8(addr + 1)? as u32;
-        let b2 = self.read_u8(addr + 2)? as u32;
-        let b3 = self.read_u8(addr + 3)? as u32;
-        Ok(b0 | (b1 << 8) | (b2 << 16) | (b3 << 24))
+        let val = self.data_u32(self.read_u32_lanes(addr)?);
+        self.notify_read(addr, 4, val);
+        Ok(val)
     }
 
     fn write_u16(&mut self, addr: u64, value: u16) -> SimResult<()> {
-        let mut wrote = self.ram.write_u16(addr, value) || self.flash.write_u16(addr, value);
-        if !wrote && self.flash.base_addr != 0 && addr + 1 < self.flash.data.len() as u64 {
-            wrote = self.flash.write_u16(self.flash.base_addr + addr, value);
-        }
-        if wrote {
-            return Ok(());
+        if self.misaligned(addr, 2) || self.flash_write_protected(addr) {
+            return Err(SimulationError::MemoryViolation(addr));
         }
-        if let Some(idx) = self.find_peripheral_index(addr) {
-            if !self.is_peripheral_clocked(idx) {
-                return Ok(()); // unclocked peripheral: write dropped (gating)
-            }
-            let off = addr - self.peripherals[idx].base;
-            self.note_mmio_activity(idx, off);
-            #[cfg(feature = "event-scheduler")]
-            self.sync_scheduler_peripheral(idx);
-            self.maybe_latch_dc(idx);
-            let c3_io_mux_capture = self.begin_esp32c3_io_mux_write(idx);
-            let r = {
-                let p = &mut self.peripherals[idx];
-                p.ticks_remaining = 0;
-                p.dev.write_u16(off, value)
-            };
-            if r.is_ok() {
-                self.finish_esp32c3_io_mux_write(c3_io_mux_capture);
-            }
-            self.maybe_arm_hcsr04(idx);
-            self.maybe_start_dht22(idx);
-            self.maybe_clock_tm1637(idx);
-            self.maybe_clock_hx711(idx);
-            self.maybe_sample_seven_segment(idx);
-            #[cfg(feature = "event-scheduler")]
-            self.collect_scheduled_events(idx);
-            if r.is_ok() {
-                let base = self.peripherals[idx].base;
-                self.sync_esp32c3_irq_cache_write(idx, addr - base);
-                self.refresh_legacy_tick_index(idx);
-                self.refresh_bus_tick_index(idx);
-            }
-            return r;
-        }
-        self.write_u8(addr, (value & 0xFF) as u8)?;
-        self.write_u8(addr + 1, ((value >> 8) & 0xFF) as u8)?;
+        self.write_u16_lanes(addr, self.data_u16(value))?;
+        self.notify_store(addr, 2, value as u32);
         Ok(())
     }
 
     fn write_u32(&mut self, addr: u64, value: u32) -> SimResult<()> {
+        if self.misaligned(addr, 4) {
+            return Err(SimulationError::MemoryViolation(addr));
+        }
         // RP2040 atomic register aliases: a write to a +0x1000/0x2000/0x3000
         // alias of a peripheral register is a read-modify-write (XOR/SET/CLR)
-        // on the aligned base register. The base access recurses into the
-        // normal path (its alias bits are clear), so there is no further alias.
+        // on the aligned base register. The read half is internal (observers
+        // see only the resulting store); the store recurses into the normal
+        // path (its alias bits are clear), so there is no further alias.
         if self.atomic_register_aliases {
             if let Some((base, op)) = self.atomic_alias_redirect(addr) {
-                let cur = self.read_u32(base)?;
+                let cur = self.data_u32(self.read_u32_lanes(base)?);
                 let new = match op {
                     crate::bus::AtomicAliasOp::Xor => cur ^ value,
                     crate::bus::AtomicAliasOp::Set => cur | value,
@@ -512,7 +770,7 @@ impl crate::Bus for SystemBus {
         // physical address.  Writing 1 sets the bit; writing 0 clears it.
         if self.bit_band_enabled {
             if let Some((phys_byte, bit)) = Self::bit_band_translate(addr) {
-                let old = self.read_u8(phys_byte)?;
+                let old = self.read_u8_raw(phys_byte)?;
                 let new_byte = if value & 1 != 0 {
                     old | (1 << bit)
                 } else {
@@ -522,50 +780,11 @@ impl crate::Bus for SystemBus {
             }
         }
 
-        let mut wrote = self.ram.write_u32(addr, value) || self.flash.write_u32(addr, value);
-        if !wrote && self.flash.base_addr != 0 && addr + 3 < self.flash.data.len() as u64 {
-            wrote = self.flash.write_u32(self.flash.base_addr + addr, value);
+        if self.flash_write_protected(addr) {
+            return Err(SimulationError::MemoryViolation(addr));
         }
-        if wrote {
-            return Ok(());
-        }
-        if let Some(idx) = self.find_peripheral_index(addr) {
-            if !self.is_peripheral_clocked(idx) {
-                return Ok(()); // unclocked peripheral: write dropped (gating)
-            }
-            let off = addr - self.peripherals[idx].base;
-            self.note_mmio_activity(idx, off);
-            #[cfg(feature = "event-scheduler")]
-            self.sync_scheduler_peripheral(idx);
-            self.maybe_latch_dc(idx);
-            let c3_io_mux_capture = self.begin_esp32c3_io_mux_write(idx);
-            let r = {
-                let p = &mut self.peripherals[idx];
-                p.ticks_remaining = 0;
-                p.dev.write_u32(off, value)
-            };
-            if r.is_ok() {
-                self.finish_esp32c3_io_mux_write(c3_io_mux_capture);
-            }
-            self.maybe_arm_hcsr04(idx);
-            self.maybe_start_dht22(idx);
-            self.maybe_clock_tm1637(idx);
-            self.maybe_clock_hx711(idx);
-            self.maybe_sample_seven_segment(idx);
-            #[cfg(feature = "event-scheduler")]
-            self.collect_scheduled_events(idx);
-            if r.is_ok() {
-                let base = self.peripherals[idx].base;
-                self.sync_esp32c3_irq_cache_write(idx, addr - base);
-                self.refresh_legacy_tick_index(idx);
-                self.refresh_bus_tick_index(idx);
-            }
-            return r;
-        }
-        self.write_u8(addr, (value & 0xFF) as u8)?;
-        self.write_u8(addr + 1, ((value >> 8) & 0xFF) as u8)?;
-        self.write_u8(addr + 2, ((value >> 16) & 0xFF) as u8)?;
-        self.write_u8(addr + 3, ((value >> 24) & 0xFF) as u8)?;
+        self.write_u32_lanes(addr, self.data_u32(value))?;
+        self.notify_store(addr, 4, value);
         Ok(())
     }
 
@@ -602,22 +821,7 @@ impl crate::Bus for SystemBus {
 
     fn execute_dma(&mut self, requests: &[crate::DmaRequest]) -> SimResult<()> {
         for req in requests {
-            match req.direction {
-                crate::DmaDirection::Read => {
-                    let _ = self.read_u8(req.addr)?;
-                }
-                crate::DmaDirection::Write => {
-                    self.write_u8(req.addr, req.val)?;
-                }
-                crate::DmaDirection::Copy => {
-                    if let Some(t) = req.transform {
-                        self.dma_copy_unit(req.src_addr, req.addr, t)?;
-                    } else {
-                        let val = self.read_u8(req.src_addr)?;
-                        self.write_u8(req.addr, val)?;
-                    }
-                }
-            }
+            self.dma_move_element(req)?;
         }
         Ok(())
     }
@@ -676,6 +880,13 @@ impl crate::Bus for SystemBus {
                     nvic.ispr[idx].fetch_and(!(1 << bit), Ordering::SeqCst);
                 }
             }
+            let irq = exception_num - 16;
+            for pos in 0..self.irq_ack_indices.len() {
+                let idx = self.irq_ack_indices[pos];
+                if self.peripherals[idx].dev.on_irq_ack(irq) {
+                    self.refresh_legacy_tick_index(idx);
+                }
+            }
         }
     }
 
@@ -303,7 +303,7 @@ impl SystemBus {
                     continue;
                 };
                 match gpio.register_layout() {
-                    GpioRegisterLayout::Stm32V2 => {
+                    GpioRegisterLayout::Stm32V2 | GpioRegisterLayout::Stm32F4 => {
                         let table = if fifo { L4 } else { F4 };
                         for &(spi, p, pin, af, sig, func) in table {
                             if spi == spi_name && p == port {
@@ -14,6 +14,7 @@ impl SystemBus {
             flash: LinearMemory::new(1024 * 1024, 0x0),
             ram: LinearMemory::new(1024 * 1024, 0x2000_0000),
             extra_mem: Vec::new(),
+            sparse_mem: Vec::new(),
             peripherals: vec![
                 PeripheralEntry {
                     name: "uart1".to_string(),
@@ -61,14 +62,18 @@ impl SystemBus {
             rcc_idx: None,
             clock_gating_bypass: false,
             fault_unclocked: std::collections::HashMap::new(),
+            unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+            unclocked_warned: Default::default(),
             peripheral_ranges: Vec::new(),
             legacy_tick_indices: Vec::new(),
             bus_tick_indices: Vec::new(),
             scheduler_driver_indices: Vec::new(),
+            irq_ack_indices: Vec::new(),
             matrix_source_scratch: Vec::new(),
             peripheral_hint: Cell::new(None),
             last_route: Cell::new(None),
             last_gap: Cell::new(None),
+            alignment_fault: Cell::new(None),
             last_gpio_in: [0; 2],
             current_cycle: 0,
             cycle_clock: crate::CycleClock::default(),
@@ -77,6 +82,7 @@ impl SystemBus {
             side_effecting_mmio: std::cell::Cell::new(0),
             legacy_walk_disabled: false,
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
             hcsr04: Vec::new(),
             gpio_devices: Vec::new(),
@@ -89,6 +95,7 @@ impl SystemBus {
             hx711: Vec::new(),
             seven_segment: Vec::new(),
             analog_inputs: Vec::new(),
+            analog_signals: Vec::new(),
             can_diagnostic_testers: Vec::new(),
             can_uds_testers: Vec::new(),
             can_log_players: Vec::new(),
@@ -127,6 +134,7 @@ impl SystemBus {
             flash: LinearMemory::new(0, 0),
             ram: LinearMemory::new(0, 0),
             extra_mem: Vec::new(),
+            sparse_mem: Vec::new(),
             peripherals: Vec::new(),
             nvic: None,
             observers: Vec::new(),
@@ -137,14 +145,18 @@ impl SystemBus {
             rcc_idx: None,
             clock_gating_bypass: false,
             fault_unclocked: std::collections::HashMap::new(),
+            unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+            unclocked_warned: Default::default(),
             peripheral_ranges: Vec::new(),
             legacy_tick_indices: Vec::new(),
             bus_tick_indices: Vec::new(),
             scheduler_driver_indices: Vec::new(),
+            irq_ack_indices: Vec::new(),
             matrix_source_scratch: Vec::new(),
             peripheral_hint: Cell::new(None),
             last_route: Cell::new(None),
             last_gap: Cell::new(None),
+            alignment_fault: Cell::new(None),
             last_gpio_in: [0; 2],
             current_cycle: 0,
             cycle_clock: crate::CycleClock::default(),
@@ -153,6 +165,7 @@ impl SystemBus {
             side_effecting_mmio: std::cell::Cell::new(0),
             legacy_walk_disabled: false,
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
             hcsr04: Vec::new(),
             gpio_devices: Vec::new(),
@@ -165,6 +178,7 @@ impl SystemBus {
             hx711: Vec::new(),
             seven_segment: Vec::new(),
             analog_inputs: Vec::new(),
+            analog_signals: Vec::new(),
             can_diagnostic_testers: Vec::new(),
             can_uds_testers: Vec::new(),
             can_log_players: Vec::new(),
@@ -237,6 +251,126 @@ impl SystemBus {
         self.rebuild_peripheral_ranges();
     }
 
+    /// Hot-attach a fully built peripheral, e.g. to plug an external sensor in
+    /// mid-run. Unlike [`add_peripheral`](Self::add_peripheral) this is checked:
+    /// a duplicate name or a window overlapping any existing peripheral is
+    /// rejected, so a hot-plugged device can never silently shadow (or be
+    /// shadowed by) the chip's own register map.
+    pub fn attach_peripheral(&mut self, entry: PeripheralEntry) -> anyhow::Result<()> {
+        if entry.size == 0 {
+            anyhow::bail!("attach_peripheral: '{}' has an empty window", entry.name);
+        }
+        if self.find_peripheral_index_by_name(&entry.name).is_some() {
+            anyhow::bail!(
+                "attach_peripheral: peripheral '{}' already exists",
+                entry.name
+            );
+        }
+        let end = entry.base.saturating_add(entry.size);
+        if let Some(other) = self
+            .peripherals
+            .iter()
+            .find(|p| {
+                !p.is_detached() && entry.base < p.base.saturating_add(p.size) && p.base < end
+            })
+        {
+            anyhow::bail!(
+                "attach_peripheral: '{}' [{:#x}, {:#x}) overlaps '{}' [{:#x}, {:#x})",
+                entry.name,
+                entry.base,
+                end,
+                other.name,
+                other.base,
+                other.base.saturating_add(other.size)
+            );
+        }
+        let PeripheralEntry {
+            name,
+            base,
+            size,
+            irq,
+            dev,
+            clock_gate,
+            ..
+        } = entry;
+        self.add_peripheral(&name, base, size, irq, dev);
+        if let Some(last) = self.peripherals.last_mut() {
+            last.clock_gate = clock_gate;
+        }
+        Ok(())
+    }
+
+    /// Remove the peripheral registered under `name` and return its entry.
+    /// Accesses to its window miss from the next access on.
+    ///
+    /// The slot is left behind as an empty, windowless tombstone (see
+    /// [`PeripheralEntry::is_detached`]) rather than shifting every later
+    /// peripheral down: the machine caches bus indices (SCB, FLASH, RTC_CNTL,
+    /// the I²C time drive) and the bus keys fault-injection state by index, so
+    /// a shift would dispatch to the wrong device. A scheduler-driven
+    /// peripheral is refused, since events already queued for its index would
+    /// otherwise land on the tombstone.
+    pub fn detach_peripheral(&mut self, name: &str) -> anyhow::Result<PeripheralEntry> {
+        let idx = self
+            .find_peripheral_index_by_name(name)
+            .ok_or_else(|| anyhow::anyhow!("detach_peripheral: no peripheral '{name}'"))?;
+        if self.peripherals[idx].dev.uses_scheduler() {
+            anyhow::bail!(
+                "detach_peripheral: '{name}' is scheduler-driven and has queued events keyed \
+                 by its index"
+            );
+        }
+        let base = self.peripherals[idx].base;
+        let entry = std::mem::replace(
+            &mut self.peripherals[idx],
+            PeripheralEntry {
+                name: String::new(),
+                base,
+                size: 0,
+                irq: None,
+                dev: Box::new(crate::peripherals::stub::StubPeripheral::new(0)),
+                ticks_remaining: 0,
+                clock_gate: None,
+            },
+        );
+        self.fault_unclocked.remove(&idx);
+        self.unclocked_warned.borrow_mut().remove(&idx);
+        self.rebuild_peripheral_ranges();
+        Ok(entry)
+    }
+
+    /// Run [`Peripheral::reset`] on every peripheral (part of
+    /// `Machine::reset`). A reset can drop or re-arm a declarative bank's timed
+    /// events, so each entry's legacy-tick membership is refreshed after it.
+    /// Warm and pin resets restore each peripheral's
+    /// [`Peripheral::warm_reset_retained`] bytes afterwards.
+    pub fn reset_peripherals(&mut self, kind: crate::ResetKind) {
+        for idx in 0..self.peripherals.len() {
+            let entry = &mut self.peripherals[idx];
+            let kept: Vec<(u64, u8)> = if kind == crate::ResetKind::PowerOn {
+                Vec::new()
+            } else {
+                entry
+                    .dev
+                    .warm_reset_retained()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|off| entry.dev.read(off).ok().map(|v| (off, v)))
+                    .collect()
+            };
+            entry.dev.reset();
+            for (off, v) in kept {
+                if let Err(e) = entry.dev.write(off, v) {
+                    tracing::warn!(
+                        "{}: restoring retained byte {off:#x} after reset failed: {e:?}",
+                        entry.name
+                    );
+                }
+            }
+            self.refresh_legacy_tick_index(idx);
+        }
+    }
+
     /// Replace the first peripheral with `name`, or append if missing.
     ///
     /// Prefer this over [`add_peripheral`] when installing a behavioral twin
@@ -439,6 +573,40 @@ impl SystemBus {
         }
     }
 
+    /// Undo [`Self::attach_uart_tx_sink`]: console UARTs stop capturing and
+    /// echo to stdout again. IO-Link wire UARTs stay silent.
+    pub fn detach_uart_tx_sink(&mut self) {
+        use crate::peripherals::components::IolinkMaster;
+        use crate::peripherals::esp32::uart::Esp32Uart;
+        use crate::peripherals::esp32s3::uart::Esp32s3Uart;
+        use crate::peripherals::nrf52::uarte::Nrf52Uarte;
+        use crate::peripherals::nrf54l::uarte::Nrf54lUarte;
+        for p in &mut self.peripherals {
+            let Some(any) = p.dev.as_any_mut() else {
+                continue;
+            };
+            if let Some(uart) = any.downcast_mut::<Uart>() {
+                let is_iolink_wire = uart
+                    .attached_streams
+                    .iter()
+                    .any(|s| s.as_any().map(|a| a.is::<IolinkMaster>()).unwrap_or(false));
+                uart.set_sink(None, !is_iolink_wire);
+            } else if let Some(uart) = any.downcast_mut::<Esp32Uart>() {
+                uart.set_sink(None);
+            } else if let Some(uarte) = any.downcast_mut::<Nrf52Uarte>() {
+                uarte.set_sink(None, true);
+            } else if let Some(uarte) = any.downcast_mut::<Nrf54lUarte>() {
+                uarte.set_sink(None, true);
+            } else if let Some(uart) = any.downcast_mut::<Esp32s3Uart>() {
+                uart.set_sink(None);
+            } else if let Some(usb) =
+                any.downcast_mut::<crate::peripherals::rp2040::usb::Rp2040Usb>()
+            {
+                usb.set_sink(None);
+            }
+        }
+    }
+
     /// Wire a capture sink into any attached IO-Link master so it records what
     /// it received over IO-Link (`MASTER PD=`, `MASTER VERDICT`, `MASTER EVENT`)
     /// into the given buffer. Pass the same `Arc<Mutex<Vec<u8>>>` used for the
@@ -535,6 +703,27 @@ impl SystemBus {
         }
     }
 
+    /// True for ARMv6-M cores (Cortex-M0/M0+/M1), which fault every unaligned
+    /// halfword/word access; seeds `SimulationConfig::strict_alignment`.
+    pub(crate) fn chip_requires_alignment(chip: &ChipDescriptor) -> bool {
+        Self::chip_arm_profile(chip) == crate::ArmProfile::V6M
+    }
+
+    /// Cortex-M profile from the chip's `core`; seeds
+    /// `SimulationConfig::arm_profile`. Chips without a `core` keep ARMv7-M.
+    pub(crate) fn chip_arm_profile(chip: &ChipDescriptor) -> crate::ArmProfile {
+        let v6m = chip.core.as_deref().is_some_and(|core| {
+            let c = core.trim().to_ascii_lowercase();
+            let c = c.strip_prefix("cortex-").unwrap_or(&c);
+            matches!(c, "m0" | "m0+" | "m0plus" | "m1")
+        });
+        if v6m {
+            crate::ArmProfile::V6M
+        } else {
+            crate::ArmProfile::V7M
+        }
+    }
+
     /// Decode an RP2040 atomic register-alias access. Returns the aligned base
     /// register address and the atomic op when `addr` lands on a `+0x1000`
     /// (XOR), `+0x2000` (SET) or `+0x3000` (CLR) alias of a peripheral register
@@ -579,17 +768,150 @@ impl SystemBus {
         in_ram && in_flash
     }
 
+    /// Why the vector table at `addr` would not boot: one message per
+    /// problem with the initial SP (must lie in RAM, top-of-stack inclusive)
+    /// or the reset handler (must lie in flash). Named `memory_regions` count
+    /// as both, so a stack in CCM/DTCM or a handler in ITCM/XIP is accepted.
+    /// Empty when the pair is sane.
+    pub fn vector_pair_issues(&self, addr: u64) -> Vec<String> {
+        let regions = self.named_memory_regions();
+        let describe = |exclude: &str, end_inclusive: bool| {
+            regions
+                .iter()
+                .filter(|(name, ..)| *name != exclude)
+                .map(|(name, base, size)| {
+                    let close = if end_inclusive { ']' } else { ')' };
+                    format!("{name} [{base:#x}, {:#x}{close}", base + size)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut issues = Vec::new();
+        // The initial SP is the top of the full-descending stack,
+        // conventionally one past the last RAM byte, so the end is inclusive.
+        let sp_ok = |sp: u64| {
+            regions
+                .iter()
+                .any(|&(name, base, size)| name != "flash" && sp >= base && sp <= base + size)
+        };
+        match self.read_u32(addr) {
+            Ok(sp) if !sp_ok(sp as u64) => {
+                issues.push(format!(
+                    "initial SP {:#010x} (vector[0] @ {:#x}) is outside RAM: {}",
+                    sp,
+                    addr,
+                    describe("flash", true)
+                ));
+            }
+            Ok(_) => {}
+            Err(_) => issues.push(format!("vector[0] @ {:#x} is not readable", addr)),
+        }
+        let pc_ok = |pc: u64| {
+            regions
+                .iter()
+                .any(|&(name, base, size)| name != "ram" && pc >= base && pc < base + size)
+        };
+        match self.read_u32(addr.wrapping_add(4)) {
+            Ok(pc) if !pc_ok((pc & !1) as u64) => {
+                issues.push(format!(
+                    "reset handler {:#010x} (vector[1] @ {:#x}) is outside flash: {}",
+                    pc,
+                    addr + 4,
+                    describe("ram", false)
+                ));
+            }
+            Ok(_) => {}
+            Err(_) => issues.push(format!("vector[1] @ {:#x} is not readable", addr + 4)),
+        }
+        issues
+    }
+
+    /// Memory-backed windows as `(name, base, size)`: `flash`, `ram`, then the
+    /// named `memory_regions` (linear, then sparse). `flash`/`ram` carry those
+    /// names even on hand-built buses; unnamed extra windows are left out.
+    pub fn named_memory_regions(&self) -> Vec<(&str, u64, u64)> {
+        let mut regions = vec![
+            ("flash", self.flash.base_addr, self.flash.data.len() as u64),
+            ("ram", self.ram.base_addr, self.ram.data.len() as u64),
+        ];
+        for mem in self.extra_mem.iter().filter(|m| !m.name.is_empty()) {
+            regions.push((mem.name.as_str(), mem.base_addr, mem.data.len() as u64));
+        }
+        for mem in self.sparse_mem.iter().filter(|m| !m.name.is_empty()) {
+            regions.push((mem.name.as_str(), mem.base_addr, mem.size));
+        }
+        regions
+    }
+
+    /// Copy `segment` into the memory window called `region` (see
+    /// [`Self::named_memory_regions`]). Fails if no window has that name or
+    /// the segment does not fit inside it.
+    pub fn load_segment_into(
+        &mut self,
+        region: &str,
+        segment: &crate::memory::Segment,
+    ) -> SimResult<()> {
+        let loaded = match region {
+            "flash" => self.flash.load_from_segment(segment),
+            "ram" => self.ram.load_from_segment(segment),
+            name => {
+                if let Some(mem) = self.extra_mem.iter_mut().find(|m| m.name == name) {
+                    mem.load_from_segment(segment)
+                } else if let Some(mem) = self.sparse_mem.iter_mut().find(|m| m.name == name) {
+                    mem.load_from_segment(segment)
+                } else {
+                    return Err(crate::SimulationError::Other(format!(
+                        "no memory region named '{name}'"
+                    )));
+                }
+            }
+        };
+        if loaded {
+            Ok(())
+        } else {
+            Err(crate::SimulationError::MemoryViolation(segment.start_addr))
+        }
+    }
+
     /// Place a built peripheral on the bus using the descriptor's window size
     /// (default 4KB) and IRQ. Shared by the per-family factory dispatch and the
     /// generic-match path in [`Self::from_config`] so both stay in lockstep.
     pub(crate) fn push_peripheral(
+        &mut self,
+        p_cfg: &labwired_config::PeripheralConfig,
+        dev: Box<dyn Peripheral>,
+    ) -> anyhow::Result<()> {
+        self.push_peripheral_with_span(p_cfg, dev, None)
+    }
+
+    /// [`Self::push_peripheral`] for models whose register map is known
+    /// (declarative / Strict IR). Without an explicit `size` the window grows
+    /// past the 4KB default to the register span rounded up to a power of two,
+    /// so sparse maps stay reachable; an explicit `size` smaller than the span
+    /// is kept but warned about.
+    pub(crate) fn push_peripheral_with_span(
         &mut self,
         p_cfg: &labwired_config::PeripheralConfig,
         mut dev: Box<dyn Peripheral>,
+        register_span: Option<u64>,
     ) -> anyhow::Result<()> {
-        let size = match &p_cfg.size {
-            Some(size) => parse_size(size)?,
-            None => 0x1000,
+        let size = match (&p_cfg.size, register_span) {
+            (Some(size), span) => {
+                let size = parse_size(size)?;
+                if let Some(span) = span.filter(|&span| span > size) {
+                    tracing::warn!(
+                        "peripheral '{}': size {:#x} is smaller than its register span {:#x}; \
+                         registers past the window are unreachable",
+                        p_cfg.id,
+                        size,
+                        span
+                    );
+                }
+                size
+            }
+            (None, Some(span)) => span.next_power_of_two().max(0x1000),
+            (None, None) => 0x1000,
         };
         // Attach choke point (walk-free plan Part 1): hand the peripheral the
         // bus's shared cycle clock before it is registered — the `from_config`
@@ -675,4 +997,20 @@ impl SystemBus {
             }
         }
     }
+
+    /// True when something can still wake a sleeping Cortex-M core: an
+    /// NVIC-enabled IRQ, or a SysTick counting with TICKINT set. `None` on
+    /// buses without an NVIC, where the question has no answer.
+    pub fn cortex_m_wake_source_enabled(&self) -> Option<bool> {
+        let nvic = self.nvic.as_ref()?;
+        if nvic.iser.iter().any(|w| w.load(Ordering::SeqCst) != 0) {
+            return Some(true);
+        }
+        Some(self.peripherals.iter().any(|p| {
+            p.dev
+                .as_any()
+                .and_then(|a| a.downcast_ref::<crate::peripherals::systick::Systick>())
+                .is_some_and(|st| st.interrupt_armed())
+        }))
+    }
 }
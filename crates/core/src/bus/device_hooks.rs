@@ -545,4 +545,46 @@ impl SystemBus {
             Err(_) => true,
         }
     }
+
+    /// Choose how accesses to unclocked peripherals are reported. The default,
+    /// [`UnclockedAccessPolicy::Ignore`], is silent like silicon; `Warn` and
+    /// `Fault` surface firmware that touches a peripheral before enabling its
+    /// RCC clock.
+    pub fn set_unclocked_access_policy(&mut self, policy: UnclockedAccessPolicy) {
+        self.unclocked_access_policy = policy;
+        self.unclocked_warned.borrow_mut().clear();
+    }
+
+    pub fn unclocked_access_policy(&self) -> UnclockedAccessPolicy {
+        self.unclocked_access_policy
+    }
+
+    /// Apply the unclocked-access policy to a suppressed access at `addr` on
+    /// peripheral `idx`. `Ok` means the caller completes the access as a no-op
+    /// (read 0 / drop the write); `Err` is the `Fault` policy's bus error.
+    pub(crate) fn unclocked_access(&self, idx: usize, addr: u64, direction: &str) -> SimResult<()> {
+        match self.unclocked_access_policy {
+            UnclockedAccessPolicy::Ignore => Ok(()),
+            UnclockedAccessPolicy::Warn => {
+                if self.unclocked_warned.borrow_mut().insert(idx) {
+                    tracing::warn!(
+                        "{} of unclocked peripheral '{}' at {:#x} ignored (RCC clock not enabled)",
+                        direction,
+                        self.peripherals[idx].name,
+                        addr
+                    );
+                }
+                Ok(())
+            }
+            UnclockedAccessPolicy::Fault => {
+                tracing::error!(
+                    "{} of unclocked peripheral '{}' at {:#x} (RCC clock not enabled)",
+                    direction,
+                    self.peripherals[idx].name,
+                    addr
+                );
+                Err(crate::SimulationError::MemoryViolation(addr))
+            }
+        }
+    }
 }
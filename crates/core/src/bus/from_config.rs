@@ -44,15 +44,125 @@ fn default_region_image_path(env: &str) -> Option<PathBuf> {
     candidates.into_iter().find(|p| p.is_file())
 }
 
+/// Apply a manifest's `memory_overrides` to a copy of `chip`. Keys name the
+/// `flash` / `ram` windows, a `memory_regions` entry, or a peripheral id;
+/// values are sizes (`"256KB"`, `"0x4000"`). The board wins over the chip, so
+/// one chip descriptor serves every package/RAM variant of a part.
+fn apply_memory_overrides(
+    chip: &ChipDescriptor,
+    overrides: &std::collections::HashMap<String, String>,
+) -> anyhow::Result<ChipDescriptor> {
+    let mut chip = chip.clone();
+    let mut keys: Vec<&String> = overrides.keys().collect();
+    keys.sort();
+    for key in keys {
+        let value = &overrides[key];
+        let size = parse_size(value)
+            .with_context(|| format!("memory_overrides.{key}: '{value}' is not a valid size"))?;
+        match key.as_str() {
+            "flash" => chip.flash.size = labwired_config::ByteSize(size),
+            "ram" => chip.ram.size = labwired_config::ByteSize(size),
+            name => {
+                if let Some(region) = chip.memory_regions.iter_mut().find(|r| r.name == name) {
+                    region.size = value.clone();
+                } else if let Some(p) = chip.peripherals.iter_mut().find(|p| p.id == name) {
+                    p.size = Some(value.clone());
+                } else {
+                    anyhow::bail!(
+                        "memory_overrides.{name}: chip '{}' has no flash/ram window, memory region \
+                         or peripheral named '{name}'",
+                        chip.name
+                    );
+                }
+            }
+        }
+    }
+    Ok(chip)
+}
+
+/// Largest flash/RAM/region size a manifest's `memory_overrides` may request
+/// unless `LABWIRED_MAX_MEMORY` raises it. Backing memory is allocated
+/// eagerly, so a board typo such as `ram: "4GB"` would otherwise exhaust host
+/// memory. Chip descriptors are not capped: the shipped onboarding boards map
+/// GiB-sized RAM, which costs nothing until touched (zero pages).
+pub const DEFAULT_MAX_MEMORY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The active override cap: `LABWIRED_MAX_MEMORY` (any `parse_size` form)
+/// when set and non-empty, else [`DEFAULT_MAX_MEMORY_SIZE`].
+fn max_memory_size() -> anyhow::Result<u64> {
+    match std::env::var("LABWIRED_MAX_MEMORY") {
+        Ok(v) if !v.trim().is_empty() => parse_size(&v)
+            .with_context(|| format!("LABWIRED_MAX_MEMORY: '{v}' is not a valid size")),
+        _ => Ok(DEFAULT_MAX_MEMORY_SIZE),
+    }
+}
+
+/// Reject a `memory_overrides` entry that would allocate more than the cap,
+/// before anything is allocated. Only windows with eager backing count:
+/// `flash`, `ram` and non-sparse memory regions. Peripheral windows are
+/// routing ranges and sparse regions allocate pages on first write.
+fn check_memory_overrides(
+    chip: &ChipDescriptor,
+    overrides: &std::collections::HashMap<String, String>,
+) -> anyhow::Result<()> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+    let cap = max_memory_size()?;
+    for (key, value) in overrides {
+        let allocated = matches!(key.as_str(), "flash" | "ram")
+            || chip
+                .memory_regions
+                .iter()
+                .any(|r| r.name == *key && !r.sparse);
+        if !allocated {
+            continue;
+        }
+        // An unparseable value is reported by `apply_memory_overrides`.
+        let Ok(size) = parse_size(value) else {
+            continue;
+        };
+        if size > cap {
+            anyhow::bail!(
+                "memory_overrides.{key}: size {size:#x} exceeds the {cap:#x}-byte memory cap; \
+                 set LABWIRED_MAX_MEMORY to allow larger windows"
+            );
+        }
+    }
+    Ok(())
+}
+
 impl SystemBus {
     pub fn from_config(chip: &ChipDescriptor, manifest: &SystemManifest) -> anyhow::Result<Self> {
-        let flash_size = parse_size(&chip.flash.size)?;
-        let ram_size = parse_size(&chip.ram.size)?;
+        check_memory_overrides(chip, &manifest.memory_overrides)?;
+        let overridden;
+        let chip = if manifest.memory_overrides.is_empty() {
+            chip
+        } else {
+            overridden = apply_memory_overrides(chip, &manifest.memory_overrides)?;
+            &overridden
+        };
+        chip.validate()?;
+        let flash_size = chip.flash.size.bytes();
+        let ram_size = chip.ram.size.bytes();
 
         let mut extra_mem = Vec::with_capacity(chip.memory_regions.len());
+        let mut sparse_mem = Vec::new();
         for region in &chip.memory_regions {
             let size = parse_size(&region.size)?;
-            let mut mem = LinearMemory::new(size as usize, region.base);
+            if region.sparse {
+                if region.image_env.is_some() {
+                    anyhow::bail!(
+                        "memory region '{}': image_env is not supported on sparse regions",
+                        region.name
+                    );
+                }
+                let mut mem = crate::memory::SparseMemory::new(size, region.base);
+                mem.name = region.name.clone();
+                sparse_mem.push(mem);
+                continue;
+            }
+            let mut mem = LinearMemory::named(&region.name, size as usize, region.base);
             // Optionally preload a raw binary image (e.g. a dumped mask ROM)
             // from a path given by an env var. Copyrighted vendor blobs are not
             // committed, so a missing image just leaves the region zero-filled.
@@ -111,29 +221,39 @@ impl SystemBus {
 
         let mut bus = Self {
             flash_thunks: std::collections::HashMap::new(),
-            flash: LinearMemory::new(flash_size as usize, chip.flash.base),
-            ram: LinearMemory::new(ram_size as usize, chip.ram.base),
+            flash: LinearMemory::named("flash", flash_size as usize, chip.flash.base),
+            ram: LinearMemory::named("ram", ram_size as usize, chip.ram.base),
             extra_mem,
+            sparse_mem,
             peripherals: Vec::new(),
             nvic: None,
             observers: Vec::new(),
-            config: crate::SimulationConfig::default(),
+            config: crate::SimulationConfig {
+                strict_alignment: Self::chip_requires_alignment(chip),
+                arm_profile: Self::chip_arm_profile(chip),
+                ..Default::default()
+            },
             bit_band_enabled: Self::chip_has_bit_band(chip),
             reset_vector_offset: chip.reset_vector_offset,
+            vtor: chip.vtor,
             atomic_register_aliases: chip.atomic_register_aliases,
             pending_cpu_irqs: [0; 2],
             dport_idx: None,
             rcc_idx: None,
             clock_gating_bypass: false,
             fault_unclocked: std::collections::HashMap::new(),
+            unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+            unclocked_warned: Default::default(),
             peripheral_ranges: Vec::new(),
             legacy_tick_indices: Vec::new(),
             bus_tick_indices: Vec::new(),
             scheduler_driver_indices: Vec::new(),
+            irq_ack_indices: Vec::new(),
             matrix_source_scratch: Vec::new(),
             peripheral_hint: Cell::new(None),
             last_route: Cell::new(None),
             last_gap: Cell::new(None),
+            alignment_fault: Cell::new(None),
             last_gpio_in: [0; 2],
             current_cycle: 0,
             cycle_clock: crate::CycleClock::default(),
@@ -152,6 +272,7 @@ impl SystemBus {
             hx711: Vec::new(),
             seven_segment: Vec::new(),
             analog_inputs: Vec::new(),
+            analog_signals: Vec::new(),
             can_diagnostic_testers: Vec::new(),
             can_uds_testers: Vec::new(),
             can_log_players: Vec::new(),
@@ -176,6 +297,8 @@ impl SystemBus {
             pin_map: std::collections::HashMap::new(),
         };
 
+        bus.flash.writable = chip.flash_writable;
+
         // Authoritative pin map (silicon truth) — resolution prefers this over the
         // label-letter parse; see routing::resolve_pin_odr.
         for (label, loc) in &chip.pins {
@@ -368,7 +491,9 @@ impl SystemBus {
             }
 
             // Remaining: the YAML descriptor loaders (declarative / strict_ir) and
-            // the unknown-type stub fallback.
+            // the unknown-type stub fallback. Descriptor-backed models report
+            // their register span so an absent `size` fits the register map.
+            let mut register_span = None;
             let dev: Box<dyn Peripheral> = match canonical_type.as_str() {
                 "uart" | "stm32_uart" | "stm32f1_uart" | "stm32f2_uart" | "stm32f4_uart"
                 | "stm32f7_usart" | "stm32h5_usart" | "efm32_uart" | "nxp_lpuart" | "ns16550"
@@ -405,8 +530,10 @@ impl SystemBus {
                             .map(|n| n as u32)
                             .unwrap_or(32);
                         Box::new(crate::peripherals::gpio::GpioPort::new_nrf52(num_pins))
-                    } else if layout == GpioRegisterLayout::Stm32V2
-                        && p_cfg.config.contains_key("reset_moder")
+                    } else if matches!(
+                        layout,
+                        GpioRegisterLayout::Stm32V2 | GpioRegisterLayout::Stm32F4
+                    ) && p_cfg.config.contains_key("reset_moder")
                     {
                         // Per-port silicon reset values (MODER/OSPEEDR/PUPDR)
                         // supplied by the chip yaml; missing keys default to 0.
@@ -418,11 +545,20 @@ impl SystemBus {
                                 .map(|n| n as u32)
                                 .unwrap_or(0)
                         };
-                        Box::new(crate::peripherals::gpio::GpioPort::new_stm32v2_with_resets(
+                        let (moder, ospeedr, pupdr) = (
                             cfg_u32("reset_moder"),
                             cfg_u32("reset_ospeedr"),
                             cfg_u32("reset_pupdr"),
-                        ))
+                        );
+                        Box::new(if layout == GpioRegisterLayout::Stm32F4 {
+                            crate::peripherals::gpio::GpioPort::new_stm32f4_with_resets(
+                                moder, ospeedr, pupdr,
+                            )
+                        } else {
+                            crate::peripherals::gpio::GpioPort::new_stm32v2_with_resets(
+                                moder, ospeedr, pupdr,
+                            )
+                        })
                     } else {
                         Box::new(crate::peripherals::gpio::GpioPort::new_with_layout(layout))
                     }
@@ -459,6 +595,16 @@ impl SystemBus {
                         .unwrap_or(crate::peripherals::esp32c3::ledc::LEDC_INTR_SOURCE_ID);
                     Box::new(crate::peripherals::esp32c3::ledc::Esp32c3Ledc::new(src))
                 }
+                // Cortex-M NVIC declared by the chip: back the window with the
+                // real register model and share its state with the bus, so every
+                // peripheral `irq` (and every named descriptor interrupt, which
+                // resolves to its number) pends ISPR exactly as on a bus wired
+                // by `configure_cortex_m`, which then reuses this state. Chips
+                // that do not declare one keep the raw fallthrough vector.
+                "nvic" => {
+                    let state = bus.nvic.get_or_insert_with(Default::default).clone();
+                    Box::new(crate::peripherals::nvic::Nvic::new(state))
+                }
                 // Nordic peripherals — register-surface models cross-validated
                 // by hw-oracle::nrf52_onboarding_diff. See peripherals/nrf52/.
                 // TWIM (I²C master with EasyDMA) — nRF52840 PS §6.31.
@@ -512,6 +658,10 @@ impl SystemBus {
                         )?
                     };
 
+                    desc.validate().with_context(|| {
+                        format!("Invalid register layout for peripheral '{}'", p_cfg.id)
+                    })?;
+                    register_span = Some(desc.register_span());
                     Box::new(crate::peripherals::declarative::GenericPeripheral::new(
                         desc,
                     ))
@@ -577,6 +727,10 @@ impl SystemBus {
 
                     let desc: labwired_config::PeripheralDescriptor = ir_peripheral.into();
 
+                    desc.validate().with_context(|| {
+                        format!("Invalid register layout for peripheral '{}'", p_cfg.id)
+                    })?;
+                    register_span = Some(desc.register_span());
                     Box::new(crate::peripherals::declarative::GenericPeripheral::new(
                         desc,
                     ))
@@ -590,21 +744,33 @@ impl SystemBus {
                         serde_yaml::from_value(val.clone())?;
                     let desc: labwired_config::PeripheralDescriptor = ir_peripheral.into();
 
+                    desc.validate().with_context(|| {
+                        format!("Invalid register layout for peripheral '{}'", p_cfg.id)
+                    })?;
+                    register_span = Some(desc.register_span());
                     Box::new(crate::peripherals::declarative::GenericPeripheral::new(
                         desc,
                     ))
                 }
-                _other => {
-                    tracing::debug!(
-                        "Mapping unknown peripheral type '{}' to Stub for id '{}'",
-                        p_cfg.r#type,
-                        p_cfg.id
-                    );
-                    Box::new(crate::peripherals::stub::StubPeripheral::new(0x00))
-                }
+                _other => match PeripheralFactory::build(p_cfg) {
+                    Some(dev) => dev.with_context(|| {
+                        format!(
+                            "Registered peripheral type '{}' failed to build '{}'",
+                            p_cfg.r#type, p_cfg.id
+                        )
+                    })?,
+                    None => {
+                        tracing::debug!(
+                            "Mapping unknown peripheral type '{}' to Stub for id '{}'",
+                            p_cfg.r#type,
+                            p_cfg.id
+                        );
+                        Box::new(crate::peripherals::stub::StubPeripheral::new(0x00))
+                    }
+                },
             };
 
-            bus.push_peripheral(p_cfg, dev)?;
+            bus.push_peripheral_with_span(p_cfg, dev, register_span)?;
         }
 
         // Bus-trace wiring is no longer a per-peripheral property: the shared
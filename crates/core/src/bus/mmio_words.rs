@@ -18,7 +18,7 @@ impl SystemBus {
     /// SAME way the interpreter fetches instructions (see
     /// [`<Self as crate::Bus>::read_u32`] / [`<Self as crate::Bus>::read_u16`]
     /// and `RiscV::step`'s `bus.read_u32(pc)` fetch): linear RAM / flash / extra
-    /// memories, and the MMU-translating flash-XIP windows (0x4200_0000 /
+    /// / sparse memories, and the MMU-translating flash-XIP windows (0x4200_0000 /
     /// 0x3C00_0000). Iterating one address at a time keeps the buffer contiguous
     /// in **virtual** space — exactly what the walker's
     /// [`CodeView`](crate::cpu::jit_framework::CodeView) indexes — even when the
@@ -62,6 +62,9 @@ impl SystemBus {
                 return Some(b);
             }
         }
+        if let Some(b) = self.sparse_mem.iter().find_map(|m| m.read_u8(addr)) {
+            return Some(b);
+        }
         // Flash-XIP window: read-only, MMU-translated exactly as the CPU's
         // instruction fetch routes it. Restricting to the XIP peripheral keeps
         // this fetch side-effect free — no MMIO is ever touched.
@@ -4,7 +4,7 @@
 // This software is released under the MIT License.
 // See the LICENSE file in the project root for full license information.
 
-use crate::memory::LinearMemory;
+use crate::memory::{LinearMemory, SparseMemory};
 use crate::peripherals::nvic::NvicState;
 use crate::peripherals::uart::Uart;
 use crate::{Bus, Peripheral, SimResult};
@@ -29,6 +29,7 @@ mod faults;
 mod from_config;
 mod mmio_activity;
 mod mmio_words;
+mod peripheral_factory;
 mod policy;
 mod profiles;
 mod resident_device;
@@ -37,6 +38,7 @@ pub mod sim_inputs;
 mod tick;
 
 pub use can_devices::*;
+pub use peripheral_factory::{PeripheralConstructor, PeripheralFactory};
 pub use resident_device::BusResidentDevice;
 
 pub use bus_trace::{new_log, BusPayload, BusTraceEvent, BusTraceLog, I2cSym};
@@ -95,6 +97,21 @@ pub struct ResolvedClockGate {
     pub bit: u8,
 }
 
+/// What the bus does when the CPU touches a clock-gated peripheral whose RCC
+/// enable bit is clear. The access itself is always suppressed (reads return
+/// 0, writes are dropped); the policy only decides how loudly that is reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnclockedAccessPolicy {
+    /// Silicon behaviour: suppress the access silently.
+    #[default]
+    Ignore,
+    /// Suppress the access and log a warning, once per peripheral.
+    Warn,
+    /// Fail the access with [`crate::SimulationError::MemoryViolation`], so a
+    /// firmware that forgot to enable a clock stops at the offending access.
+    Fault,
+}
+
 /// The `peripheral_tick_interval` recommended for a fully scheduler-driven
 /// (walk-deleted) bus — see [`SystemBus::max_safe_tick_interval`]. Native
 /// C3 OLED throughput keeps climbing through a few hundred (host drain tax
@@ -120,6 +137,14 @@ pub struct PeripheralEntry {
     pub clock_gate: Option<ResolvedClockGate>,
 }
 
+impl PeripheralEntry {
+    /// True for the tombstone [`SystemBus::detach_peripheral`] leaves in a
+    /// removed peripheral's slot: no name, no window.
+    pub fn is_detached(&self) -> bool {
+        self.name.is_empty() && self.size == 0
+    }
+}
+
 /// RP2040 atomic register-alias operation (see
 /// [`SystemBus::atomic_alias_redirect`]).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -160,6 +185,8 @@ pub struct SystemBus {
     /// `0x4037C000` and flash-DROM `0x3C000000`), from the chip's
     /// `memory_regions`. Checked after `ram`/`flash`, before peripherals.
     pub extra_mem: Vec<LinearMemory>,
+    /// `memory_regions` marked `sparse`, checked after `extra_mem`.
+    pub sparse_mem: Vec<SparseMemory>,
     pub peripherals: Vec<PeripheralEntry>,
     pub nvic: Option<Arc<NvicState>>,
     pub observers: Vec<Arc<dyn crate::SimulationObserver>>,
@@ -174,6 +201,8 @@ pub struct SystemBus {
     /// descriptor so `Machine::load_firmware` can relocate the reset vector
     /// past the stage-2 blob. See `ChipDescriptor::reset_vector_offset`.
     pub reset_vector_offset: u64,
+    /// Boot-time VTOR from the chip config. See `ChipDescriptor::vtor`.
+    pub vtor: Option<u64>,
     /// RP2040 atomic register aliases enabled (see
     /// `ChipDescriptor::atomic_register_aliases`). When set, word accesses in
     /// the APB peripheral window whose offset has bits [13:12] set decode as
@@ -204,6 +233,9 @@ pub struct SystemBus {
     /// `rebuild_peripheral_ranges` so IRQ-level re-derivation on MMIO write
     /// can poll only those models (not the full bus).
     scheduler_driver_indices: Vec<usize>,
+    /// Indices of peripherals with `handles_irq_ack() == true`, the only
+    /// ones `clear_nvic_pending` notifies when an IRQ is taken.
+    irq_ack_indices: Vec<usize>,
     /// Retained scratch for `poll_scheduler_matrix_sources`: each scheduler-driven
     /// peripheral fills this buffer via `Peripheral::matrix_irq_sources_into`
     /// instead of returning a freshly-allocated `Vec` per poll. Cleared before
@@ -225,6 +257,11 @@ pub struct SystemBus {
     /// clears `last_route`). Same staleness contract as `last_route`: cleared
     /// on range rebuild.
     last_gap: Cell<Option<(u64, u64)>>,
+    /// Address of the last unaligned access rejected under
+    /// `config.strict_alignment`. The Cortex-M core drops most data-access
+    /// errors, so the machine drains this after each CPU window and stops
+    /// with `MemoryViolation` instead of letting the fault go unnoticed.
+    alignment_fault: Cell<Option<u64>>,
     /// Cached index of the classic-ESP32 DPORT peripheral, if one is
     /// registered (`None` otherwise — the common case, incl. every ESP32-S3
     /// bus). Recomputed in `rebuild_peripheral_ranges` on each peripheral
@@ -249,6 +286,12 @@ pub struct SystemBus {
     /// mapped to a count of accesses suppressed because of the fault (the
     /// runtime fired-observation). Empty in the common case.
     fault_unclocked: std::collections::HashMap<usize, std::sync::atomic::AtomicU64>,
+    /// How an access to an unclocked peripheral is reported; see
+    /// [`set_unclocked_access_policy`].
+    unclocked_access_policy: UnclockedAccessPolicy,
+    /// Peripheral indices already warned about under
+    /// [`UnclockedAccessPolicy::Warn`], so a polling loop logs once.
+    unclocked_warned: std::cell::RefCell<std::collections::HashSet<usize>>,
     /// Last-known IN value of GPIO ports 0 and 1, used by the per-tick
     /// edge-detection pass that drives GPIOTE EVENTS_IN. Both default to
     /// 0 at construction; the first tick after a GPIO write will produce
@@ -352,6 +395,9 @@ pub struct SystemBus {
     /// computed a level at attach and dropped the model, which made these
     /// parts un-drivable at runtime. Empty by default -> zero cost.
     pub analog_inputs: Vec<sim_inputs::AnalogInputSource>,
+    /// Time-varying levels driven onto ADC channels, sampled at the start of
+    /// every peripheral tick. Set through [`Self::set_analog_signal`].
+    pub analog_signals: Vec<sim_inputs::AnalogSignal>,
     /// Reusable CAN diagnostic clients declared as external devices. They
     /// inject configured CAN frames into a named FDCAN peripheral once it is
     /// running, so ECU examples can be driven by a virtual off-board tester
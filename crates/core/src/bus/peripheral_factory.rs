@@ -0,0 +1,72 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+// SPDX-License-Identifier: MIT
+
+//! Runtime registry of downstream peripheral types for `SystemBus::from_config`.
+//!
+//! The built-in `type:` strings are a closed set resolved by the per-family
+//! factories and the central match in `from_config`. A crate that ships its own
+//! model registers a constructor here before building the bus; `from_config`
+//! consults the registry for any type it does not know instead of mapping the
+//! entry to a stub. Built-in types (and the spellings the canonicalizer folds
+//! onto them, e.g. anything containing `uart`) always win, so pick a distinct
+//! name.
+
+use crate::Peripheral;
+use labwired_config::PeripheralConfig;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Builds one peripheral instance from its chip-descriptor entry.
+pub type PeripheralConstructor =
+    dyn Fn(&PeripheralConfig) -> anyhow::Result<Box<dyn Peripheral>> + Send + Sync;
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, Arc<PeripheralConstructor>>>> =
+    LazyLock::new(Default::default);
+
+/// Process-wide `type` → constructor registry. Type names match
+/// case-insensitively, like the built-in ones.
+pub struct PeripheralFactory;
+
+impl PeripheralFactory {
+    /// Register `constructor` for `type_name`, replacing (and returning `true`
+    /// for) any earlier registration of the same name.
+    pub fn register<F>(type_name: &str, constructor: F) -> bool
+    where
+        F: Fn(&PeripheralConfig) -> anyhow::Result<Box<dyn Peripheral>> + Send + Sync + 'static,
+    {
+        REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(type_name.to_ascii_lowercase(), Arc::new(constructor))
+            .is_some()
+    }
+
+    /// Remove `type_name`; returns whether it was registered.
+    pub fn unregister(type_name: &str) -> bool {
+        REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&type_name.to_ascii_lowercase())
+            .is_some()
+    }
+
+    pub fn is_registered(type_name: &str) -> bool {
+        REGISTRY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(&type_name.to_ascii_lowercase())
+    }
+
+    /// Build `p_cfg` through its registered constructor, or `None` when its
+    /// type is not registered. The lock is released before the constructor
+    /// runs, so a constructor may itself register types.
+    pub(crate) fn build(p_cfg: &PeripheralConfig) -> Option<anyhow::Result<Box<dyn Peripheral>>> {
+        let constructor = REGISTRY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&p_cfg.r#type.to_ascii_lowercase())
+            .cloned()?;
+        Some(constructor(p_cfg))
+    }
+}
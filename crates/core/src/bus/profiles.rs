@@ -313,7 +313,8 @@ impl SystemBus {
     ///
     ///   1. An explicit `config.profile` always wins (author's deliberate choice).
     ///   2. A type whose silicon layout the *name* pins down routes to it:
-    ///      `*nrf*` → Nordic; `stm32f4`/`*h5*`/`*v2*` → modern STM32;
+    ///      `*nrf*` → Nordic; `stm32f4` → the BRR-less F4 map; `*h5*`/`*v2*` →
+    ///      modern STM32;
     ///      `stm32_gpioport`/`stm32f1`/`stm32f2` and the legacy placeholder
     ///      ports (`efmgpioport`/`npcx_gpio`/`imxrt_gpio`, historically run on
     ///      the F1 map) → classic STM32F1.
@@ -342,7 +343,9 @@ impl SystemBus {
         let has = |needle: &str| raw.contains(needle);
         let layout = if has("nrf") {
             GpioRegisterLayout::Nrf52
-        } else if has("stm32f4") || has("h5") || has("stm32v2") {
+        } else if has("stm32f4") {
+            GpioRegisterLayout::Stm32F4
+        } else if has("h5") || has("stm32v2") {
             GpioRegisterLayout::Stm32V2
         } else if raw == "stm32_gpioport" || has("stm32f1") || has("stm32f2") {
             GpioRegisterLayout::Stm32F1
@@ -361,7 +364,7 @@ impl SystemBus {
                 "GPIO peripheral '{}' is declared with the vendor-neutral `type: gpio` but no \
                  `config.profile`; it will NOT be silently mapped onto STM32F1 (a wrong layout \
                  moves the output register and blanks a display's D/C line). Choose a layout \
-                 explicitly with `config: {{ profile: <stm32f1|stm32v2|nrf52|kinetis> }}`.",
+                 explicitly with `config: {{ profile: <stm32f1|stm32v2|stm32f4|nrf52|kinetis> }}`.",
                 p_cfg.id
             );
         } else {
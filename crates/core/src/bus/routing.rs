@@ -233,6 +233,12 @@ impl SystemBus {
             .enumerate()
             .filter_map(|(index, p)| p.dev.uses_scheduler().then_some(index))
             .collect();
+        self.irq_ack_indices = self
+            .peripherals
+            .iter()
+            .enumerate()
+            .filter_map(|(index, p)| p.dev.handles_irq_ack().then_some(index))
+            .collect();
         self.peripheral_hint.set(None);
         self.last_route.set(None);
         self.last_gap.set(None);
@@ -5,6 +5,7 @@
 //! SimInput discovery and dispatch on attached devices.
 
 use super::*;
+use crate::physics::analog::Waveform;
 
 /// A component whose stimulus manifests as an analog level on an ADC channel
 /// rather than as bytes on a bus — a potentiometer wiper, a thermistor
@@ -29,6 +30,15 @@ pub struct AnalogInputSource {
     pub source: Box<dyn AnalogSource>,
 }
 
+/// A [`Waveform`] driving one ADC channel over time.
+pub struct AnalogSignal {
+    pub connection: String,
+    pub channel: u8,
+    pub waveform: Waveform,
+    /// Level last seeded, so unchanged samples skip the ADC lookup.
+    last_mv: u16,
+}
+
 impl SystemBus {
     /// Write `millivolts` into `channel` of the ADC named `connection`.
     ///
@@ -94,6 +104,58 @@ impl SystemBus {
             self.seed_adc_channel(&connection, channel, mv);
         }
     }
+
+    /// Drive `channel` of the ADC named `connection` from `waveform`,
+    /// replacing any signal already on that channel. The level is sampled
+    /// against `current_cycle` at the start of every peripheral tick, so each
+    /// conversion latches the waveform's value at the time it completes.
+    pub fn set_analog_signal(
+        &mut self,
+        connection: &str,
+        channel: u8,
+        waveform: Waveform,
+    ) -> anyhow::Result<()> {
+        let mv = waveform.sample(self.current_cycle);
+        if !self.seed_adc_channel(connection, channel, mv) {
+            anyhow::bail!("no ADC for analog signal on '{connection}' channel {channel}");
+        }
+        self.clear_analog_signal(connection, channel);
+        self.analog_signals.push(AnalogSignal {
+            connection: connection.to_string(),
+            channel,
+            waveform,
+            last_mv: mv,
+        });
+        Ok(())
+    }
+
+    /// Stop driving `channel` of `connection`; the last sampled level stays
+    /// injected. Returns whether a signal was removed.
+    pub fn clear_analog_signal(&mut self, connection: &str, channel: u8) -> bool {
+        let before = self.analog_signals.len();
+        self.analog_signals
+            .retain(|s| !(s.connection == connection && s.channel == channel));
+        self.analog_signals.len() != before
+    }
+
+    /// Re-sample every analog signal and seed the channels whose level moved.
+    pub(crate) fn drive_analog_signals(&mut self) {
+        if self.analog_signals.is_empty() {
+            return;
+        }
+        let now = self.current_cycle;
+        let mut changed = Vec::new();
+        for signal in self.analog_signals.iter_mut() {
+            let mv = signal.waveform.sample(now);
+            if mv != signal.last_mv {
+                signal.last_mv = mv;
+                changed.push((signal.connection.clone(), signal.channel, mv));
+            }
+        }
+        for (connection, channel, mv) in changed {
+            self.seed_adc_channel(&connection, channel, mv);
+        }
+    }
 }
 
 impl SystemBus {
@@ -300,6 +362,66 @@ impl SystemBus {
         Ok(())
     }
 
+    /// Drive input `pin` of the GPIO port peripheral `gpio` to `level` from
+    /// outside the firmware (a button, a board input). A level change is also
+    /// presented to EXTI line `pin` as a rising/falling edge — filtered by the
+    /// AFIO EXTICR port select where one is modelled and by RTSR/FTSR — so a
+    /// configured line pends its mapped NVIC IRQ exactly as on silicon.
+    pub fn set_board_input(&mut self, gpio: &str, pin: u8, level: bool) -> Result<(), String> {
+        let idx = self
+            .find_peripheral_index_by_name(gpio)
+            .ok_or_else(|| format!("GPIO peripheral '{gpio}' not found"))?;
+        let previous = self.peripherals[idx].dev.read_gpio_input(pin);
+        if !self.peripherals[idx].dev.set_gpio_input(pin, level) {
+            return Err(format!(
+                "peripheral '{gpio}' does not expose GPIO input control for pin {pin}"
+            ));
+        }
+        if previous == Some(level) || !self.exti_line_selects_port(gpio, pin) {
+            return Ok(());
+        }
+        for exti_idx in 0..self.peripherals.len() {
+            let Some(exti) = self.peripherals[exti_idx]
+                .dev
+                .as_any_mut()
+                .and_then(|a| a.downcast_mut::<crate::peripherals::exti::Exti>())
+            else {
+                continue;
+            };
+            if exti.input_edge(pin, level) {
+                // A newly pending line must arm the scheduler chain, same as
+                // the SWIER write path does after an MMIO write.
+                #[cfg(feature = "event-scheduler")]
+                self.collect_scheduled_events(exti_idx);
+            }
+            break;
+        }
+        Ok(())
+    }
+
+    /// Whether EXTI line `line` is multiplexed to the port `gpio` (`gpioa` =
+    /// port 0, …) by the F1 AFIO EXTICR registers. Buses without a modelled
+    /// AFIO (or port names that don't follow `gpio<letter>`) don't filter.
+    fn exti_line_selects_port(&self, gpio: &str, line: u8) -> bool {
+        let Some(port) = gpio
+            .strip_prefix("gpio")
+            .and_then(|s| s.bytes().next().filter(|_| s.len() == 1))
+            .filter(u8::is_ascii_lowercase)
+            .map(|c| c - b'a')
+        else {
+            return true;
+        };
+        let afio = self.peripherals.iter().find_map(|p| {
+            p.dev
+                .as_any()
+                .and_then(|a| a.downcast_ref::<crate::peripherals::afio::Afio>())
+        });
+        match afio {
+            Some(afio) if line < 16 => afio.get_exti_mapping(line) == port,
+            _ => true,
+        }
+    }
+
     /// Snapshot of the universal bus-transaction trace (logic analyzer):
     /// every I²C/SPI byte recorded so far by peripherals wired to
     /// `self.bus_trace` (see `crate::bus::bus_trace`), oldest first.
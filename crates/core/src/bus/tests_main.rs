@@ -202,6 +202,7 @@ fn declarative_descriptor(timing: Option<Vec<TimingDescriptor>>) -> PeripheralDe
         ],
         interrupts: None,
         timing,
+        interrupt_descriptors: Vec::new(),
     }
 }
 
@@ -268,6 +269,114 @@ fn declarative_peripherals_enter_legacy_tick_set_only_while_events_are_pending()
     );
 }
 
+#[test]
+fn declarative_interrupt_ack_trigger_clears_status_flag_on_irq_entry() {
+    let mut bus = SystemBus::empty();
+    let nvic = std::sync::Arc::new(NvicState::default());
+    bus.nvic = Some(nvic.clone());
+    let mut desc = declarative_descriptor(Some(vec![TimingDescriptor {
+        id: "ack-clears-flag".to_string(),
+        trigger: TimingTrigger::OnInterruptAck {
+            interrupt: "EVT".to_string(),
+        },
+        delay_cycles: 0,
+        action: TimingAction::ClearBits {
+            register: "STATUS".to_string(),
+            bits: 1,
+        },
+        interrupt: None,
+    }]));
+    desc.interrupts = Some([("EVT".to_string(), 5)].into_iter().collect());
+    bus.add_peripheral(
+        "ack_declarative",
+        0x2000,
+        0x100,
+        None,
+        Box::new(crate::peripherals::declarative::GenericPeripheral::new(
+            desc,
+        )),
+    );
+
+    bus.write_u32(0x2004, 1).unwrap();
+    nvic.ispr[0].fetch_or(1 << 5, std::sync::atomic::Ordering::SeqCst);
+    assert!(bus.legacy_tick_indices.is_empty());
+    assert_eq!(bus.irq_ack_indices, vec![0], "only the ack-hooked model is notified");
+
+    // Exception entry for IRQ 5 (exception 21) acknowledges the pending bit.
+    bus.clear_nvic_pending(16 + 5);
+    assert!(!nvic_pending(&nvic, 5));
+    assert_eq!(
+        bus.legacy_tick_indices,
+        vec![0],
+        "the ack should arm the peripheral's timing event"
+    );
+
+    bus.tick_peripherals_fully();
+    assert_eq!(
+        bus.read_u32(0x2004).unwrap(),
+        0,
+        "servicing the IRQ clears the flag"
+    );
+
+    // An unrelated IRQ leaves the peripheral alone.
+    bus.write_u32(0x2004, 1).unwrap();
+    bus.clear_nvic_pending(16 + 6);
+    bus.tick_peripherals_fully();
+    assert_eq!(bus.read_u32(0x2004).unwrap(), 1);
+}
+
+#[test]
+fn declarative_pend_and_clear_interrupt_actions_drive_nvic_ispr() {
+    let mut bus = SystemBus::empty();
+    let nvic = std::sync::Arc::new(NvicState::default());
+    bus.nvic = Some(nvic.clone());
+    let on_ctrl = |id: &str, value: u32, action: TimingAction| TimingDescriptor {
+        id: id.to_string(),
+        trigger: TimingTrigger::Write {
+            register: "CTRL".to_string(),
+            value: Some(value),
+            mask: None,
+        },
+        delay_cycles: 0,
+        action,
+        interrupt: None,
+    };
+    let mut desc = declarative_descriptor(Some(vec![
+        on_ctrl(
+            "pend",
+            1,
+            TimingAction::PendInterrupt {
+                name: "EVT".to_string(),
+            },
+        ),
+        on_ctrl(
+            "clear",
+            2,
+            TimingAction::ClearInterrupt {
+                name: "EVT".to_string(),
+            },
+        ),
+    ]));
+    desc.interrupts = Some([("EVT".to_string(), 37)].into_iter().collect());
+    bus.add_peripheral(
+        "irq_declarative",
+        0x2000,
+        0x100,
+        None,
+        Box::new(crate::peripherals::declarative::GenericPeripheral::new(
+            desc,
+        )),
+    );
+
+    bus.write_u32(0x2000, 1).unwrap();
+    bus.tick_peripherals_fully();
+    assert!(nvic_pending(&nvic, 37), "pend_interrupt sets the ISPR bit");
+
+    bus.write_u32(0x2000, 2).unwrap();
+    bus.tick_peripherals_fully();
+    assert!(!nvic_pending(&nvic, 37), "clear_interrupt drops the ISPR bit");
+}
+
 #[cfg(feature = "event-scheduler")]
 #[test]
 fn scheduler_peripherals_do_not_enter_legacy_tick_index() {
@@ -511,6 +620,193 @@ fn test_system_bus_from_config_declarative() {
     assert_eq!(count_val, 0x12345678);
 }
 
+#[test]
+fn declarative_peripheral_without_size_covers_its_register_span() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let chip_path = root.join("tests/fixtures/test_chip_declarative.yaml");
+    let manifest_path = root.join("tests/fixtures/test_system_declarative.yaml");
+    let mut chip = ChipDescriptor::from_file(&chip_path).unwrap();
+    let manifest = SystemManifest::from_file(&manifest_path).unwrap();
+
+    let descriptor = root.join("tests/fixtures/test_sparse_descriptor.yaml");
+    chip.peripherals[0].size = None;
+    chip.peripherals[0].config.insert(
+        "path".to_string(),
+        serde_yaml::Value::String(descriptor.to_string_lossy().into_owned()),
+    );
+
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    let found = bus
+        .peripherals
+        .iter()
+        .find(|p| p.name == "TIMER1")
+        .expect("TIMER1 not found");
+    assert!(
+        found.size >= 0x804,
+        "window {:#x} misses STATUS",
+        found.size
+    );
+
+    // DATE at 0x1FFC lies past the 4KB default window.
+    assert_eq!(found.size, 0x2000);
+
+    bus.write_u32(0x4000_1800, 0xA5A5_5A5A).unwrap();
+    assert_eq!(bus.read_u32(0x4000_1800).unwrap(), 0xA5A5_5A5A);
+    assert_eq!(bus.read_u32(0x4000_2FFC).unwrap(), 0x2026_0101);
+}
+
+#[test]
+fn declarative_peripheral_with_overlapping_registers_is_rejected() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let chip_path = root.join("tests/fixtures/test_chip_declarative.yaml");
+    let manifest_path = root.join("tests/fixtures/test_system_declarative.yaml");
+    let mut chip = ChipDescriptor::from_file(&chip_path).unwrap();
+    let manifest = SystemManifest::from_file(&manifest_path).unwrap();
+
+    let descriptor = root.join("tests/fixtures/test_overlapping_descriptor.yaml");
+    chip.peripherals[0].config.insert(
+        "path".to_string(),
+        serde_yaml::Value::String(descriptor.to_string_lossy().into_owned()),
+    );
+
+    let err = match SystemBus::from_config(&chip, &manifest) {
+        Ok(_) => panic!("overlapping registers must be rejected"),
+        Err(e) => format!("{:#}", e),
+    };
+    assert!(err.contains("TIMER1"), "{err}");
+    assert!(err.contains("'CTRL'") && err.contains("'STATUS'"), "{err}");
+}
+
+#[test]
+fn memory_overrides_resize_ram_and_peripheral_windows() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let chip_path = root.join("tests/fixtures/test_chip_declarative.yaml");
+    let manifest_path = root.join("tests/fixtures/test_system_declarative.yaml");
+    let chip = ChipDescriptor::from_file(&chip_path).unwrap();
+    let mut manifest = SystemManifest::from_file(&manifest_path).unwrap();
+    assert_eq!(chip.ram.size.bytes(), 20 * 1024);
+
+    manifest
+        .memory_overrides
+        .insert("ram".to_string(), "64KB".to_string());
+    manifest
+        .memory_overrides
+        .insert("TIMER1".to_string(), "2KB".to_string());
+    let bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert_eq!(bus.ram.data.len(), 64 * 1024);
+    let timer = bus.peripherals.iter().find(|p| p.name == "TIMER1").unwrap();
+    assert_eq!(timer.size, 2 * 1024);
+
+    manifest
+        .memory_overrides
+        .insert("ram".to_string(), "lots".to_string());
+    let err = format!(
+        "{:#}",
+        SystemBus::from_config(&chip, &manifest).err().unwrap()
+    );
+    assert!(err.contains("memory_overrides.ram: 'lots'"), "{err}");
+
+    manifest.memory_overrides.clear();
+    manifest
+        .memory_overrides
+        .insert("sram9".to_string(), "4KB".to_string());
+    let err = format!(
+        "{:#}",
+        SystemBus::from_config(&chip, &manifest).err().unwrap()
+    );
+    assert!(
+        err.contains("no flash/ram window, memory region or peripheral named 'sram9'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn oversized_ram_is_rejected_before_allocation() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let chip_path = root.join("tests/fixtures/test_chip_declarative.yaml");
+    let manifest_path = root.join("tests/fixtures/test_system_declarative.yaml");
+    let chip = ChipDescriptor::from_file(&chip_path).unwrap();
+    let mut manifest = SystemManifest::from_file(&manifest_path).unwrap();
+
+    manifest
+        .memory_overrides
+        .insert("ram".to_string(), "4GB".to_string());
+    let err = format!(
+        "{:#}",
+        SystemBus::from_config(&chip, &manifest).err().unwrap()
+    );
+    assert!(
+        err.contains("memory_overrides.ram") && err.contains("exceeds"),
+        "{err}"
+    );
+    assert!(err.contains("LABWIRED_MAX_MEMORY"), "{err}");
+}
+
+/// The override cap must not reject chips as shipped: the onboarding boards
+/// declare GiB-sized RAM (sifive-fu740: 8 GiB) and still have to build.
+#[test]
+fn every_shipped_chip_builds_a_bus() {
+    let chips = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../configs/chips");
+    let mut paths = Vec::new();
+    for dir in [chips.clone(), chips.join("onboarding")] {
+        for entry in std::fs::read_dir(&dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("yaml") {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    assert!(paths.len() > 100, "found only {} chips", paths.len());
+    for path in paths {
+        let chip = ChipDescriptor::from_file(&path)
+            .unwrap_or_else(|e| panic!("{}: {e:#}", path.display()));
+        let manifest = SystemManifest {
+            cosim_models: Vec::new(),
+            walk_deleted: Some(false),
+            schema_version: "1.0".to_string(),
+            name: "every-chip".to_string(),
+            chip: path.to_string_lossy().to_string(),
+            memory_overrides: HashMap::new(),
+            external_devices: Vec::new(),
+            board_io: Vec::new(),
+            debug_uart: None,
+            peripherals: Vec::new(),
+        };
+        if let Err(e) = SystemBus::from_config(&chip, &manifest) {
+            panic!("{}: {e:#}", path.display());
+        }
+    }
+}
+
+#[test]
+fn sparse_region_backs_a_large_window_on_demand() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let chip_path = root.join("tests/fixtures/test_chip_declarative.yaml");
+    let manifest_path = root.join("tests/fixtures/test_system_declarative.yaml");
+    let mut chip = ChipDescriptor::from_file(&chip_path).unwrap();
+    let manifest = SystemManifest::from_file(&manifest_path).unwrap();
+    chip.memory_regions.push(labwired_config::NamedMemoryRange {
+        name: "dram".to_string(),
+        base: 0x1_0000_0000,
+        size: "4GB".to_string(),
+        image_env: None,
+        sparse: true,
+    });
+
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert!(bus.extra_mem.is_empty());
+    bus.write_u32(0x1_0000_0100, 0xCAFE_F00D).unwrap();
+    bus.write_u8(0x1_8000_0000, 0x42).unwrap();
+    assert_eq!(bus.read_u32(0x1_0000_0100).unwrap(), 0xCAFE_F00D);
+    assert_eq!(bus.read_u8(0x1_8000_0000).unwrap(), 0x42);
+    assert_eq!(bus.read_u8(0x1_4000_0000).unwrap(), 0);
+    assert_eq!(bus.sparse_mem[0].allocated_pages(), 2);
+
+    // The JIT's side-effect-free fetch sees the same bytes.
+    assert_eq!(bus.read_code_slice(0x1_0000_0100, 4), 0xCAFE_F00Du32.to_le_bytes());
+}
+
 #[test]
 fn test_system_bus_resolves_descriptor_path_relative_to_chip_file() {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -546,18 +842,20 @@ fn test_from_config_attaches_adxl345_external_device_to_i2c() {
     let chip = ChipDescriptor {
         schema_version: "1.0".to_string(),
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
+        flash_writable: true,
         memory_regions: Vec::new(),
         name: "stm32f103-test".to_string(),
         arch: Arch::Arm,
         core: None,
         flash: MemoryRange {
             base: 0x0800_0000,
-            size: "64KB".to_string(),
+            size: "64KB".parse().unwrap(),
         },
         ram: MemoryRange {
             base: 0x2000_0000,
-            size: "20KB".to_string(),
+            size: "20KB".parse().unwrap(),
         },
         peripherals: vec![PeripheralConfig {
             id: "i2c1".to_string(),
@@ -1123,18 +1421,20 @@ fn test_from_config_attaches_bmp280_to_esp32c3_i2c0() {
     let chip = ChipDescriptor {
         schema_version: "1.0".to_string(),
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
+        flash_writable: true,
         memory_regions: Vec::new(),
         name: "esp32c3-i2c-test".to_string(),
         arch: Arch::RiscV,
         core: None,
         flash: MemoryRange {
             base: 0x4200_0000,
-            size: "4MB".to_string(),
+            size: "4MB".parse().unwrap(),
         },
         ram: MemoryRange {
             base: 0x3FC8_0000,
-            size: "400KB".to_string(),
+            size: "400KB".parse().unwrap(),
         },
         peripherals: vec![
             PeripheralConfig {
@@ -1268,18 +1568,20 @@ fn test_from_config_attaches_mlx90640_to_esp32c3_i2c0_and_reads_eeprom() {
     let chip = ChipDescriptor {
         schema_version: "1.0".to_string(),
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
+        flash_writable: true,
         memory_regions: Vec::new(),
         name: "esp32c3-mlx-test".to_string(),
         arch: Arch::RiscV,
         core: None,
         flash: MemoryRange {
             base: 0x4200_0000,
-            size: "4MB".to_string(),
+            size: "4MB".parse().unwrap(),
         },
         ram: MemoryRange {
             base: 0x3FC8_0000,
-            size: "400KB".to_string(),
+            size: "400KB".parse().unwrap(),
         },
         peripherals: vec![
             PeripheralConfig {
@@ -2164,6 +2466,137 @@ fn from_config_m33_gpio_in_alias_range_receives_word_accesses() {
     assert_eq!(odr & 1, 1, "GPIO BSRR write was shadowed by bit-band alias");
 }
 
+/// The F4/H5 family profile names select the MODER/ODR/BSRR (stm32v2) map:
+/// BSRR sets and resets land in ODR, and BRR clears, at the real offsets.
+#[test]
+fn from_config_f4_and_h5_gpio_profiles_use_bsrr_layout() {
+    // (profile, core, base, has BRR at 0x28)
+    for (profile, core, base, has_brr) in [
+        ("stm32f4", "cortex-m4", 0x4002_0000u64, false),
+        ("stm32h5", "cortex-m33", 0x4202_0000u64, true),
+    ] {
+        let chip = bit_band_test_chip(
+            &format!("name: \"{profile}-test\"\narch: \"arm\"\ncore: \"{core}\""),
+            &format!("{base:#x}"),
+            profile,
+        );
+        let mut bus = SystemBus::from_config(&chip, &empty_manifest()).unwrap();
+        let odr = |bus: &SystemBus| crate::Bus::read_u32(bus, base + 0x14).unwrap();
+
+        // BSRR (0x18): set pins 0 and 5.
+        crate::Bus::write_u32(&mut bus, base + 0x18, (1 << 5) | 1).unwrap();
+        assert_eq!(odr(&bus), (1 << 5) | 1, "{profile}");
+        // BSRR reset half: clear pin 5.
+        crate::Bus::write_u32(&mut bus, base + 0x18, 1 << (16 + 5)).unwrap();
+        assert_eq!(odr(&bus), 1, "{profile}");
+        // BRR (0x28): clears pin 0 on H5; reserved on F4.
+        crate::Bus::write_u32(&mut bus, base + 0x28, 1).unwrap();
+        assert_eq!(odr(&bus), if has_brr { 0 } else { 1 }, "{profile}");
+    }
+}
+
+/// A `profile: stm32v2` USART built from config exposes the H5 map the HIL
+/// showcase polls: CR1@0x00, CR3@0x08, BRR@0x0C, ISR@0x1C (TXE bit 7, TC bit
+/// 6), RDR@0x24, TDR@0x28.
+#[test]
+fn from_config_stm32v2_usart_polls_isr_txe_and_writes_tdr() {
+    let chip: ChipDescriptor = serde_yaml::from_str(
+        r#"
+name: "h5-usart-test"
+arch: "arm"
+core: "cortex-m33"
+flash:
+  base: 0x08000000
+  size: "128KB"
+ram:
+  base: 0x20000000
+  size: "64KB"
+peripherals:
+  - id: "usart3"
+    type: "uart"
+    base_address: 0x40004800
+    size: "1KB"
+    config:
+      profile: "stm32v2"
+"#,
+    )
+    .unwrap();
+    let mut bus = SystemBus::from_config(&chip, &empty_manifest()).unwrap();
+    let sink = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    bus.attach_uart_tx_sink(sink.clone(), false);
+
+    const USART3: u64 = 0x4000_4800;
+    crate::Bus::write_u32(&mut bus, USART3 + 0x0C, 0x2B6).unwrap(); // BRR
+    crate::Bus::write_u32(&mut bus, USART3 + 0x08, 0).unwrap(); // CR3
+    crate::Bus::write_u32(&mut bus, USART3, 0b1101).unwrap(); // CR1 = UE | RE | TE
+    assert_eq!(crate::Bus::read_u32(&bus, USART3 + 0x0C).unwrap(), 0x2B6);
+    assert_eq!(crate::Bus::read_u32(&bus, USART3).unwrap(), 0b1101);
+
+    for &byte in b"OK" {
+        // The driver's `while (!(USART3->ISR & USART_ISR_TXE))` poll.
+        let isr = crate::Bus::read_u32(&bus, USART3 + 0x1C).unwrap();
+        assert_ne!(isr & (1 << 7), 0, "ISR.TXE must be set at idle");
+        assert_ne!(isr & (1 << 6), 0, "ISR.TC must be set at idle");
+        crate::Bus::write_u32(&mut bus, USART3 + 0x28, byte as u32).unwrap();
+    }
+    assert_eq!(sink.lock().unwrap().as_slice(), b"OK");
+}
+
+#[test]
+fn from_config_builds_unknown_type_through_registered_factory() {
+    #[derive(Debug)]
+    struct Widget {
+        id: u8,
+    }
+    impl crate::Peripheral for Widget {
+        fn read(&self, _offset: u64) -> SimResult<u8> {
+            Ok(self.id)
+        }
+
+        fn write(&mut self, _offset: u64, _value: u8) -> SimResult<()> {
+            Ok(())
+        }
+    }
+
+    assert!(!PeripheralFactory::register("acme_widget", |cfg| {
+        let id = cfg.config.get("id").and_then(|v| v.as_u64()).unwrap_or(0) as u8;
+        Ok(Box::new(Widget { id }))
+    }));
+    assert!(PeripheralFactory::is_registered("ACME_WIDGET"));
+
+    let chip: ChipDescriptor = serde_yaml::from_str(
+        r#"
+name: "plugin-test"
+arch: "arm"
+flash:
+  base: 0x08000000
+  size: "128KB"
+ram:
+  base: 0x20000000
+  size: "64KB"
+peripherals:
+  - id: "widget0"
+    type: "Acme_Widget"
+    base_address: 0x40000000
+    size: "1KB"
+    config:
+      id: 0x5A
+  - id: "mystery"
+    type: "acme_gadget"
+    base_address: 0x40001000
+    size: "1KB"
+"#,
+    )
+    .unwrap();
+    let bus = SystemBus::from_config(&chip, &empty_manifest()).unwrap();
+    assert!(PeripheralFactory::unregister("acme_widget"));
+
+    assert_eq!(bus.read_u8(0x4000_0000).unwrap(), 0x5A);
+    assert_eq!(bus.read_u8(0x4000_0001).unwrap(), 0x5A);
+    // Unregistered unknown types still fall back to a zero-reading stub.
+    assert_eq!(bus.read_u8(0x4000_1000).unwrap(), 0x00);
+}
+
 /// Cortex-M3 parts (STM32F1) DO have the bit-band feature: word accesses
 /// to the 0x4200_0000 alias region must keep translating to single-bit
 /// operations on the underlying 0x4000_0000 peripheral registers.
@@ -2223,18 +2656,20 @@ fn chip_with_i2c_and_uart() -> labwired_config::ChipDescriptor {
     labwired_config::ChipDescriptor {
         schema_version: "1.0".to_string(),
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
+        flash_writable: true,
         memory_regions: Vec::new(),
         name: "stm32f103-test".to_string(),
         arch: Arch::Arm,
         core: None,
         flash: MemoryRange {
             base: 0x0800_0000,
-            size: "64KB".to_string(),
+            size: "64KB".parse().unwrap(),
         },
         ram: MemoryRange {
             base: 0x2000_0000,
-            size: "20KB".to_string(),
+            size: "20KB".parse().unwrap(),
         },
         peripherals: vec![
             PeripheralConfig {
@@ -2412,6 +2847,156 @@ fn test_system_bus_memory_observer() {
         assert_eq!(w.len(), 2);
         assert_eq!(w[1], (0x4000C000, 0xC0, 0xBB));
     }
+
+    // Word stores report each lane, like the equivalent byte stores.
+    bus.write_u32(0x20000004, 0x1122_3344).unwrap();
+    {
+        let w = writes.lock().unwrap();
+        assert_eq!(
+            w[2..],
+            [
+                (0x20000004, 0, 0x44),
+                (0x20000005, 0, 0x33),
+                (0x20000006, 0, 0x22),
+                (0x20000007, 0, 0x11),
+            ]
+        );
+    }
+}
+
+#[test]
+fn rtc_backup_register_survives_warm_reset_but_not_power_on() {
+    use crate::ResetKind;
+
+    let mut bus = SystemBus::new();
+    bus.add_peripheral(
+        "rtc",
+        0x4000_2800,
+        0x400,
+        None,
+        Box::new(crate::peripherals::rtc::Rtc::new()),
+    );
+    let bkp0r = 0x4000_2850;
+    let cr = 0x4000_2808;
+    bus.write_u32(bkp0r, 0xDEAD_BEEF).unwrap();
+    bus.write_u32(cr, 0x0000_0040).unwrap();
+
+    for kind in [ResetKind::Warm, ResetKind::Pin] {
+        bus.reset_peripherals(kind);
+        assert_eq!(bus.read_u32(bkp0r).unwrap(), 0xDEAD_BEEF, "{kind:?}");
+        assert_eq!(bus.read_u32(cr).unwrap(), 0x40, "{kind:?}");
+    }
+
+    bus.reset_peripherals(ResetKind::PowerOn);
+    assert_eq!(bus.read_u32(bkp0r).unwrap(), 0);
+    assert_eq!(bus.read_u32(cr).unwrap(), 0);
+}
+
+#[test]
+fn test_write_bytes_matches_byte_writes_with_fewer_dispatches() {
+    use super::accessors::BYTE_WRITE_DISPATCHES;
+
+    let image: Vec<u8> = (0..=255u8).collect();
+    let dispatches = |f: &mut dyn FnMut()| {
+        BYTE_WRITE_DISPATCHES.with(|n| n.set(0));
+        f();
+        BYTE_WRITE_DISPATCHES.with(|n| n.get())
+    };
+
+    let mut bulk = SystemBus::new();
+    let mut bytewise = SystemBus::new();
+    for addr in [0x2000_0100, 0x0000_0400] {
+        let bulk_calls = dispatches(&mut || bulk.write_bytes(addr, &image).unwrap());
+        let byte_calls = dispatches(&mut || {
+            for (i, &b) in image.iter().enumerate() {
+                bytewise.write_u8(addr + i as u64, b).unwrap();
+            }
+        });
+        assert_eq!(bulk_calls, 0);
+        assert_eq!(byte_calls, image.len() as u64);
+    }
+    assert_eq!(bulk.ram.data, bytewise.ram.data);
+    assert_eq!(bulk.flash.data, bytewise.flash.data);
+
+    // A range that runs off the end of RAM falls back to byte stores and
+    // reports the first unmapped address.
+    let tail = 0x2000_0000 + bulk.ram.data.len() as u64 - 2;
+    assert!(matches!(
+        bulk.write_bytes(tail, &[1, 2, 3, 4]),
+        Err(crate::SimulationError::MemoryViolation(a)) if a == tail + 2
+    ));
+    assert_eq!(bulk.read_u16(tail).unwrap(), 0x0201);
+}
+
+#[test]
+fn test_system_bus_memory_read_observer() {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[derive(Debug)]
+    struct ReadObserver {
+        reads: Arc<Mutex<Vec<(u64, u8, u32)>>>,
+    }
+
+    impl crate::SimulationObserver for ReadObserver {
+        fn on_memory_read(&self, addr: u64, width: u8, value: u32) {
+            self.reads.lock().unwrap().push((addr, width, value));
+        }
+    }
+
+    let reads = Arc::new(Mutex::new(Vec::new()));
+    let mut bus = SystemBus::new();
+    bus.observers.push(Arc::new(ReadObserver {
+        reads: reads.clone(),
+    }));
+
+    // UART status register: the hook sees the value the peripheral returned.
+    let sr = bus.read_u8(0x4000C000).unwrap();
+    assert_eq!(*reads.lock().unwrap(), [(0x4000C000, 1, sr as u32)]);
+
+    // A word read is reported once, with its width.
+    bus.write_u32(0x4001_080C, 0x0000_00A5).unwrap();
+    reads.lock().unwrap().clear();
+    assert_eq!(bus.read_u32(0x4001_080C).unwrap(), 0xA5);
+    assert_eq!(*reads.lock().unwrap(), [(0x4001_080C, 4, 0xA5)]);
+}
+
+#[test]
+fn test_system_bus_memory_store_observer_reports_atomic_alias_as_one_store() {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct AccessObserver {
+        accesses: Mutex<Vec<(char, u64, u8, u32)>>,
+    }
+
+    impl crate::SimulationObserver for AccessObserver {
+        fn on_memory_read(&self, addr: u64, width: u8, value: u32) {
+            self.accesses.lock().unwrap().push(('R', addr, width, value));
+        }
+        fn on_memory_store(&self, addr: u64, width: u8, value: u32) {
+            self.accesses.lock().unwrap().push(('W', addr, width, value));
+        }
+    }
+
+    let observer = Arc::new(AccessObserver::default());
+    let mut bus = SystemBus::new();
+    bus.atomic_register_aliases = true;
+    bus.observers.push(observer.clone());
+
+    // GPIOA ODR, then its SET alias: the read half of the RMW is internal.
+    bus.write_u32(0x4001_080C, 0x01).unwrap();
+    bus.write_u32(0x4001_080C + 0x2000, 0x20).unwrap();
+    bus.write_u16(0x2000_0000, 0xBEEF).unwrap();
+    assert_eq!(
+        *observer.accesses.lock().unwrap(),
+        [
+            ('W', 0x4001_080C, 4, 0x01),
+            ('W', 0x4001_080C, 4, 0x21),
+            ('W', 0x2000_0000, 2, 0xBEEF),
+        ]
+    );
 }
 
 #[test]
@@ -2420,6 +3005,7 @@ fn test_flash_boot_alias_read_and_write() {
         flash: LinearMemory::new(256, 0x0800_0000),
         ram: LinearMemory::new(256, 0x2000_0000),
         extra_mem: Vec::new(),
+        sparse_mem: Vec::new(),
         peripherals: Vec::new(),
         nvic: None,
         observers: Vec::new(),
@@ -2430,15 +3016,19 @@ fn test_flash_boot_alias_read_and_write() {
         rcc_idx: None,
         clock_gating_bypass: false,
         fault_unclocked: std::collections::HashMap::new(),
+        unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+        unclocked_warned: Default::default(),
         flash_thunks: std::collections::HashMap::new(),
         peripheral_ranges: Vec::new(),
         legacy_tick_indices: Vec::new(),
         bus_tick_indices: Vec::new(),
         scheduler_driver_indices: Vec::new(),
+        irq_ack_indices: Vec::new(),
         matrix_source_scratch: Vec::new(),
         peripheral_hint: Cell::new(None),
         last_route: Cell::new(None),
         last_gap: Cell::new(None),
+        alignment_fault: Cell::new(None),
         last_gpio_in: [0; 2],
         current_cycle: 0,
         cycle_clock: crate::CycleClock::default(),
@@ -2447,6 +3037,7 @@ fn test_flash_boot_alias_read_and_write() {
         side_effecting_mmio: std::cell::Cell::new(0),
         legacy_walk_disabled: false,
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
         hcsr04: Vec::new(),
         gpio_devices: Vec::new(),
@@ -2459,6 +3050,7 @@ fn test_flash_boot_alias_read_and_write() {
         hx711: Vec::new(),
         seven_segment: Vec::new(),
         analog_inputs: Vec::new(),
+        analog_signals: Vec::new(),
         can_diagnostic_testers: Vec::new(),
         can_uds_testers: Vec::new(),
         can_log_players: Vec::new(),
@@ -2506,6 +3098,7 @@ fn h5_flash_bus(gate: bool) -> SystemBus {
         flash,
         ram: LinearMemory::new(256, 0x2000_0000),
         extra_mem: Vec::new(),
+        sparse_mem: Vec::new(),
         peripherals: vec![PeripheralEntry {
             name: "flash".to_string(),
             base: 0x4002_2000,
@@ -2529,15 +3122,19 @@ fn h5_flash_bus(gate: bool) -> SystemBus {
         rcc_idx: None,
         clock_gating_bypass: false,
         fault_unclocked: std::collections::HashMap::new(),
+        unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+        unclocked_warned: Default::default(),
         flash_thunks: std::collections::HashMap::new(),
         peripheral_ranges: Vec::new(),
         legacy_tick_indices: Vec::new(),
         bus_tick_indices: Vec::new(),
         scheduler_driver_indices: Vec::new(),
+        irq_ack_indices: Vec::new(),
         matrix_source_scratch: Vec::new(),
         peripheral_hint: Cell::new(None),
         last_route: Cell::new(None),
         last_gap: Cell::new(None),
+        alignment_fault: Cell::new(None),
         last_gpio_in: [0; 2],
         current_cycle: 0,
         cycle_clock: crate::CycleClock::default(),
@@ -2546,6 +3143,7 @@ fn h5_flash_bus(gate: bool) -> SystemBus {
         side_effecting_mmio: std::cell::Cell::new(0),
         legacy_walk_disabled: false,
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
         hcsr04: Vec::new(),
         gpio_devices: Vec::new(),
@@ -2558,6 +3156,7 @@ fn h5_flash_bus(gate: bool) -> SystemBus {
         hx711: Vec::new(),
         seven_segment: Vec::new(),
         analog_inputs: Vec::new(),
+        analog_signals: Vec::new(),
         can_diagnostic_testers: Vec::new(),
         can_uds_testers: Vec::new(),
         can_log_players: Vec::new(),
@@ -2733,6 +3332,7 @@ impl crate::Cpu for PcCpu {
             pending_exceptions: 0,
             pending_exceptions_hi: Vec::new(),
             vtor: 0,
+            ..Default::default()
         })
     }
     fn apply_snapshot(&mut self, _snapshot: &crate::snapshot::CpuSnapshot) {}
@@ -2756,6 +3356,7 @@ fn h5_rww_bus(gate: bool) -> SystemBus {
         flash,
         ram: LinearMemory::new(0x1000, 0x2000_0000),
         extra_mem: Vec::new(),
+        sparse_mem: Vec::new(),
         peripherals: vec![PeripheralEntry {
             name: "flash".to_string(),
             base: 0x4002_2000,
@@ -2779,15 +3380,19 @@ fn h5_rww_bus(gate: bool) -> SystemBus {
         rcc_idx: None,
         clock_gating_bypass: false,
         fault_unclocked: std::collections::HashMap::new(),
+        unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+        unclocked_warned: Default::default(),
         flash_thunks: std::collections::HashMap::new(),
         peripheral_ranges: Vec::new(),
         legacy_tick_indices: Vec::new(),
         bus_tick_indices: Vec::new(),
         scheduler_driver_indices: Vec::new(),
+        irq_ack_indices: Vec::new(),
         matrix_source_scratch: Vec::new(),
         peripheral_hint: Cell::new(None),
         last_route: Cell::new(None),
         last_gap: Cell::new(None),
+        alignment_fault: Cell::new(None),
         last_gpio_in: [0; 2],
         current_cycle: 0,
         cycle_clock: crate::CycleClock::default(),
@@ -2796,6 +3401,7 @@ fn h5_rww_bus(gate: bool) -> SystemBus {
         side_effecting_mmio: std::cell::Cell::new(0),
         legacy_walk_disabled: false,
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
         hcsr04: Vec::new(),
         gpio_devices: Vec::new(),
@@ -2808,6 +3414,7 @@ fn h5_rww_bus(gate: bool) -> SystemBus {
         hx711: Vec::new(),
         seven_segment: Vec::new(),
         analog_inputs: Vec::new(),
+        analog_signals: Vec::new(),
         can_diagnostic_testers: Vec::new(),
         can_uds_testers: Vec::new(),
         can_log_players: Vec::new(),
@@ -2998,6 +3605,7 @@ fn test_peripheral_range_index_lookup() {
         flash: LinearMemory::new(256, 0x0800_0000),
         ram: LinearMemory::new(256, 0x2000_0000),
         extra_mem: Vec::new(),
+        sparse_mem: Vec::new(),
         peripherals: vec![
             PeripheralEntry {
                 name: "high".to_string(),
@@ -3027,15 +3635,19 @@ fn test_peripheral_range_index_lookup() {
         rcc_idx: None,
         clock_gating_bypass: false,
         fault_unclocked: std::collections::HashMap::new(),
+        unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+        unclocked_warned: Default::default(),
         flash_thunks: std::collections::HashMap::new(),
         peripheral_ranges: Vec::new(),
         legacy_tick_indices: Vec::new(),
         bus_tick_indices: Vec::new(),
         scheduler_driver_indices: Vec::new(),
+        irq_ack_indices: Vec::new(),
         matrix_source_scratch: Vec::new(),
         peripheral_hint: Cell::new(None),
         last_route: Cell::new(None),
         last_gap: Cell::new(None),
+        alignment_fault: Cell::new(None),
         last_gpio_in: [0; 2],
         current_cycle: 0,
         cycle_clock: crate::CycleClock::default(),
@@ -3044,6 +3656,7 @@ fn test_peripheral_range_index_lookup() {
         side_effecting_mmio: std::cell::Cell::new(0),
         legacy_walk_disabled: false,
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
         hcsr04: Vec::new(),
         gpio_devices: Vec::new(),
@@ -3056,6 +3669,7 @@ fn test_peripheral_range_index_lookup() {
         hx711: Vec::new(),
         seven_segment: Vec::new(),
         analog_inputs: Vec::new(),
+        analog_signals: Vec::new(),
         can_diagnostic_testers: Vec::new(),
         can_uds_testers: Vec::new(),
         can_log_players: Vec::new(),
@@ -3097,7 +3711,6 @@ fn test_execute_dma_copy_request() {
     let req = crate::DmaRequest {
         src_addr: 0x2000_0010,
         addr: 0x2000_0020,
-        val: 0,
         direction: crate::DmaDirection::Copy,
         transform: None,
     };
@@ -3112,6 +3725,7 @@ fn test_dma_tick_executes_copy_and_raises_irq() {
         flash: LinearMemory::new(256, 0x0800_0000),
         ram: LinearMemory::new(256, 0x2000_0000),
         extra_mem: Vec::new(),
+        sparse_mem: Vec::new(),
         peripherals: vec![PeripheralEntry {
             name: "dma1".to_string(),
             base: 0x4002_0000,
@@ -3130,15 +3744,19 @@ fn test_dma_tick_executes_copy_and_raises_irq() {
         rcc_idx: None,
         clock_gating_bypass: false,
         fault_unclocked: std::collections::HashMap::new(),
+        unclocked_access_policy: UnclockedAccessPolicy::Ignore,
+        unclocked_warned: Default::default(),
         flash_thunks: std::collections::HashMap::new(),
         peripheral_ranges: Vec::new(),
         legacy_tick_indices: Vec::new(),
         bus_tick_indices: Vec::new(),
         scheduler_driver_indices: Vec::new(),
+        irq_ack_indices: Vec::new(),
         matrix_source_scratch: Vec::new(),
         peripheral_hint: Cell::new(None),
         last_route: Cell::new(None),
         last_gap: Cell::new(None),
+        alignment_fault: Cell::new(None),
         last_gpio_in: [0; 2],
         current_cycle: 0,
         cycle_clock: crate::CycleClock::default(),
@@ -3147,6 +3765,7 @@ fn test_dma_tick_executes_copy_and_raises_irq() {
         side_effecting_mmio: std::cell::Cell::new(0),
         legacy_walk_disabled: false,
         reset_vector_offset: 0,
+        vtor: None,
         atomic_register_aliases: false,
         hcsr04: Vec::new(),
         gpio_devices: Vec::new(),
@@ -3159,6 +3778,7 @@ fn test_dma_tick_executes_copy_and_raises_irq() {
         hx711: Vec::new(),
         seven_segment: Vec::new(),
         analog_inputs: Vec::new(),
+        analog_signals: Vec::new(),
         can_diagnostic_testers: Vec::new(),
         can_uds_testers: Vec::new(),
         can_log_players: Vec::new(),
@@ -3214,6 +3834,187 @@ fn test_dma_tick_executes_copy_and_raises_irq() {
     assert!(interrupts.contains(&16), "TCIE should pend NVIC IRQ 16");
 }
 
+/// Word-wide DMA1 mem-to-mem: each element moves all four bytes, CNDTR counts
+/// down one per tick, and completion latches TCIF1/GIF1 and pends the channel
+/// IRQ. A memory -> peripheral element (UART TDR style, RAM standing in for
+/// the data register) moves the memory byte as well.
+#[test]
+fn dma1_word_mem2mem_copies_every_byte_and_sets_tcif() {
+    use crate::peripherals::dma::Dma1;
+    const DMA: u64 = 0x4002_0000;
+    const SRC: u64 = 0x2000_0100;
+    const DST: u64 = 0x2000_0200;
+    const TDR: u64 = 0x2000_0300;
+    let mut bus = SystemBus::new();
+    let nvic = std::sync::Arc::new(NvicState::default());
+    bus.nvic = Some(nvic.clone());
+    bus.add_peripheral("dma1", DMA, 0x400, Some(11), Box::new(Dma1::new()));
+    let idx = bus.find_peripheral_index_by_name("dma1").unwrap();
+    if let Some(dma) = bus.peripherals[idx]
+        .dev
+        .as_any_mut()
+        .and_then(|a| a.downcast_mut::<Dma1>())
+    {
+        dma.force_legacy_walk();
+    }
+    bus.refresh_legacy_tick_index(idx);
+    bus.recompute_walk_deletable();
+    nvic.iser[0].store(u32::MAX, std::sync::atomic::Ordering::SeqCst);
+
+    let words = [0x1122_3344u32, 0x5566_7788, 0x99AA_BBCC];
+    for (i, w) in words.iter().enumerate() {
+        bus.write_u32(SRC + 4 * i as u64, *w).unwrap();
+    }
+    bus.write_u32(DMA + 0x14, SRC as u32).unwrap(); // CMAR1 (source)
+    bus.write_u32(DMA + 0x10, DST as u32).unwrap(); // CPAR1 (destination)
+    bus.write_u32(DMA + 0x0C, words.len() as u32).unwrap(); // CNDTR1
+
+    // EN | TCIE | DIR | PINC | MINC | PSIZE=32 | MSIZE=32 | MEM2MEM
+    let ccr = (1 << 0) | (1 << 1) | (1 << 4) | (1 << 6) | (1 << 7) | (2 << 8) | (2 << 10);
+    bus.write_u32(DMA + 0x08, ccr | (1 << 14)).unwrap();
+
+    bus.tick_peripherals_fully();
+    assert_eq!(bus.read_u32(DMA + 0x0C).unwrap(), 2, "one element per tick");
+    assert_eq!(bus.read_u32(DMA).unwrap() & 0b10, 0, "TCIF1 not yet set");
+    bus.tick_peripherals_fully();
+    bus.tick_peripherals_fully();
+
+    for (i, w) in words.iter().enumerate() {
+        assert_eq!(bus.read_u32(DST + 4 * i as u64).unwrap(), *w, "word {i}");
+    }
+    assert_eq!(bus.read_u32(DMA + 0x0C).unwrap(), 0);
+    assert_eq!(bus.read_u32(DMA).unwrap() & 0b11, 0b11, "TCIF1 | GIF1");
+    assert!(nvic_pending(&nvic, 11), "TCIE pends the channel IRQ");
+
+    // CH2 memory -> peripheral, byte wide: CCR2 = EN | DIR | MINC.
+    bus.write_u8(SRC, 0xA5).unwrap();
+    bus.write_u32(DMA + 0x28, SRC as u32).unwrap(); // CMAR2
+    bus.write_u32(DMA + 0x24, TDR as u32).unwrap(); // CPAR2
+    bus.write_u32(DMA + 0x20, 1).unwrap(); // CNDTR2
+    bus.write_u32(DMA + 0x1C, 0b1001_0001).unwrap(); // CCR2
+    bus.tick_peripherals_fully();
+    assert_eq!(bus.read_u8(TDR).unwrap(), 0xA5);
+    assert_ne!(bus.read_u32(DMA).unwrap() & (1 << 5), 0, "TCIF2");
+}
+
+/// `flash_writable: false` write-protects flash (and its boot alias) for every
+/// store width; the default keeps flash writable for self-programming firmware.
+#[test]
+fn flash_write_protection_follows_chip_descriptor() {
+    let manifest: SystemManifest = serde_yaml::from_str(
+        r#"
+name: "flash-wp"
+chip: "unused"
+external_devices: []
+board_io: []
+"#,
+    )
+    .unwrap();
+    let chip_yaml = |extra: &str| {
+        format!(
+            r#"
+name: "flash-wp-test"
+arch: "arm"
+flash:
+  base: 0x08000000
+  size: "64KB"
+ram:
+  base: 0x20000000
+  size: "20KB"
+{extra}
+peripherals: []
+"#
+        )
+    };
+
+    let chip: ChipDescriptor = serde_yaml::from_str(&chip_yaml("flash_writable: false")).unwrap();
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert!(matches!(
+        bus.write_u8(0x0800_0100, 0xAA),
+        Err(crate::SimulationError::MemoryViolation(0x0800_0100))
+    ));
+    assert!(bus.write_u16(0x0800_0100, 0xAAAA).is_err());
+    assert!(bus.write_u32(0x0800_0100, 0xAAAA_AAAA).is_err());
+    assert!(bus.write_u32(0x100, 0xAAAA_AAAA).is_err(), "boot alias too");
+    assert_eq!(bus.read_u32(0x0800_0100).unwrap(), 0);
+    bus.write_u32(0x2000_0000, 0x1234_5678).unwrap();
+
+    let chip: ChipDescriptor = serde_yaml::from_str(&chip_yaml("")).unwrap();
+    assert!(chip.flash_writable);
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    bus.write_u32(0x0800_0100, 0xAAAA_AAAA).unwrap();
+    assert_eq!(bus.read_u32(0x0800_0100).unwrap(), 0xAAAA_AAAA);
+}
+
+#[test]
+fn strict_alignment_defaults_from_core_and_rejects_unaligned_words() {
+    let manifest: SystemManifest = serde_yaml::from_str(
+        r#"
+name: "align"
+chip: "unused"
+external_devices: []
+board_io: []
+"#,
+    )
+    .unwrap();
+    let chip_yaml = |core: &str| {
+        format!(
+            r#"
+name: "align-test"
+arch: "arm"
+core: "{core}"
+flash:
+  base: 0x10000000
+  size: "64KB"
+ram:
+  base: 0x20000000
+  size: "16KB"
+peripherals: []
+"#
+        )
+    };
+
+    let chip: ChipDescriptor = serde_yaml::from_str(&chip_yaml("cortex-m0+")).unwrap();
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert!(bus.config.strict_alignment);
+    assert!(matches!(
+        bus.write_u32(0x2000_0001, 0xAABB_CCDD),
+        Err(crate::SimulationError::MemoryViolation(0x2000_0001))
+    ));
+    assert_eq!(bus.take_alignment_fault(), Some(0x2000_0001));
+    assert!(bus.read_u16(0x2000_0003).is_err());
+    bus.write_u8(0x2000_0001, 0x5A).unwrap();
+    bus.write_u16(0x2000_0002, 0x1234).unwrap();
+    assert_eq!(bus.read_u32(0x2000_0000).unwrap(), 0x1234_5A00);
+
+    let chip: ChipDescriptor = serde_yaml::from_str(&chip_yaml("cortex-m4")).unwrap();
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert!(!bus.config.strict_alignment);
+    bus.write_u32(0x2000_0001, 0xAABB_CCDD).unwrap();
+    assert_eq!(bus.read_u32(0x2000_0001).unwrap(), 0xAABB_CCDD);
+    assert_eq!(bus.take_alignment_fault(), None);
+}
+
+#[test]
+fn big_endian_bus_round_trips_words_in_memory_order() {
+    let mut bus = SystemBus::new();
+    bus.config.endianness = crate::Endianness::Big;
+
+    bus.write_u32(0x2000_0000, 0x1122_3344).unwrap();
+    assert_eq!(bus.read_u8(0x2000_0000).unwrap(), 0x11);
+    assert_eq!(bus.read_u8(0x2000_0003).unwrap(), 0x44);
+    assert_eq!(bus.read_u32(0x2000_0000).unwrap(), 0x1122_3344);
+    assert_eq!(bus.read_u16(0x2000_0002).unwrap(), 0x3344);
+
+    bus.write_u16(0x2000_0010, 0xABCD).unwrap();
+    assert_eq!(bus.read_u8(0x2000_0010).unwrap(), 0xAB);
+    assert_eq!(bus.read_u16(0x2000_0010).unwrap(), 0xABCD);
+
+    // The same bytes read back little-endian once the order flips.
+    bus.config.endianness = crate::Endianness::Little;
+    assert_eq!(bus.read_u32(0x2000_0000).unwrap(), 0x4433_2211);
+}
+
 /// RCC clock-gating (silicon fidelity): a peripheral with a declared
 /// `clock:` gate is inert until its RCC enable bit is set — writes are
 /// dropped and reads return 0 — and behaves normally once clocked. The
@@ -3392,6 +4193,75 @@ board_io: []
     );
 }
 
+/// The unclocked-access policy: `Fault` turns a GPIO access before its RCC
+/// clock is enabled into a bus error instead of a silent no-op, `Warn` keeps
+/// the silent suppression, and enabling the clock clears both.
+#[test]
+fn unclocked_gpio_access_follows_configured_policy() {
+    let chip: ChipDescriptor = serde_yaml::from_str(
+        r#"
+name: "l4-unclocked-policy-test"
+arch: "arm"
+core: "cortex-m4"
+flash:
+  base: 0x08000000
+  size: "1MB"
+ram:
+  base: 0x20000000
+  size: "96KB"
+peripherals:
+  - id: "rcc"
+    type: "rcc"
+    base_address: 0x40021000
+    size: "1KB"
+    config:
+      profile: "stm32l4"
+  - id: "gpioa"
+    type: "gpio"
+    base_address: 0x48000000
+    size: "1KB"
+    config:
+      profile: "stm32v2"
+    clock: { reg: "ahb2enr", bit: 0 }
+"#,
+    )
+    .unwrap();
+    let manifest: SystemManifest = serde_yaml::from_str(
+        r#"
+name: "unclocked-policy"
+chip: "unused"
+external_devices: []
+board_io: []
+"#,
+    )
+    .unwrap();
+    let mut bus = SystemBus::from_config(&chip, &manifest).unwrap();
+    assert_eq!(bus.unclocked_access_policy(), UnclockedAccessPolicy::Ignore);
+
+    const GPIOA_MODER: u64 = 0x4800_0000;
+    bus.set_unclocked_access_policy(UnclockedAccessPolicy::Fault);
+    assert!(matches!(
+        bus.write_u32(GPIOA_MODER, 0x55),
+        Err(crate::SimulationError::MemoryViolation(GPIOA_MODER))
+    ));
+    assert!(matches!(
+        bus.read_u32(GPIOA_MODER),
+        Err(crate::SimulationError::MemoryViolation(GPIOA_MODER))
+    ));
+
+    // Warn suppresses exactly like the default policy.
+    bus.set_unclocked_access_policy(UnclockedAccessPolicy::Warn);
+    bus.write_u32(GPIOA_MODER, 0x55).unwrap();
+    assert_eq!(bus.read_u32(GPIOA_MODER).unwrap(), 0);
+
+    // Once clocked, the GPIO is reachable under the Fault policy.
+    bus.set_unclocked_access_policy(UnclockedAccessPolicy::Fault);
+    const RCC_AHB2ENR: u64 = 0x4002_104C;
+    bus.write_u32(RCC_AHB2ENR, 1 << 0).unwrap();
+    bus.write_u32(GPIOA_MODER, 0x55).unwrap();
+    assert_eq!(bus.read_u32(GPIOA_MODER).unwrap() & 0x55, 0x55);
+}
+
 // -----------------------------------------------------------------------
 // Script-driven FSM tests
 // -----------------------------------------------------------------------
@@ -3997,3 +4867,190 @@ fn iolink_master_cache_tracks_every_mutation_path() {
     assert_eq!(bus.has_iolink_master(), bus.scan_iolink_master());
     assert!(bus.has_iolink_master());
 }
+
+// -----------------------------------------------------------------------
+// EXTI → NVIC routing
+// -----------------------------------------------------------------------
+
+const EXTI_BASE: u64 = 0x4001_0400;
+
+/// F1-style bus: NVIC + GPIOA/AFIO/EXTI, with the EXTI pinned to the legacy
+/// walk so `tick_peripherals_fully` delivers its held level in every build.
+fn exti_bus() -> (SystemBus, std::sync::Arc<NvicState>) {
+    use crate::peripherals::{afio::Afio, exti::Exti, gpio::GpioPort};
+    let mut bus = SystemBus::new();
+    let nvic = std::sync::Arc::new(NvicState::default());
+    bus.nvic = Some(nvic.clone());
+    bus.add_peripheral("gpioa", 0x4001_0800, 0x400, None, Box::new(GpioPort::new()));
+    bus.add_peripheral("afio", 0x4001_0000, 0x400, None, Box::new(Afio::new()));
+    bus.add_peripheral("exti", EXTI_BASE, 0x400, None, Box::new(Exti::new()));
+    let idx = bus.find_peripheral_index_by_name("exti").unwrap();
+    if let Some(exti) = bus.peripherals[idx]
+        .dev
+        .as_any_mut()
+        .and_then(|a| a.downcast_mut::<Exti>())
+    {
+        exti.force_legacy_walk();
+    }
+    bus.refresh_legacy_tick_index(idx);
+    bus.recompute_walk_deletable();
+    // NVIC_ISER0: enable every bank-0 IRQ so pends surface on the CPU side.
+    nvic.iser[0].store(u32::MAX, std::sync::atomic::Ordering::SeqCst);
+    (bus, nvic)
+}
+
+fn nvic_pending(nvic: &NvicState, irq: u32) -> bool {
+    nvic.ispr[(irq / 32) as usize].load(std::sync::atomic::Ordering::SeqCst) & (1 << (irq % 32))
+        != 0
+}
+
+#[test]
+fn exti_swier_pends_mapped_nvic_irq() {
+    let (mut bus, nvic) = exti_bus();
+    bus.write_u32(EXTI_BASE, 1 << 3).unwrap(); // IMR line 3
+    bus.write_u32(EXTI_BASE + 0x10, 1 << 3).unwrap(); // SWIER line 3 → PR
+    assert_eq!(bus.read_u32(EXTI_BASE + 0x14).unwrap(), 1 << 3);
+    bus.tick_peripherals_fully();
+    assert!(nvic_pending(&nvic, 9), "EXTI3 must pend NVIC IRQ 9");
+    assert!(!nvic_pending(&nvic, 6), "EXTI0 must stay idle");
+}
+
+#[test]
+fn exti_gpio_edge_respects_trigger_selection_and_port_mux() {
+    let (mut bus, nvic) = exti_bus();
+    bus.write_u32(EXTI_BASE, 1 << 0).unwrap(); // IMR line 0
+    bus.write_u32(EXTI_BASE + 0x0C, 1 << 0).unwrap(); // FTSR line 0 only
+
+    // Rising edge on PA0 is not selected: nothing pends.
+    bus.set_board_input("gpioa", 0, true).unwrap();
+    assert_eq!(bus.read_u32(EXTI_BASE + 0x14).unwrap(), 0);
+
+    // AFIO_EXTICR1 routes line 0 to port B: a PA0 edge is ignored.
+    bus.write_u32(0x4001_0008, 0x1).unwrap();
+    bus.set_board_input("gpioa", 0, false).unwrap();
+    assert_eq!(bus.read_u32(EXTI_BASE + 0x14).unwrap(), 0);
+
+    // Back on port A, the falling edge pends line 0 → NVIC IRQ 6.
+    bus.write_u32(0x4001_0008, 0x0).unwrap();
+    bus.set_board_input("gpioa", 0, true).unwrap();
+    bus.set_board_input("gpioa", 0, false).unwrap();
+    assert_eq!(bus.read_u32(EXTI_BASE + 0x14).unwrap(), 1 << 0);
+    bus.tick_peripherals_fully();
+    assert!(nvic_pending(&nvic, 6), "EXTI0 must pend NVIC IRQ 6");
+}
+
+fn stub_entry(name: &str, base: u64, value: u32) -> PeripheralEntry {
+    PeripheralEntry {
+        name: name.to_string(),
+        base,
+        size: 0x100,
+        irq: None,
+        dev: Box::new(crate::peripherals::stub::StubPeripheral::new(value)),
+        ticks_remaining: 0,
+        clock_gate: None,
+    }
+}
+
+#[test]
+fn hot_attached_peripheral_serves_reads_until_detached() {
+    const SENSOR_BASE: u64 = 0x5800_0000;
+    let mut bus = SystemBus::new();
+    bus.write_u32(0x2000_0000, 0x1234_5678).unwrap();
+    // Prime the negative route cache: the window is a gap before the attach.
+    assert!(bus.read_u32(SENSOR_BASE).is_err());
+
+    bus.attach_peripheral(stub_entry("sensor", SENSOR_BASE, 0xC0FF_EE00))
+        .unwrap();
+    assert_eq!(bus.read_u32(SENSOR_BASE + 4).unwrap(), 0xC0FF_EE00);
+    assert_eq!(bus.read_u32(0x2000_0000).unwrap(), 0x1234_5678);
+
+    assert!(
+        bus.attach_peripheral(stub_entry("sensor", 0x5900_0000, 0))
+            .is_err(),
+        "duplicate names must be rejected"
+    );
+    assert!(
+        bus.attach_peripheral(stub_entry("shadow", SENSOR_BASE + 0x80, 0))
+            .is_err(),
+        "overlapping windows must be rejected"
+    );
+
+    let entry = bus.detach_peripheral("sensor").unwrap();
+    assert_eq!(entry.base, SENSOR_BASE);
+    assert!(bus.read_u32(SENSOR_BASE + 4).is_err());
+    assert!(bus.detach_peripheral("sensor").is_err());
+}
+
+/// Reference router: linear scan, greatest containing base wins and equal
+/// bases resolve to the last-registered entry.
+fn scan_window(bus: &SystemBus, addr: u64) -> Option<(u64, u64)> {
+    bus.peripherals
+        .iter()
+        .filter(|p| SystemBus::is_peripheral_addr(p, addr))
+        .max_by_key(|p| p.base)
+        .map(|p| (p.base, p.size))
+}
+
+fn assert_index_matches_scan(bus: &SystemBus, stage: &str) {
+    for p in &bus.peripherals {
+        for addr in [
+            p.base.saturating_sub(1),
+            p.base,
+            p.base + p.size / 2,
+            p.base + p.size - 1,
+            p.base + p.size,
+        ] {
+            assert_eq!(
+                bus.resolve_window(addr),
+                scan_window(bus, addr),
+                "{stage}: indexed lookup diverged from the scan at {addr:#x}"
+            );
+        }
+    }
+}
+
+#[test]
+fn indexed_lookup_matches_linear_scan_across_refreshes() {
+    let mut bus = SystemBus::new();
+    // Registration order deliberately differs from address order.
+    for i in (0..48u64).rev() {
+        bus.add_peripheral(
+            &format!("p{i}"),
+            0x5000_0000 + i * 0x800,
+            0x400,
+            None,
+            Box::new(TagPeripheral(i as u8)),
+        );
+    }
+    bus.add_peripheral(
+        "catch_all",
+        0x5000_0000,
+        0x1_0000,
+        None,
+        Box::new(TagPeripheral(0xFF)),
+    );
+    assert_index_matches_scan(&bus, "add_peripheral");
+
+    // Hand-edited vector: stale until `refresh_peripheral_index`.
+    bus.peripherals.retain(|p| p.name != "p7");
+    bus.refresh_peripheral_index();
+    assert_index_matches_scan(&bus, "refresh_peripheral_index");
+    assert_eq!(bus.read_u8(0x5000_0000 + 7 * 0x800).unwrap(), 0xFF);
+
+    bus.detach_peripheral("catch_all").unwrap();
+    assert_index_matches_scan(&bus, "detach_peripheral");
+    assert!(bus.read_u8(0x5000_0000 + 7 * 0x800).is_err());
+
+    bus.attach_peripheral(PeripheralEntry {
+        name: "p7".to_string(),
+        base: 0x5000_0000 + 7 * 0x800,
+        size: 0x400,
+        irq: None,
+        dev: Box::new(TagPeripheral(0x77)),
+        ticks_remaining: 0,
+        clock_gate: None,
+    })
+    .unwrap();
+    assert_index_matches_scan(&bus, "attach_peripheral");
+    assert_eq!(bus.read_u8(0x5000_0000 + 7 * 0x800).unwrap(), 0x77);
+}
@@ -43,6 +43,18 @@ fn pend_nvic(
     }
 }
 
+/// Inverse of [`pend_nvic`]: drop `irq`'s pending bit from NVIC.ISPR.
+/// Without an NVIC there is nothing latched to clear.
+fn unpend_nvic(nvic: &Option<Arc<crate::peripherals::nvic::NvicState>>, irq: u32) {
+    if let Some(nvic) = nvic {
+        let idx = (irq / 32) as usize;
+        let bit = irq % 32;
+        if idx < 8 {
+            nvic.ispr[idx].fetch_and(!(1 << bit), std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+}
+
 impl SystemBus {
     /// Config-time derivation of walk-deletability (issue: browser-perf chain).
     ///
@@ -233,6 +245,7 @@ impl SystemBus {
         if self.peripheral_ranges.len() != self.peripherals.len() {
             self.rebuild_peripheral_ranges();
         }
+        self.drive_analog_signals();
 
         // ── Pre-tick bus-aware pass ─────────────────────────────────────────
         // Some peripherals (currently just RADIO) need to read/write the bus
@@ -381,6 +394,12 @@ impl SystemBus {
                 explicit_source_ids.extend(irqs);
             }
 
+            if let Some(irqs) = res.cleared_irqs {
+                for irq in irqs {
+                    unpend_nvic(&self.nvic, irq);
+                }
+            }
+
             // System exceptions (SysTick = 15, etc) bypass NVIC and are
             // pushed directly so the CPU sees them on next dispatch.
             if let Some(exc) = res.system_exception {
@@ -921,6 +940,17 @@ impl SystemBus {
         self.recompute_esp32s3_irq_lines();
     }
 
+    /// Move one DMA element: read `src_addr`, write `addr`, whatever the
+    /// [`crate::DmaDirection`]. `transform` selects the source/destination
+    /// widths; without one the element is a single byte.
+    pub(crate) fn dma_move_element(&mut self, req: &crate::DmaRequest) -> crate::SimResult<()> {
+        if let Some(t) = req.transform {
+            return self.dma_copy_unit(req.src_addr, req.addr, t);
+        }
+        let val = self.read_u8(req.src_addr)?;
+        self.write_u8(req.addr, val)
+    }
+
     /// One DMA source-unit -> destination-unit copy with the STM32H5 GPDMA
     /// data-handling semantics (RM0481 §15): width conversion via PAM
     /// (zero-pad / sign-extend / left- or right-truncate) and the SBX / DBX /
@@ -983,8 +1013,11 @@ impl SystemBus {
         Ok(())
     }
 
+    /// Append every enabled-and-pending NVIC interrupt as an exception number,
+    /// highest priority (lowest IPR value) first, ties by IRQ number.
     fn collect_enabled_nvic_interrupts(&self, interrupts: &mut Vec<u32>) {
         if let Some(nvic) = &self.nvic {
+            let start = interrupts.len();
             for idx in 0..8 {
                 let mask =
                     nvic.iser[idx].load(Ordering::SeqCst) & nvic.ispr[idx].load(Ordering::SeqCst);
@@ -997,6 +1030,7 @@ impl SystemBus {
                     }
                 }
             }
+            interrupts[start..].sort_by_key(|&exc| (nvic.ipr_priority((exc - 16) as usize), exc));
         }
     }
 
@@ -1092,30 +1126,13 @@ impl SystemBus {
 
         // Phase 2: Execute DMA requests (this now has access to self.flash/ram via write_u8)
         for req in pending_dma {
-            match req.direction {
-                crate::DmaDirection::Read => {
-                    if let Ok(val) = self.read_u8(req.addr) {
-                        tracing::trace!("DMA Read: {:#x} -> {:#x}", req.addr, val);
-                    }
-                }
-                crate::DmaDirection::Write => {
-                    let _ = self.write_u8(req.addr, req.val);
-                    tracing::trace!("DMA Write: {:#x} <- {:#x}", req.addr, req.val);
-                }
-                crate::DmaDirection::Copy => {
-                    if let Some(t) = req.transform {
-                        let _ = self.dma_copy_unit(req.src_addr, req.addr, t);
-                    } else if let Ok(val) = self.read_u8(req.src_addr) {
-                        let _ = self.write_u8(req.addr, val);
-                        tracing::trace!(
-                            "DMA Copy: {:#x} -> {:#x} ({:#x})",
-                            req.src_addr,
-                            req.addr,
-                            val
-                        );
-                    }
-                }
-            }
+            tracing::trace!(
+                "DMA {:?}: {:#x} -> {:#x}",
+                req.direction,
+                req.src_addr,
+                req.addr
+            );
+            let _ = self.dma_move_element(&req);
         }
 
         // Phase 3: Scan NVIC
@@ -33,6 +33,31 @@ pub struct SimulationConfig {
     /// the `jit` feature.
     #[serde(default)]
     pub riscv_jit_enabled: bool,
+
+    /// Fault unaligned 16/32-bit bus accesses with `MemoryViolation` instead
+    /// of splitting them into byte accesses. `SystemBus::from_config` turns it
+    /// on for ARMv6-M cores (Cortex-M0/M0+/M1), which have no unaligned
+    /// support; other profiles keep the permissive byte-wise behavior.
+    /// Enforced by the bus, so it is read from `SystemBus::config`;
+    /// `Machine::with_config` turns it on there when the host config sets it.
+    #[serde(default)]
+    pub strict_alignment: bool,
+
+    /// Byte order the bus uses for 16/32-bit accesses. `Machine::load_firmware`
+    /// sets it from the ELF header; little-endian unless the image says otherwise.
+    #[serde(default)]
+    pub endianness: crate::Endianness,
+
+    /// Cortex-M profile the decoder enforces. `SystemBus::from_config` derives
+    /// it from the chip's core; ARMv6-M faults on Thumb-2 instructions outside
+    /// its subset. Read from `SystemBus::config`, like `strict_alignment`.
+    #[serde(default)]
+    pub arm_profile: crate::ArmProfile,
+
+    /// Per-instruction-class and peripheral-activity costs behind
+    /// `Cpu::get_energy_consumption` and `Machine::energy_consumption`.
+    #[serde(default)]
+    pub energy_model: crate::energy::EnergyModel,
 }
 
 impl Default for SimulationConfig {
@@ -44,6 +69,73 @@ impl Default for SimulationConfig {
             batch_mode_enabled: true,
             idle_fast_forward_enabled: false,
             riscv_jit_enabled: false,
+            strict_alignment: false,
+            endianness: crate::Endianness::Little,
+            arm_profile: crate::ArmProfile::V7M,
+            energy_model: crate::energy::EnergyModel::default(),
         }
     }
 }
+
+impl SimulationConfig {
+    /// Start a [`SimulationConfigBuilder`] from the defaults.
+    pub fn builder() -> SimulationConfigBuilder {
+        SimulationConfigBuilder::default()
+    }
+}
+
+/// Builds a [`SimulationConfig`] without spelling out every field, for hosts
+/// (Python, CLI) that only change a few knobs.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationConfigBuilder {
+    config: SimulationConfig,
+}
+
+impl SimulationConfigBuilder {
+    pub fn decode_cache_enabled(mut self, enabled: bool) -> Self {
+        self.config.decode_cache_enabled = enabled;
+        self
+    }
+
+    /// Clamped to at least 1, the exact per-instruction interval.
+    pub fn peripheral_tick_interval(mut self, interval: u32) -> Self {
+        self.config.peripheral_tick_interval = interval.max(1);
+        self
+    }
+
+    pub fn optimized_bus_access(mut self, enabled: bool) -> Self {
+        self.config.optimized_bus_access = enabled;
+        self
+    }
+
+    pub fn batch_mode_enabled(mut self, enabled: bool) -> Self {
+        self.config.batch_mode_enabled = enabled;
+        self
+    }
+
+    pub fn idle_fast_forward_enabled(mut self, enabled: bool) -> Self {
+        self.config.idle_fast_forward_enabled = enabled;
+        self
+    }
+
+    pub fn riscv_jit_enabled(mut self, enabled: bool) -> Self {
+        self.config.riscv_jit_enabled = enabled;
+        self
+    }
+
+    /// Fault unaligned accesses even on cores that allow them. `false` keeps
+    /// the chip-derived default; it cannot relax an ARMv6-M bus.
+    pub fn strict_alignment(mut self, enabled: bool) -> Self {
+        self.config.strict_alignment = enabled;
+        self
+    }
+
+    pub fn energy_model(mut self, model: crate::energy::EnergyModel) -> Self {
+        self.config.energy_model = model;
+        self
+    }
+
+    pub fn build(self) -> SimulationConfig {
+        self.config
+    }
+}
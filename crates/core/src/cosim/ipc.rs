@@ -0,0 +1,224 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Bus-access transport for [`CosimPeripheral`](super::CosimPeripheral): the
+//! peripheral's register reads and writes are forwarded to an external
+//! process, which answers with read values — so a device can be modelled in
+//! any language.
+//!
+//! The wire format is newline-delimited JSON, the same framing as
+//! [`ExternalProcessCosimAdapter`](super::ExternalProcessCosimAdapter), over
+//! any byte stream: a child's stdin/stdout or a Unix socket. Every request
+//! gets exactly one response object, and one carrying `"error"` fails the
+//! access.
+//!
+//! ```text
+//! -> {"op":"hello","version":1,"peripheral":"fpga","base":1342177280,"size":256}
+//! <- {"op":"hello","version":1}
+//! -> {"op":"read","offset":16,"width":4}
+//! <- {"value":3735928559}
+//! -> {"op":"write","offset":0,"width":1,"value":1}
+//! <- {"ok":true}
+//! ```
+//!
+//! A response that does not arrive within the link's timeout fails the
+//! access and poisons the link, since a late answer would otherwise be taken
+//! for the next request's.
+
+use crate::{SimResult, SimulationError};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Version exchanged in the `hello` handshake.
+pub const COSIM_PROTOCOL_VERSION: u64 = 1;
+
+/// How long a request waits for its response unless the link says otherwise.
+pub const DEFAULT_COSIM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One request/response channel to an external device model.
+pub struct CosimLink {
+    writer: Box<dyn Write + Send>,
+    responses: Receiver<std::io::Result<String>>,
+    timeout: Duration,
+    poisoned: bool,
+    child: Option<Child>,
+}
+
+impl std::fmt::Debug for CosimLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CosimLink")
+            .field("timeout", &self.timeout)
+            .field("poisoned", &self.poisoned)
+            .field("child", &self.child.as_ref().map(Child::id))
+            .finish_non_exhaustive()
+    }
+}
+
+impl CosimLink {
+    /// A link over an already-connected stream pair. Responses are read on a
+    /// helper thread so a silent endpoint cannot block past `timeout`.
+    pub fn new<R, W>(reader: R, writer: W, timeout: Duration) -> Self
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let (tx, responses) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            writer: Box::new(writer),
+            responses,
+            timeout,
+            poisoned: false,
+            child: None,
+        }
+    }
+
+    /// Spawn `program` and talk to it over its stdin/stdout.
+    pub fn spawn(program: &str, args: &[&str], timeout: Duration) -> SimResult<Self> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                SimulationError::Other(format!("failed to spawn co-sim process '{program}': {err}"))
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(SimulationError::Other(format!(
+                "failed to open stdio for co-sim process '{program}'"
+            )));
+        };
+        let mut link = Self::new(stdout, stdin, timeout);
+        link.child = Some(child);
+        Ok(link)
+    }
+
+    /// Connect to an endpoint listening on the Unix socket at `path`.
+    #[cfg(unix)]
+    pub fn connect_unix(path: &std::path::Path, timeout: Duration) -> SimResult<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(path).map_err(|err| {
+            SimulationError::Other(format!(
+                "failed to connect to co-sim socket {}: {err}",
+                path.display()
+            ))
+        })?;
+        let reader = stream.try_clone().map_err(|err| {
+            SimulationError::Other(format!("failed to clone co-sim socket: {err}"))
+        })?;
+        Ok(Self::new(reader, stream, timeout))
+    }
+
+    /// Exchange `hello` messages and check the endpoint speaks our version.
+    pub(crate) fn handshake(&mut self, peripheral: &str, base: u64, size: u64) -> SimResult<()> {
+        let reply = self.request(json!({
+            "op": "hello",
+            "version": COSIM_PROTOCOL_VERSION,
+            "peripheral": peripheral,
+            "base": base,
+            "size": size,
+        }))?;
+        match reply.get("version").and_then(Value::as_u64) {
+            Some(COSIM_PROTOCOL_VERSION) => Ok(()),
+            other => Err(SimulationError::Other(format!(
+                "co-sim endpoint for '{peripheral}' answered hello with version {other:?}, \
+                 expected {COSIM_PROTOCOL_VERSION}"
+            ))),
+        }
+    }
+
+    pub(crate) fn read(&mut self, offset: u64, width: u8) -> SimResult<u64> {
+        let reply = self.request(json!({ "op": "read", "offset": offset, "width": width }))?;
+        reply.get("value").and_then(Value::as_u64).ok_or_else(|| {
+            SimulationError::Other(format!(
+                "co-sim read response has no integer 'value': {reply}"
+            ))
+        })
+    }
+
+    pub(crate) fn write(&mut self, offset: u64, width: u8, value: u64) -> SimResult<()> {
+        self.request(json!({ "op": "write", "offset": offset, "width": width, "value": value }))
+            .map(drop)
+    }
+
+    fn request(&mut self, message: Value) -> SimResult<Value> {
+        if self.poisoned {
+            return Err(SimulationError::Other(
+                "co-sim link is unusable after an earlier timeout or I/O error".to_string(),
+            ));
+        }
+        let result = self.exchange(&message);
+        if result.is_err() {
+            self.poisoned = true;
+        }
+        let reply = result?;
+        if let Some(error) = reply.get("error") {
+            return Err(SimulationError::Other(format!(
+                "co-sim endpoint rejected {}: {error}",
+                message["op"]
+            )));
+        }
+        Ok(reply)
+    }
+
+    fn exchange(&mut self, message: &Value) -> SimResult<Value> {
+        let io_err = |err: std::io::Error| {
+            SimulationError::Other(format!("failed to send co-sim request: {err}"))
+        };
+        serde_json::to_writer(&mut self.writer, message).map_err(|err| {
+            SimulationError::Other(format!("failed to encode co-sim request: {err}"))
+        })?;
+        self.writer.write_all(b"\n").map_err(io_err)?;
+        self.writer.flush().map_err(io_err)?;
+
+        let line = match self.responses.recv_timeout(self.timeout) {
+            Ok(Ok(line)) => line,
+            Ok(Err(err)) => {
+                return Err(SimulationError::Other(format!(
+                    "failed to read co-sim response: {err}"
+                )))
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(SimulationError::Other(format!(
+                    "co-sim endpoint did not answer {} within {:?}",
+                    message["op"], self.timeout
+                )))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(SimulationError::Other(
+                    "co-sim endpoint closed the connection".to_string(),
+                ))
+            }
+        };
+        let reply: Value = serde_json::from_str(&line).map_err(|err| {
+            SimulationError::Other(format!("failed to decode co-sim response: {err}"))
+        })?;
+        if !reply.is_object() {
+            return Err(SimulationError::Other(
+                "co-sim response must be a JSON object".to_string(),
+            ));
+        }
+        Ok(reply)
+    }
+}
+
+impl Drop for CosimLink {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
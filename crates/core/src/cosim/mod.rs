@@ -5,10 +5,12 @@
 // See the LICENSE file in the project root for full license information.
 
 mod external_process;
+pub mod ipc;
 mod registry;
 pub mod shm;
 
 pub use external_process::ExternalProcessCosimAdapter;
+pub use ipc::{CosimLink, COSIM_PROTOCOL_VERSION, DEFAULT_COSIM_TIMEOUT};
 pub use registry::{
     build_cosim_adapter, CosimModelStep, CosimRoutedModelStep, CosimRunner, CosimRunnerModel,
 };
@@ -16,6 +18,7 @@ pub use registry::{
 use crate::{Peripheral, PeripheralTickResult, SimResult};
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::sync::Mutex;
 
 /// Scalar value exchanged at a co-simulation boundary.
 #[derive(Debug, Clone, PartialEq)]
@@ -70,23 +73,72 @@ impl CosimAdapter for StaticCosimAdapter {
     }
 }
 
-/// A peripheral that proxies its operations to an external process via IPC.
-/// This is used for high-performance co-simulation with RTL models (e.g. Verilator).
+/// A peripheral whose register accesses are forwarded to an external process
+/// over a [`CosimLink`] (see the [`ipc`] module for the wire format), for
+/// device models written outside LabWired, e.g. a Verilator RTL harness.
+///
+/// Register it like any other peripheral:
+///
+/// ```no_run
+/// # use labwired_core::bus::SystemBus;
+/// # use labwired_core::cosim::{CosimLink, CosimPeripheral, DEFAULT_COSIM_TIMEOUT};
+/// # fn main() -> labwired_core::SimResult<()> {
+/// # let mut bus = SystemBus::new();
+/// let link = CosimLink::spawn("./fpga_model", &[], DEFAULT_COSIM_TIMEOUT)?;
+/// let fpga = CosimPeripheral::connect("fpga", 0x5000_0000, 0x100, link)?;
+/// bus.add_peripheral("fpga", 0x5000_0000, 0x100, None, Box::new(fpga));
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct CosimPeripheral {
     pub name: String,
-    // Add IPC transport (e.g. SharedMemory)
+    link: Mutex<CosimLink>,
+}
+
+impl CosimPeripheral {
+    /// Handshake with the endpoint behind `link` for a peripheral mapped at
+    /// `base..base + size`.
+    pub fn connect(name: &str, base: u64, size: u64, mut link: CosimLink) -> SimResult<Self> {
+        link.handshake(name, base, size)?;
+        Ok(Self {
+            name: name.to_string(),
+            link: Mutex::new(link),
+        })
+    }
+
+    fn read_width(&self, offset: u64, width: u8) -> SimResult<u64> {
+        self.link.lock().unwrap().read(offset, width)
+    }
+
+    fn write_width(&mut self, offset: u64, width: u8, value: u64) -> SimResult<()> {
+        self.link.get_mut().unwrap().write(offset, width, value)
+    }
 }
 
 impl Peripheral for CosimPeripheral {
-    fn read(&self, _offset: u64) -> SimResult<u8> {
-        // TODO: Propose transaction over IPC and wait for result
-        Ok(0)
+    fn read(&self, offset: u64) -> SimResult<u8> {
+        self.read_width(offset, 1).map(|v| v as u8)
+    }
+
+    fn read_u16(&self, offset: u64) -> SimResult<u16> {
+        self.read_width(offset, 2).map(|v| v as u16)
+    }
+
+    fn read_u32(&self, offset: u64) -> SimResult<u32> {
+        self.read_width(offset, 4).map(|v| v as u32)
+    }
+
+    fn write(&mut self, offset: u64, value: u8) -> SimResult<()> {
+        self.write_width(offset, 1, value.into())
+    }
+
+    fn write_u16(&mut self, offset: u64, value: u16) -> SimResult<()> {
+        self.write_width(offset, 2, value.into())
     }
 
-    fn write(&mut self, _offset: u64, _value: u8) -> SimResult<()> {
-        // TODO: Propose transaction over IPC
-        Ok(())
+    fn write_u32(&mut self, offset: u64, value: u32) -> SimResult<()> {
+        self.write_width(offset, 4, value.into())
     }
 
     fn tick(&mut self) -> PeripheralTickResult {
@@ -6,6 +6,7 @@
 
 use crate::bus::SystemBus;
 use crate::decoder::arm::{decode_thumb_16, decode_thumb_32, Instruction};
+use crate::energy::InstructionClass;
 use crate::{Bus, Cpu, SimResult, SimulationConfig, SimulationObserver};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
@@ -95,11 +96,20 @@ pub struct CortexM {
     /// (D0..D15 = pairs of S regs) is NOT modelled; firmware compiled for
     /// `-mfpu=fpv4-sp-d16` only emits single-precision ops anyway.
     pub fpu_s: [u32; 32],
-    /// True while the core is suspended in WFI sleep. Set by the `Wfi`
-    /// executor when no wake-up event is pending, cleared at the top of every
+    /// True while the core is suspended in WFI/WFE sleep. Set by the `Wfi`
+    /// and `Wfe` executors when no wake-up event is pending, cleared at the top of every
     /// `step_internal`. Gates idle fast-forward; transient (not snapshotted),
     /// mirroring the RISC-V `waiting_for_interrupt` flag.
     sleeping: bool,
+    /// ARMv7-M event register: set by SEV and by exception entry/return,
+    /// consumed by WFE. Transient, like `sleeping`.
+    event_register: bool,
+    /// Set by an injected `skip_instruction` fault: the next instruction is
+    /// fetched and retired without executing. Transient, like `sleeping`.
+    skip_next: bool,
+    /// Energy retired since reset, in picojoules, per the stepping config's
+    /// `energy_model`.
+    pub energy_pj: f64,
 }
 
 impl Default for CortexM {
@@ -140,6 +150,9 @@ impl Default for CortexM {
             decode_cache: Box::new([None; 4096]),
             fpu_s: [0u32; 32],
             sleeping: false,
+            event_register: false,
+            skip_next: false,
+            energy_pj: 0.0,
         }
     }
 }
@@ -223,6 +236,38 @@ impl CortexM {
         self.basepri != 0 && prio >= self.basepri as i32
     }
 
+    /// The highest-priority pending exception that would preempt a context
+    /// running at `base_prio`, after PRIMASK, BASEPRI and FAULTMASK. Shared by
+    /// the instruction-boundary dispatch and the tail-chain check on
+    /// exception return.
+    fn preempting_exception(&self, base_prio: i32) -> Option<u32> {
+        if self.primask || !self.pending_exceptions.iter().any(|&w| w != 0) {
+            return None;
+        }
+        let exc = self.highest_priority_pending().filter(|&exc| exc != 0)?;
+        let prio = self.exception_priority(exc);
+        (prio < base_prio && !self.masked_by_basepri(prio) && !self.faultmask_blocks(exc))
+            .then_some(exc)
+    }
+
+    /// Mirror an external interrupt's active state into NVIC IABR, so nesting
+    /// is visible to firmware. System exceptions (< 16) have no IABR bit.
+    fn set_nvic_active(&self, exc: u32, active: bool) {
+        let Some(nvic) = self.nvic_state.as_ref().filter(|_| exc >= 16) else {
+            return;
+        };
+        let irq = exc - 16;
+        let Some(word) = nvic.iabr.get((irq / 32) as usize) else {
+            return;
+        };
+        let bit = 1u32 << (irq % 32);
+        if active {
+            word.fetch_or(bit, Ordering::SeqCst);
+        } else {
+            word.fetch_and(!bit, Ordering::SeqCst);
+        }
+    }
+
     /// Among the pending exceptions, return the one with the highest
     /// priority (lowest numeric value). Ties break by exception number
     /// (lower number wins, per ARMv7-M B1.5.4).
@@ -481,6 +526,8 @@ impl CortexM {
         if self.active_exception != 2 {
             self.faultmask = false;
         }
+        self.set_nvic_active(self.active_exception, false);
+        self.event_register = true;
 
         // We are in Handler mode, so the live `sp` is MSP — capture it.
         self.sync_sp_to_bank();
@@ -490,6 +537,36 @@ impl CortexM {
         let frame_on_psp = (exc_return & 0x4) != 0;
         let frame_ptr = if frame_on_psp { self.psp } else { self.msp };
 
+        // Tail-chaining: if a pending exception would preempt the context we
+        // are returning to, enter it directly on the existing frame instead
+        // of unstacking and immediately restacking the same state.
+        let return_prio = self.exception_priority(bus.read_u32((frame_ptr + 28) as u64)? & 0x1FF);
+        if let Some(next) = self.preempting_exception(return_prio) {
+            self.pending_exceptions[(next / 64) as usize] &= !(1u64 << (next % 64));
+            // Same stale-ISPR rule as instruction-boundary dispatch: a bit
+            // software cleared via ICPR is dropped, not taken.
+            if next < 16 || bus.is_nvic_irq_pending(next) {
+                bus.clear_nvic_pending(next);
+                self.set_active_exception(next);
+                self.set_nvic_active(next, true);
+                self.it_state = 0;
+                // The frame is shared, so the new handler returns with the
+                // same EXC_RETURN.
+                self.lr = exc_return;
+                let vtor = self.vtor.load(Ordering::SeqCst);
+                if let Ok(handler) = bus.read_u32((vtor + next * 4) as u64) {
+                    self.pc = handler & !1;
+                }
+                tracing::debug!(
+                    "EXC_TAILCHAIN: exc={} handler={:#010x} frame={:#010x}",
+                    next,
+                    self.pc,
+                    frame_ptr
+                );
+                return Ok(());
+            }
+        }
+
         self.r0 = bus.read_u32(frame_ptr as u64)?;
         self.r1 = bus.read_u32((frame_ptr + 4) as u64)?;
         self.r2 = bus.read_u32((frame_ptr + 8) as u64)?;
@@ -537,6 +614,81 @@ impl CortexM {
     }
 }
 
+/// Energy class of a decoded instruction (see [`crate::energy`]).
+fn instruction_class(instruction: &Instruction) -> InstructionClass {
+    use Instruction::*;
+    match instruction {
+        LdrImm { .. }
+        | StrImm { .. }
+        | StrReg { .. }
+        | LdrLit { .. }
+        | LdrImm32 { .. }
+        | StrImm32 { .. }
+        | LdrImm32Idx { .. }
+        | StrImm32Idx { .. }
+        | LdrbImm { .. }
+        | LdrbReg { .. }
+        | StrbImm { .. }
+        | StrbReg { .. }
+        | LdrhImm { .. }
+        | StrhImm { .. }
+        | StrhReg { .. }
+        | LdrsbReg { .. }
+        | LdrhReg { .. }
+        | LdrshReg { .. }
+        | Push { .. }
+        | Pop { .. }
+        | Ldm { .. }
+        | Stm { .. }
+        | LdrSp { .. }
+        | StrSp { .. }
+        | LdrReg { .. }
+        | LdmiaW { .. }
+        | StmdbW { .. }
+        | StmiaW { .. }
+        | LdmdbW { .. }
+        | Ldrd { .. }
+        | Strd { .. }
+        | Vldr { .. }
+        | Vstr { .. }
+        | Vldr64 { .. }
+        | Vstr64 { .. }
+        | VfpLoadMultiple { .. }
+        | VfpStoreMultiple { .. } => InstructionClass::Memory,
+        Branch { .. }
+        | BranchCond { .. }
+        | Cbz { .. }
+        | Cbnz { .. }
+        | Bl { .. }
+        | Bx { .. }
+        | BlxReg { .. }
+        | Tbb { .. }
+        | Tbh { .. } => InstructionClass::Branch,
+        Mul { .. }
+        | Mul32 { .. }
+        | Mla { .. }
+        | Mls { .. }
+        | Smull { .. }
+        | Umull { .. }
+        | Smlal { .. }
+        | Umlal { .. }
+        | Umaal { .. }
+        | Udiv { .. }
+        | Sdiv { .. } => InstructionClass::Multiply,
+        VmulF32 { .. }
+        | VaddF32 { .. }
+        | VsubF32 { .. }
+        | VdivF32 { .. }
+        | VcvtF32FromInt { .. }
+        | VcvtIntFromF32 { .. }
+        | VaddF64 { .. }
+        | VsubF64 { .. }
+        | VmulF64 { .. }
+        | VdivF64 { .. } => InstructionClass::Float,
+        _ => InstructionClass::Alu,
+    }
+}
+
 impl Cpu for CortexM {
     fn reset(&mut self, bus: &mut dyn Bus) -> SimResult<()> {
         self.pc = 0x0000_0000;
@@ -544,6 +696,8 @@ impl Cpu for CortexM {
         self.pending_exceptions = [0; 4];
         self.set_active_exception(0);
         self.decode_cache.fill(None);
+        self.energy_pj = 0.0;
+        self.event_register = false;
 
         // Out of reset the core is in Thread mode using MSP (CONTROL=0); PSP
         // is architecturally UNKNOWN — start it at 0.
@@ -603,6 +757,12 @@ impl Cpu for CortexM {
             pending_exceptions: self.pending_exceptions[0],
             pending_exceptions_hi: self.pending_exceptions[1..].to_vec(),
             vtor: self.vtor.load(Ordering::Relaxed),
+            msp: Some(self.read_msp()),
+            psp: Some(self.read_psp()),
+            control: Some(self.control),
+            faultmask: Some(self.faultmask),
+            basepri: Some(self.basepri),
+            active_exception: Some(self.active_exception),
         })
     }
 
@@ -634,6 +794,27 @@ impl Cpu for CortexM {
                 self.pending_exceptions[i + 1] = *w;
             }
             self.vtor.store(s.vtor, Ordering::Relaxed);
+            // `sp` was restored above as the live bank; mode and SPSEL decide
+            // which one that is, and the banked copies carry the other. A
+            // snapshot that predates these fields leaves them untouched.
+            if let Some(msp) = s.msp {
+                self.msp = msp;
+            }
+            if let Some(psp) = s.psp {
+                self.psp = psp;
+            }
+            if let Some(control) = s.control {
+                self.control = control;
+            }
+            if let Some(faultmask) = s.faultmask {
+                self.faultmask = faultmask;
+            }
+            if let Some(basepri) = s.basepri {
+                self.basepri = basepri;
+            }
+            if let Some(active) = s.active_exception {
+                self.set_active_exception(active);
+            }
         }
     }
 
@@ -648,6 +829,26 @@ impl Cpu for CortexM {
         names
     }
 
+    fn get_energy_consumption(&self) -> f64 {
+        crate::energy::pj_to_joules(self.energy_pj)
+    }
+
+    fn inject_fault(&mut self, target: &str) -> SimResult<()> {
+        match crate::vfi::Fault::parse(target, self)? {
+            crate::vfi::Fault::InstructionSkip => self.skip_next = true,
+            crate::vfi::Fault::RegisterBitFlip { register_id, bit } => {
+                let value = self.read_reg(register_id);
+                self.write_reg(register_id, value ^ (1 << bit));
+            }
+            crate::vfi::Fault::MemoryBitFlip { .. } => {
+                return Err(crate::SimulationError::Other(format!(
+                    "fault target '{target}' needs the bus; inject it through the machine"
+                )))
+            }
+        }
+        Ok(())
+    }
+
     fn index_of_register(&self, name: &str) -> Option<u8> {
         match name.to_uppercase().as_str() {
             "R0" => Some(0),
@@ -677,7 +878,7 @@ impl Cpu for CortexM {
         observers: &[Arc<dyn SimulationObserver>],
         config: &SimulationConfig,
     ) -> SimResult<()> {
-        self.step_internal(bus, observers, config)
+        self.step_internal(bus, observers, config).map(|_| ())
     }
 
     fn step_batch(
@@ -710,82 +911,40 @@ impl Cpu for CortexM {
             return Ok(max_count);
         }
 
-        let mut executed = 0;
-
-        if let Some(sysbus) = bus.as_any_mut().and_then(|a| a.downcast_mut::<SystemBus>()) {
-            while executed < max_count {
-                // End the batch early when a takeable exception is pending:
-                // its priority must be strictly higher (smaller number) than
-                // the currently-active one (or 256 = thread mode baseline).
-                // Only ONCE the batch has made progress (`executed > 0`) —
-                // at the batch top the pending exception must instead be
-                // DISPATCHED by the `step_internal` below (which takes it
-                // exactly like the single-step path). Breaking at zero made
-                // `Machine::run` return no-progress forever the moment a
-                // walk/scheduler-pended IRQ (e.g. SysTick) became takeable
-                // between batches, wedging every batched IRQ-driven Cortex-M
-                // firmware (walk-free campaign B1 surfaced this — batching is
-                // pointless if an armed SysTick freezes the run loop).
-                if executed > 0 && self.pending_exceptions.iter().any(|&w| w != 0) && !self.primask
-                {
-                    if let Some(exc) = self.highest_priority_pending() {
-                        let exc_prio = self.exception_priority(exc);
-                        let active_prio = self.exception_priority(self.active_exception);
-                        if exc_prio < active_prio
-                            && !self.masked_by_basepri(exc_prio)
-                            && !self.faultmask_blocks(exc)
-                        {
-                            break;
-                        }
-                    }
-                }
-                if let Some(tap) = &tap {
-                    tap.bump_clock();
-                }
-                self.step_internal(sysbus, observers, config)?;
-                executed += 1;
-                // Taken branches no longer break the batch — the run loop bounds
-                // it to the next peripheral tick, so bouncing back through
-                // `Machine::run` at every branch was pure overhead. Only WFI
-                // sleep leaves the batch, so the machine can fast-forward the
-                // idle window.
-                if config.idle_fast_forward_enabled
-                    && self.idle_fast_forward_budget(sysbus).is_some()
-                {
-                    break;
-                }
-            }
+        // Observers that only want totals (`PerformanceMetrics`) are left out
+        // of the per-instruction hooks and get one `on_batch_retired` call at
+        // the end, so a metrics-only run executes the batch without a single
+        // observer call. The filtered list is built once per batch.
+        let bulk = observers.iter().any(|o| !o.wants_step_events());
+        let filtered: Vec<Arc<dyn SimulationObserver>>;
+        let step_observers = if bulk {
+            filtered = observers
+                .iter()
+                .filter(|o| o.wants_step_events())
+                .cloned()
+                .collect();
+            &filtered[..]
         } else {
-            while executed < max_count {
-                // Same early-out rule as the SystemBus arm above: break only
-                // after progress; at the batch top a takeable pending
-                // exception is dispatched by `step_internal`, never spun on.
-                if executed > 0 && self.pending_exceptions.iter().any(|&w| w != 0) && !self.primask
-                {
-                    if let Some(exc) = self.highest_priority_pending() {
-                        let exc_prio = self.exception_priority(exc);
-                        let active_prio = self.exception_priority(self.active_exception);
-                        if exc_prio < active_prio
-                            && !self.masked_by_basepri(exc_prio)
-                            && !self.faultmask_blocks(exc)
-                        {
-                            break;
-                        }
-                    }
-                }
-                if let Some(tap) = &tap {
-                    tap.bump_clock();
-                }
-                self.step_internal(bus, observers, config)?;
-                executed += 1;
-                if config.idle_fast_forward_enabled && self.idle_fast_forward_budget(bus).is_some()
-                {
-                    break;
-                }
+            observers
+        };
+
+        let tap = tap.as_ref();
+        let mut retired = (0u32, 0u64);
+        let result =
+            if let Some(sysbus) = bus.as_any_mut().and_then(|a| a.downcast_mut::<SystemBus>()) {
+                self.run_batch(sysbus, step_observers, config, max_count, tap, &mut retired)
+            } else {
+                self.run_batch(bus, step_observers, config, max_count, tap, &mut retired)
+            };
+
+        // Flushed on the error path too: instructions retired before a fault
+        // still count.
+        if bulk && retired.0 > 0 {
+            for observer in observers.iter().filter(|o| !o.wants_step_events()) {
+                observer.on_batch_retired(retired.0, retired.1);
             }
         }
-
-        Ok(executed)
+        result
     }
 
     fn idle_fast_forward_budget(&self, _bus: &dyn Bus) -> Option<u64> {
@@ -803,6 +962,10 @@ impl Cpu for CortexM {
         Some(u64::MAX)
     }
 
+    fn is_parked_idle(&self) -> bool {
+        self.sleeping && !self.wfi_wake_pending()
+    }
+
     fn fast_forward_idle_cycles(&mut self, _cycles: u64) {
         // Cortex-M keeps no core-local cycle counter (unlike RISC-V mtime); the
         // machine owns `total_cycles` and advances it. Nothing to do here.
@@ -810,13 +973,76 @@ impl Cpu for CortexM {
 }
 
 impl CortexM {
+    /// The batched execution loop behind `step_batch`, generic so the
+    /// `SystemBus` arm is statically dispatched. `retired` accumulates the
+    /// instructions and core cycles owed to bulk observers, and stays valid
+    /// when a step fails part-way through the batch.
+    fn run_batch<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        observers: &[Arc<dyn SimulationObserver>],
+        config: &SimulationConfig,
+        max_count: u32,
+        tap: Option<&crate::logic_capture::LogicTap>,
+        retired: &mut (u32, u64),
+    ) -> SimResult<u32> {
+        let mut executed = 0;
+        while executed < max_count {
+            // End the batch early when a takeable exception is pending:
+            // its priority must be strictly higher (smaller number) than
+            // the currently-active one (or 256 = thread mode baseline).
+            // Only ONCE the batch has made progress (`executed > 0`) —
+            // at the batch top the pending exception must instead be
+            // DISPATCHED by the `step_internal` below (which takes it
+            // exactly like the single-step path). Breaking at zero made
+            // `Machine::run` return no-progress forever the moment a
+            // walk/scheduler-pended IRQ (e.g. SysTick) became takeable
+            // between batches, wedging every batched IRQ-driven Cortex-M
+            // firmware (walk-free campaign B1 surfaced this — batching is
+            // pointless if an armed SysTick freezes the run loop).
+            if executed > 0 && self.pending_exceptions.iter().any(|&w| w != 0) && !self.primask {
+                if let Some(exc) = self.highest_priority_pending() {
+                    let exc_prio = self.exception_priority(exc);
+                    let active_prio = self.exception_priority(self.active_exception);
+                    if exc_prio < active_prio
+                        && !self.masked_by_basepri(exc_prio)
+                        && !self.faultmask_blocks(exc)
+                    {
+                        break;
+                    }
+                }
+            }
+            if let Some(tap) = tap {
+                tap.bump_clock();
+            }
+            let cycles = self.step_internal(bus, observers, config)?;
+            executed += 1;
+            if cycles > 0 {
+                retired.0 += 1;
+                retired.1 += u64::from(cycles);
+            }
+            // Taken branches no longer break the batch — the run loop bounds
+            // it to the next peripheral tick, so bouncing back through
+            // `Machine::run` at every branch was pure overhead. Only WFI
+            // sleep leaves the batch, so the machine can fast-forward the
+            // idle window (the `idle_fast_forward_budget` condition).
+            if config.idle_fast_forward_enabled && self.sleeping && !self.wfi_wake_pending() {
+                break;
+            }
+        }
+        Ok(executed)
+    }
+
+    /// Execute one instruction, or take one pending exception. Returns the
+    /// retired instruction's core cycles; 0 when the step was an exception
+    /// entry instead.
     #[inline(always)]
     fn step_internal<B: Bus + ?Sized>(
         &mut self,
         bus: &mut B,
-        _observers: &[Arc<dyn SimulationObserver>],
+        observers: &[Arc<dyn SimulationObserver>],
         config: &SimulationConfig,
-    ) -> SimResult<()> {
+    ) -> SimResult<u32> {
         // Leave WFI sleep before this step commits: the flag is re-armed only if
         // this instruction is itself a WFI with no wake event pending.
         self.sleeping = false;
@@ -827,113 +1053,108 @@ impl CortexM {
         // active exception's. This is the dispatch path that makes
         // FreeRTOS PendSV-driven context switches behave correctly —
         // PendSV at priority 0xFF only runs when no other ISR is active.
-        let exception_num = self.highest_priority_pending().unwrap_or(0);
-        if self.pending_exceptions.iter().any(|&w| w != 0) && !self.primask && exception_num != 0 {
-            let take_prio = self.exception_priority(exception_num);
-            let active_prio = self.exception_priority(self.active_exception);
-            let can_take = take_prio < active_prio
-                && !self.masked_by_basepri(take_prio)
-                && !self.faultmask_blocks(exception_num);
-
-            if can_take {
-                // For NVIC-routed exceptions (num >= 16): verify the NVIC ISPR bit is
-                // still set before taking the exception.  Firmware may have called
-                // NVIC_ClearPendingIRQ (writing NVIC ICPR) while the ISR was active,
-                // which clears ISPR but leaves our cpu-side `pending_exceptions` stale.
-                // Without this check the stale bit causes a spurious second ISR after the
-                // real one returns.  On real ARM Cortex-M the hardware never re-latches a
-                // pending bit whose ISPR was cleared by software before ISR exit.
-                if exception_num >= 16 && !bus.is_nvic_irq_pending(exception_num) {
-                    // Stale pending_exceptions bit — drop it without taking the exception.
-                    self.pending_exceptions[(exception_num / 64) as usize] &=
-                        !(1u64 << (exception_num % 64));
-                    // Fall through to normal instruction execution.
+        let active_prio = self.exception_priority(self.active_exception);
+        if let Some(exception_num) = self.preempting_exception(active_prio) {
+            // For NVIC-routed exceptions (num >= 16): verify the NVIC ISPR bit is
+            // still set before taking the exception.  Firmware may have called
+            // NVIC_ClearPendingIRQ (writing NVIC ICPR) while the ISR was active,
+            // which clears ISPR but leaves our cpu-side `pending_exceptions` stale.
+            // Without this check the stale bit causes a spurious second ISR after the
+            // real one returns.  On real ARM Cortex-M the hardware never re-latches a
+            // pending bit whose ISPR was cleared by software before ISR exit.
+            if exception_num >= 16 && !bus.is_nvic_irq_pending(exception_num) {
+                // Stale pending_exceptions bit — drop it without taking the exception.
+                self.pending_exceptions[(exception_num / 64) as usize] &=
+                    !(1u64 << (exception_num % 64));
+                // Fall through to normal instruction execution.
+            } else {
+                self.pending_exceptions[(exception_num / 64) as usize] &=
+                    !(1u64 << (exception_num % 64));
+
+                // Clear NVIC ISPR for this exception so it isn't immediately re-pended.
+                // On real ARM hardware this happens automatically when the exception is taken.
+                bus.clear_nvic_pending(exception_num);
+
+                // Capture the entry context BEFORE switching to Handler mode:
+                // which mode/stack we came from determines EXC_RETURN.
+                let entered_from_handler = self.active_exception != 0;
+                let entry_on_psp = self.use_psp();
+                self.event_register = true;
+
+                // Perform Stacking on the CURRENT (preempted) stack.
+                let sp = self.sp;
+                let frame_ptr = sp.wrapping_sub(32);
+
+                // Save the previous active_exception in xPSR IPSR bits [8:0] so that
+                // exception_return can restore the correct nesting level.
+                let save_xpsr =
+                    self.xpsr_with_itstate((self.xpsr & !0x1FF) | self.active_exception);
+
+                // Stack: R0, R1, R2, R3, R12, LR, PC, xPSR (with previous IPSR)
+                let stacked_lr = self.lr;
+                let stacked_pc = self.pc;
+                let _ = bus.write_u32(frame_ptr as u64, self.r0);
+                let _ = bus.write_u32((frame_ptr + 4) as u64, self.r1);
+                let _ = bus.write_u32((frame_ptr + 8) as u64, self.r2);
+                let _ = bus.write_u32((frame_ptr + 12) as u64, self.r3);
+                let _ = bus.write_u32((frame_ptr + 16) as u64, self.r12);
+                let _ = bus.write_u32((frame_ptr + 20) as u64, self.lr);
+                let _ = bus.write_u32((frame_ptr + 24) as u64, self.pc);
+                let _ = bus.write_u32((frame_ptr + 28) as u64, save_xpsr);
+
+                // Bank the preempted stack pointer into its bank (PSP or MSP)
+                // BEFORE entering Handler mode, then switch the live `sp` to MSP.
+                if entry_on_psp {
+                    self.psp = frame_ptr;
                 } else {
-                    self.pending_exceptions[(exception_num / 64) as usize] &=
-                        !(1u64 << (exception_num % 64));
-
-                    // Clear NVIC ISPR for this exception so it isn't immediately re-pended.
-                    // On real ARM hardware this happens automatically when the exception is taken.
-                    bus.clear_nvic_pending(exception_num);
-
-                    // Capture the entry context BEFORE switching to Handler mode:
-                    // which mode/stack we came from determines EXC_RETURN.
-                    let entered_from_handler = self.active_exception != 0;
-                    let entry_on_psp = self.use_psp();
-
-                    // Perform Stacking on the CURRENT (preempted) stack.
-                    let sp = self.sp;
-                    let frame_ptr = sp.wrapping_sub(32);
-
-                    // Save the previous active_exception in xPSR IPSR bits [8:0] so that
-                    // exception_return can restore the correct nesting level.
-                    let save_xpsr =
-                        self.xpsr_with_itstate((self.xpsr & !0x1FF) | self.active_exception);
-
-                    // Stack: R0, R1, R2, R3, R12, LR, PC, xPSR (with previous IPSR)
-                    let stacked_lr = self.lr;
-                    let stacked_pc = self.pc;
-                    let _ = bus.write_u32(frame_ptr as u64, self.r0);
-                    let _ = bus.write_u32((frame_ptr + 4) as u64, self.r1);
-                    let _ = bus.write_u32((frame_ptr + 8) as u64, self.r2);
-                    let _ = bus.write_u32((frame_ptr + 12) as u64, self.r3);
-                    let _ = bus.write_u32((frame_ptr + 16) as u64, self.r12);
-                    let _ = bus.write_u32((frame_ptr + 20) as u64, self.lr);
-                    let _ = bus.write_u32((frame_ptr + 24) as u64, self.pc);
-                    let _ = bus.write_u32((frame_ptr + 28) as u64, save_xpsr);
-
-                    // Bank the preempted stack pointer into its bank (PSP or MSP)
-                    // BEFORE entering Handler mode, then switch the live `sp` to MSP.
-                    if entry_on_psp {
-                        self.psp = frame_ptr;
-                    } else {
-                        self.msp = frame_ptr;
-                    }
+                    self.msp = frame_ptr;
+                }
 
-                    // Update active exception (→ Handler mode) so nested exceptions
-                    // see the correct level. Handler always runs on MSP.
-                    self.set_active_exception(exception_num);
-                    self.sp = self.msp;
-                    self.it_state = 0;
-
-                    // EXC_RETURN encodes the mode/stack to restore on return:
-                    //   0xFFFFFFF1 → return to Handler mode (nested), frame on MSP
-                    //   0xFFFFFFF9 → return to Thread/MSP
-                    //   0xFFFFFFFD → return to Thread/PSP
-                    self.lr = if entered_from_handler {
-                        0xFFFF_FFF1
-                    } else if entry_on_psp {
-                        0xFFFF_FFFD
-                    } else {
-                        0xFFFF_FFF9
-                    };
+                // Update active exception (→ Handler mode) so nested exceptions
+                // see the correct level. Handler always runs on MSP.
+                self.set_active_exception(exception_num);
+                self.set_nvic_active(exception_num, true);
+                self.sp = self.msp;
+                self.it_state = 0;
 
-                    // Jump to ISR handler
-                    let vtor = self.vtor.load(Ordering::SeqCst);
-                    let vector_addr = vtor + (exception_num * 4);
-                    if std::env::var("LABWIRED_TRACE_EXC").is_ok() {
-                        eprintln!(
-                            "EXC take num={} vtor=0x{:08X} vec=0x{:08X} fetch={:?}",
-                            exception_num,
-                            vtor,
-                            vector_addr,
-                            bus.read_u32(vector_addr as u64)
-                        );
-                    }
-                    if let Ok(handler) = bus.read_u32(vector_addr as u64) {
-                        self.pc = handler & !1;
-                        tracing::debug!(
-                        "EXC_ENTRY: exc={} handler={:#010x} frame={:#010x} stacked_lr={:#010x} stacked_pc={:#010x}",
-                        exception_num, self.pc, frame_ptr, stacked_lr, stacked_pc
+                // EXC_RETURN encodes the mode/stack to restore on return:
+                //   0xFFFFFFF1 → return to Handler mode (nested), frame on MSP
+                //   0xFFFFFFF9 → return to Thread/MSP
+                //   0xFFFFFFFD → return to Thread/PSP
+                self.lr = if entered_from_handler {
+                    0xFFFF_FFF1
+                } else if entry_on_psp {
+                    0xFFFF_FFFD
+                } else {
+                    0xFFFF_FFF9
+                };
+
+                // Jump to ISR handler
+                let vtor = self.vtor.load(Ordering::SeqCst);
+                let vector_addr = vtor + (exception_num * 4);
+                if std::env::var("LABWIRED_TRACE_EXC").is_ok() {
+                    eprintln!(
+                        "EXC take num={} vtor=0x{:08X} vec=0x{:08X} fetch={:?}",
+                        exception_num,
+                        vtor,
+                        vector_addr,
+                        bus.read_u32(vector_addr as u64)
                     );
-                    }
+                }
+                if let Ok(handler) = bus.read_u32(vector_addr as u64) {
+                    self.pc = handler & !1;
+                    tracing::debug!(
+                    "EXC_ENTRY: exc={} handler={:#010x} frame={:#010x} stacked_lr={:#010x} stacked_pc={:#010x}",
+                    exception_num, self.pc, frame_ptr, stacked_lr, stacked_pc
+                );
+                }
 
-                    return Ok(());
-                } // end else (NVIC ISPR still set — take the exception)
-            }
-            // Can't take this exception right now (lower priority than active).
-            // Fall through and execute the current instruction normally.
+                return Ok(0);
+            } // end else (NVIC ISPR still set — take the exception)
         }
+        // Nothing can preempt the current execution priority (or the pending
+        // bit was stale): execute the current instruction normally.
+
         // Fetch/Decode with optional Cache
         let cache_idx = ((self.pc >> 1) & 0xFFF) as usize;
         let entry = if config.decode_cache_enabled {
@@ -950,15 +1171,24 @@ impl CortexM {
             None
         };
 
-        let (instruction, opcode, mut pc_increment, _cycles) = if let Some(e) = entry {
+        let (instruction, opcode, mut pc_increment, cycles) = if let Some(e) = entry {
             (e.instruction, e.opcode, e.pc_increment as u32, e.cycles)
         } else {
             let fetch_pc = self.pc & !1;
-            let h1 = bus.read_u16(fetch_pc as u64)?;
+            // BE8 images keep instructions little-endian; undo the bus's
+            // big-endian data ordering for fetches.
+            let fetch = |addr: u32| -> SimResult<u16> {
+                let h = bus.read_u16(addr as u64)?;
+                Ok(match bus.config().endianness {
+                    crate::Endianness::Little => h,
+                    crate::Endianness::Big => h.swap_bytes(),
+                })
+            };
+            let h1 = fetch(fetch_pc)?;
             let is_32bit = (h1 & 0xE000) == 0xE000 && (h1 & 0x1800) != 0;
 
             let (instr, op, pincr, cyc) = if is_32bit {
-                let h2 = bus.read_u16((fetch_pc + 2) as u64)?;
+                let h2 = fetch(fetch_pc + 2)?;
                 let instr = decode_thumb_32(h1, h2);
                 let op = ((h1 as u32) << 16) | h2 as u32;
                 (instr, op, 4, 2)
@@ -967,6 +1197,21 @@ impl CortexM {
                 (instr, h1 as u32, 2, 1)
             };
 
+            // ARMv6-M implements only BL, MSR, MRS and DMB/DSB/ISB among the
+            // 32-bit encodings, and none of CBZ/CBNZ/IT among the 16-bit
+            // ones. With no UsageFault on M0/M0+, an UNDEFINED encoding
+            // escalates straight to HardFault with the PC left on it.
+            if bus.config().arm_profile == crate::ArmProfile::V6M
+                && !is_armv6m_instruction(&instr, op, is_32bit)
+            {
+                if matches!(self.active_exception, 2 | 3) {
+                    // A fault inside NMI or HardFault is lockup.
+                    return Err(crate::SimulationError::DecodeError(fetch_pc as u64));
+                }
+                self.set_exception_pending(3);
+                return Ok(0);
+            }
+
             if config.decode_cache_enabled {
                 self.decode_cache[cache_idx] = Some(DecodeCacheEntry {
                     tag: self.pc,
@@ -983,13 +1228,16 @@ impl CortexM {
         // Per-instruction PC trace gated on LABWIRED_TRACE_INSN env var.
         // Use only for short runs — VERY chatty. Format suitable for grepping:
         //   INSN pc=0xPPPPPPPP op=0xOOOOOOOO
-        if std::env::var("LABWIRED_TRACE_INSN").is_ok() {
+        if trace_insn_enabled() {
             eprintln!("INSN pc=0x{:08X} op=0x{:08X}", self.pc, opcode);
         }
 
         let retired_pc = self.pc;
-        if !_observers.is_empty() {
-            for observer in _observers {
+        self.energy_pj += config
+            .energy_model
+            .instruction_pj(instruction_class(&instruction));
+        if !observers.is_empty() {
+            for observer in observers {
                 observer.on_step_start(self.pc, opcode);
             }
         }
@@ -1003,6 +1251,12 @@ impl CortexM {
             execute = self.check_condition(cond);
         }
 
+        // An injected skip fault retires this instruction unexecuted, like a
+        // failed condition: PC, ITSTATE, energy and observers advance as usual.
+        if std::mem::take(&mut self.skip_next) {
+            execute = false;
+        }
+
         if execute {
             #[cfg(debug_assertions)]
             tracing::debug!(
@@ -1880,6 +2134,20 @@ impl CortexM {
                         self.sleeping = true;
                     }
                 }
+                Instruction::Wfe => {
+                    // ARMv7-M WFE: a set event register (SEV, or an exception
+                    // entry/return since the last WFE) is consumed and WFE
+                    // completes as a NOP. Otherwise it sleeps exactly like WFI
+                    // and wakes on the same pending-exception condition.
+                    if self.event_register {
+                        self.event_register = false;
+                    } else if !self.wfi_wake_pending() {
+                        self.sleeping = true;
+                    }
+                }
+                Instruction::Sev => {
+                    self.event_register = true;
+                }
                 Instruction::MovImm { rd, imm } => {
                     self.write_reg(rd, imm as u32);
                     if !it_block_instruction {
@@ -3192,7 +3460,7 @@ impl CortexM {
         // Building the register snapshot is pure waste when nothing observes it,
         // and this runs on every instruction. Gate it on having observers, the
         // same way on_step_start above is gated.
-        if !_observers.is_empty() {
+        if !observers.is_empty() {
             let mut registers = [0u32; 17];
             for (i, reg) in registers.iter_mut().enumerate().take(16) {
                 *reg = self.get_register(i as u8);
@@ -3200,23 +3468,48 @@ impl CortexM {
             registers[16] = self.xpsr;
 
             crate::emit_trace_event(
-                _observers,
+                observers,
                 labwired_hw_trace::TraceEvent::InstructionRetired {
                     pc: retired_pc,
                     opcode,
                 },
             );
-            for obs in _observers {
-                obs.on_step_end(_cycles, &registers);
+            for obs in observers {
+                obs.on_step_end(cycles, &registers);
             }
         }
 
-        Ok(())
+        Ok(cycles)
+    }
+}
+
+/// `LABWIRED_TRACE_INSN` is read once per process: an env lookup on every
+/// instruction cost more than most instructions do.
+fn trace_insn_enabled() -> bool {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("LABWIRED_TRACE_INSN").is_ok())
+}
+
+/// True for the Thumb encodings ARMv6-M implements. Among the 32-bit forms
+/// that is BL, MSR, MRS and the DMB/DSB/ISB barriers (CLREX shares `Barrier`
+/// but is ARMv7-M only); among the 16-bit forms everything but CBZ, CBNZ and
+/// IT.
+fn is_armv6m_instruction(instr: &Instruction, opcode: u32, is_32bit: bool) -> bool {
+    if !is_32bit {
+        return !matches!(
+            instr,
+            Instruction::Cbz { .. } | Instruction::Cbnz { .. } | Instruction::It { .. }
+        );
+    }
+    match instr {
+        Instruction::Bl { .. } | Instruction::Msr { .. } | Instruction::Mrs { .. } => true,
+        Instruction::Barrier => (opcode >> 4) & 0xF != 2,
+        _ => false,
     }
 }
 
 // Thumb expand immediate - implements ARM's modified immediate constant expansion
-fn thumb_expand_imm(imm12: u32) -> u32 {
+pub(crate) fn thumb_expand_imm(imm12: u32) -> u32 {
     let i = (imm12 >> 11) & 1;
     let imm3 = (imm12 >> 8) & 7;
     let imm8 = imm12 & 0xFF;
@@ -4669,6 +4962,71 @@ mod tests {
         assert_eq!(cpu.msp, 0x7FE0, "MSP bank updated");
     }
 
+    #[test]
+    fn snapshot_round_trips_stack_banks_and_handler_mode() {
+        let mut cpu = CortexM::new();
+        let mut bus = MockBus::new();
+        cpu.pc = 0x1000;
+        cpu.control = 0x2; // thread/PSP
+        cpu.sp = 0x6000;
+        cpu.msp = 0x8000;
+        cpu.basepri = 0x40;
+        let thread = cpu.snapshot();
+
+        take_exception(&mut cpu, &mut bus, 16, 0x2000);
+        assert_eq!(cpu.active_exception, 16, "entry leaves thread mode");
+        let handler = cpu.snapshot();
+        match &handler {
+            crate::snapshot::CpuSnapshot::Arm(s) => {
+                assert_eq!((s.msp, s.psp), (Some(0x8000), Some(0x5FE0)));
+                assert_eq!(s.active_exception, Some(16));
+            }
+            other => panic!("expected Arm snapshot, got {other:?}"),
+        }
+
+        // Restoring into a fresh core re-enters handler mode on MSP.
+        let mut restored = CortexM::new();
+        restored.apply_snapshot(&handler);
+        assert_eq!(restored.active_exception, 16);
+        assert_eq!(restored.vectactive.load(Ordering::Relaxed), 16);
+        assert_eq!(restored.sp, 0x8000);
+        assert_eq!(restored.read_psp(), 0x5FE0);
+        assert_eq!((restored.control, restored.basepri), (0x2, 0x40));
+
+        // And the earlier snapshot drops it back to thread mode on PSP.
+        restored.apply_snapshot(&thread);
+        assert_eq!(restored.active_exception, 0);
+        assert_eq!(restored.sp, 0x6000);
+        assert_eq!(restored.read_msp(), 0x8000);
+    }
+
+    #[test]
+    fn restoring_a_pre_banking_snapshot_keeps_psp_and_control() {
+        let mut cpu = CortexM::new();
+        cpu.control = 0x2;
+        cpu.psp = 0x5000;
+        cpu.sp = 0x5000;
+        cpu.msp = 0x8000;
+        // A snapshot written before the banked fields existed.
+        let old: crate::snapshot::CpuSnapshot = serde_json::from_value(serde_json::json!({
+            "type": "arm",
+            "registers": [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x4F00, 0, 0x1000],
+            "pc": 0x1000,
+            "xpsr": 0x0100_0000,
+            "primask": false,
+            "pending_exceptions": 0,
+            "vtor": 0
+        }))
+        .unwrap();
+
+        cpu.apply_snapshot(&old);
+
+        assert_eq!(cpu.pc, 0x1000);
+        assert_eq!(cpu.control, 0x2, "CONTROL must not be zeroed");
+        assert_eq!(cpu.sp, 0x4F00);
+        assert_eq!(cpu.read_msp(), 0x8000);
+    }
+
     #[test]
     fn nested_exception_entry_sets_exc_return_f1() {
         // Already in a handler → nested exception returns to Handler mode (F1),
@@ -4748,6 +5106,68 @@ mod tests {
         assert_eq!(cpu.control & 0x2, 0, "SPSEL stays MSP");
     }
 
+    #[test]
+    fn high_priority_irq_preempts_low_priority_handler_and_tail_chains() {
+        // IRQ0 prio 0xC0 (low), IRQ1 prio 0x40 (high), IRQ2 prio 0x80 (mid).
+        let mut cpu = CortexM::new();
+        let mut bus = MockBus::new();
+        let nvic = Arc::new(crate::peripherals::nvic::NvicState::default());
+        nvic.ipr[0].store(0x0080_40C0, Ordering::Relaxed);
+        cpu.set_shared_nvic_state(nvic.clone());
+        cpu.pc = 0x1000;
+        cpu.sp = 0x8000;
+        cpu.r0 = 0x1234_5678;
+        bus.write_u32(16 * 4, 0x2000).unwrap();
+        bus.write_u32(17 * 4, 0x3000).unwrap();
+        bus.write_u32(18 * 4, 0x4000).unwrap();
+        bus.write_u16(0x2000, 0xBF00).unwrap(); // low: NOP
+        bus.write_u16(0x2002, 0xBF00).unwrap(); //      NOP
+        bus.write_u16(0x2004, 0x4770).unwrap(); //      BX LR
+        bus.write_u16(0x3000, 0x4770).unwrap(); // high: BX LR
+        bus.write_u16(0x4000, 0x4770).unwrap(); // mid: BX LR
+        let cfg = bus.config.clone();
+
+        cpu.set_exception_pending(16);
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap();
+        assert_eq!(cpu.active_exception, 16);
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap(); // NOP @0x2000
+
+        // High-priority IRQ1 preempts the running low-priority handler.
+        cpu.set_exception_pending(17);
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap();
+        assert_eq!(cpu.active_exception, 17, "IRQ1 preempted IRQ0");
+        assert_eq!(cpu.pc, 0x3000);
+        assert_eq!(cpu.lr, 0xFFFF_FFF1, "nested return to Handler mode");
+        assert_eq!(cpu.sp, 0x7FC0, "second frame below the first");
+        assert_eq!(nvic.iabr[0].load(Ordering::SeqCst), 0b11, "both active");
+
+        // IRQ2 (0x80) cannot preempt IRQ1 (0x40) but does outrank the
+        // interrupted IRQ0 (0xC0), so IRQ1's return tail-chains into it.
+        cpu.set_exception_pending(18);
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap(); // BX LR @0x3000
+        assert_eq!(cpu.active_exception, 18, "tail-chained into IRQ2");
+        assert_eq!(cpu.pc, 0x4000);
+        assert_eq!(cpu.lr, 0xFFFF_FFF1, "tail-chain keeps EXC_RETURN");
+        assert_eq!(cpu.sp, 0x7FC0, "no unstack/restack on tail-chain");
+        assert_eq!(nvic.iabr[0].load(Ordering::SeqCst), 0b101);
+
+        // IRQ2 returns into the low-priority handler where it left off.
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap(); // BX LR @0x4000
+        assert_eq!(cpu.active_exception, 16, "back in IRQ0");
+        assert_eq!(cpu.pc, 0x2002, "resumes after the preempted NOP");
+        assert_eq!(cpu.lr, 0xFFFF_FFF9);
+        assert_eq!(cpu.sp, 0x7FE0);
+        assert_eq!(nvic.iabr[0].load(Ordering::SeqCst), 0b1);
+
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap(); // NOP @0x2002
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap(); // BX LR @0x2004
+        assert_eq!(cpu.active_exception, 0, "back to thread mode");
+        assert_eq!(cpu.pc, 0x1000);
+        assert_eq!(cpu.sp, 0x8000);
+        assert_eq!(cpu.r0, 0x1234_5678);
+        assert_eq!(nvic.iabr[0].load(Ordering::SeqCst), 0);
+    }
+
     #[test]
     fn wfi_decodes_and_retires_like_nop() {
         // 0xBF30 must decode to the dedicated WFI variant (not the hint-space
@@ -4860,6 +5280,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wfe_consumes_event_set_by_sev() {
+        // SEV latches the event register; the next WFE consumes it and retires
+        // as a NOP. A second WFE finds the register clear and sleeps.
+        let mut cpu = CortexM::new();
+        let mut bus = MockBus::new();
+        cpu.pc = 0x1000;
+        run_test_instr(&mut cpu, &mut bus, 0xBF40, false); // SEV
+        run_test_instr(&mut cpu, &mut bus, 0xBF20, false); // WFE
+        assert_eq!(cpu.pc, 0x1004);
+        assert!(
+            cpu.idle_fast_forward_budget(&bus).is_none(),
+            "WFE after SEV falls through without sleeping"
+        );
+        run_test_instr(&mut cpu, &mut bus, 0xBF20, false); // WFE
+        assert_eq!(cpu.pc, 0x1006);
+        assert!(
+            cpu.idle_fast_forward_budget(&bus).is_some(),
+            "WFE with a clear event register sleeps"
+        );
+    }
+
+    #[test]
+    fn exception_entry_sets_event_register() {
+        // Taking an exception is an event: a WFE issued in the handler falls
+        // through instead of sleeping.
+        let mut cpu = CortexM::new();
+        let mut bus = MockBus::new();
+        cpu.pc = 0x1000;
+        cpu.sp = 0x8000;
+        bus.write_u32(0x3C, 0x2001).unwrap(); // SysTick vector
+        cpu.set_exception_pending(15);
+        let cfg = bus.config.clone();
+        cpu.step_internal(&mut bus, &[], &cfg).unwrap();
+        assert_eq!(cpu.pc, 0x2000);
+        run_test_instr(&mut cpu, &mut bus, 0xBF20, false); // WFE
+        assert!(cpu.idle_fast_forward_budget(&bus).is_none());
+    }
+
+    /// `WFI; b .-2` at 0x100 with a SysTick handler spinning at 0x200, SysTick
+    /// armed to fire 500 cycles in. SysTick is re-registered so it picks up the
+    /// bus cycle clock (scheduler mode under `event-scheduler`).
+    fn systick_wfi_machine(ff: bool) -> Machine<CortexM> {
+        let mut bus = SystemBus::new();
+        bus.replace_or_add_peripheral(
+            "systick",
+            0xE000_E010,
+            0x100,
+            Some(15),
+            Box::new(crate::peripherals::systick::Systick::new()),
+        );
+        bus.write_u32(0x3C, 0x201).unwrap(); // SysTick vector
+        bus.write_u16(0x100, 0xBF30).unwrap(); // WFI
+        bus.write_u16(0x102, 0xE7FD).unwrap(); // B -> 0x100
+        bus.write_u16(0x200, 0xE7FE).unwrap(); // B .
+        let mut cpu = CortexM::new();
+        cpu.pc = 0x100;
+        cpu.sp = 0x2000_1000;
+        let mut machine = Machine::new(cpu, bus);
+        machine.config.idle_fast_forward_enabled = ff;
+        machine.bus.legacy_walk_disabled = true;
+        machine.bus.write_u32(0xE000_E014, 499).unwrap(); // RVR
+        machine.bus.write_u32(0xE000_E010, 0x7).unwrap(); // ENABLE|TICKINT|CLKSOURCE
+        machine
+    }
+
+    #[test]
+    fn systick_wakes_wfi_sleep_at_the_same_cycle_with_fast_forward() {
+        // Fast-forward must land the SysTick exception exactly where the
+        // stepped reference takes it: same handler state, same total_cycles,
+        // with the sleep window skipped rather than spun.
+        use crate::DebugControl;
+        let mut off = systick_wfi_machine(false);
+        off.run(Some(2000)).unwrap();
+        let mut on = systick_wfi_machine(true);
+        on.run(Some(2000)).unwrap();
+
+        assert_eq!(off.cpu.pc, 0x200, "SysTick woke the core into its handler");
+        assert_eq!(off.cpu.active_exception, 15);
+        assert_eq!(on.cpu.pc, off.cpu.pc);
+        assert_eq!(on.cpu.active_exception, off.cpu.active_exception);
+        assert_eq!(on.cpu.sp, off.cpu.sp);
+        assert_eq!(on.total_cycles, off.total_cycles);
+        if cfg!(feature = "event-scheduler") {
+            assert!(
+                on.idle_fast_forward_cycles_skipped >= 400,
+                "most of the 500-cycle sleep is skipped, got {}",
+                on.idle_fast_forward_cycles_skipped
+            );
+            assert!(on.step_profile().cpu_instructions < off.step_profile().cpu_instructions);
+        }
+    }
+
+    #[test]
+    fn wfi_with_no_enabled_wake_source_is_a_sleep_deadlock() {
+        let mut bus = SystemBus::new();
+        let (mut cpu, nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        bus.write_u16(0x100, 0xBF30).unwrap(); // WFI
+        bus.write_u16(0x102, 0xE7FD).unwrap(); // B -> 0x100
+        cpu.pc = 0x100;
+        cpu.sp = 0x2000_1000;
+        let mut machine = Machine::new(cpu, bus);
+        assert!(!machine.is_sleep_deadlocked(), "awake before the WFI");
+
+        machine.step().unwrap();
+        assert!(machine.is_sleep_deadlocked());
+
+        nvic.iser[0].store(1 << 5, Ordering::SeqCst);
+        assert!(!machine.is_sleep_deadlocked(), "an enabled IRQ can wake it");
+        nvic.iser[0].store(0, Ordering::SeqCst);
+
+        machine.bus.write_u32(0xE000_E014, 999).unwrap(); // RVR
+        machine.bus.write_u32(0xE000_E010, 0x7).unwrap(); // ENABLE|TICKINT
+        assert!(
+            !machine.is_sleep_deadlocked(),
+            "an armed SysTick can wake it"
+        );
+    }
+
     /// Build the minimal `WFI; b .-2` idle loop on a real `SystemBus` and run it
     /// with the legacy walk disabled so idle fast-forward is legal.
     fn wfi_idle_machine(ff: bool) -> Machine<CortexM> {
@@ -4937,4 +5476,62 @@ mod tests {
             );
         }
     }
+
+    #[derive(Debug, Default)]
+    struct StepCounter {
+        starts: std::sync::atomic::AtomicU64,
+    }
+
+    impl SimulationObserver for StepCounter {
+        fn on_step_start(&self, _pc: u32, _opcode: u32) {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn step_batch_retires_same_counts_as_single_steps() {
+        use crate::metrics::PerformanceMetrics;
+        const STEPS: u32 = 150;
+
+        // MOVS r0,#50; loop: SUBS r0,#1; BNE loop; B .
+        let program = [0x2032u16, 0x3801, 0xD1FD, 0xE7FE];
+        let fresh = || {
+            let mut bus = SystemBus::new();
+            for (i, half) in program.iter().enumerate() {
+                bus.write_u16(i as u64 * 2, *half).unwrap();
+            }
+            let mut cpu = CortexM::new();
+            cpu.pc = 0x0;
+            cpu.sp = 0x2000_1000;
+            (cpu, bus)
+        };
+        let (mut single, mut single_bus) = fresh();
+        let single_metrics = Arc::new(PerformanceMetrics::new());
+        let observers: Vec<Arc<dyn SimulationObserver>> = vec![single_metrics.clone()];
+        let cfg = single_bus.config.clone();
+        for _ in 0..STEPS {
+            single.step(&mut single_bus, &observers, &cfg).unwrap();
+        }
+
+        let (mut batched, mut batched_bus) = fresh();
+        let batched_metrics = Arc::new(PerformanceMetrics::new());
+        let counter = Arc::new(StepCounter::default());
+        let observers: Vec<Arc<dyn SimulationObserver>> =
+            vec![batched_metrics.clone(), counter.clone()];
+        let executed = batched
+            .step_batch(&mut batched_bus, &observers, &cfg, STEPS)
+            .unwrap();
+
+        assert_eq!(executed, STEPS);
+        assert_eq!(batched.pc, single.pc);
+        assert_eq!(batched.r0, single.r0);
+        assert_eq!(single_metrics.get_instructions(), u64::from(STEPS));
+        assert_eq!(
+            batched_metrics.get_instructions(),
+            single_metrics.get_instructions()
+        );
+        assert_eq!(batched_metrics.get_cycles(), single_metrics.get_cycles());
+        // Per-step observers still see every instruction in the batch.
+        assert_eq!(counter.starts.load(Ordering::SeqCst), u64::from(STEPS));
+    }
 }
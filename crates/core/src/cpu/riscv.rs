@@ -1460,16 +1460,29 @@ impl Cpu for RiscV {
     }
 
     fn index_of_register(&self, name: &str) -> Option<u8> {
-        if let Some(stripped) = name.strip_prefix('x') {
-            stripped.parse().ok()
-        } else if name.to_lowercase() == "pc" {
-            Some(32)
-        } else {
-            None
+        let name = name.to_lowercase();
+        if let Some(index) = name.strip_prefix('x').and_then(|n| n.parse::<u8>().ok()) {
+            return (index < 32).then_some(index);
+        }
+        match name.as_str() {
+            "pc" => Some(32),
+            // `fp` aliases `s0` (x8).
+            "fp" => Some(8),
+            _ => ABI_REGISTER_NAMES
+                .iter()
+                .position(|abi| *abi == name)
+                .map(|i| i as u8),
         }
     }
 }
 
+/// Standard ABI names of `x0`..`x31`, accepted by `index_of_register`.
+const ABI_REGISTER_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1504,6 +1517,20 @@ mod tests {
         assert_eq!(machine.cpu.pc, 4);
     }
 
+    #[test]
+    fn test_index_of_register_accepts_abi_names() {
+        let cpu = RiscV::new();
+        assert_eq!(cpu.index_of_register("x10"), Some(10));
+        assert_eq!(cpu.index_of_register("a0"), Some(10));
+        assert_eq!(cpu.index_of_register("zero"), Some(0));
+        assert_eq!(cpu.index_of_register("SP"), Some(2));
+        assert_eq!(cpu.index_of_register("fp"), Some(8));
+        assert_eq!(cpu.index_of_register("t6"), Some(31));
+        assert_eq!(cpu.index_of_register("pc"), Some(32));
+        assert_eq!(cpu.index_of_register("x32"), None);
+        assert_eq!(cpu.index_of_register("q0"), None);
+    }
+
     #[test]
     fn esp32c3_rejects_standard_cycle_csr_but_exposes_pccr_machine() {
         let mut bus = SystemBus::new();
@@ -9,9 +9,14 @@ pub enum Instruction {
     Nop,
     /// Wait For Interrupt (0xBF30). Suspends the core until a wake-up event
     /// (a sufficiently-prioritised pending exception) arrives; see the
-    /// `CortexM` executor and idle fast-forward. WFE/YIELD/SEV stay `Nop` —
-    /// no event register is modelled.
+    /// `CortexM` executor and idle fast-forward. YIELD stays `Nop`.
     Wfi,
+    /// Wait For Event (0xBF20). Consumes a set event register, otherwise
+    /// sleeps like [`Instruction::Wfi`].
+    Wfe,
+    /// Send Event (0xBF40). Sets the event register so the next WFE falls
+    /// through.
+    Sev,
     MovImm {
         rd: u8,
         imm: u8,
@@ -796,6 +801,592 @@ pub enum Instruction {
     Unknown32(u16, u16),
 }
 
+const CONDS: [&str; 16] = [
+    "eq", "ne", "cs", "cc", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "", "",
+];
+
+/// ARM register name (`r0`..`r12`, `sp`, `lr`, `pc`).
+fn reg(r: u8) -> String {
+    match r {
+        13 => "sp".to_string(),
+        14 => "lr".to_string(),
+        15 => "pc".to_string(),
+        r => format!("r{r}"),
+    }
+}
+
+/// `{r0, r4, lr}` for a register bitmask (bit n = register n).
+fn reg_list(mask: u16) -> String {
+    let regs: Vec<String> = (0..16u8)
+        .filter(|r| mask & (1 << r) != 0)
+        .map(reg)
+        .collect();
+    format!("{{{}}}", regs.join(", "))
+}
+
+/// Branch target relative to the instruction (`.+8`); `offset` is relative
+/// to the PC value the instruction reads (its address + 4).
+fn rel(offset: i32) -> String {
+    format!(".{:+}", offset + 4)
+}
+
+/// `[rn, #±imm]`, `[rn, #±imm]!` or `[rn], #±imm` for T4/LDRD-style indexing.
+fn indexed(rn: u8, imm: u32, add: bool, pre_index: bool, writeback: bool) -> String {
+    let sign = if add { "" } else { "-" };
+    if !pre_index {
+        format!("[{}], #{sign}{imm}", reg(rn))
+    } else if writeback {
+        format!("[{}, #{sign}{imm}]!", reg(rn))
+    } else {
+        format!("[{}, #{sign}{imm}]", reg(rn))
+    }
+}
+
+fn sysm_name(sysm: u8) -> String {
+    match sysm {
+        0 => "apsr".to_string(),
+        8 => "msp".to_string(),
+        9 => "psp".to_string(),
+        0x10 => "primask".to_string(),
+        0x11 => "basepri".to_string(),
+        0x12 => "basepri_max".to_string(),
+        0x13 => "faultmask".to_string(),
+        0x14 => "control".to_string(),
+        n => format!("sysm{n}"),
+    }
+}
+
+/// Barrel-shift suffix of a register operand (`, lsl #2`, `, rrx`).
+fn shift_suffix(shift_type: u8, imm5: u8) -> String {
+    match (shift_type, imm5) {
+        (0, 0) => String::new(),
+        (0, n) => format!(", lsl #{n}"),
+        (1, 0) => ", lsr #32".to_string(),
+        (1, n) => format!(", lsr #{n}"),
+        (2, 0) => ", asr #32".to_string(),
+        (2, n) => format!(", asr #{n}"),
+        (_, 0) => ", rrx".to_string(),
+        (_, n) => format!(", ror #{n}"),
+    }
+}
+
+/// Mnemonic of a T32 data-processing opcode, with the compare/move aliases
+/// (`tst`, `mov`, `cmp`, …) chosen from `rd`/`rn` like the assembler does.
+fn data_proc_name(op: u8, rn: u8, rd: u8, set_flags: bool) -> (&'static str, bool, bool) {
+    // (mnemonic, has_rd, has_rn)
+    let compare = rd == 15 && set_flags;
+    match op {
+        0x0 if compare => ("tst", false, true),
+        0x0 => ("and", true, true),
+        0x1 => ("bic", true, true),
+        0x2 if rn == 15 => ("mov", true, false),
+        0x2 => ("orr", true, true),
+        0x3 if rn == 15 => ("mvn", true, false),
+        0x3 => ("orn", true, true),
+        0x4 if compare => ("teq", false, true),
+        0x4 => ("eor", true, true),
+        0x6 => ("pkh", true, true),
+        0x8 if compare => ("cmn", false, true),
+        0x8 => ("add", true, true),
+        0xA => ("adc", true, true),
+        0xB => ("sbc", true, true),
+        0xD if compare => ("cmp", false, true),
+        0xD => ("sub", true, true),
+        0xE => ("rsb", true, true),
+        _ => ("dp?", true, true),
+    }
+}
+
+impl Instruction {
+    /// ARM (UAL-style) assembly text, e.g. `mov r0, #1` or `ldr r3, [r2, #4]`.
+    /// Branch targets are relative to the instruction (`b .-4`). Same as the
+    /// `Display` output.
+    pub fn to_asm(&self) -> String {
+        self.to_string()
+    }
+
+    /// Absolute target of a PC-relative branch or call (`b`, `b<cond>`, `bl`,
+    /// `cbz`, `cbnz`) located at `pc`; `None` for every other instruction.
+    pub fn branch_target(&self, pc: u32) -> Option<u32> {
+        let offset = match *self {
+            Instruction::Branch { offset }
+            | Instruction::BranchCond { offset, .. }
+            | Instruction::Bl { offset } => offset,
+            Instruction::Cbz { imm, .. } | Instruction::Cbnz { imm, .. } => imm as i32,
+            _ => return None,
+        };
+        Some((pc & !1).wrapping_add(4).wrapping_add_signed(offset))
+    }
+
+    /// [`Instruction::to_asm`] for the instruction at `pc`: branch targets
+    /// are absolute, followed by `<name>` when `symbolize` resolves them,
+    /// e.g. `bl 0x8000100 <main>`.
+    pub fn to_asm_at(&self, pc: u32, symbolize: Option<&dyn Fn(u32) -> Option<String>>) -> String {
+        let text = self.to_string();
+        let Some(target) = self.branch_target(pc) else {
+            return text;
+        };
+        // The relative target is always the last operand.
+        let head = text
+            .rsplit_once(' ')
+            .map_or(text.as_str(), |(head, _)| head);
+        match symbolize.and_then(|s| s(target)) {
+            Some(name) => format!("{head} {target:#x} <{name}>"),
+            None => format!("{head} {target:#x}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Instruction::*;
+        let r = reg;
+        let s = |n: u8| format!("s{n}");
+        let d = |n: u8| format!("d{}", n / 2);
+        match *self {
+            Nop => write!(f, "nop"),
+            Wfi => write!(f, "wfi"),
+            Wfe => write!(f, "wfe"),
+            Sev => write!(f, "sev"),
+            MovImm { rd, imm } => write!(f, "mov {}, #{imm}", r(rd)),
+            Branch { offset } => write!(f, "b {}", rel(offset)),
+            BranchCond { cond, offset } => {
+                write!(f, "b{} {}", CONDS[cond as usize & 0xF], rel(offset))
+            }
+            AddReg { rd, rn, rm } => write!(f, "add {}, {}, {}", r(rd), r(rn), r(rm)),
+            AddImm3 { rd, rn, imm } => write!(f, "add {}, {}, #{imm}", r(rd), r(rn)),
+            AddImm8 { rd, imm } => write!(f, "add {}, #{imm}", r(rd)),
+            SubReg { rd, rn, rm } => write!(f, "sub {}, {}, {}", r(rd), r(rn), r(rm)),
+            SubImm3 { rd, rn, imm } => write!(f, "sub {}, {}, #{imm}", r(rd), r(rn)),
+            SubImm8 { rd, imm } => write!(f, "sub {}, #{imm}", r(rd)),
+            CmpImm { rn, imm } => write!(f, "cmp {}, #{imm}", r(rn)),
+            CmpReg { rn, rm } => write!(f, "cmp {}, {}", r(rn), r(rm)),
+            Cmn { rn, rm } => write!(f, "cmn {}, {}", r(rn), r(rm)),
+            Tst { rn, rm } => write!(f, "tst {}, {}", r(rn), r(rm)),
+            MovReg { rd, rm } => write!(f, "mov {}, {}", r(rd), r(rm)),
+            Movw { rd, imm } => write!(f, "movw {}, #{imm:#x}", r(rd)),
+            Movt { rd, imm } => write!(f, "movt {}, #{imm:#x}", r(rd)),
+            AddSp { imm } => write!(f, "add sp, #{imm}"),
+            SubSp { imm } => write!(f, "sub sp, #{imm}"),
+            AddRegHigh { rd, rm } => write!(f, "add {}, {}", r(rd), r(rm)),
+            Cpsie { primask, faultmask } | Cpsid { primask, faultmask } => {
+                let op = if matches!(self, Cpsie { .. }) {
+                    "cpsie"
+                } else {
+                    "cpsid"
+                };
+                let flags = match (primask, faultmask) {
+                    (true, true) => "if",
+                    (false, true) => "f",
+                    _ => "i",
+                };
+                write!(f, "{op} {flags}")
+            }
+            And { rd, rm } => write!(f, "and {}, {}", r(rd), r(rm)),
+            Bic { rd, rm } => write!(f, "bic {}, {}", r(rd), r(rm)),
+            Orr { rd, rm } => write!(f, "orr {}, {}", r(rd), r(rm)),
+            Eor { rd, rm } => write!(f, "eor {}, {}", r(rd), r(rm)),
+            Mvn { rd, rm } => write!(f, "mvn {}, {}", r(rd), r(rm)),
+            Lsl { rd, rm, imm } => write!(f, "lsl {}, {}, #{imm}", r(rd), r(rm)),
+            Lsr { rd, rm, imm } => write!(f, "lsr {}, {}, #{imm}", r(rd), r(rm)),
+            Asr { rd, rm, imm } => write!(f, "asr {}, {}, #{imm}", r(rd), r(rm)),
+            LslReg { rd, rm } => write!(f, "lsl {}, {}", r(rd), r(rm)),
+            LsrReg { rd, rm } => write!(f, "lsr {}, {}", r(rd), r(rm)),
+            AsrReg { rd, rm } => write!(f, "asr {}, {}", r(rd), r(rm)),
+            Adc { rd, rm } => write!(f, "adc {}, {}", r(rd), r(rm)),
+            Sbc { rd, rm } => write!(f, "sbc {}, {}", r(rd), r(rm)),
+            Ror { rd, rm } => write!(f, "ror {}, {}", r(rd), r(rm)),
+            LdrImm { rt, rn, imm } => write!(f, "ldr {}, [{}, #{imm}]", r(rt), r(rn)),
+            StrImm { rt, rn, imm } => write!(f, "str {}, [{}, #{imm}]", r(rt), r(rn)),
+            LdrLit { rt, imm } => write!(f, "ldr {}, [pc, #{imm}]", r(rt)),
+            LdrImm32 { rt, rn, imm12 } => write!(f, "ldr.w {}, [{}, #{imm12}]", r(rt), r(rn)),
+            StrImm32 { rt, rn, imm12 } => write!(f, "str.w {}, [{}, #{imm12}]", r(rt), r(rn)),
+            LdrImm32Idx {
+                rt,
+                rn,
+                imm8,
+                pre_index,
+                add,
+                writeback,
+            } => write!(
+                f,
+                "ldr.w {}, {}",
+                r(rt),
+                indexed(rn, imm8 as u32, add, pre_index, writeback)
+            ),
+            StrImm32Idx {
+                rt,
+                rn,
+                imm8,
+                pre_index,
+                add,
+                writeback,
+            } => write!(
+                f,
+                "str.w {}, {}",
+                r(rt),
+                indexed(rn, imm8 as u32, add, pre_index, writeback)
+            ),
+            LdrbImm { rt, rn, imm } => write!(f, "ldrb {}, [{}, #{imm}]", r(rt), r(rn)),
+            StrbImm { rt, rn, imm } => write!(f, "strb {}, [{}, #{imm}]", r(rt), r(rn)),
+            LdrhImm { rt, rn, imm } => write!(f, "ldrh {}, [{}, #{imm}]", r(rt), r(rn)),
+            StrhImm { rt, rn, imm } => write!(f, "strh {}, [{}, #{imm}]", r(rt), r(rn)),
+            LdrReg { rt, rn, rm } => write!(f, "ldr {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            StrReg { rt, rn, rm } => write!(f, "str {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            LdrbReg { rt, rn, rm } => write!(f, "ldrb {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            StrbReg { rt, rn, rm } => write!(f, "strb {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            LdrhReg { rt, rn, rm } => write!(f, "ldrh {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            StrhReg { rt, rn, rm } => write!(f, "strh {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            LdrsbReg { rt, rn, rm } => write!(f, "ldrsb {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            LdrshReg { rt, rn, rm } => write!(f, "ldrsh {}, [{}, {}]", r(rt), r(rn), r(rm)),
+            Push { registers, m } => {
+                let lr = if m { 1 << 14 } else { 0 };
+                write!(f, "push {}", reg_list(registers as u16 | lr))
+            }
+            Pop { registers, p } => {
+                let pc = if p { 1 << 15 } else { 0 };
+                write!(f, "pop {}", reg_list(registers as u16 | pc))
+            }
+            Ldm { rn, registers } => {
+                // T1 LDM writes the base back unless it is in the list.
+                let wb = if registers & (1 << rn) == 0 { "!" } else { "" };
+                write!(f, "ldm {}{wb}, {}", r(rn), reg_list(registers as u16))
+            }
+            Stm { rn, registers } => write!(f, "stm {}!, {}", r(rn), reg_list(registers as u16)),
+            Cbz { rn, imm } => write!(f, "cbz {}, {}", r(rn), rel(imm as i32)),
+            Cbnz { rn, imm } => write!(f, "cbnz {}, {}", r(rn), rel(imm as i32)),
+            Bl { offset } => write!(f, "bl {}", rel(offset)),
+            Bx { rm } => write!(f, "bx {}", r(rm)),
+            BlxReg { rm } => write!(f, "blx {}", r(rm)),
+            Mul { rd, rn } => write!(f, "mul {}, {}, {}", r(rd), r(rn), r(rd)),
+            LdrSp { rt, imm } => write!(f, "ldr {}, [sp, #{imm}]", r(rt)),
+            StrSp { rt, imm } => write!(f, "str {}, [sp, #{imm}]", r(rt)),
+            AddSpReg { rd, imm } => write!(f, "add {}, sp, #{imm}", r(rd)),
+            Mul32 { rd, rn, rm } => write!(f, "mul.w {}, {}, {}", r(rd), r(rn), r(rm)),
+            Uxtb { rd, rm } => write!(f, "uxtb {}, {}", r(rd), r(rm)),
+            Sxth { rd, rm } => write!(f, "sxth {}, {}", r(rd), r(rm)),
+            Sxtb { rd, rm } => write!(f, "sxtb {}, {}", r(rd), r(rm)),
+            Uxth { rd, rm } => write!(f, "uxth {}, {}", r(rd), r(rm)),
+            ExtendW {
+                rd,
+                rn,
+                rm,
+                rotate,
+                op,
+            } => {
+                let base = if op & 1 == 0 { "sxt" } else { "uxt" };
+                let size = if op >= 4 { "b" } else { "h" };
+                let ror = if rotate != 0 {
+                    format!(", ror #{rotate}")
+                } else {
+                    String::new()
+                };
+                if rn == 0xF {
+                    write!(f, "{base}{size}.w {}, {}{ror}", r(rd), r(rm))
+                } else {
+                    write!(f, "{base}a{size} {}, {}, {}{ror}", r(rd), r(rn), r(rm))
+                }
+            }
+            Adr { rd, imm } => write!(f, "adr {}, #{imm}", r(rd)),
+            AddwImm { rd, rn, imm } => write!(f, "addw {}, {}, #{imm}", r(rd), r(rn)),
+            SubwImm { rd, rn, imm } => write!(f, "subw {}, {}, #{imm}", r(rd), r(rn)),
+            Rsbs { rd, rn } => write!(f, "rsbs {}, {}, #0", r(rd), r(rn)),
+            Bfi { rd, rn, lsb, width } => write!(f, "bfi {}, {}, #{lsb}, #{width}", r(rd), r(rn)),
+            Bfc { rd, lsb, width } => write!(f, "bfc {}, #{lsb}, #{width}", r(rd)),
+            Sbfx { rd, rn, lsb, width } => write!(f, "sbfx {}, {}, #{lsb}, #{width}", r(rd), r(rn)),
+            Ubfx { rd, rn, lsb, width } => write!(f, "ubfx {}, {}, #{lsb}, #{width}", r(rd), r(rn)),
+            Clz { rd, rm } => write!(f, "clz {}, {}", r(rd), r(rm)),
+            Rbit { rd, rm } => write!(f, "rbit {}, {}", r(rd), r(rm)),
+            Rev { rd, rm } => write!(f, "rev {}, {}", r(rd), r(rm)),
+            Rev16 { rd, rm } => write!(f, "rev16 {}, {}", r(rd), r(rm)),
+            RevSh { rd, rm } => write!(f, "revsh {}, {}", r(rd), r(rm)),
+            SimdAddSub8 { rd, rn, rm, op } => {
+                let name = ["sadd8", "uadd8", "ssub8", "usub8"]
+                    .get(op as usize)
+                    .copied()
+                    .unwrap_or("simd8?");
+                write!(f, "{name} {}, {}, {}", r(rd), r(rn), r(rm))
+            }
+            Sel { rd, rn, rm } => write!(f, "sel {}, {}, {}", r(rd), r(rn), r(rm)),
+            Udiv { rd, rn, rm } => write!(f, "udiv {}, {}, {}", r(rd), r(rn), r(rm)),
+            Sdiv { rd, rn, rm } => write!(f, "sdiv {}, {}, {}", r(rd), r(rn), r(rm)),
+            DataProc32 {
+                op,
+                rn,
+                rd,
+                rm,
+                imm5,
+                shift_type,
+                set_flags,
+            } => {
+                let (name, has_rd, has_rn) = data_proc_name(op, rn, rd, set_flags);
+                let flags = if set_flags && has_rd { "s" } else { "" };
+                let mut ops = Vec::new();
+                if has_rd {
+                    ops.push(r(rd));
+                }
+                if has_rn {
+                    ops.push(r(rn));
+                }
+                ops.push(r(rm));
+                let shift = shift_suffix(shift_type, imm5);
+                write!(f, "{name}{flags}.w {}{shift}", ops.join(", "))
+            }
+            DataProcImm32 {
+                op,
+                rn,
+                rd,
+                imm12,
+                set_flags,
+            } => {
+                let (name, has_rd, has_rn) = data_proc_name(op, rn, rd, set_flags);
+                let flags = if set_flags && has_rd { "s" } else { "" };
+                let mut ops = Vec::new();
+                if has_rd {
+                    ops.push(r(rd));
+                }
+                if has_rn {
+                    ops.push(r(rn));
+                }
+                let imm = crate::cpu::cortex_m::thumb_expand_imm(imm12);
+                write!(f, "{name}{flags}.w {}, #{imm:#x}", ops.join(", "))
+            }
+            ShiftReg32 {
+                rd,
+                rn,
+                rm,
+                shift_type,
+            } => {
+                let name = ["lsl", "lsr", "asr", "ror"][shift_type as usize & 3];
+                write!(f, "{name}.w {}, {}, {}", r(rd), r(rn), r(rm))
+            }
+            It { cond, mask } => {
+                // Each mask bit above the terminating 1 adds a then (bit ==
+                // firstcond[0]) or else slot.
+                let pattern: String = (mask.trailing_zeros() as u8 + 1..4)
+                    .rev()
+                    .map(|i| {
+                        if (mask >> i) & 1 == cond & 1 {
+                            't'
+                        } else {
+                            'e'
+                        }
+                    })
+                    .collect();
+                let name = CONDS[cond as usize & 0xF];
+                let name = if name.is_empty() { "al" } else { name };
+                write!(f, "it{pattern} {name}")
+            }
+            LdmiaW {
+                rn,
+                reg_list: list,
+                writeback,
+            } => {
+                let wb = if writeback { "!" } else { "" };
+                write!(f, "ldmia.w {}{wb}, {}", r(rn), reg_list(list))
+            }
+            StmdbW {
+                rn,
+                reg_list: list,
+                writeback,
+            } => {
+                let wb = if writeback { "!" } else { "" };
+                write!(f, "stmdb {}{wb}, {}", r(rn), reg_list(list))
+            }
+            StmiaW {
+                rn,
+                reg_list: list,
+                writeback,
+            } => {
+                let wb = if writeback { "!" } else { "" };
+                write!(f, "stmia.w {}{wb}, {}", r(rn), reg_list(list))
+            }
+            LdmdbW {
+                rn,
+                reg_list: list,
+                writeback,
+            } => {
+                let wb = if writeback { "!" } else { "" };
+                write!(f, "ldmdb {}{wb}, {}", r(rn), reg_list(list))
+            }
+            Ldrd {
+                rt,
+                rt2,
+                rn,
+                imm8,
+                add_imm,
+                index,
+                writeback,
+            } => write!(
+                f,
+                "ldrd {}, {}, {}",
+                r(rt),
+                r(rt2),
+                indexed(rn, imm8 * 4, add_imm, index, writeback)
+            ),
+            Strd {
+                rt,
+                rt2,
+                rn,
+                imm8,
+                add_imm,
+                index,
+                writeback,
+            } => write!(
+                f,
+                "strd {}, {}, {}",
+                r(rt),
+                r(rt2),
+                indexed(rn, imm8 * 4, add_imm, index, writeback)
+            ),
+            Tbb { rn, rm } => write!(f, "tbb [{}, {}]", r(rn), r(rm)),
+            Tbh { rn, rm } => write!(f, "tbh [{}, {}, lsl #1]", r(rn), r(rm)),
+            Bkpt { imm8 } => write!(f, "bkpt #{imm8}"),
+            Svc { imm8 } => write!(f, "svc #{imm8}"),
+            // DMB/DSB/ISB decode to one variant; the kind is not kept.
+            Barrier => write!(f, "barrier"),
+            Mrs { rd, sysm } => write!(f, "mrs {}, {}", r(rd), sysm_name(sysm)),
+            Msr { sysm, rn } => write!(f, "msr {}, {}", sysm_name(sysm), r(rn)),
+            Smull {
+                rd_lo,
+                rd_hi,
+                rn,
+                rm,
+            }
+            | Umull {
+                rd_lo,
+                rd_hi,
+                rn,
+                rm,
+            }
+            | Smlal {
+                rd_lo,
+                rd_hi,
+                rn,
+                rm,
+            }
+            | Umlal {
+                rd_lo,
+                rd_hi,
+                rn,
+                rm,
+            }
+            | Umaal {
+                rd_lo,
+                rd_hi,
+                rn,
+                rm,
+            } => {
+                let name = match self {
+                    Smull { .. } => "smull",
+                    Umull { .. } => "umull",
+                    Smlal { .. } => "smlal",
+                    Umlal { .. } => "umlal",
+                    _ => "umaal",
+                };
+                write!(f, "{name} {}, {}, {}, {}", r(rd_lo), r(rd_hi), r(rn), r(rm))
+            }
+            Mla { rd, rn, rm, ra } => {
+                write!(f, "mla {}, {}, {}, {}", r(rd), r(rn), r(rm), r(ra))
+            }
+            Mls { rd, rn, rm, ra } => {
+                write!(f, "mls {}, {}, {}, {}", r(rd), r(rn), r(rm), r(ra))
+            }
+            Vldr { sd, rn, imm, add } => {
+                let sign = if add { "" } else { "-" };
+                write!(f, "vldr {}, [{}, #{sign}{imm}]", s(sd), r(rn))
+            }
+            Vstr { sd, rn, imm, add } => {
+                let sign = if add { "" } else { "-" };
+                write!(f, "vstr {}, [{}, #{sign}{imm}]", s(sd), r(rn))
+            }
+            VmulF32 { sd, sn, sm } => write!(f, "vmul.f32 {}, {}, {}", s(sd), s(sn), s(sm)),
+            VaddF32 { sd, sn, sm } => write!(f, "vadd.f32 {}, {}, {}", s(sd), s(sn), s(sm)),
+            VsubF32 { sd, sn, sm } => write!(f, "vsub.f32 {}, {}, {}", s(sd), s(sn), s(sm)),
+            VdivF32 { sd, sn, sm } => write!(f, "vdiv.f32 {}, {}, {}", s(sd), s(sn), s(sm)),
+            VmovSnRt { sn, rt } => write!(f, "vmov {}, {}", s(sn), r(rt)),
+            VmovRtSn { rt, sn } => write!(f, "vmov {}, {}", r(rt), s(sn)),
+            VmovF32Reg { sd, sm } => write!(f, "vmov.f32 {}, {}", s(sd), s(sm)),
+            VmovF32Imm { sd, imm_bits } => {
+                write!(f, "vmov.f32 {}, #{}", s(sd), f32::from_bits(imm_bits))
+            }
+            VcvtF32FromInt {
+                sd,
+                sm,
+                signed,
+                fbits,
+            } => {
+                let src = if signed { "s32" } else { "u32" };
+                if fbits == 0 {
+                    write!(f, "vcvt.f32.{src} {}, {}", s(sd), s(sm))
+                } else {
+                    write!(f, "vcvt.f32.{src} {}, {}, #{fbits}", s(sd), s(sm))
+                }
+            }
+            VcvtIntFromF32 {
+                sd,
+                sm,
+                signed,
+                fbits,
+            } => {
+                let dst = if signed { "s32" } else { "u32" };
+                if fbits == 0 {
+                    write!(f, "vcvt.{dst}.f32 {}, {}", s(sd), s(sm))
+                } else {
+                    write!(f, "vcvt.{dst}.f32 {}, {}, #{fbits}", s(sd), s(sm))
+                }
+            }
+            VfpStoreMultiple {
+                rn,
+                s_first,
+                count,
+                add,
+                wback,
+            }
+            | VfpLoadMultiple {
+                rn,
+                s_first,
+                count,
+                add,
+                wback,
+            } => {
+                let op = if matches!(self, VfpStoreMultiple { .. }) {
+                    "vstm"
+                } else {
+                    "vldm"
+                };
+                let mode = if add { "ia" } else { "db" };
+                let wb = if wback { "!" } else { "" };
+                let last = s_first + count.saturating_sub(1);
+                write!(
+                    f,
+                    "{op}{mode} {}{wb}, {{{}-{}}}",
+                    r(rn),
+                    s(s_first),
+                    s(last)
+                )
+            }
+            Vldr64 { dd, rn, imm, add } => {
+                let sign = if add { "" } else { "-" };
+                write!(f, "vldr {}, [{}, #{sign}{imm}]", d(dd), r(rn))
+            }
+            Vstr64 { dd, rn, imm, add } => {
+                let sign = if add { "" } else { "-" };
+                write!(f, "vstr {}, [{}, #{sign}{imm}]", d(dd), r(rn))
+            }
+            VmovF64Reg { dd, dm } => write!(f, "vmov.f64 {}, {}", d(dd), d(dm)),
+            VmovDRtRt2 { dm, rt, rt2 } => write!(f, "vmov {}, {}, {}", d(dm), r(rt), r(rt2)),
+            VmovRtRt2D { rt, rt2, dm } => write!(f, "vmov {}, {}, {}", r(rt), r(rt2), d(dm)),
+            VaddF64 { dd, dn, dm } => write!(f, "vadd.f64 {}, {}, {}", d(dd), d(dn), d(dm)),
+            VsubF64 { dd, dn, dm } => write!(f, "vsub.f64 {}, {}, {}", d(dd), d(dn), d(dm)),
+            VmulF64 { dd, dn, dm } => write!(f, "vmul.f64 {}, {}, {}", d(dd), d(dn), d(dm)),
+            VdivF64 { dd, dn, dm } => write!(f, "vdiv.f64 {}, {}, {}", d(dd), d(dn), d(dm)),
+            Unknown(h) => write!(f, ".inst.n {h:#06x}"),
+            Unknown32(h1, h2) => write!(f, ".inst.w {h1:#06x}{h2:04x}"),
+        }
+    }
+}
+
 /// Low S-register indices (2*Dreg) of the three operands of a double-precision
 /// VFP data-processing encoding: `1110 111o oDoo nnnn dddd 1011 NoMo mmmm`.
 fn vfp_dp_regs(h1: u16, h2: u16) -> (u8, u8, u8) {
@@ -1212,13 +1803,15 @@ pub fn decode_thumb_16(opcode: u16) -> Instruction {
                 return Instruction::It { cond, mask };
             }
             // Hint space (mask == 0): the `cond` nibble selects the hint.
-            // 0x3 = WFI, which we model with a real sleep + idle fast-forward.
-            // WFE (0x2)/YIELD (0x1)/SEV (0x4) fall through to Nop: no event
-            // register is modelled, so they have no observable effect.
-            if cond == 0x3 {
-                return Instruction::Wfi;
-            }
-            return Instruction::Nop;
+            // WFI (0x3) and WFE (0x2) sleep with idle fast-forward; SEV
+            // (0x4) sets the event register WFE consumes. YIELD (0x1) and the
+            // rest have no observable effect and decode to Nop.
+            return match cond {
+                0x2 => Instruction::Wfe,
+                0x3 => Instruction::Wfi,
+                0x4 => Instruction::Sev,
+                _ => Instruction::Nop,
+            };
         }
     }
 
@@ -2950,6 +3543,51 @@ mod tests {
         assert_eq!(decode_thumb_16(0xBF00), Instruction::Nop);
     }
 
+    #[test]
+    fn test_decode_hints() {
+        assert_eq!(decode_thumb_16(0xBF10), Instruction::Nop); // YIELD
+        assert_eq!(decode_thumb_16(0xBF20), Instruction::Wfe);
+        assert_eq!(decode_thumb_16(0xBF30), Instruction::Wfi);
+        assert_eq!(decode_thumb_16(0xBF40), Instruction::Sev);
+    }
+
+    #[test]
+    fn test_display_disassembles_to_arm_syntax() {
+        assert_eq!(decode_thumb_16(0x2001).to_asm(), "mov r0, #1");
+        assert_eq!(decode_thumb_16(0x6851).to_asm(), "ldr r1, [r2, #4]");
+        assert_eq!(decode_thumb_16(0xB501).to_string(), "push {r0, lr}");
+        assert_eq!(decode_thumb_16(0xE7FE).to_string(), "b .+0");
+        assert_eq!(decode_thumb_16(0xBF0C).to_string(), "ite eq");
+        assert_eq!(
+            decode_thumb_32(0xE9D1, 0x0708).to_string(),
+            "ldrd r0, r7, [r1, #32]"
+        );
+    }
+
+    #[test]
+    fn test_to_asm_at_annotates_call_targets() {
+        // bl +0xFC from 0x0800_0000 → 0x0800_0100.
+        let bl = decode_thumb_32(0xF000, 0xF87E);
+        assert_eq!(bl.branch_target(0x0800_0000), Some(0x0800_0100));
+        let symbolize = |addr: u32| (addr == 0x0800_0100).then(|| "main".to_string());
+        assert_eq!(
+            bl.to_asm_at(0x0800_0000, Some(&symbolize)),
+            "bl 0x8000100 <main>"
+        );
+        assert_eq!(bl.to_asm_at(0x0800_0000, None), "bl 0x8000100");
+
+        // cbz r0 keeps its register operand; unresolved targets stay bare.
+        let cbz = decode_thumb_16(0xB108); // cbz r0, .+6
+        assert_eq!(
+            cbz.to_asm_at(0x0800_0010, Some(&symbolize)),
+            "cbz r0, 0x8000016"
+        );
+        assert_eq!(
+            decode_thumb_16(0x2001).to_asm_at(0, Some(&symbolize)),
+            "mov r0, #1"
+        );
+    }
+
     #[test]
     fn test_decode_shifts() {
         // LSLS R0, R1, #2 -> 0x0088 (000 00 00010 001 000)
@@ -0,0 +1,74 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Rough energy estimation: a per-instruction-class cost for the core plus a
+//! per-cycle cost for peripheral activity. Good enough to compare two builds
+//! of the same firmware on the same chip ("this refactor burns 12% more"),
+//! not to predict a battery's life — the defaults are order-of-magnitude
+//! figures for a 40 nm Cortex-M4 at nominal voltage.
+
+use serde::{Deserialize, Serialize};
+
+/// Coarse instruction categories with distinct energy costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionClass {
+    /// Register-to-register data processing, moves, compares, system ops.
+    Alu,
+    /// Loads and stores, including push/pop and multi-register transfers.
+    Memory,
+    /// Branches, calls and table branches.
+    Branch,
+    /// Integer multiply, multiply-accumulate and divide.
+    Multiply,
+    /// Floating-point arithmetic and conversions.
+    Float,
+}
+
+/// Energy cost per event, in picojoules. Set through
+/// [`crate::SimulationConfig::energy_model`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnergyModel {
+    pub alu_pj: f64,
+    pub memory_pj: f64,
+    pub branch_pj: f64,
+    pub multiply_pj: f64,
+    pub float_pj: f64,
+    /// Per cycle a peripheral reports busy from its `tick`.
+    pub peripheral_active_pj: f64,
+}
+
+impl Default for EnergyModel {
+    fn default() -> Self {
+        Self {
+            alu_pj: 20.0,
+            memory_pj: 45.0,
+            branch_pj: 30.0,
+            multiply_pj: 35.0,
+            float_pj: 50.0,
+            peripheral_active_pj: 10.0,
+        }
+    }
+}
+
+impl EnergyModel {
+    /// Cost of retiring one instruction of `class`.
+    #[inline]
+    pub fn instruction_pj(&self, class: InstructionClass) -> f64 {
+        match class {
+            InstructionClass::Alu => self.alu_pj,
+            InstructionClass::Memory => self.memory_pj,
+            InstructionClass::Branch => self.branch_pj,
+            InstructionClass::Multiply => self.multiply_pj,
+            InstructionClass::Float => self.float_pj,
+        }
+    }
+}
+
+/// Picojoules to the joules `Cpu::get_energy_consumption` reports.
+pub(crate) fn pj_to_joules(pj: f64) -> f64 {
+    pj * 1e-12
+}
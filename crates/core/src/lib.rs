@@ -13,11 +13,13 @@ pub mod coverage;
 pub mod cpu;
 pub mod cycle_clock;
 pub mod decoder;
+pub mod energy;
 pub mod fidelity;
 pub mod inspect;
 pub mod interrupt;
 pub mod logic_capture;
 pub mod machine;
+pub mod mem_access_log;
 pub mod memory;
 pub mod metrics;
 pub mod multi_core;
@@ -35,7 +37,7 @@ pub mod trace;
 pub mod vfi;
 pub mod world;
 
-pub use config::SimulationConfig;
+pub use config::{SimulationConfig, SimulationConfigBuilder};
 pub use cycle_clock::CycleClock;
 pub use machine::{
     AdvanceLimits, AdvanceReport, AdvanceRequest, AdvanceStop, BatchPolicy, BreakpointPolicy,
@@ -55,6 +57,27 @@ pub enum Arch {
     Unknown,
 }
 
+/// Byte order of multi-byte data accesses on the target, taken from the ELF
+/// header's `EI_DATA`. `Big` covers byte-invariant BE8 images: data words are
+/// big-endian while Thumb instructions stay little-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+/// Cortex-M architecture profile. ARMv6-M cores (M0/M0+/M1) implement only a
+/// handful of 32-bit Thumb-2 encodings; everything else is UNDEFINED there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArmProfile {
+    V6M,
+    #[default]
+    V7M,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SimulationError {
     #[error("Memory access violation at {0:#x}")]
@@ -77,10 +100,57 @@ pub enum SimulationError {
 
 pub type SimResult<T> = Result<T, SimulationError>;
 
+/// Which kind of CPU fault stopped the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultCause {
+    /// A bus access (fetch, load/store, or strict-alignment check) failed.
+    MemoryViolation,
+    /// The instruction at `pc` could not be decoded.
+    DecodeError,
+    /// The CPU raised an architectural exception it could not handle.
+    Exception,
+}
+
+/// Context of the fault that stopped a CPU window, passed to
+/// [`SimulationObserver::on_fault`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FaultInfo {
+    pub kind: FaultCause,
+    /// PC of the faulting instruction. Exact for single steps; inside a batch
+    /// a latched data-access fault reports the PC the batch stopped at.
+    pub pc: u32,
+    /// Offending address: the failed access for a memory violation, the
+    /// undecodable instruction for a decode error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<u64>,
+}
+
+impl FaultInfo {
+    /// Classify `err`; `None` for stops that are not faults (halt,
+    /// breakpoint, host-side errors).
+    pub fn from_error(err: &SimulationError, pc: u32) -> Option<Self> {
+        let (kind, pc, address) = match *err {
+            SimulationError::MemoryViolation(addr) => (FaultCause::MemoryViolation, pc, Some(addr)),
+            SimulationError::DecodeError(addr) => (FaultCause::DecodeError, pc, Some(addr)),
+            SimulationError::ExceptionRaised { pc, .. } => (FaultCause::Exception, pc, None),
+            _ => return None,
+        };
+        Some(Self { kind, pc, address })
+    }
+}
+
+/// Which side of a [`DmaRequest`] is the peripheral. The bus moves every
+/// element the same way — read `src_addr`, write `addr` — so a
+/// peripheral-side access keeps its register side effects (e.g. a data
+/// register read clearing RXNE).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DmaDirection {
+    /// Peripheral → memory.
     Read,
+    /// Memory → peripheral.
     Write,
+    /// Memory → memory.
     Copy,
 }
 
@@ -88,7 +158,6 @@ pub enum DmaDirection {
 pub struct DmaRequest {
     pub src_addr: u64,
     pub addr: u64,
-    pub val: u8,
     pub direction: DmaDirection,
     /// Unit-level reshape for engines that convert between source and
     /// destination data widths (STM32H5 GPDMA CTR1 PAM / SBX / DBX / DHX).
@@ -126,6 +195,8 @@ pub struct PeripheralTickResult {
     pub dma_requests: Option<Vec<DmaRequest>>,
     /// Extra NVIC IRQ positions beyond the peripheral's default.
     pub explicit_irqs: Option<Vec<u32>>,
+    /// NVIC IRQ positions whose pending bit the bus should clear (ICPR).
+    pub cleared_irqs: Option<Vec<u32>>,
     /// System exception number (0..15) to raise — for SysTick (15) and
     /// other core-tied exceptions that bypass NVIC. Pushed directly to
     /// the CPU's pending_exceptions bitmap; PRIMASK still gates it.
@@ -163,8 +234,34 @@ pub trait SimulationObserver: std::fmt::Debug + Send + Sync {
     fn on_trace_event(&self, _event: labwired_hw_trace::TraceEvent) {}
     fn on_step_start(&self, _pc: u32, _opcode: u32) {}
     fn on_step_end(&self, _cycles: u32, _registers: &[u32]) {}
+    /// Whether this observer needs `on_step_start`/`on_step_end` for every
+    /// instruction. Observers that only accumulate totals return `false`; a
+    /// CPU with a batched fast path (Cortex-M `step_batch`) then reports to
+    /// them once per batch through `on_batch_retired` instead. Single steps
+    /// and CPUs without that path keep calling the per-step hooks, so such an
+    /// observer must implement both — each instruction reaches it exactly once.
+    fn wants_step_events(&self) -> bool {
+        true
+    }
+    /// Instructions retired and their core cycles since the last report, for
+    /// observers whose `wants_step_events` is `false`.
+    fn on_batch_retired(&self, _instructions: u32, _cycles: u64) {}
+    /// Periodic run progress: total primary steps retired since reporting
+    /// was armed with `Machine::set_progress_interval`, and the average
+    /// instructions per second over that span (0 where no wall clock exists).
+    fn on_progress(&self, _steps: u64, _ips: f64) {}
     fn on_memory_write(&self, _addr: u64, _old: u8, _new: u8) {}
+    /// Called once per bus read after it completes, with its width in bytes
+    /// (1, 2 or 4) and the value returned.
+    fn on_memory_read(&self, _addr: u64, _width: u8, _value: u32) {}
+    /// Called once per `write_u8`/`write_u16`/`write_u32` after it lands,
+    /// with its width in bytes. `on_memory_write` reports the same store
+    /// split into byte lanes.
+    fn on_memory_store(&self, _addr: u64, _width: u8, _value: u32) {}
     fn on_peripheral_tick(&self, _name: &str, _cycles: u32) {}
+    /// Called once when a CPU fault stops `Machine::advance`, before the
+    /// error is returned.
+    fn on_fault(&self, _fault: FaultInfo) {}
 }
 
 pub fn emit_trace_event(
@@ -274,6 +371,9 @@ pub trait Cpu: Send {
     fn inject_fault(&mut self, _target: &str) -> SimResult<()> {
         Ok(())
     }
+    /// Energy the core has spent since reset, in joules, under the
+    /// [`energy::EnergyModel`] in the `SimulationConfig` it was stepped with.
+    /// Cores without a model report 0.
     fn get_energy_consumption(&self) -> f64 {
         0.0
     }
@@ -531,6 +631,21 @@ pub trait Peripheral: std::fmt::Debug + Send {
     /// polarity. Default no-op.
     fn observe_gpio_change(&mut self, _changes: &[(u8, u8, u8)]) {}
 
+    /// Interrupt-acknowledge hook: the bus calls this with the NVIC IRQ
+    /// number when the CPU takes that exception and clears its pending bit.
+    /// Models whose flags self-clear on vectoring override it. Returns `true`
+    /// when the ack scheduled new tick work, so the bus re-indexes the
+    /// peripheral for the legacy walk. Default no-op.
+    fn on_irq_ack(&mut self, _irq: u32) -> bool {
+        false
+    }
+
+    /// True if this peripheral overrides [`Self::on_irq_ack`]; the bus only
+    /// calls the hook on those. Default false.
+    fn handles_irq_ack(&self) -> bool {
+        false
+    }
+
     /// GPIO capability: read the firmware-visible input level for `pin`.
     /// Non-GPIO peripherals return `None`.
     fn read_gpio_input(&self, _pin: u8) -> Option<bool> {
@@ -745,6 +860,22 @@ pub trait Peripheral: std::fmt::Debug + Send {
         Ok(())
     }
 
+    /// Return to the power-on register state. Called by [`Machine::reset`]
+    /// (firmware load, SYSRESETREQ, FLASH bank swap) for every peripheral on
+    /// the bus. Input levels driven from outside the chip (GPIO IDR, ADC
+    /// channel inputs) are not registers and survive. Default no-op for
+    /// models with no resettable register file.
+    fn reset(&mut self) {}
+
+    /// Register byte offsets that keep their contents across a
+    /// [`ResetKind::Warm`] or [`ResetKind::Pin`] reset (backup-domain
+    /// registers such as the RTC backup registers). The bus reads them before
+    /// [`Self::reset`] and writes them back after, so they must be plain
+    /// read/write storage. Empty by default: everything is cleared.
+    fn warm_reset_retained(&self) -> Vec<std::ops::Range<u64>> {
+        Vec::new()
+    }
+
     /// Optional source register descriptor for debugger clients that need the
     /// config-level layout (including reset values and descriptions), rather
     /// than the display-oriented [`Self::describe_registers`] schema.
@@ -1121,6 +1252,16 @@ pub trait DebugControl {
     fn read_core_reg(&self, id: u8) -> u32;
     fn write_core_reg(&mut self, id: u8, val: u32);
 
+    /// Read a core register by the name the CPU resolves with
+    /// [`Cpu::index_of_register`] (e.g. `"pc"`, `"r0"`, `"a0"`).
+    fn read_register_by_name(&self, name: &str) -> SimResult<u32>;
+    /// Write a core register by name; see [`Self::read_register_by_name`].
+    fn write_register_by_name(&mut self, name: &str, val: u32) -> SimResult<()>;
+
+    /// Inject a fault named by `target` (see [`vfi::Fault::parse`] for the
+    /// accepted strings, e.g. `"bitflip:r0:3"` or `"skip_instruction"`).
+    fn inject_fault(&mut self, target: &str) -> SimResult<()>;
+
     fn read_memory(&self, addr: u32, len: usize) -> SimResult<Vec<u8>>;
     fn write_memory(&mut self, addr: u32, data: &[u8]) -> SimResult<()>;
 
@@ -1128,18 +1269,61 @@ pub trait DebugControl {
     fn set_pc(&mut self, addr: u32);
     fn get_register_names(&self) -> Vec<String>;
     fn get_cycle_count(&self) -> u64;
+    /// Primary-core instructions retired since the step profile was last
+    /// reset (see [`StepProfile::cpu_instructions`]).
+    fn get_instruction_count(&self) -> u64;
     fn get_peripherals(&self) -> Vec<(String, u64, u64)>;
     fn get_peripheral_descriptor(
         &self,
         name: &str,
     ) -> Option<labwired_config::PeripheralDescriptor>;
+    /// Power-on reset; shorthand for `reset_with(ResetKind::PowerOn)`.
     fn reset(&mut self) -> SimResult<()>;
+    fn reset_with(&mut self, kind: ResetKind) -> SimResult<()>;
+
+    /// Register an observer, e.g. a host-language progress callback.
+    fn add_observer(&mut self, observer: Arc<dyn SimulationObserver>);
+    /// Report `on_progress` every `interval` retired steps (`None` = off).
+    fn set_progress_interval(&mut self, interval: Option<u64>);
+    /// Flag that makes `run` return [`StopReason::ManualStop`] when set,
+    /// usable from another thread while `run` holds the machine.
+    fn stop_handle(&self) -> Arc<std::sync::atomic::AtomicBool>;
+    /// Capture console UART output into `sink` (still echoed to stdout), or
+    /// stop capturing with `None`.
+    fn set_uart_tx_sink(&mut self, sink: Option<Arc<std::sync::Mutex<Vec<u8>>>>);
 
     // State Management
     fn snapshot(&self) -> snapshot::MachineSnapshot;
     fn restore(&mut self, snapshot: &snapshot::MachineSnapshot) -> SimResult<()>;
 }
 
+/// What triggered a reset. Matches the STM32 split: a power-on (or
+/// brown-out) reset clears everything including the backup domain, while a
+/// system reset (SYSRESETREQ, watchdog) or an NRST pin reset keeps the
+/// registers peripherals list in [`Peripheral::warm_reset_retained`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResetKind {
+    #[default]
+    PowerOn,
+    Warm,
+    Pin,
+}
+
+impl std::str::FromStr for ResetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "power_on" | "por" | "bor" => Ok(ResetKind::PowerOn),
+            "warm" | "system" => Ok(ResetKind::Warm),
+            "pin" | "nrst" => Ok(ResetKind::Pin),
+            other => Err(format!(
+                "unknown reset kind '{other}' (expected power_on, warm or pin)"
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum StopReason {
     Breakpoint(u32),
@@ -1181,11 +1365,26 @@ pub struct Machine<C: Cpu> {
     // Debug state
     pub breakpoints: std::collections::HashSet<u32>,
     pub last_breakpoint: Option<u32>,
+    /// The CPU fault that ended the last `advance`, if any. Hosts read it
+    /// after a run instead of registering an observer (which would disable
+    /// the observer-free fast paths). Cleared when a new advance starts.
+    pub last_fault: Option<FaultInfo>,
+    progress: machine::progress::ProgressReporter,
+    /// Cooperative cancellation flag; see [`Machine::stop_handle`].
+    stop_request: Arc<std::sync::atomic::AtomicBool>,
     pub total_cycles: u64,
+    /// CPU-side cycles only (retired instructions plus idle fast-forward),
+    /// without the peripheral tick costs `total_cycles` also carries. The
+    /// peripheral tick cadence runs on this clock, so a tick fires every
+    /// `peripheral_tick_interval` instructions whatever those ticks cost.
+    pub cpu_cycles: u64,
     /// Cumulative CPU cycles advanced by idle fast-forward (WFI skip), not
     /// interpreted. Lets the browser `?perf=1` HUD prove FF is firing; 0 means
     /// either FF is off or firmware never parks in a skippable idle.
     pub idle_fast_forward_cycles_skipped: u64,
+    /// Cycles peripherals reported busy from `tick` since reset; priced by
+    /// `EnergyModel::peripheral_active_pj` in [`Machine::energy_consumption`].
+    pub peripheral_active_cycles: u64,
     pub config: SimulationConfig,
     step_profile: StepProfile,
 
@@ -1341,6 +1540,12 @@ impl<C: Cpu> Machine<C> {
         self.bus.set_input(Some(component), channel, value)
     }
 
+    /// Drive a board-level GPIO input (delegates to
+    /// [`bus::SystemBus::set_board_input`]); the edge also reaches EXTI.
+    pub fn set_board_input(&mut self, gpio: &str, pin: u8, level: bool) -> Result<(), String> {
+        self.bus.set_board_input(gpio, pin, level)
+    }
+
     /// Apply several input sets as one atomic transaction (delegates to
     /// [`bus::SystemBus::set_inputs`]): every set is validated first and
     /// either all apply or none do, with no execution in between — the way to
@@ -1531,8 +1736,13 @@ impl<C: Cpu> Machine<C> {
             observers: Vec::new(),
             breakpoints: HashSet::new(),
             last_breakpoint: None,
+            last_fault: None,
+            progress: Default::default(),
+            stop_request: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             total_cycles: 0,
+            cpu_cycles: 0,
             idle_fast_forward_cycles_skipped: 0,
+            peripheral_active_cycles: 0,
             config: SimulationConfig::default(),
             step_profile: StepProfile::default(),
             sched: sched::EventScheduler::new(),
@@ -1555,6 +1765,26 @@ impl<C: Cpu> Machine<C> {
         }
     }
 
+    /// Like [`Self::new`], but with host-chosen run-loop settings.
+    ///
+    /// The bus keeps its chip-derived fields (alignment, profile, byte order);
+    /// `peripheral_tick_interval` is mirrored onto it, since the bus's
+    /// legacy-walk quantum must agree with the machine's tick pacing, and
+    /// `strict_alignment` can turn alignment faults on (never off: an ARMv6-M
+    /// core has no unaligned support to fall back on).
+    pub fn with_config(cpu: C, bus: bus::SystemBus, config: SimulationConfig) -> Self {
+        let mut machine = Self::new(cpu, bus);
+        machine.bus.config.peripheral_tick_interval = config.peripheral_tick_interval;
+        machine.bus.config.strict_alignment |= config.strict_alignment;
+        machine.config = SimulationConfig {
+            strict_alignment: machine.bus.config.strict_alignment,
+            endianness: machine.bus.config.endianness,
+            arm_profile: machine.bus.config.arm_profile,
+            ..config
+        };
+        machine
+    }
+
     /// Advance every centrally-driven I²C slave's data-ready clock to the chip's
     /// authoritative simulated-µs "now" (Option A). Called once per scheduler
     /// slice from [`Self::commit_advance_boundary`].
@@ -1597,6 +1827,44 @@ impl<C: Cpu> Machine<C> {
         self.last_i2c_time_us = now;
     }
 
+    /// Call [`SimulationObserver::on_progress`] every `interval` retired
+    /// primary steps, counting from now; `None` turns reporting off. CPU
+    /// windows are cut at each report boundary, so reports land on exact
+    /// multiples of `interval`.
+    pub fn set_progress_interval(&mut self, interval: Option<u64>) {
+        self.progress.set_interval(interval);
+    }
+
+    /// Shared flag that stops the current (or next) `advance` at the next CPU
+    /// window boundary with [`AdvanceStop::StopRequested`]. Setting it from
+    /// another thread interrupts an unbounded `run(None)`; the machine clears
+    /// it when it stops.
+    pub fn stop_handle(&self) -> Arc<std::sync::atomic::AtomicBool> {
+        self.stop_request.clone()
+    }
+
+    /// Sanity-check the vector table the Cortex-M core booted from (the one
+    /// VTOR points at after [`Machine::load_firmware`]): the initial SP must
+    /// lie in RAM and the reset handler in flash, where any named memory
+    /// region counts as either (see
+    /// [`crate::bus::SystemBus::vector_pair_issues`]). Returns one message
+    /// per problem; empty when the table looks sane or the bus has no NVIC
+    /// (not a Cortex-M machine).
+    pub fn boot_vector_issues(&self) -> Vec<String> {
+        if self.bus.nvic.is_none() {
+            return Vec::new();
+        }
+        let vtor = self.bus.read_u32(0xE000_ED08).unwrap_or(0) as u64;
+        self.bus.vector_pair_issues(vtor)
+    }
+
+    /// True when the core sleeps in WFI/WFE and nothing is enabled that could
+    /// ever wake it: no NVIC-enabled IRQ and no SysTick with TICKINT. Only
+    /// answered for Cortex-M buses (an NVIC attached); always false elsewhere.
+    pub fn is_sleep_deadlocked(&self) -> bool {
+        self.cpu.is_parked_idle() && self.bus.cortex_m_wake_source_enabled() == Some(false)
+    }
+
     /// Enable dual-core mode by attaching a secondary CPU instance.
     /// The secondary CPU shares the same bus and steps in lockstep with
     /// the primary (one instruction each per `Machine::step()`). Used by
@@ -1614,6 +1882,20 @@ impl<C: Cpu> Machine<C> {
         self.step_profile
     }
 
+    /// Estimated energy spent since reset, in joules: every core's
+    /// instruction energy plus peripheral activity, under
+    /// `config.energy_model`. A rough figure for comparing firmware builds.
+    pub fn energy_consumption(&self) -> f64 {
+        let cores = self.cpu.get_energy_consumption()
+            + self
+                .cpu_secondary
+                .as_ref()
+                .map_or(0.0, |cpu| cpu.get_energy_consumption());
+        let peripherals =
+            self.peripheral_active_cycles as f64 * self.config.energy_model.peripheral_active_pj;
+        cores + energy::pj_to_joules(peripherals)
+    }
+
     fn record_peripheral_tick_profile(&mut self, cost_entries: usize) {
         let (bus_tick_entries, legacy_tick_entries) = self.bus.tick_profile_entry_counts();
         self.step_profile.peripheral_ticks += 1;
@@ -1700,6 +1982,7 @@ impl<C: Cpu> Machine<C> {
             let skipped = budget.min(u32::MAX as u64) as u32;
             self.cpu.fast_forward_idle_cycles(skipped as u64);
             self.total_cycles += skipped as u64;
+            self.cpu_cycles += skipped as u64;
             self.idle_fast_forward_cycles_skipped += skipped as u64;
             self.bus.set_current_cycle(self.total_cycles);
             self.bus.bus_trace.set_cycle(self.total_cycles);
@@ -1760,6 +2043,14 @@ impl<C: Cpu> Machine<C> {
             }
             snap.memories = memories;
         }
+        // Sparse windows on every arch: one entry per backed page. Pages are
+        // never released, so every page the firmware load allocated on the
+        // restoring machine is among them and gets overwritten.
+        for mem in &self.bus.sparse_mem {
+            for (addr, page) in mem.backed_pages() {
+                snap.memories.push((addr, page.to_vec()));
+            }
+        }
         snap
     }
 
@@ -1788,11 +2079,25 @@ impl<C: Cpu> Machine<C> {
                 })?;
             entry.dev.restore_runtime_snapshot(blob)?;
         }
-        // Restore flat linear windows (RISC-V SRAM/IRAM; empty for other
-        // arches). Match each captured window to its live backing by base
-        // address and fail loudly on a topology/size mismatch — a resume must
-        // be applied on the same machine layout it was captured on.
+        // Restore flat linear windows (RISC-V SRAM/IRAM) and sparse pages.
+        // Match each captured window to its live backing by base address (a
+        // sparse page to the window containing it) and fail loudly on a
+        // topology/size mismatch — a resume must be applied on the same
+        // machine layout it was captured on.
         for (base, bytes) in &snap.memories {
+            if let Some(sparse) = self
+                .bus
+                .sparse_mem
+                .iter_mut()
+                .find(|m| m.read_u8(*base).is_some())
+            {
+                if !sparse.write_bytes(*base, bytes) {
+                    return Err(SimulationError::NotImplemented(format!(
+                        "apply_runtime_snapshot: page @ {base:#010x} overruns its sparse window"
+                    )));
+                }
+                continue;
+            }
             let target = if *base == self.bus.ram.base_addr {
                 Some(&mut self.bus.ram)
             } else {
@@ -1819,7 +2124,10 @@ impl<C: Cpu> Machine<C> {
 
 impl<C: Cpu> Machine<C> {
     pub fn load_firmware(&mut self, image: &memory::ProgramImage) -> SimResult<()> {
-        for segment in &image.segments {
+        // Multi-byte bus accesses follow the image's declared byte order.
+        self.bus.config.endianness = image.endianness;
+        self.config.endianness = image.endianness;
+        for segment in &image.coalesced(&self.memory_region_bounds()) {
             // 1. Try Cortex-M / ARM flash backing.
             if self.bus.flash.load_from_segment(segment) {
                 continue;
@@ -1837,6 +2145,15 @@ impl<C: Cpu> Machine<C> {
             {
                 continue;
             }
+            // 2c. Try sparse windows (large 64-bit / external flash maps).
+            if self
+                .bus
+                .sparse_mem
+                .iter_mut()
+                .any(|m| m.load_from_segment(segment))
+            {
+                continue;
+            }
             // 3. Fall back to peripheral-backed memory (ESP32 IRAM /
             //    flash XIP, RP2040 SRAM, etc — anything registered as a
             //    Peripheral rather than living in bus.flash/bus.ram).
@@ -1857,7 +2174,55 @@ impl<C: Cpu> Machine<C> {
                 );
             }
         }
+        self.boot_loaded_image(image)
+    }
+
+    /// [`Machine::load_firmware`] with explicit routing: each segment goes to
+    /// the named memory region ([`crate::bus::SystemBus::named_memory_regions`]) that
+    /// wholly contains it, including `memory_regions` such as external RAM or
+    /// a second flash bank. A segment no region contains is an error rather
+    /// than a byte-wise bus fallback.
+    pub fn load_firmware_by_region(&mut self, image: &memory::ProgramImage) -> SimResult<()> {
+        self.bus.config.endianness = image.endianness;
+        self.config.endianness = image.endianness;
+        // Route every segment before writing any, so an unroutable image
+        // leaves memory untouched.
+        let regions = self.bus.named_memory_regions();
+        let routed = image
+            .coalesced(&self.memory_region_bounds())
+            .into_iter()
+            .map(|segment| {
+                let (start, end) = (segment.start_addr, segment.end_addr());
+                regions
+                    .iter()
+                    .find(|&&(_, base, size)| start >= base && end <= base + size)
+                    .map(|&(name, _, _)| (name.to_string(), segment))
+                    .ok_or_else(|| {
+                        SimulationError::Other(format!(
+                            "segment {start:#x}..{end:#x} is not inside any named memory region"
+                        ))
+                    })
+            })
+            .collect::<SimResult<Vec<_>>>()?;
+        for (region, segment) in &routed {
+            self.bus.load_segment_into(region, segment)?;
+        }
+        self.boot_loaded_image(image)
+    }
 
+    /// `(base, size)` of every named memory region, the boundaries
+    /// [`memory::ProgramImage::coalesced`] must not join segments across.
+    fn memory_region_bounds(&self) -> Vec<(u64, u64)> {
+        self.bus
+            .named_memory_regions()
+            .into_iter()
+            .map(|(_, base, size)| (base, size))
+            .collect()
+    }
+
+    /// Post-load half of the loaders: notify observers, reset, then resolve
+    /// the reset vector for the freshly loaded image.
+    fn boot_loaded_image(&mut self, image: &memory::ProgramImage) -> SimResult<()> {
         for observer in &self.observers {
             observer.on_simulation_start();
         }
@@ -1873,7 +2238,20 @@ impl<C: Cpu> Machine<C> {
         // table at `flash_base` (no stage-2 boot2). Prefer flash-base vectors
         // when they are valid; only then fall back to post-boot2 relocation.
         let flash_base = self.bus.flash.base_addr;
-        if self.bus.vector_pair_valid(flash_base) {
+        if let Some(table) = self
+            .bus
+            .vtor
+            .filter(|&table| self.bus.vector_pair_valid(table))
+        {
+            // Configured boot VTOR (application behind a bootloader, or a RAM
+            // table) wins over the flash-base vectors, which may be valid but
+            // belong to the bootloader.
+            let sp = self.bus.read_u32(table)?;
+            let pc = self.bus.read_u32(table + 4)? & !1;
+            let _ = self.bus.write_u32(0xE000_ED08, table as u32);
+            self.cpu.set_sp(sp);
+            self.cpu.set_pc(pc);
+        } else if self.bus.vector_pair_valid(flash_base) {
             let sp = self.bus.read_u32(flash_base)?;
             let pc = self.bus.read_u32(flash_base + 4)? & !1;
             // If address 0 does not already present the same SP (bootrom or
@@ -1913,7 +2291,16 @@ impl<C: Cpu> Machine<C> {
         Ok(())
     }
 
+    /// Power-on reset: every peripheral returns to its reset state.
     pub fn reset(&mut self) -> SimResult<()> {
+        self.reset_with(ResetKind::PowerOn)
+    }
+
+    /// Reset the peripherals and CPU(s). Warm and pin resets keep each
+    /// peripheral's [`Peripheral::warm_reset_retained`] registers.
+    pub fn reset_with(&mut self, kind: ResetKind) -> SimResult<()> {
+        self.bus.reset_peripherals(kind);
+        self.peripheral_active_cycles = 0;
         self.cpu.reset(&mut self.bus)?;
         if let Some(cpu1) = self.cpu_secondary.as_mut() {
             cpu1.reset(&mut self.bus)?;
@@ -2002,7 +2389,7 @@ impl<C: Cpu> Machine<C> {
                         flash.mark_swapped();
                     }
                     tracing::debug!("FLASH SwapAndReset: banks swapped, resetting CPU");
-                    self.reset()?;
+                    self.reset_with(ResetKind::Warm)?;
                 }
             }
         }
@@ -2321,6 +2708,7 @@ impl<C: Cpu> Machine<C> {
                 .bus
                 .peripherals
                 .iter()
+                .filter(|p| !p.is_detached())
                 .map(|p| (p.name.clone(), p.dev.snapshot()))
                 .collect(),
         }
@@ -2336,7 +2724,12 @@ impl<C: Cpu> Machine<C> {
         self.cpu.apply_snapshot(&snapshot.cpu);
         for p in &mut self.bus.peripherals {
             if let Some(state) = snapshot.peripherals.get(&p.name) {
-                p.dev.restore(state.clone())?;
+                p.dev.restore(state.clone()).map_err(|e| match e {
+                    SimulationError::Other(msg) => {
+                        SimulationError::Other(format!("peripheral '{}': {msg}", p.name))
+                    }
+                    e => e,
+                })?;
             }
         }
         self.bus.refresh_peripheral_index();
@@ -2366,6 +2759,7 @@ impl<C: Cpu> Machine<C> {
             .bus
             .peripherals
             .iter()
+            .filter(|entry| !entry.is_detached())
             .filter(|entry| filter.is_none_or(|f| entry.name == f))
             .map(|entry| entry.dev.inspect(entry.base, &entry.name, opts))
             .collect();
@@ -2407,6 +2801,7 @@ impl<C: Cpu> DebugControl for Machine<C> {
             AdvanceStop::Breakpoint(pc) => StopReason::Breakpoint(pc),
             AdvanceStop::FuelLimit => StopReason::MaxStepsReached,
             AdvanceStop::CycleLimit | AdvanceStop::NoProgress => StopReason::StepDone,
+            AdvanceStop::StopRequested => StopReason::ManualStop,
         })
     }
     fn step_single(&mut self) -> SimResult<StopReason> {
@@ -2422,6 +2817,35 @@ impl<C: Cpu> DebugControl for Machine<C> {
         self.cpu.set_register(id, val);
     }
 
+    fn read_register_by_name(&self, name: &str) -> SimResult<u32> {
+        let id = self
+            .cpu
+            .index_of_register(name)
+            .ok_or_else(|| SimulationError::Other(format!("unknown register '{name}'")))?;
+        Ok(self.cpu.get_register(id))
+    }
+
+    fn write_register_by_name(&mut self, name: &str, val: u32) -> SimResult<()> {
+        let id = self
+            .cpu
+            .index_of_register(name)
+            .ok_or_else(|| SimulationError::Other(format!("unknown register '{name}'")))?;
+        self.cpu.set_register(id, val);
+        Ok(())
+    }
+
+    fn inject_fault(&mut self, target: &str) -> SimResult<()> {
+        // Memory faults are applied here, where the bus is; the rest are the
+        // core's to model.
+        match vfi::Fault::parse(target, &self.cpu)? {
+            vfi::Fault::MemoryBitFlip { address, bit } => {
+                let value = self.bus.read_u8(address)?;
+                self.bus.write_u8(address, value ^ (1 << bit))
+            }
+            _ => self.cpu.inject_fault(target),
+        }
+    }
+
     fn read_memory(&self, addr: u32, len: usize) -> SimResult<Vec<u8>> {
         let mut data = Vec::with_capacity(len);
         for i in 0..len {
@@ -2432,10 +2856,7 @@ impl<C: Cpu> DebugControl for Machine<C> {
     }
 
     fn write_memory(&mut self, addr: u32, data: &[u8]) -> SimResult<()> {
-        for (i, byte) in data.iter().enumerate() {
-            self.bus.write_u8((addr as u64) + (i as u64), *byte)?;
-        }
-        Ok(())
+        self.bus.write_bytes(addr as u64, data)
     }
 
     fn get_pc(&self) -> u32 {
@@ -2454,6 +2875,10 @@ impl<C: Cpu> DebugControl for Machine<C> {
         self.total_cycles
     }
 
+    fn get_instruction_count(&self) -> u64 {
+        self.step_profile.cpu_instructions
+    }
+
     fn get_peripherals(&self) -> Vec<(String, u64, u64)> {
         self.bus
             .peripherals
@@ -2474,6 +2899,29 @@ impl<C: Cpu> DebugControl for Machine<C> {
         Machine::reset(self)
     }
 
+    fn reset_with(&mut self, kind: ResetKind) -> SimResult<()> {
+        Machine::reset_with(self, kind)
+    }
+
+    fn add_observer(&mut self, observer: Arc<dyn SimulationObserver>) {
+        self.observers.push(observer);
+    }
+
+    fn set_progress_interval(&mut self, interval: Option<u64>) {
+        Machine::set_progress_interval(self, interval)
+    }
+
+    fn stop_handle(&self) -> Arc<std::sync::atomic::AtomicBool> {
+        Machine::stop_handle(self)
+    }
+
+    fn set_uart_tx_sink(&mut self, sink: Option<Arc<std::sync::Mutex<Vec<u8>>>>) {
+        match sink {
+            Some(sink) => self.bus.attach_uart_tx_sink(sink, true),
+            None => self.bus.detach_uart_tx_sink(),
+        }
+    }
+
     fn snapshot(&self) -> snapshot::MachineSnapshot {
         self.snapshot()
     }
@@ -5,6 +5,7 @@ use crate::{
     AdvanceReport, AdvanceRequest, AdvanceStop, BreakpointPolicy, Cpu, IdlePolicy, Machine,
     SimResult,
 };
+use std::sync::atomic::Ordering;
 
 #[derive(Default)]
 struct AdvanceState {
@@ -33,7 +34,8 @@ impl<C: Cpu> Machine<C> {
     /// Advances the machine through its authoritative execution path.
     ///
     /// Normal stop conditions are checked before the next unit of work, in
-    /// this order: honored breakpoint, fuel limit, then simulated-cycle limit.
+    /// this order: stop request, honored breakpoint, fuel limit, then
+    /// simulated-cycle limit.
     /// Fuel counts primary scheduling quanta plus cycles skipped by idle fast
     /// forward. A simulated-cycle limit is observed only at committed machine
     /// boundaries: CPU work is planned not to exceed the remaining budget, but
@@ -54,10 +56,17 @@ impl<C: Cpu> Machine<C> {
     pub fn advance(&mut self, request: AdvanceRequest) -> SimResult<AdvanceReport> {
         let start_cycles = self.total_cycles;
         let mut state = AdvanceState::default();
+        self.last_fault = None;
 
         loop {
             let elapsed = self.total_cycles - start_cycles;
 
+            // Checked before every CPU window so another thread can interrupt
+            // an unbounded run; consuming the request re-arms the flag.
+            if self.stop_request.swap(false, Ordering::AcqRel) {
+                return Ok(state.report(AdvanceStop::StopRequested, elapsed));
+            }
+
             if request.breakpoint_policy() == BreakpointPolicy::Honor {
                 let pc = self.cpu.get_pc();
                 let aligned = pc & !1;
@@ -129,16 +138,51 @@ impl<C: Cpu> Machine<C> {
                 ExecutionMode::RunBatch
             };
             let batch_start = self.total_cycles;
-            let progress = self.execute_cpu_window(mode, count)?;
+            // Only CPU-window accesses count; drop anything a debugger or
+            // host-side read latched while the machine was stopped.
+            self.bus.take_alignment_fault();
+            let window_pc = self.cpu.get_pc();
+            let progress = match self.execute_cpu_window(mode, count) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    self.notify_fault(&e, self.cpu.get_pc());
+                    return Err(e);
+                }
+            };
+            if let Some(addr) = self.bus.take_alignment_fault() {
+                let e = crate::SimulationError::MemoryViolation(addr);
+                // The store/load already retired; a one-instruction window
+                // still knows which instruction it was.
+                let pc = if progress.primary_steps == 1 {
+                    window_pc
+                } else {
+                    self.cpu.get_pc()
+                };
+                self.notify_fault(&e, pc);
+                return Err(e);
+            }
             if progress.primary_steps == 0 {
                 return Ok(state.report(AdvanceStop::NoProgress, self.total_cycles - start_cycles));
             }
 
             self.commit_advance_boundary(mode, batch_start, progress)?;
+            self.progress
+                .retired(u64::from(progress.primary_steps), &self.observers);
             state.fuel_consumed += u64::from(progress.primary_steps);
             state.primary_steps += u64::from(progress.primary_steps);
             state.secondary_steps += u64::from(progress.secondary_steps);
             state.cpu_batches += 1;
         }
     }
+
+    /// Record a CPU-window error in `last_fault` and report it to every
+    /// observer if it is a fault.
+    fn notify_fault(&mut self, err: &crate::SimulationError, pc: u32) {
+        self.last_fault = crate::FaultInfo::from_error(err, pc);
+        if let Some(fault) = self.last_fault {
+            for observer in &self.observers {
+                observer.on_fault(fault);
+            }
+        }
+    }
 }
@@ -25,6 +25,7 @@ impl<C: Cpu> Machine<C> {
             ExecutionMode::SingleDirect | ExecutionMode::RunDual => {
                 debug_assert_eq!(count, 1);
                 self.total_cycles += 1;
+                self.cpu_cycles += 1;
                 self.bus.set_current_cycle(self.total_cycles);
                 self.bus.bus_trace.set_cycle(self.total_cycles);
                 if self.logic_capture.push_active() {
@@ -144,6 +145,7 @@ impl<C: Cpu> Machine<C> {
     ) -> SimResult<()> {
         if mode == ExecutionMode::RunBatch {
             self.total_cycles += u64::from(progress.primary_steps);
+            self.cpu_cycles += u64::from(progress.primary_steps);
         }
         self.record_cpu_progress(progress.primary_steps);
 
@@ -160,10 +162,12 @@ impl<C: Cpu> Machine<C> {
         let coalesced_dual_idle = mode == ExecutionMode::RunBatch
             && progress.secondary_steps > 0
             && progress.primary_steps > 1;
+        // Cadence on `cpu_cycles`: tick costs below land in `total_cycles`
+        // only, so they cannot shift or skip the next tick boundary.
         let should_tick = if coalesced_dual_idle {
             true
         } else {
-            self.total_cycles % tick_interval == 0
+            self.cpu_cycles % tick_interval == 0
         };
         if should_tick {
             let saved_m = self.config.peripheral_tick_interval;
@@ -183,6 +187,7 @@ impl<C: Cpu> Machine<C> {
             self.record_peripheral_tick_profile(costs.len());
             for c in costs.iter() {
                 self.total_cycles += c.cycles as u64;
+                self.peripheral_active_cycles += c.cycles as u64;
                 if let Some(p) = self.bus.peripherals.get(c.index) {
                     for observer in &self.observers {
                         observer.on_peripheral_tick(&p.name, c.cycles);
@@ -244,14 +249,15 @@ impl<C: Cpu> Machine<C> {
         // AIRCR-writing store and any pending peripheral effects of this
         // instruction have been applied — then reuse the power-on reset
         // machinery so MSP/PC reload from vector[0]/vector[1] via the CPU
-        // reset path. No-op on non-Cortex-M targets (no SCB on the bus).
+        // reset path. It is a system (warm) reset, so backup-domain registers
+        // survive. No-op on non-Cortex-M targets (no SCB on the bus).
         if self.drain_scb_reset_request() {
             // AIRCR.SYSRESETREQ is self-clearing across the reset. Clear the
             // modeled readback bit too so later boundaries do not retrigger it.
             if let Some(index) = self.scb_index {
                 self.bus.peripherals[index].dev.write_u32(0x0c, 0)?;
             }
-            self.reset()?;
+            self.reset_with(crate::ResetKind::Warm)?;
             tracing::debug!("SCB SYSRESETREQ: CPU rebooted through vector table");
         }
 
@@ -9,6 +9,7 @@ use std::num::NonZeroU32;
 mod advance;
 mod boundary;
 mod plan;
+pub(crate) mod progress;
 
 /// Controls whether an advance request observes configured breakpoints.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -164,6 +165,8 @@ pub enum AdvanceStop {
     Breakpoint(u32),
     /// The CPU reported zero forward progress.
     NoProgress,
+    /// A stop was requested through [`crate::Machine::stop_handle`].
+    StopRequested,
 }
 
 /// Structured progress and stop accounting for one advance operation.
@@ -21,6 +21,9 @@ impl<C: Cpu> Machine<C> {
         if let BatchPolicy::AtMost(cap) = request.batch_policy() {
             count = count.min(u64::from(cap.get()));
         }
+        if let Some(until_report) = self.progress.steps_until_report() {
+            count = count.min(until_report);
+        }
 
         // Dual-core: only lockstep while APP is active or still in reset-hold.
         // WAITI-parked APP (FreeRTOS idle) lets PRO batch.
@@ -59,7 +62,7 @@ impl<C: Cpu> Machine<C> {
             count = count.min(1024);
         } else {
             // Normal path: batch only up to the next peripheral tick boundary.
-            let until_tick = tick_interval - (self.total_cycles % tick_interval);
+            let until_tick = tick_interval - (self.cpu_cycles % tick_interval);
             count = count.min(until_tick);
         }
 
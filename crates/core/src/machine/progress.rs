@@ -0,0 +1,74 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Periodic [`SimulationObserver::on_progress`] reports for long runs.
+
+use crate::SimulationObserver;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+/// Counts retired primary steps across `advance` calls and reports to the
+/// observers every `interval` of them. Off (`interval == None`) by default,
+/// which leaves CPU window planning untouched.
+#[derive(Debug, Default)]
+pub(crate) struct ProgressReporter {
+    interval: Option<NonZeroU64>,
+    steps: u64,
+    next_report: u64,
+    /// Wall clock for the IPS figure. `Instant` is unavailable on
+    /// `wasm32-unknown-unknown`, where IPS is reported as 0.
+    #[cfg(not(target_arch = "wasm32"))]
+    started: Option<std::time::Instant>,
+}
+
+impl ProgressReporter {
+    /// (Re)arm reporting every `interval` steps, counting from zero.
+    pub(crate) fn set_interval(&mut self, interval: Option<u64>) {
+        *self = Self {
+            interval: interval.and_then(NonZeroU64::new),
+            next_report: interval.unwrap_or(0),
+            ..Self::default()
+        };
+    }
+
+    /// Steps left before the next report, so the planner can end a CPU
+    /// window exactly on it; `None` when reporting is off.
+    pub(crate) fn steps_until_report(&self) -> Option<u64> {
+        self.interval?;
+        Some(self.next_report.saturating_sub(self.steps).max(1))
+    }
+
+    /// Account for `steps` retired primary steps and report if a boundary was
+    /// reached.
+    pub(crate) fn retired(&mut self, steps: u64, observers: &[Arc<dyn SimulationObserver>]) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        let started = *self.started.get_or_insert_with(std::time::Instant::now);
+        self.steps += steps;
+        if self.steps < self.next_report {
+            return;
+        }
+        let interval = interval.get();
+        self.next_report = (self.steps / interval + 1) * interval;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let ips = {
+            let elapsed = started.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                self.steps as f64 / elapsed
+            } else {
+                0.0
+            }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let ips = 0.0;
+        for observer in observers {
+            observer.on_progress(self.steps, ips);
+        }
+    }
+}
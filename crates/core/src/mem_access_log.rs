@@ -0,0 +1,157 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Address-filtered memory access log for debugging peripheral drivers.
+//!
+//! [`MemoryAccessLogger`] records every bus read and write that lands in one
+//! address window, one line per access with its width, tagged with the PC of
+//! the instruction that issued it. Register it on the bus (`bus.observers`) to
+//! receive the accesses and on the machine (`add_observer`) so
+//! `on_step_start` keeps the PC current; without the latter every line reports
+//! `PC=0x00000000`. The log keeps the most recent `capacity` lines and counts
+//! the ones it dropped, so a long run cannot grow it without bound.
+
+use crate::SimulationObserver;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Lines [`MemoryAccessLogger::new`] keeps before dropping the oldest.
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+/// Observer that logs accesses inside `range` as
+/// `PC=0x........ R|W<bits> addr=0x........ val=0x..` lines, e.g.
+/// `PC=0x08000010 W32 addr=0x4001080c val=0x00000021`.
+#[derive(Debug)]
+pub struct MemoryAccessLogger {
+    range: Range<u64>,
+    capacity: usize,
+    pc: AtomicU32,
+    lines: Mutex<VecDeque<String>>,
+    dropped: AtomicU64,
+}
+
+impl MemoryAccessLogger {
+    pub fn new(range: Range<u64>) -> Self {
+        Self::with_capacity(range, DEFAULT_CAPACITY)
+    }
+
+    /// Logger that keeps at most `capacity` lines, dropping the oldest.
+    pub fn with_capacity(range: Range<u64>, capacity: usize) -> Self {
+        Self {
+            range,
+            capacity,
+            pc: AtomicU32::new(0),
+            lines: Mutex::new(VecDeque::new()),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Logged lines still held, oldest first.
+    pub fn lines(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|l| l.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Logged lines still held, leaving the log empty.
+    pub fn take_lines(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|mut l| std::mem::take(&mut *l).into())
+            .unwrap_or_default()
+    }
+
+    /// Lines discarded because the log was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn record(&self, kind: char, addr: u64, width: u8, value: u32) {
+        if !self.range.contains(&addr) || self.capacity == 0 {
+            return;
+        }
+        let pc = self.pc.load(Ordering::Relaxed);
+        let bits = u32::from(width) * 8;
+        let digits = usize::from(width) * 2 + 2;
+        let line = format!("PC={pc:#010x} {kind}{bits} addr={addr:#010x} val={value:#0digits$x}");
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            lines.push_back(line);
+        }
+    }
+}
+
+impl SimulationObserver for MemoryAccessLogger {
+    fn on_step_start(&self, pc: u32, _opcode: u32) {
+        self.pc.store(pc, Ordering::Relaxed);
+    }
+
+    fn on_memory_read(&self, addr: u64, width: u8, value: u32) {
+        self.record('R', addr, width, value);
+    }
+
+    fn on_memory_store(&self, addr: u64, width: u8, value: u32) {
+        self.record('W', addr, width, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::SystemBus;
+    use crate::Bus;
+    use std::sync::Arc;
+
+    #[test]
+    fn logs_only_accesses_inside_the_gpio_window() {
+        let logger = Arc::new(MemoryAccessLogger::new(0x4001_0800..0x4001_0C00));
+        let mut bus = SystemBus::new();
+        bus.observers.push(logger.clone());
+
+        logger.on_step_start(0x0800_0010, 0x6001);
+        bus.write_u32(0x4001_080C, 0x0000_0021).unwrap(); // GPIOA ODR
+        bus.write_u8(0x2000_0000, 0xAA).unwrap(); // RAM, outside the window
+        logger.on_step_start(0x0800_0012, 0x6801);
+        let odr = bus.read_u16(0x4001_080C).unwrap();
+        bus.read_u8(0x2000_0000).unwrap();
+
+        assert_eq!(odr, 0x0021);
+        assert_eq!(
+            logger.take_lines(),
+            [
+                "PC=0x08000010 W32 addr=0x4001080c val=0x00000021",
+                "PC=0x08000012 R16 addr=0x4001080c val=0x0021",
+            ]
+        );
+        assert!(logger.lines().is_empty());
+    }
+
+    #[test]
+    fn keeps_only_the_most_recent_lines() {
+        let logger = Arc::new(MemoryAccessLogger::with_capacity(0x2000_0000..0x2000_0100, 2));
+        let mut bus = SystemBus::new();
+        bus.observers.push(logger.clone());
+
+        for i in 0..5u8 {
+            bus.write_u8(0x2000_0000 + u64::from(i), i).unwrap();
+        }
+
+        assert_eq!(
+            logger.lines(),
+            [
+                "PC=0x00000000 W8 addr=0x20000003 val=0x03",
+                "PC=0x00000000 W8 addr=0x20000004 val=0x04",
+            ]
+        );
+        assert_eq!(logger.dropped(), 3);
+    }
+}
@@ -5,6 +5,7 @@
 // See the LICENSE file in the project root for full license information.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Segment {
@@ -12,13 +13,16 @@ pub struct Segment {
     pub data: Vec<u8>,
 }
 
-use crate::Arch;
+use crate::{Arch, Endianness};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgramImage {
     pub entry_point: u64,
     pub segments: Vec<Segment>,
     pub arch: Arch,
+    /// Data byte order declared by the image (ELF `EI_DATA`).
+    #[serde(default)]
+    pub endianness: Endianness,
 }
 
 impl ProgramImage {
@@ -27,18 +31,82 @@ impl ProgramImage {
             entry_point,
             segments: Vec::new(),
             arch,
+            endianness: Endianness::Little,
         }
     }
 
+    /// Append a load segment as given. Use [`ProgramImage::coalesced`] to
+    /// join contiguous segments and [`ProgramImage::overlaps`] to reject a
+    /// malformed image.
     pub fn add_segment(&mut self, start_addr: u64, data: Vec<u8>) {
         self.segments.push(Segment { start_addr, data });
     }
+
+    /// The segments sorted by address, with contiguous neighbours joined
+    /// when both lie in the same `(base, size)` memory region, so split ELF
+    /// sections load as one run without a run straddling two regions.
+    pub fn coalesced(&self, regions: &[(u64, u64)]) -> Vec<Segment> {
+        let region_of = |addr: u64| {
+            regions
+                .iter()
+                .position(|&(base, size)| addr >= base && addr - base < size)
+        };
+        let mut sorted = self.segments.clone();
+        sorted.sort_by_key(|s| s.start_addr);
+        let mut out: Vec<Segment> = Vec::with_capacity(sorted.len());
+        for segment in sorted {
+            if let Some(prev) = out.last_mut() {
+                let joinable = prev.end_addr() == segment.start_addr
+                    && !segment.data.is_empty()
+                    && region_of(prev.start_addr).is_some_and(|r| {
+                        let (base, size) = regions[r];
+                        segment.end_addr() <= base + size
+                    });
+                if joinable {
+                    prev.data.extend(segment.data);
+                    continue;
+                }
+            }
+            out.push(segment);
+        }
+        out
+    }
+
+    /// Address ranges covered by more than one segment, as `(start, end)`
+    /// half-open pairs. Empty for a well-formed image.
+    pub fn overlaps(&self) -> Vec<(u64, u64)> {
+        let mut out = Vec::new();
+        for (i, a) in self.segments.iter().enumerate() {
+            for b in &self.segments[i + 1..] {
+                let start = a.start_addr.max(b.start_addr);
+                let end = a.end_addr().min(b.end_addr());
+                if start < end {
+                    out.push((start, end));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Segment {
+    /// One past the last address of the segment.
+    pub fn end_addr(&self) -> u64 {
+        self.start_addr + self.data.len() as u64
+    }
 }
 
 /// A simple flat memory storage
 pub struct LinearMemory {
     pub data: Vec<u8>,
     pub base_addr: u64,
+    /// Store permission for CPU/DMA bus writes. The bus checks it before
+    /// storing; image loading and flash-controller commits write the backing
+    /// directly, so a read-only region can still be programmed that way.
+    pub writable: bool,
+    /// Chip-descriptor name of the window (`flash`, `ram`, a `memory_regions`
+    /// entry); empty for windows built outside `SystemBus::from_config`.
+    pub name: String,
 }
 
 impl LinearMemory {
@@ -46,6 +114,16 @@ impl LinearMemory {
         Self {
             data: vec![0; size],
             base_addr,
+            writable: true,
+            name: String::new(),
+        }
+    }
+
+    /// [`LinearMemory::new`] with a region name.
+    pub fn named(name: &str, size: usize, base_addr: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::new(size, base_addr)
         }
     }
 
@@ -115,6 +193,24 @@ impl LinearMemory {
         }
     }
 
+    /// Copy `data` to `addr` when the whole range lies inside this window;
+    /// returns false (storing nothing) otherwise.
+    pub fn write_bytes(&mut self, addr: u64, data: &[u8]) -> bool {
+        let Some(offset) = addr.checked_sub(self.base_addr).map(|o| o as usize) else {
+            return false;
+        };
+        match offset
+            .checked_add(data.len())
+            .and_then(|end| self.data.get_mut(offset..end))
+        {
+            Some(slot) => {
+                slot.copy_from_slice(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Fill `[offset, offset+len)` with `byte`, where `offset` is buffer-relative
     /// (i.e. `absolute_addr - self.base_addr`, the same buffer space `read_u8`/
     /// `write_u8` reach after subtracting `base_addr`). Returns false if the
@@ -164,6 +260,171 @@ impl LinearMemory {
     }
 }
 
+/// Bytes per [`SparseMemory`] page.
+pub const SPARSE_PAGE_SIZE: u64 = 0x1000;
+
+/// Page-backed storage for large, mostly empty windows (64-bit maps, big
+/// external flash). Pages are allocated on first store; reads of a page that
+/// was never written return zero without allocating. Same access interface as
+/// [`LinearMemory`].
+pub struct SparseMemory {
+    pages: HashMap<u64, Box<[u8; SPARSE_PAGE_SIZE as usize]>>,
+    pub base_addr: u64,
+    pub size: u64,
+    /// See [`LinearMemory::name`].
+    pub name: String,
+}
+
+impl SparseMemory {
+    pub fn new(size: u64, base_addr: u64) -> Self {
+        Self {
+            pages: HashMap::new(),
+            base_addr,
+            size,
+            name: String::new(),
+        }
+    }
+
+    /// Window-relative offset of `addr`, or `None` outside the window.
+    fn offset(&self, addr: u64) -> Option<u64> {
+        addr.checked_sub(self.base_addr).filter(|&o| o < self.size)
+    }
+
+    /// Window-relative offset of an `n`-byte access, if all of it is inside.
+    fn span(&self, addr: u64, n: u64) -> Option<u64> {
+        let offset = self.offset(addr)?;
+        (offset.checked_add(n)? <= self.size).then_some(offset)
+    }
+
+    /// Number of pages backed by host memory.
+    pub fn allocated_pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Pages backed by host memory as `(address, bytes)`, in address order.
+    /// Pages never written read as zero and are not listed.
+    pub fn backed_pages(&self) -> Vec<(u64, &[u8])> {
+        let mut pages: Vec<(u64, &[u8])> = self
+            .pages
+            .iter()
+            .map(|(&n, page)| (self.base_addr + n * SPARSE_PAGE_SIZE, &page[..]))
+            .collect();
+        pages.sort_by_key(|&(addr, _)| addr);
+        pages
+    }
+
+    fn byte(&self, offset: u64) -> u8 {
+        self.pages
+            .get(&(offset / SPARSE_PAGE_SIZE))
+            .map_or(0, |page| page[(offset % SPARSE_PAGE_SIZE) as usize])
+    }
+
+    fn set_byte(&mut self, offset: u64, value: u8) {
+        let page = self
+            .pages
+            .entry(offset / SPARSE_PAGE_SIZE)
+            .or_insert_with(|| Box::new([0; SPARSE_PAGE_SIZE as usize]));
+        page[(offset % SPARSE_PAGE_SIZE) as usize] = value;
+    }
+
+    fn read_le<const N: usize>(&self, addr: u64) -> Option<[u8; N]> {
+        let offset = self.span(addr, N as u64)?;
+        Some(std::array::from_fn(|i| self.byte(offset + i as u64)))
+    }
+
+    fn write_le(&mut self, addr: u64, bytes: &[u8]) -> bool {
+        let Some(offset) = self.span(addr, bytes.len() as u64) else {
+            return false;
+        };
+        for (i, b) in bytes.iter().enumerate() {
+            self.set_byte(offset + i as u64, *b);
+        }
+        true
+    }
+
+    pub fn read_u8(&self, addr: u64) -> Option<u8> {
+        self.offset(addr).map(|o| self.byte(o))
+    }
+
+    pub fn write_u8(&mut self, addr: u64, value: u8) -> bool {
+        self.write_le(addr, &[value])
+    }
+
+    pub fn read_u16(&self, addr: u64) -> Option<u16> {
+        self.read_le(addr).map(u16::from_le_bytes)
+    }
+
+    pub fn read_u32(&self, addr: u64) -> Option<u32> {
+        self.read_le(addr).map(u32::from_le_bytes)
+    }
+
+    pub fn write_u16(&mut self, addr: u64, value: u16) -> bool {
+        self.write_le(addr, &value.to_le_bytes())
+    }
+
+    pub fn write_u32(&mut self, addr: u64, value: u32) -> bool {
+        self.write_le(addr, &value.to_le_bytes())
+    }
+
+    pub fn write_bytes(&mut self, addr: u64, data: &[u8]) -> bool {
+        self.write_le(addr, data)
+    }
+
+    pub fn load_from_segment(&mut self, segment: &Segment) -> bool {
+        self.write_le(segment.start_addr, &segment.data)
+    }
+}
+
+#[cfg(test)]
+mod sparse_tests {
+    use super::*;
+
+    #[test]
+    fn far_apart_writes_allocate_two_pages() {
+        let mut m = SparseMemory::new(1 << 40, 0x1_0000_0000);
+        assert_eq!(m.read_u32(0x1_0000_0000), Some(0));
+        assert_eq!(m.allocated_pages(), 0);
+
+        assert!(m.write_u32(0x1_0000_0010, 0xDEAD_BEEF));
+        assert!(m.write_u8(0x80_0000_0000, 0x5A));
+        assert_eq!(m.allocated_pages(), 2);
+        assert_eq!(m.read_u32(0x1_0000_0010), Some(0xDEAD_BEEF));
+        assert_eq!(m.read_u16(0x1_0000_0012), Some(0xDEAD));
+        assert_eq!(m.read_u8(0x80_0000_0000), Some(0x5A));
+
+        // Out of the window, and a word straddling its end.
+        assert_eq!(m.read_u8(0x0FFF_FFFF), None);
+        assert!(!m.write_u32(0x1_0000_0000 + (1 << 40) - 2, 0));
+    }
+
+    #[test]
+    fn segment_load_crosses_pages() {
+        let mut m = SparseMemory::new(0x10_0000, 0x8000_0000);
+        let seg = Segment {
+            start_addr: 0x8000_0FFE,
+            data: vec![1, 2, 3, 4],
+        };
+        assert!(m.load_from_segment(&seg));
+        assert_eq!(m.allocated_pages(), 2);
+        assert_eq!(m.read_u32(0x8000_0FFE), Some(0x0403_0201));
+    }
+
+    #[test]
+    fn backed_pages_list_written_pages_in_address_order() {
+        let mut m = SparseMemory::new(0x10_0000, 0x8000_0000);
+        assert!(m.write_u8(0x8000_5001, 0xAA));
+        assert!(m.write_u8(0x8000_1000, 0x55));
+
+        let pages = m.backed_pages();
+        assert_eq!(
+            pages.iter().map(|&(addr, _)| addr).collect::<Vec<_>>(),
+            [0x8000_1000, 0x8000_5000]
+        );
+        assert_eq!(pages[0].1[0], 0x55);
+        assert_eq!(pages[1].1[1], 0xAA);
+    }
+}
+
 #[cfg(test)]
 mod bank_tests {
     use super::LinearMemory;
@@ -222,6 +483,44 @@ mod tests {
         assert_eq!(mem.read_u8(0x1400), None);
     }
 
+    #[test]
+    fn adjacent_segments_merge() {
+        let mut image = ProgramImage::new(0x0800_0000, Arch::Arm);
+        image.add_segment(0x0800_0000, vec![1, 2]);
+        image.add_segment(0x0800_0004, vec![5, 6]);
+        image.add_segment(0x0800_0002, vec![3, 4]); // bridges the two
+        image.add_segment(0x2000_0000, vec![9]);
+
+        let merged = image.coalesced(&[(0x0800_0000, 0x1000), (0x2000_0000, 0x1000)]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].start_addr, 0x0800_0000);
+        assert_eq!(merged[0].data, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(merged[1].start_addr, 0x2000_0000);
+        assert!(image.overlaps().is_empty());
+    }
+
+    #[test]
+    fn adjacent_segments_do_not_merge_across_regions() {
+        let mut image = ProgramImage::new(0x2000_0000, Arch::Arm);
+        image.add_segment(0x2000_0000, vec![1; 0x10]);
+        image.add_segment(0x2000_0010, vec![2; 0x10]);
+
+        // SRAM1 ends where SRAM2 starts: each segment stays in its own bank.
+        let merged = image.coalesced(&[(0x2000_0000, 0x10), (0x2000_0010, 0x10)]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].start_addr, 0x2000_0010);
+    }
+
+    #[test]
+    fn overlapping_segments_are_detected() {
+        let mut image = ProgramImage::new(0x0800_0000, Arch::Arm);
+        image.add_segment(0x0800_0000, vec![0; 0x10]);
+        image.add_segment(0x0800_0008, vec![0xFF; 0x10]);
+
+        assert_eq!(image.segments.len(), 2);
+        assert_eq!(image.overlaps(), [(0x0800_0008, 0x0800_0010)]);
+    }
+
     #[test]
     fn test_load_from_segment() {
         let mut mem = LinearMemory::new(1024, 0x1000);
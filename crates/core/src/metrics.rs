@@ -108,6 +108,16 @@ impl SimulationObserver for PerformanceMetrics {
         self.cycle_count.fetch_add(cycles as u64, Ordering::SeqCst);
     }
 
+    fn wants_step_events(&self) -> bool {
+        false
+    }
+
+    fn on_batch_retired(&self, instructions: u32, cycles: u64) {
+        self.instruction_count
+            .fetch_add(u64::from(instructions), Ordering::SeqCst);
+        self.cycle_count.fetch_add(cycles, Ordering::SeqCst);
+    }
+
     fn on_peripheral_tick(&self, name: &str, cycles: u32) {
         if cycles == 0 {
             return;
@@ -66,14 +66,14 @@ impl FromStr for AdcRegisterLayout {
 }
 
 /// STM32F1 ADC control registers (status `sr` + data `dr` are shared on `Adc`).
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct F1AdcRegs {
     cr1: u32, // 0x04
     cr2: u32, // 0x08
 }
 
 /// STM32L4 ADC register file (data `dr` is shared on `Adc`).
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct L4AdcRegs {
     isr: u32,        // 0x00
     ier: u32,        // 0x04
@@ -90,7 +90,7 @@ pub struct L4AdcRegs {
 }
 
 /// Family-isolated ADC control registers.
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 enum AdcRegs {
     Stm32F1(F1AdcRegs),
     Stm32L4(L4AdcRegs),
@@ -465,6 +465,37 @@ impl Peripheral for Adc {
     fn snapshot(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    fn reset(&mut self) {
+        let layout = match self.regs {
+            AdcRegs::Stm32F1(_) => AdcRegisterLayout::Stm32F1,
+            AdcRegs::Stm32L4(_) => AdcRegisterLayout::Stm32L4,
+        };
+        // Injected channel inputs are the host's analog world, not ADC state.
+        let power_on = Self::new_with_layout(layout);
+        self.regs = power_on.regs;
+        self.sr = 0;
+        self.dr = 0;
+        self.converting = false;
+        self.cycles_remaining = 0;
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        crate::peripherals::restore_fields!(
+            state,
+            self,
+            [
+                regs,
+                sr,
+                dr,
+                converting,
+                cycles_remaining,
+                conversion_time,
+                channel_inputs
+            ]
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -29,6 +29,33 @@ struct StuckBit {
     level: u8,
 }
 
+/// The byte left behind by a write of `value` over `old` under a register's
+/// `write_action` (SVD `modifiedWriteValues`).
+fn write_action_byte(action: Option<&labwired_config::WriteAction>, old: u8, value: u8) -> u8 {
+    use labwired_config::WriteAction;
+    match action {
+        Some(WriteAction::WriteOneToClear) => old & !value,
+        Some(WriteAction::WriteZeroToClear) => old & value,
+        Some(WriteAction::WriteOneToSet) => old | value,
+        Some(WriteAction::WriteZeroToSet) => old | !value,
+        Some(WriteAction::WriteOneToToggle) => old ^ value,
+        Some(WriteAction::WriteZeroToToggle) => old ^ !value,
+        Some(WriteAction::Clear) => 0,
+        Some(WriteAction::Set) => 0xFF,
+        Some(WriteAction::None) | None => value,
+    }
+}
+
+/// The byte a read leaves behind under a register's `read_action`, or `None`
+/// when the read does not change the stored value.
+fn read_action_byte(action: Option<&labwired_config::ReadAction>) -> Option<u8> {
+    match action {
+        Some(labwired_config::ReadAction::Clear) => Some(0),
+        Some(labwired_config::ReadAction::Set) => Some(0xFF),
+        _ => None,
+    }
+}
+
 /// A generic peripheral implementation that uses a `PeripheralDescriptor` to define its
 /// register layout and access permissions.
 ///
@@ -54,6 +81,9 @@ pub struct GenericPeripheral {
     /// relative to the peripheral's declared address space. Registers are static
     /// after construction, so the table is built once and never rebuilt.
     reg_at_byte: Vec<u32>,
+    /// Per-byte mask of bits owned by `read-only` fields. CPU writes leave these
+    /// bits unchanged even when the enclosing register is writable.
+    read_only_bits: Vec<u8>,
     inflight_events: RefCell<Vec<InflightEvent>>,
     stuck_bits: RefCell<Vec<StuckBit>>,
 }
@@ -73,26 +103,7 @@ impl GenericPeripheral {
         }
 
         let mut data = vec![0; max_addr as usize];
-
-        // Initialize with reset values
-        for reg in &descriptor.registers {
-            let val = reg.reset_value;
-            let offset = reg.address_offset as usize;
-            match reg.size {
-                8 => data[offset] = val as u8,
-                16 => {
-                    data[offset] = (val & 0xFF) as u8;
-                    data[offset + 1] = ((val >> 8) & 0xFF) as u8;
-                }
-                32 => {
-                    data[offset] = (val & 0xFF) as u8;
-                    data[offset + 1] = ((val >> 8) & 0xFF) as u8;
-                    data[offset + 2] = ((val >> 16) & 0xFF) as u8;
-                    data[offset + 3] = ((val >> 24) & 0xFF) as u8;
-                }
-                _ => {}
-            }
-        }
+        Self::write_reset_values(&mut data, &descriptor.registers);
 
         // Build the O(1) offset->register lookup once. Earlier registers claim
         // their bytes first, so an overlap resolves to the same register the old
@@ -108,19 +119,63 @@ impl GenericPeripheral {
             }
         }
 
+        let mut read_only_bits = vec![0u8; max_addr as usize];
+        for reg in &descriptor.registers {
+            for field in &reg.fields {
+                if field.access != Some(labwired_config::Access::ReadOnly) {
+                    continue;
+                }
+                let [msb, lsb] = field.bit_range;
+                for bit in lsb..=msb.min(reg.size.saturating_sub(1)) {
+                    let byte = reg.address_offset as usize + bit as usize / 8;
+                    read_only_bits[byte] |= 1 << (bit % 8);
+                }
+            }
+        }
+
         let p = Self {
             descriptor,
             data: RefCell::new(data),
             reg_at_byte,
+            read_only_bits,
             inflight_events: RefCell::new(Vec::new()),
             stuck_bits: RefCell::new(Vec::new()),
         };
 
-        // Initialize periodic events
-        if let Some(timing) = &p.descriptor.timing {
+        p.arm_periodic_events();
+        p
+    }
+
+    /// Store every register's `reset_value` into the backing bytes. Registers
+    /// are written in declaration order, so an overlap ends with the last one.
+    fn write_reset_values(data: &mut [u8], registers: &[labwired_config::RegisterDescriptor]) {
+        for reg in registers {
+            let val = reg.reset_value;
+            let offset = reg.address_offset as usize;
+            match reg.size {
+                8 => data[offset] = val as u8,
+                16 => {
+                    data[offset] = (val & 0xFF) as u8;
+                    data[offset + 1] = ((val >> 8) & 0xFF) as u8;
+                }
+                32 => {
+                    data[offset] = (val & 0xFF) as u8;
+                    data[offset + 1] = ((val >> 8) & 0xFF) as u8;
+                    data[offset + 2] = ((val >> 16) & 0xFF) as u8;
+                    data[offset + 3] = ((val >> 24) & 0xFF) as u8;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Queue the descriptor's periodic timing hooks, first firing one period
+    /// from now.
+    fn arm_periodic_events(&self) {
+        if let Some(timing) = &self.descriptor.timing {
             for hook in timing {
                 if let labwired_config::TimingTrigger::Periodic { period_cycles } = &hook.trigger {
-                    p.inflight_events.borrow_mut().push(InflightEvent {
+                    self.inflight_events.borrow_mut().push(InflightEvent {
                         id: hook.id.clone(),
                         delay_remaining: *period_cycles,
                         action: hook.action.clone(),
@@ -130,7 +185,6 @@ impl GenericPeripheral {
                 }
             }
         }
-        p
     }
 
     pub fn get_descriptor(&self) -> &labwired_config::PeripheralDescriptor {
@@ -304,7 +358,8 @@ impl GenericPeripheral {
                             true // Any write triggers it
                         }
                     }
-                    labwired_config::TimingTrigger::Periodic { .. } => false,
+                    labwired_config::TimingTrigger::Periodic { .. }
+                    | labwired_config::TimingTrigger::OnInterruptAck { .. } => false,
                 };
 
                 if triggered {
@@ -320,7 +375,11 @@ impl GenericPeripheral {
         }
     }
 
-    fn apply_action(&self, action: &labwired_config::TimingAction) {
+    fn apply_action(
+        &self,
+        action: &labwired_config::TimingAction,
+        result: &mut PeripheralTickResult,
+    ) {
         let mut data = self.data.borrow_mut();
         match action {
             labwired_config::TimingAction::SetBits {
@@ -363,9 +422,37 @@ impl GenericPeripheral {
                     }
                 }
             }
+            labwired_config::TimingAction::ToggleBits {
+                register: reg_id,
+                bits,
+            } => {
+                if let Some(reg) = self.descriptor.registers.iter().find(|r| &r.id == reg_id) {
+                    let offset = reg.address_offset as usize;
+                    for i in 0..(reg.size / 8) {
+                        let shift = i * 8;
+                        let byte_bits = ((bits >> shift) & 0xFF) as u8;
+                        data[offset + i as usize] ^= byte_bits;
+                    }
+                }
+            }
+            labwired_config::TimingAction::PendInterrupt { name } => {
+                if let Some(irq) = self.interrupt_number(name) {
+                    result.explicit_irqs.get_or_insert_with(Vec::new).push(irq);
+                }
+            }
+            labwired_config::TimingAction::ClearInterrupt { name } => {
+                if let Some(irq) = self.interrupt_number(name) {
+                    result.cleared_irqs.get_or_insert_with(Vec::new).push(irq);
+                }
+            }
         }
     }
 
+    /// Resolves a name from the descriptor's `interrupts` map to its IRQ number.
+    fn interrupt_number(&self, name: &str) -> Option<u32> {
+        self.descriptor.interrupts.as_ref()?.get(name).copied()
+    }
+
     fn has_read_trigger(&self) -> bool {
         self.descriptor.timing.as_ref().is_some_and(|timing| {
             timing
@@ -374,6 +461,17 @@ impl GenericPeripheral {
         })
     }
 
+    fn has_ack_trigger(&self) -> bool {
+        self.descriptor.timing.as_ref().is_some_and(|timing| {
+            timing.iter().any(|hook| {
+                matches!(
+                    hook.trigger,
+                    labwired_config::TimingTrigger::OnInterruptAck { .. }
+                )
+            })
+        })
+    }
+
     fn has_write_trigger(&self) -> bool {
         self.descriptor.timing.as_ref().is_some_and(|timing| {
             timing
@@ -397,8 +495,8 @@ impl Peripheral for GenericPeripheral {
 
             // Side Effects: ReadAction
             if let Some(side_effects) = &reg.side_effects {
-                if let Some(labwired_config::ReadAction::Clear) = side_effects.read_action {
-                    data[offset as usize] = 0;
+                if let Some(after) = read_action_byte(side_effects.read_action.as_ref()) {
+                    data[offset as usize] = after;
                 }
             }
 
@@ -421,21 +519,13 @@ impl Peripheral for GenericPeripheral {
             let mut data = self.data.borrow_mut();
 
             // Side Effects: WriteAction
-            if let Some(side_effects) = &reg.side_effects {
-                match side_effects.write_action {
-                    Some(labwired_config::WriteAction::WriteOneToClear) => {
-                        data[offset as usize] &= !value;
-                    }
-                    Some(labwired_config::WriteAction::WriteZeroToClear) => {
-                        data[offset as usize] &= value;
-                    }
-                    _ => {
-                        data[offset as usize] = value;
-                    }
-                }
-            } else {
-                data[offset as usize] = value;
-            }
+            let action = reg
+                .side_effects
+                .as_ref()
+                .and_then(|se| se.write_action.as_ref());
+            let old = data[offset as usize];
+            let ro = self.read_only_bits[offset as usize];
+            data[offset as usize] = (write_action_byte(action, old, value) & !ro) | (old & ro);
 
             // For triggers, we need the full register value being written (ideally).
             // But GenericPeripheral writes byte-by-byte.
@@ -475,11 +565,8 @@ impl Peripheral for GenericPeripheral {
 
                 // Side Effects: ReadAction
                 if let Some(side_effects) = &reg.side_effects {
-                    if let Some(labwired_config::ReadAction::Clear) = side_effects.read_action {
-                        data[offset as usize] = 0;
-                        data[(offset + 1) as usize] = 0;
-                        data[(offset + 2) as usize] = 0;
-                        data[(offset + 3) as usize] = 0;
+                    if let Some(after) = read_action_byte(side_effects.read_action.as_ref()) {
+                        data[offset as usize..(offset + 4) as usize].fill(after);
                     }
                 }
 
@@ -512,32 +599,14 @@ impl Peripheral for GenericPeripheral {
                 let b2 = ((value >> 16) & 0xFF) as u8;
                 let b3 = ((value >> 24) & 0xFF) as u8;
 
-                if let Some(side_effects) = &reg.side_effects {
-                    match side_effects.write_action {
-                        Some(labwired_config::WriteAction::WriteOneToClear) => {
-                            data[offset as usize] &= !b0;
-                            data[(offset + 1) as usize] &= !b1;
-                            data[(offset + 2) as usize] &= !b2;
-                            data[(offset + 3) as usize] &= !b3;
-                        }
-                        Some(labwired_config::WriteAction::WriteZeroToClear) => {
-                            data[offset as usize] &= b0;
-                            data[(offset + 1) as usize] &= b1;
-                            data[(offset + 2) as usize] &= b2;
-                            data[(offset + 3) as usize] &= b3;
-                        }
-                        _ => {
-                            data[offset as usize] = b0;
-                            data[(offset + 1) as usize] = b1;
-                            data[(offset + 2) as usize] = b2;
-                            data[(offset + 3) as usize] = b3;
-                        }
-                    }
-                } else {
-                    data[offset as usize] = b0;
-                    data[(offset + 1) as usize] = b1;
-                    data[(offset + 2) as usize] = b2;
-                    data[(offset + 3) as usize] = b3;
+                let action = reg
+                    .side_effects
+                    .as_ref()
+                    .and_then(|se| se.write_action.as_ref());
+                for (i, b) in [b0, b1, b2, b3].into_iter().enumerate() {
+                    let at = offset as usize + i;
+                    let (old, ro) = (data[at], self.read_only_bits[at]);
+                    data[at] = (write_action_byte(action, old, b) & !ro) | (old & ro);
                 }
 
                 self.check_triggers(&reg.id, true, Some(value));
@@ -575,12 +644,10 @@ impl Peripheral for GenericPeripheral {
                 i += 1;
             } else {
                 let event = events.remove(i);
-                self.apply_action(&event.action);
+                self.apply_action(&event.action, &mut result);
                 if let Some(ref int_name) = event.interrupt {
-                    if let Some(ints) = &self.descriptor.interrupts {
-                        if let Some(&val) = ints.get(int_name) {
-                            result.explicit_irqs.get_or_insert_with(Vec::new).push(val);
-                        }
+                    if let Some(val) = self.interrupt_number(int_name) {
+                        result.explicit_irqs.get_or_insert_with(Vec::new).push(val);
                     }
                 }
 
@@ -610,11 +677,37 @@ impl Peripheral for GenericPeripheral {
     }
 
     fn legacy_tick_dynamic(&self) -> bool {
-        !self.has_read_trigger() && self.has_write_trigger()
+        !self.has_read_trigger() && (self.has_write_trigger() || self.has_ack_trigger())
+    }
+
+    fn handles_irq_ack(&self) -> bool {
+        self.has_ack_trigger()
+    }
+
+    fn on_irq_ack(&mut self, irq: u32) -> bool {
+        let Some(timing) = &self.descriptor.timing else {
+            return false;
+        };
+        let mut armed = false;
+        for hook in timing {
+            if let labwired_config::TimingTrigger::OnInterruptAck { interrupt } = &hook.trigger {
+                if self.interrupt_number(interrupt) == Some(irq) {
+                    self.inflight_events.borrow_mut().push(InflightEvent {
+                        id: hook.id.clone(),
+                        delay_remaining: hook.delay_cycles,
+                        action: hook.action.clone(),
+                        interrupt: hook.interrupt.clone(),
+                        periodic_interval: None,
+                    });
+                    armed = true;
+                }
+            }
+        }
+        armed
     }
 
     /// A declarative register bank does walk work only when it can hold an
-    /// inflight timed event — which requires a read/write trigger in the
+    /// inflight timed event — which requires a read/write/ack trigger in the
     /// descriptor (or an event armed at construction). A trigger-free bank's
     /// `tick()` loop body can never execute, so it is walk-independent for every
     /// state. Mirrors the `legacy_tick_active`/`legacy_tick_dynamic` reachability
@@ -622,6 +715,7 @@ impl Peripheral for GenericPeripheral {
     fn needs_legacy_walk(&self) -> bool {
         self.has_read_trigger()
             || self.has_write_trigger()
+            || self.has_ack_trigger()
             || !self.inflight_events.borrow().is_empty()
     }
 
@@ -633,6 +727,19 @@ impl Peripheral for GenericPeripheral {
         Some(self)
     }
 
+    /// Back to the power-on state: every register holds its `reset_value`
+    /// (including one overridden by a `wrong_reset_value` fault), pending
+    /// timed events are dropped and periodic hooks re-armed. Injected stuck
+    /// bits are faults in the silicon, so they survive.
+    fn reset(&mut self) {
+        let mut data = self.data.borrow_mut();
+        data.fill(0);
+        Self::write_reset_values(&mut data, &self.descriptor.registers);
+        drop(data);
+        self.inflight_events.borrow_mut().clear();
+        self.arm_periodic_events();
+    }
+
     fn snapshot(&self) -> serde_json::Value {
         serde_json::json!({
             "peripheral": self.descriptor.peripheral,
@@ -761,6 +868,7 @@ mod tests {
             ],
             interrupts: None,
             timing: None,
+            interrupt_descriptors: Vec::new(),
         }
     }
 
@@ -828,6 +936,23 @@ mod tests {
         assert_eq!(p.read(0x07).unwrap(), 0xAB);
     }
 
+    #[test]
+    fn reset_restores_declared_reset_values() {
+        let mut p = GenericPeripheral::new(mock_descriptor());
+        p.write_u32(0x00, 0).unwrap();
+        p.write_u16(0x06, 0x1111).unwrap();
+        assert!(p.force_register_value("RO_REG", 0x55));
+
+        p.reset();
+        assert_eq!(p.read_u32(0x00).unwrap(), 0x12345678);
+        assert_eq!(p.read_u16(0x06).unwrap(), 0xABCD);
+        assert_eq!(
+            p.read(0x04).unwrap(),
+            0x55,
+            "a wrong_reset_value fault is the new reset value"
+        );
+    }
+
     #[test]
     fn test_write_read() {
         let mut p = GenericPeripheral::new(mock_descriptor());
@@ -978,6 +1103,57 @@ mod tests {
         assert_eq!(p.read(0x00).unwrap(), 0x70); // 0x78 & !0x08 = 0x70
     }
 
+    #[test]
+    fn test_side_effects_w1s_and_toggle() {
+        let mut desc = mock_descriptor();
+        desc.registers[0].side_effects = Some(labwired_config::SideEffectsDescriptor {
+            read_action: None,
+            write_action: Some(labwired_config::WriteAction::WriteOneToSet),
+            on_read: None,
+            on_write: None,
+        });
+        let mut p = GenericPeripheral::new(desc.clone());
+        p.write(0x00, 0x01).unwrap();
+        assert_eq!(p.read(0x00).unwrap(), 0x79); // 0x78 | 0x01
+
+        desc.registers[0]
+            .side_effects
+            .as_mut()
+            .unwrap()
+            .write_action = Some(labwired_config::WriteAction::WriteOneToToggle);
+        let mut p = GenericPeripheral::new(desc);
+        p.write_u32(0x00, 0x0000_0018).unwrap();
+        assert_eq!(p.read_u32(0x00).unwrap(), 0x1234_5660); // 0x...78 ^ 0x18
+    }
+
+    #[test]
+    fn test_read_only_field_ignores_writes() {
+        let mut desc = mock_descriptor();
+        desc.registers[0].fields = vec![
+            labwired_config::FieldDescriptor {
+                name: "EN".to_string(),
+                bit_range: [0, 0],
+                description: None,
+                access: None,
+            },
+            labwired_config::FieldDescriptor {
+                name: "BUSY".to_string(),
+                bit_range: [11, 4],
+                description: None,
+                access: Some(Access::ReadOnly),
+            },
+        ];
+
+        // REG1 resets to 0x1234_5678: BUSY (bits 11:4) holds 0x67.
+        let mut p = GenericPeripheral::new(desc);
+        p.write_u32(0x00, 0x0000_0001).unwrap();
+        assert_eq!(p.read_u32(0x00).unwrap(), 0x0000_0671);
+
+        // The byte path masks the same bits.
+        p.write(0x00, 0x00).unwrap();
+        assert_eq!(p.read_u32(0x00).unwrap(), 0x0000_0670);
+    }
+
     #[test]
     fn test_timing_hook() {
         let mut desc = mock_descriptor();
@@ -1108,4 +1284,81 @@ mod tests {
         p.tick();
         assert_eq!(p.read(0x10).unwrap(), 0x01);
     }
+
+    #[test]
+    fn test_periodic_pend_interrupt_action() {
+        let mut desc = mock_descriptor();
+        desc.interrupts = Some({
+            let mut h = std::collections::HashMap::new();
+            h.insert("TICK".to_string(), 7);
+            h
+        });
+        desc.timing = Some(vec![
+            labwired_config::TimingDescriptor {
+                id: "tick".to_string(),
+                trigger: labwired_config::TimingTrigger::Periodic { period_cycles: 1 },
+                delay_cycles: 0,
+                action: labwired_config::TimingAction::PendInterrupt {
+                    name: "TICK".to_string(),
+                },
+                interrupt: None,
+            },
+            labwired_config::TimingDescriptor {
+                id: "blink".to_string(),
+                trigger: labwired_config::TimingTrigger::Periodic { period_cycles: 1 },
+                delay_cycles: 0,
+                action: labwired_config::TimingAction::ToggleBits {
+                    register: "REG1".to_string(),
+                    bits: 0x0000_00FF,
+                },
+                interrupt: None,
+            },
+        ]);
+
+        let mut p = GenericPeripheral::new(desc);
+
+        // Tick 1: 1 -> 0
+        let res = p.tick();
+        assert!(res.explicit_irqs.is_none());
+        assert_eq!(p.read(0x00).unwrap(), 0x78);
+
+        // Tick 2: fired — IRQ pended, low byte of REG1 inverted
+        let res = p.tick();
+        assert_eq!(res.explicit_irqs, Some(vec![7]));
+        assert_eq!(p.read(0x00).unwrap(), 0x87);
+
+        p.tick();
+        // Tick 4: fired again — toggled back
+        let res = p.tick();
+        assert_eq!(res.explicit_irqs, Some(vec![7]));
+        assert_eq!(p.read(0x00).unwrap(), 0x78);
+    }
+
+    #[test]
+    fn test_clear_interrupt_action_reports_cleared_irq() {
+        let mut desc = mock_descriptor();
+        desc.interrupts = Some({
+            let mut h = std::collections::HashMap::new();
+            h.insert("RX".to_string(), 12);
+            h
+        });
+        desc.timing = Some(vec![labwired_config::TimingDescriptor {
+            id: "ack".to_string(),
+            trigger: labwired_config::TimingTrigger::Read {
+                register: "REG1".to_string(),
+            },
+            delay_cycles: 0,
+            action: labwired_config::TimingAction::ClearInterrupt {
+                name: "RX".to_string(),
+            },
+            interrupt: None,
+        }]);
+
+        let mut p = GenericPeripheral::new(desc);
+        p.read(0x00).unwrap();
+
+        let res = p.tick();
+        assert_eq!(res.cleared_irqs, Some(vec![12]));
+        assert!(res.explicit_irqs.is_none());
+    }
 }
@@ -4,7 +4,10 @@
 // This software is released under the MIT License.
 // See the LICENSE file in the project root for full license information.
 
-use crate::{CycleClock, DmaDirection, DmaRequest, Peripheral, PeripheralTickResult, SimResult};
+use crate::{
+    CycleClock, DmaDirection, DmaRequest, DmaUnitTransform, Peripheral, PeripheralTickResult,
+    SimResult,
+};
 use std::any::Any;
 
 #[derive(Debug, Default, serde::Serialize)]
@@ -87,9 +90,10 @@ struct DmaChannel {
 /// - **One element per active tick**: a non-mem2mem channel transfers a single
 ///   element then clears `active` (peripheral-request paced); a mem2mem channel
 ///   stays `active` and drains one element per tick.
-/// - **Byte-granular copy with width-strided pointers**: each element moves one
-///   byte through [`DmaRequest`] while PINC/MINC advance CPAR/CMAR internal
-///   pointers by the PSIZE/MSIZE width — the historical model detail, preserved.
+/// - **Element-width copy**: each element moves PSIZE/MSIZE bytes through
+///   [`DmaRequest`] (a [`DmaUnitTransform`] when either side is wider than a
+///   byte) while PINC/MINC advance the CPAR/CMAR internal pointers by the same
+///   widths.
 /// - **HTIF at the half-way crossing, TCIF at completion, GIF tracks the OR** —
 ///   set on the exact element the walk sets them.
 ///
@@ -270,35 +274,37 @@ impl Dma1 {
             (chan.cpar_ptr, chan.cmar_ptr, DmaDirection::Read)
         };
 
+        // CCR PSIZE [9:8] / MSIZE [11:10]: 8/16/32-bit elements (the reserved
+        // 0b11 encoding is treated as 32-bit).
+        let psize = 1u32 << ((chan.ccr >> 8) & 3).min(2);
+        let msize = 1u32 << ((chan.ccr >> 10) & 3).min(2);
+        let (src_width, dst_width) = match direction {
+            DmaDirection::Read => (psize, msize),
+            _ => (msize, psize),
+        };
+        // Byte-wide elements keep the plain single-byte move; wider ones copy
+        // the whole element, zero-padding or keeping the LSBs when the two
+        // sides differ (RM0008 §13.3.4, same as GPDMA PAM=0).
+        let transform = (src_width > 1 || dst_width > 1).then(|| DmaUnitTransform {
+            src_width: src_width as u8,
+            dst_width: dst_width as u8,
+            ..Default::default()
+        });
         let request = DmaRequest {
             src_addr: src as u64,
             addr: dst as u64,
-            val: 0,
             direction,
-            transform: None,
+            transform,
         };
 
         chan.cndtr -= 1;
-        // Increment internal memory/peripheral pointers if MINC/PINC is set.
-        // The CCR PSIZE/MSIZE bits select 1/2/4 byte width; we treat each
-        // tick as one element so the increment matches.
+        // Advance the internal memory/peripheral pointers by one element
+        // when MINC/PINC is set.
         if (chan.ccr & (1 << 7)) != 0 {
-            chan.cmar_ptr = chan.cmar_ptr.wrapping_add(if (chan.ccr & (1 << 10)) != 0 {
-                4
-            } else if (chan.ccr & (1 << 8)) != 0 {
-                2
-            } else {
-                1
-            });
+            chan.cmar_ptr = chan.cmar_ptr.wrapping_add(msize);
         }
         if (chan.ccr & (1 << 6)) != 0 {
-            chan.cpar_ptr = chan.cpar_ptr.wrapping_add(if (chan.ccr & (1 << 11)) != 0 {
-                4
-            } else if (chan.ccr & (1 << 8)) != 0 {
-                2
-            } else {
-                1
-            });
+            chan.cpar_ptr = chan.cpar_ptr.wrapping_add(psize);
         }
 
         let cndtr_after = chan.cndtr;
@@ -477,6 +483,50 @@ impl Peripheral for Dma1 {
     fn snapshot(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    fn reset(&mut self) {
+        self.isr = 0;
+        self.ifcr = 0;
+        self.cselr = 0;
+        for channel in &mut self.channels {
+            // A live chain finds the channel inactive and dies on arrival.
+            *channel = DmaChannel {
+                chain_live: channel.chain_live,
+                ..Default::default()
+            };
+        }
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        let channels: Vec<serde_json::Value> =
+            crate::peripherals::snapshot_field(&state, "channels")?;
+        if channels.len() != self.channels.len() {
+            return Err(crate::SimulationError::Other(format!(
+                "snapshot field `channels` has {} entries, expected {}",
+                channels.len(),
+                self.channels.len()
+            )));
+        }
+        crate::peripherals::restore_fields!(state, self, [isr, ifcr, cselr]);
+        // Per channel, so `chain_live` keeps tracking the live scheduler heap.
+        for (channel, state) in self.channels.iter_mut().zip(&channels) {
+            crate::peripherals::restore_fields!(
+                state,
+                channel,
+                [
+                    ccr,
+                    cndtr,
+                    cpar,
+                    cmar,
+                    active,
+                    cpar_ptr,
+                    cmar_ptr,
+                    cndtr_initial
+                ]
+            );
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
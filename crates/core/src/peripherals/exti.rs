@@ -93,6 +93,17 @@ impl ExtiBank {
     }
 }
 
+/// L4 bank-2 wakeup lines → their peripheral's NVIC IRQ (RM0351 §13.3). Lines
+/// without an entry are tracked at the register level but don't synthesize an
+/// IRQ yet.
+const L4_BANK2_LINE_IRQS: [(u32, u32); 5] = [
+    (35, 70), // LPUART1 wakeup
+    (36, 31), // I2C1 wakeup
+    (37, 33), // I2C2 wakeup
+    (38, 72), // I2C3 wakeup
+    (39, 37), // USART1 wakeup
+];
+
 /// Bank-1 IRQ routing — identical on every family (lines 0..4 → IRQ 6..10,
 /// 9..5 → 23, 15..10 → 40). Shared behaviour, not shared state.
 fn route_bank1_irqs(active1: u32, irqs: &mut Vec<u32>) {
@@ -206,6 +217,29 @@ impl Exti {
         }
     }
 
+    /// Feed an external GPIO edge into `line`: sets the PR bit when the line's
+    /// RTSR (rising) or FTSR (falling) selects this edge. Returns whether the
+    /// line became pending; IMR still decides whether that raises the IRQ.
+    pub fn input_edge(&mut self, line: u8, rising: bool) -> bool {
+        let (bank, bit) = match self {
+            Self::Stm32F1(e) if line < 32 && (e.line_mask >> line) & 1 != 0 => {
+                (&mut e.bank1, 1u32 << line)
+            }
+            Self::Stm32L4(e) => match line {
+                0..=31 => (&mut e.bank1, 1u32 << line),
+                32..=39 => (&mut e.bank2, 1u32 << (line - 32)),
+                _ => return false,
+            },
+            _ => return false,
+        };
+        let selected = if rising { bank.rtsr } else { bank.ftsr };
+        if selected & bit == 0 {
+            return false;
+        }
+        bank.pr |= bit;
+        true
+    }
+
     fn read_reg(&self, offset: u64) -> u32 {
         match self {
             Self::Stm32F1(e) => match offset {
@@ -255,16 +289,7 @@ impl Exti {
                     route_bank1_irqs(active1, &mut irqs);
                 }
                 if active2 != 0 {
-                    // Bank-2 wakeup lines → their peripheral's NVIC IRQ
-                    // (RM0351 §13.3). Lines without an entry are tracked at the
-                    // register level but don't synthesize an IRQ yet.
-                    for &(line, irq) in &[
-                        (35u32, 70u32), // LPUART1 wakeup
-                        (36, 31),       // I2C1 wakeup
-                        (37, 33),       // I2C2 wakeup
-                        (38, 72),       // I2C3 wakeup
-                        (39, 37),       // USART1 wakeup
-                    ] {
+                    for &(line, irq) in &L4_BANK2_LINE_IRQS {
                         if (active2 >> (line - 32)) & 1 != 0 {
                             irqs.push(irq);
                         }
@@ -393,10 +418,10 @@ impl Peripheral for Exti {
         // pending line (SWIER→PR, or IMR unmasking a pending PR). Every EXTI
         // PR-setting path today is an MMIO write (SWIER edge-detect or a direct
         // PR/IMR write), so `take_scheduled_events` — drained after every MMIO
-        // write — always catches the activation. (`trigger_line`, the external
-        // GPIO-edge injector, has no runtime caller; were it ever wired to a bus
-        // path, that path must re-arm the chain the same way, since a `&mut`
-        // injector cannot itself return events.) delay-0 → deadline
+        // write — always catches the activation. The external GPIO-edge path
+        // (`input_edge`, driven by `SystemBus::set_board_input`) re-arms the
+        // chain the same way by collecting events right after the edge, since
+        // a `&mut` injector cannot itself return events. delay-0 → deadline
         // `current_cycle + 1` = the walk's next tick.
         if self.scheduler_mode() && self.active() && !self.chain_live() {
             self.set_chain_live(true);
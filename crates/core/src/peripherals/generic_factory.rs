@@ -54,6 +54,7 @@ pub const MODEL_TYPES: &[&str] = &[
     "declarative",
     "strict_ir",
     "strict_ir_internal",
+    "nvic",
     "pwr",
     "flash",
     "rng",
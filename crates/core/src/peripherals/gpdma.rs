@@ -349,7 +349,6 @@ impl Peripheral for Gpdma {
                                 dma_requests.get_or_insert_with(Vec::new).push(DmaRequest {
                                     src_addr: node + word * 4 + k,
                                     addr: ch_base + reg_off + k,
-                                    val: 0,
                                     direction: DmaDirection::Copy,
                                     transform: None,
                                 });
@@ -374,7 +373,6 @@ impl Peripheral for Gpdma {
             dma_requests.get_or_insert_with(Vec::new).push(DmaRequest {
                 src_addr: ch.csar as u64,
                 addr: ch.cdar as u64,
-                val: 0,
                 direction: DmaDirection::Copy,
                 transform: Some(crate::DmaUnitTransform {
                     src_width: src_w as u8,
@@ -44,6 +44,9 @@ pub enum GpioRegisterLayout {
     #[default]
     Stm32F1,
     Stm32V2,
+    /// STM32F2/F4/F7: the V2 register map minus BRR, which these parts lack
+    /// (0x28 is reserved; clears go through the BSRR reset half).
+    Stm32F4,
     Nrf52,
     /// NXP Kinetis (KW41Z GPIOA/B/C): PDOR @0x0 (output), PSOR/PCOR/PTOR
     /// set/clear/toggle, PDIR @0x10 (input), PDDR @0x14 (direction).
@@ -58,10 +61,11 @@ impl FromStr for GpioRegisterLayout {
         match v.as_str() {
             "stm32f1" | "f1" | "legacy" => Ok(Self::Stm32F1),
             "stm32v2" | "v2" | "modern" | "stm32-modern" | "h5" | "stm32h5" => Ok(Self::Stm32V2),
+            "stm32f4" | "f4" => Ok(Self::Stm32F4),
             "nrf52" | "nordic" => Ok(Self::Nrf52),
             "kinetis" | "kw41z" | "nxp" => Ok(Self::Kinetis),
             _ => Err(format!(
-                "unsupported GPIO register layout '{}'; supported: stm32f1, stm32v2, nrf52, kinetis",
+                "unsupported GPIO register layout '{}'; supported: stm32f1, stm32v2 (stm32h5), stm32f4, nrf52, kinetis",
                 value
             )),
         }
@@ -69,7 +73,7 @@ impl FromStr for GpioRegisterLayout {
 }
 
 // ── STM32F1 (CRL/CRH config registers) ───────────────────────────────────────
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct F1Gpio {
     crl: u32,  // 0x00
     crh: u32,  // 0x04
@@ -120,7 +124,7 @@ impl F1Gpio {
 }
 
 // ── STM32v2 / H5-style (MODER/OTYPER/OSPEEDR/PUPDR/AFR) ───────────────────────
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct V2Gpio {
     moder: u32,   // 0x00
     otyper: u32,  // 0x04
@@ -134,6 +138,14 @@ pub struct V2Gpio {
 }
 
 impl V2Gpio {
+    fn with_resets(moder: u32, ospeedr: u32, pupdr: u32) -> Self {
+        Self {
+            moder,
+            ospeedr,
+            pupdr,
+            ..Default::default()
+        }
+    }
     fn read_reg(&self, offset: u64) -> u32 {
         match offset {
             0x00 => self.moder,
@@ -176,7 +188,7 @@ impl V2Gpio {
 }
 
 // ── nRF52 (DIR / OUT / IN / PIN_CNF) ──────────────────────────────────────────
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Nrf52Gpio {
     odr: u32,        // OUT        0x504
     idr: u32,        // IN         0x510 (latched input)
@@ -275,7 +287,7 @@ impl Nrf52Gpio {
 // ── NXP Kinetis (KW41Z GPIOA/B/C) ────────────────────────────────────────────
 // PDOR @0x0 (data output), PSOR @0x4 (set, w1s), PCOR @0x8 (clear, w1c),
 // PTOR @0xC (toggle), PDIR @0x10 (data input), PDDR @0x14 (data direction).
-#[derive(Debug, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct KinetisGpio {
     pdor: u32, // 0x00 output
     pdir: u32, // 0x10 input
@@ -305,10 +317,11 @@ impl KinetisGpio {
 
 /// The per-family register set of a [`GpioPort`]. Register sets are fully
 /// isolated — a register from one family cannot exist on another.
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub enum GpioFamily {
     Stm32F1(F1Gpio),
     Stm32V2(V2Gpio),
+    Stm32F4(V2Gpio),
     Nrf52(Nrf52Gpio),
     Kinetis(KinetisGpio),
 }
@@ -317,7 +330,7 @@ impl GpioFamily {
     fn read_reg(&self, offset: u64) -> u32 {
         match self {
             Self::Stm32F1(g) => g.read_reg(offset),
-            Self::Stm32V2(g) => g.read_reg(offset),
+            Self::Stm32V2(g) | Self::Stm32F4(g) => g.read_reg(offset),
             Self::Nrf52(g) => g.read_reg(offset),
             Self::Kinetis(g) => g.read_reg(offset),
         }
@@ -327,11 +340,35 @@ impl GpioFamily {
         match self {
             Self::Stm32F1(g) => g.write_reg(offset, value),
             Self::Stm32V2(g) => g.write_reg(offset, value),
+            // No BRR on F2/F4/F7: a write to the reserved 0x28 is dropped.
+            Self::Stm32F4(_) if offset == 0x28 => {}
+            Self::Stm32F4(g) => g.write_reg(offset, value),
             Self::Nrf52(g) => g.write_reg(offset, value),
             Self::Kinetis(g) => g.write_reg(offset, value),
         }
     }
 
+    /// Externally driven input levels (the raw IDR/IN/PDIR latch). Read and
+    /// written as a field, not through the register map: F1 and Kinetis have
+    /// no IDR write arm, and the nRF52 IN read mixes in the output latch.
+    fn input_levels(&self) -> u32 {
+        match self {
+            Self::Stm32F1(g) => g.idr,
+            Self::Stm32V2(g) | Self::Stm32F4(g) => g.idr,
+            Self::Nrf52(g) => g.idr,
+            Self::Kinetis(g) => g.pdir,
+        }
+    }
+
+    fn set_input_levels(&mut self, levels: u32) {
+        match self {
+            Self::Stm32F1(g) => g.idr = levels & 0xFFFF,
+            Self::Stm32V2(g) | Self::Stm32F4(g) => g.idr = levels & 0xFFFF,
+            Self::Nrf52(g) => g.idr = levels & g.pin_mask(),
+            Self::Kinetis(g) => g.pdir = levels,
+        }
+    }
+
     /// Direction-aware pad level (the logic-probe truth). See
     /// [`crate::Peripheral::read_gpio_pad`]; kept on the family so the
     /// push-capture tap can read pre/post-write levels while the tap state is
@@ -358,7 +395,7 @@ impl GpioFamily {
                     Some(bit(g.read_reg(0x0C)))
                 }
             }
-            Self::Stm32V2(g) => {
+            Self::Stm32V2(g) | Self::Stm32F4(g) => {
                 // MODER: 00 input, 01 output, 10 alternate function (wire state
                 // owned by the peripheral — unknown here), 11 analog.
                 let mode = (g.read_reg(0x00) >> (pin * 2)) & 0b11;
@@ -420,6 +457,9 @@ pub(crate) struct SpiPadRoute {
 #[derive(Debug)]
 pub struct GpioPort {
     family: GpioFamily,
+    /// Register file as constructed, restored by [`crate::Peripheral::reset`]. Kept
+    /// per port because V2 ports take per-port MODER/OSPEEDR/PUPDR resets.
+    power_on: GpioFamily,
     /// `Some` while the logic analyzer watches pads on this port in push mode
     /// (installed via `install_logic_tap`). Every register write then reports
     /// watched pad-level changes into the tap. Not snapshot state — the watch
@@ -441,6 +481,7 @@ impl Default for GpioPort {
 impl GpioPort {
     fn from_family(family: GpioFamily) -> Self {
         Self {
+            power_on: family.clone(),
             family,
             tap: None,
             spi_cells: Vec::new(),
@@ -456,6 +497,7 @@ impl GpioPort {
         Self::from_family(match layout {
             GpioRegisterLayout::Stm32F1 => GpioFamily::Stm32F1(F1Gpio::new()),
             GpioRegisterLayout::Stm32V2 => GpioFamily::Stm32V2(V2Gpio::default()),
+            GpioRegisterLayout::Stm32F4 => GpioFamily::Stm32F4(V2Gpio::default()),
             GpioRegisterLayout::Nrf52 => GpioFamily::Nrf52(Nrf52Gpio::default()),
             GpioRegisterLayout::Kinetis => GpioFamily::Kinetis(KinetisGpio::default()),
         })
@@ -473,12 +515,12 @@ impl GpioPort {
     /// 0xFFFFFFFF analog). The chip yaml supplies them via
     /// `config: { reset_moder / reset_ospeedr / reset_pupdr }`.
     pub fn new_stm32v2_with_resets(moder: u32, ospeedr: u32, pupdr: u32) -> Self {
-        Self::from_family(GpioFamily::Stm32V2(V2Gpio {
-            moder,
-            ospeedr,
-            pupdr,
-            ..Default::default()
-        }))
+        Self::from_family(GpioFamily::Stm32V2(V2Gpio::with_resets(moder, ospeedr, pupdr)))
+    }
+
+    /// [`Self::new_stm32v2_with_resets`] for the BRR-less F2/F4/F7 layout.
+    pub fn new_stm32f4_with_resets(moder: u32, ospeedr: u32, pupdr: u32) -> Self {
+        Self::from_family(GpioFamily::Stm32F4(V2Gpio::with_resets(moder, ospeedr, pupdr)))
     }
 
     fn read_reg(&self, offset: u64) -> u32 {
@@ -494,7 +536,7 @@ impl GpioPort {
     pub fn odr_offset(&self) -> u64 {
         match &self.family {
             GpioFamily::Stm32F1(_) => 0x0C,
-            GpioFamily::Stm32V2(_) => 0x14,
+            GpioFamily::Stm32V2(_) | GpioFamily::Stm32F4(_) => 0x14,
             GpioFamily::Nrf52(_) => 0x504,
             GpioFamily::Kinetis(_) => 0x00,
         }
@@ -505,7 +547,7 @@ impl GpioPort {
     pub fn idr_offset(&self) -> u64 {
         match &self.family {
             GpioFamily::Stm32F1(_) => 0x08,
-            GpioFamily::Stm32V2(_) => 0x10,
+            GpioFamily::Stm32V2(_) | GpioFamily::Stm32F4(_) => 0x10,
             GpioFamily::Nrf52(_) => 0x510,
             GpioFamily::Kinetis(_) => 0x10,
         }
@@ -517,6 +559,7 @@ impl GpioPort {
         match &self.family {
             GpioFamily::Stm32F1(_) => GpioRegisterLayout::Stm32F1,
             GpioFamily::Stm32V2(_) => GpioRegisterLayout::Stm32V2,
+            GpioFamily::Stm32F4(_) => GpioRegisterLayout::Stm32F4,
             GpioFamily::Nrf52(_) => GpioRegisterLayout::Nrf52,
             GpioFamily::Kinetis(_) => GpioRegisterLayout::Kinetis,
         }
@@ -569,7 +612,7 @@ impl GpioPort {
                 return false;
             }
             match (family, r.af) {
-                (GpioFamily::Stm32V2(g), Some(af)) => {
+                (GpioFamily::Stm32V2(g) | GpioFamily::Stm32F4(g), Some(af)) => {
                     if (g.read_reg(0x00) >> (pin * 2)) & 0b11 != 0b10 {
                         return false;
                     }
@@ -789,7 +832,7 @@ impl crate::Peripheral for GpioPort {
                     GpioMode::Output
                 }
             }
-            GpioFamily::Stm32V2(g) => {
+            GpioFamily::Stm32V2(g) | GpioFamily::Stm32F4(g) => {
                 // MODER: 00 input, 01 output, 10 alternate function, 11 analog.
                 match (g.read_reg(0x00) >> (pin * 2)) & 0b11 {
                     0b00 => GpioMode::Input,
@@ -823,7 +866,7 @@ impl crate::Peripheral for GpioPort {
         let func = if mode == GpioMode::Af {
             if let Some(r) = Self::active_spi_route(&self.family, &self.spi_routes, pin) {
                 Some(r.func.to_string())
-            } else if let GpioFamily::Stm32V2(g) = &self.family {
+            } else if let GpioFamily::Stm32V2(g) | GpioFamily::Stm32F4(g) = &self.family {
                 let (afr_off, sh) = if pin < 8 {
                     (0x20, (pin * 4) as u32)
                 } else {
@@ -847,19 +890,27 @@ impl crate::Peripheral for GpioPort {
         Some((reg & (1u32 << pin)) != 0)
     }
 
+    fn reset(&mut self) {
+        // Input levels are driven from outside the chip and survive a reset.
+        let inputs = self.family.input_levels();
+        self.tap_snapshot();
+        self.family = self.power_on.clone();
+        self.family.set_input_levels(inputs);
+        self.tap_report();
+    }
+
     fn set_gpio_input(&mut self, pin: u8, level: bool) -> bool {
         if pin >= 32 {
             return false;
         }
-        let offset = self.idr_offset();
-        let mut reg = self.read_reg(offset);
+        let mut reg = self.family.input_levels();
         if level {
             reg |= 1u32 << pin;
         } else {
             reg &= !(1u32 << pin);
         }
         self.tap_snapshot();
-        self.write_reg(offset, reg);
+        self.family.set_input_levels(reg);
         self.tap_report();
         true
     }
@@ -898,13 +949,34 @@ impl crate::Peripheral for GpioPort {
         // matching the pre-split format the snapshot contract depends on.
         match &self.family {
             GpioFamily::Stm32F1(g) => serde_json::to_value(g),
-            GpioFamily::Stm32V2(g) => serde_json::to_value(g),
+            GpioFamily::Stm32V2(g) | GpioFamily::Stm32F4(g) => serde_json::to_value(g),
             GpioFamily::Nrf52(g) => serde_json::to_value(g),
             GpioFamily::Kinetis(g) => serde_json::to_value(g),
         }
         .unwrap_or(serde_json::Value::Null)
     }
 
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        // The flat snapshot carries no family tag: decode it as the active one.
+        // Each family's register set has fields the others lack, so a snapshot
+        // from another family fails here instead of half-applying.
+        let layout = self.register_layout();
+        let decode = |e: serde_json::Error| {
+            crate::SimulationError::Other(format!(
+                "GPIO snapshot does not match the {layout:?} register layout: {e}"
+            ))
+        };
+        match &mut self.family {
+            GpioFamily::Stm32F1(g) => *g = serde_json::from_value(state).map_err(decode)?,
+            GpioFamily::Stm32V2(g) | GpioFamily::Stm32F4(g) => {
+                *g = serde_json::from_value(state).map_err(decode)?
+            }
+            GpioFamily::Nrf52(g) => *g = serde_json::from_value(state).map_err(decode)?,
+            GpioFamily::Kinetis(g) => *g = serde_json::from_value(state).map_err(decode)?,
+        }
+        Ok(())
+    }
+
     fn as_any(&self) -> Option<&dyn std::any::Any> {
         Some(self)
     }
@@ -1066,6 +1138,33 @@ mod tests {
         assert_eq!(rd32(&gpio, 0x14) & 0xFFFF, 0x1234);
     }
 
+    #[test]
+    fn set_gpio_input_survives_reset_on_every_family() {
+        // (layout, input register offset, pin)
+        for (layout, idr, pin) in [
+            (GpioRegisterLayout::Stm32F1, 0x08, 3),
+            (GpioRegisterLayout::Stm32V2, 0x10, 3),
+            (GpioRegisterLayout::Nrf52, 0x510, 3),
+            (GpioRegisterLayout::Kinetis, 0x10, 3),
+        ] {
+            let mut gpio = GpioPort::new_with_layout(layout);
+            assert!(gpio.set_gpio_input(pin, true));
+            assert_eq!(gpio.read_u32(idr).unwrap(), 1 << pin, "{layout:?} before reset");
+            gpio.reset();
+            assert_eq!(gpio.read_u32(idr).unwrap(), 1 << pin, "{layout:?} after reset");
+        }
+    }
+
+    #[test]
+    fn nrf52_reset_does_not_latch_output_bits_as_inputs() {
+        let mut gpio = GpioPort::new_with_layout(GpioRegisterLayout::Nrf52);
+        gpio.write_u32(0x514, 1 << 7).unwrap(); // DIR: pin 7 output
+        gpio.write_u32(0x508, 1 << 7).unwrap(); // OUTSET pin 7
+        assert_eq!(gpio.read_u32(0x510).unwrap(), 1 << 7, "IN shows the driven pad");
+        gpio.reset();
+        assert_eq!(gpio.read_u32(0x510).unwrap(), 0);
+    }
+
     #[test]
     fn test_gpio_v2_bsrr_and_brr() {
         let mut gpio = GpioPort::new_with_layout(GpioRegisterLayout::Stm32V2);
@@ -1077,4 +1176,14 @@ mod tests {
         gpio.write(0x28, 0x01).unwrap();
         assert_eq!(rd32(&gpio, 0x14) & 0x0001, 0x0000);
     }
+
+    #[test]
+    fn test_gpio_f4_has_no_brr() {
+        let mut gpio = GpioPort::new_with_layout(GpioRegisterLayout::Stm32F4);
+        gpio.write_u32(0x18, 0x01).unwrap();
+        // 0x28 is reserved on F2/F4/F7; the pin stays set.
+        gpio.write_u32(0x28, 0x01).unwrap();
+        assert_eq!(rd32(&gpio, 0x14) & 0x0001, 0x0001);
+        assert_eq!(gpio.register_layout(), GpioRegisterLayout::Stm32F4);
+    }
 }
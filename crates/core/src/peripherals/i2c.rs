@@ -90,7 +90,7 @@ impl FromStr for I2cRegisterLayout {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
 enum I2cState {
     #[default]
     Idle,
@@ -1553,6 +1553,71 @@ impl crate::Peripheral for I2c {
         }
         .unwrap_or(serde_json::Value::Null)
     }
+
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        match self {
+            Self::Stm32F1(i) => crate::peripherals::restore_fields!(
+                state,
+                i,
+                [
+                    cr1,
+                    cr2,
+                    oar1,
+                    oar2,
+                    dr,
+                    sr1,
+                    sr2,
+                    ccr,
+                    trise,
+                    state,
+                    cycles_remaining
+                ]
+            ),
+            Self::Stm32L4(i) => crate::peripherals::restore_fields!(
+                state,
+                i,
+                [
+                    cr1,
+                    cr2,
+                    oar1,
+                    oar2,
+                    timingr,
+                    timeoutr,
+                    isr,
+                    icr,
+                    pecr,
+                    rxdr,
+                    txdr,
+                    state,
+                    cycles_remaining,
+                    nbytes,
+                    first_tx_loaded,
+                ]
+            ),
+            Self::Kinetis(i) => crate::peripherals::restore_fields!(
+                state,
+                i,
+                [
+                    a1,
+                    f,
+                    c1,
+                    s,
+                    d,
+                    c2,
+                    flt,
+                    ra,
+                    smb,
+                    a2,
+                    slth,
+                    sltl,
+                    expect_address,
+                    rx_dummy_pending,
+                    is_reading,
+                ]
+            ),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -62,3 +62,32 @@ pub mod tsc;
 pub mod uart;
 pub mod usb_otg;
 pub mod wwdg;
+
+/// Copy the listed fields of a JSON [`crate::Peripheral::snapshot`] back into
+/// `$target` for a `restore`. Fields that are not listed (sinks, cycle clocks,
+/// attached devices, scheduler bookkeeping) keep their live values. A listed
+/// field missing from `$state` or of the wrong shape returns the error from
+/// [`snapshot_field`] before any field is written.
+macro_rules! restore_fields {
+    ($state:expr, $target:expr, [$($field:ident),* $(,)?]) => {{
+        let snapshot_state: &serde_json::Value = &$state;
+        ($($target.$field,)*) = (
+            $(crate::peripherals::snapshot_field(snapshot_state, stringify!($field))?,)*
+        );
+    }};
+}
+pub(crate) use restore_fields;
+
+/// Decode one field of a peripheral snapshot, naming it in the error when it
+/// is missing or does not deserialize as `T`.
+pub(crate) fn snapshot_field<T: serde::de::DeserializeOwned>(
+    state: &serde_json::Value,
+    field: &str,
+) -> crate::SimResult<T> {
+    let value = state.get(field).ok_or_else(|| {
+        crate::SimulationError::Other(format!("snapshot is missing field `{field}`"))
+    })?;
+    serde_json::from_value(value.clone()).map_err(|e| {
+        crate::SimulationError::Other(format!("snapshot field `{field}` is invalid: {e}"))
+    })
+}
@@ -330,6 +330,23 @@ impl Peripheral for Nrf52Timer {
             ..Default::default()
         }
     }
+
+    fn tick_elapsed(&mut self, cycles: u64) -> PeripheralTickResult {
+        // The walk at `peripheral_tick_interval` N hands over N cycles at
+        // once. Replay each so compare matches, shorts and PPI events land
+        // where N single ticks would put them, merged into one result.
+        let mut merged = self.tick();
+        for _ in 1..cycles {
+            if !self.running || self.mode != MODE_TIMER {
+                break;
+            }
+            let step = self.tick();
+            merged.irq |= step.irq;
+            merged.cycles += step.cycles;
+            merged.fired_events.extend(step.fired_events);
+        }
+        merged
+    }
 }
 
 #[cfg(test)]
@@ -531,6 +548,37 @@ mod tests {
         assert_eq!(t.counter, 4);
     }
 
+    #[test]
+    fn tick_elapsed_replays_every_cycle() {
+        let armed = || {
+            let mut t = Nrf52Timer::new();
+            t.write_u32(OFF_BITMODE, 3).unwrap();
+            t.write_u32(OFF_PRESCALER, 1).unwrap(); // divider = 2
+            t.write_u32(OFF_CC0, 3).unwrap();
+            t.write_u32(OFF_SHORTS, 1).unwrap(); // COMPARE[0]_CLEAR
+            t.write_u32(OFF_INTENSET, 1 << 16).unwrap();
+            t.write_u32(OFF_TASKS_START, 1).unwrap();
+            t
+        };
+        let mut reference = armed();
+        let mut batched = armed();
+        for window in 0..4 {
+            let mut irq = false;
+            let mut events = Vec::new();
+            for _ in 0..5 {
+                let step = reference.tick();
+                irq |= step.irq;
+                events.extend(step.fired_events);
+            }
+            let step = batched.tick_elapsed(5);
+            assert_eq!((step.irq, step.fired_events), (irq, events), "window {window}");
+            assert_eq!(
+                (batched.counter, batched.prescaler_accum),
+                (reference.counter, reference.prescaler_accum)
+            );
+        }
+    }
+
     #[test]
     fn capture_records_current_counter() {
         let mut t = Nrf52Timer::new();
@@ -8,9 +8,10 @@
 //!
 //! A single free-running 64-bit counter that, on real silicon, increments once
 //! per microsecond off the watchdog tick. This model advances the counter by
-//! one on every peripheral tick — the absolute rate is arbitrary (the sim has
-//! no wall clock), but the counter is genuinely monotonic, so firmware that
-//! samples it twice always sees forward progress.
+//! one per elapsed peripheral-tick cycle — the absolute rate is arbitrary (the
+//! sim has no wall clock), but it does not depend on `peripheral_tick_interval`
+//! and the counter is genuinely monotonic, so firmware that samples it twice
+//! always sees forward progress.
 //!
 //! `TIMERAWL` / `TIMERAWH` read the live low / high words. `TIMELR` / `TIMEHR`
 //! read the same live counter: the datasheet's read-latch protocol (TIMELR
@@ -154,17 +155,35 @@ impl Peripheral for Rp2040Timer {
     }
 
     fn tick(&mut self) -> crate::PeripheralTickResult {
+        self.tick_elapsed(1)
+    }
+
+    fn tick_elapsed(&mut self, cycles: u64) -> crate::PeripheralTickResult {
+        let before = self.counter as u32;
         if !self.paused {
-            self.counter = self.counter.wrapping_add(1);
+            self.counter = self.counter.wrapping_add(cycles);
         }
 
         // Fire any armed alarm whose 32-bit target the counter has reached.
         // Silicon compares the low word for exact equality; the monotonic
-        // +1 counter always hits it within one wrap. A target already in the
-        // past never matches here — pico-sdk forces those via INTF instead.
+        // +1 counter always hits it within one wrap. A walk window of N
+        // cycles fires every target it stepped over, as N single ticks
+        // would. A target already in the past never matches here — pico-sdk
+        // forces those via INTF instead.
         let low = self.counter as u32;
+        let stepped = if self.paused || cycles == 0 {
+            None
+        } else {
+            Some(cycles.min(u64::from(u32::MAX)) as u32)
+        };
         for idx in 0..4 {
-            if self.armed & (1 << idx) != 0 && self.alarm[idx as usize] == low {
+            let target = self.alarm[idx as usize];
+            let reached = match stepped {
+                // Targets in (before, before + stepped], wrapping.
+                Some(n) => target.wrapping_sub(before).wrapping_sub(1) < n,
+                None => target == low,
+            };
+            if self.armed & (1 << idx) != 0 && reached {
                 self.intr |= 1 << idx;
                 self.armed &= !(1 << idx);
             }
@@ -290,6 +309,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tick_elapsed_fires_alarms_it_steps_over() {
+        let mut t = Rp2040Timer::new();
+        t.write_u32(INTE, 0b11).unwrap();
+        t.write_u32(ALARM0, 5).unwrap();
+        t.write_u32(ALARM0 + 4, 9).unwrap(); // ALARM1
+        assert!(t.tick_elapsed(4).explicit_irqs.is_none());
+        // 4 → 8 passes alarm 0's target without landing on it.
+        assert_eq!(t.tick_elapsed(4).explicit_irqs, Some(vec![0]));
+        assert_eq!(t.read_u32(TIMERAWL).unwrap(), 8);
+        assert_eq!(t.tick_elapsed(4).explicit_irqs, Some(vec![0, 1]));
+        assert_eq!(t.read_u32(ARMED).unwrap(), 0);
+    }
+
     #[test]
     fn raw_high_low_track_64bit_counter() {
         let mut t = Rp2040Timer::new();
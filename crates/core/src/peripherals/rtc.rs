@@ -12,6 +12,10 @@
 //!
 //! Reset values per RM0351 §38: TR=0, DR=0x2101 (year=00 day=01 month=01
 //! weekday=Monday), CR=0, ISR=0x0007 (write flags asserted by default).
+//!
+//! The RTC lives in the backup domain: a warm or pin reset keeps the calendar
+//! and the 32 backup registers BKP0R..BKP31R (0x50..0xCC); only a power-on
+//! reset clears them.
 
 use crate::SimResult;
 
@@ -27,6 +31,7 @@ pub struct Rtc {
     alrmbr: u32,
     wpr: u32,
     write_unlocked: bool,
+    bkp: [u32; 32],
 }
 
 impl Rtc {
@@ -44,6 +49,7 @@ impl Rtc {
             alrmbr: 0,
             wpr: 0,
             write_unlocked: false,
+            bkp: [0; 32],
         }
     }
 
@@ -58,6 +64,7 @@ impl Rtc {
             0x1C => self.alrmar,
             0x20 => self.alrmbr,
             0x24 => self.wpr,
+            0x50..=0xCC => self.bkp[((offset - 0x50) / 4) as usize],
             _ => 0,
         }
     }
@@ -96,6 +103,7 @@ impl Rtc {
             0x14 => self.wutr = value & 0xFFFF,
             0x1C => self.alrmar = value,
             0x20 => self.alrmbr = value,
+            0x50..=0xCC => self.bkp[((offset - 0x50) / 4) as usize] = value,
             _ => {}
         }
     }
@@ -130,4 +138,13 @@ impl crate::Peripheral for Rtc {
     fn snapshot(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn warm_reset_retained(&self) -> Vec<std::ops::Range<u64>> {
+        // Calendar/alarm registers and BKPxR; WPR is a write-only key latch.
+        vec![0x00..0x24, 0x50..0xD0]
+    }
 }
@@ -279,7 +279,7 @@ impl SpiLineLevels {
 /// Wire timing snapshot, derived from the live CR1/CR2 registers at frame
 /// start (datasheet reset values apply when firmware never programs them —
 /// no invented constants).
-#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 struct FrameTiming {
     /// SCK half-period in peripheral-clock cycles = `2^BR` (CR1 BR[5:3]).
     half_ticks: u32,
@@ -297,7 +297,7 @@ struct FrameTiming {
 /// The frame currently shifting on the wire. Every bit period is two counted
 /// half-periods; the (SCK, MOSI, MISO) levels are a pure function of this
 /// state (see [`Spi::stm32_frame_levels`]).
-#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 struct ActiveFrame {
     t: FrameTiming,
     /// The full frame value clocked out on MOSI.
@@ -315,7 +315,7 @@ struct ActiveFrame {
 /// STM32 SPI register file (F1/F4/L0 classic and L4/F7/G4 FIFO share this map;
 /// `fifo` selects the FIFO DS/data-packing behaviour). H5/H7 use the separate
 /// "SPI v3" map in [`Stm32H5SpiRegs`].
-#[derive(Debug, Clone, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct Stm32SpiRegs {
     fifo: bool,
     cr1: u16,
@@ -367,7 +367,7 @@ impl Stm32SpiRegs {
 /// frame and decrements CTSIZE (same class of divergence as the RNG
 /// kernel-clock note in the chip yaml). RX is not modelled yet: the engine is
 /// TX-only and RXDR always reads 0.
-#[derive(Debug, Clone, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct Stm32H5SpiRegs {
     cr1: u32,
     cr2: u32,
@@ -495,7 +495,7 @@ impl Stm32H5SpiRegs {
 ///   0x548  TXD.MAXCNT  — number of bytes to transmit
 ///   0x54C  TXD.AMOUNT  — bytes actually transmitted (HW-updated, PS §6.30.4D8)
 ///   0x5C0  ORC         — over-read character (sent when TXD exhausted but RXD still running)
-#[derive(Debug, Clone, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct Nrf52SpiRegs {
     // EVENTS — HW-set only; SW may only write 0 to clear
     events_stopped: u32,
@@ -632,7 +632,7 @@ const DSPI_SR_TFFF: u32 = 0x0200_0000;
 const DSPI_SR_EOQF: u32 = 0x1000_0000;
 const DSPI_SR_TCF: u32 = 0x8000_0000;
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct KinetisDspiRegs {
     mcr: u32,
     tcr: u32,
@@ -675,7 +675,7 @@ impl KinetisDspiRegs {
 
 /// Family-isolated SPI register state. STM32 and nRF register sets cannot
 /// coexist on one instance.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 enum SpiRegs {
     Stm32(Stm32SpiRegs),
     Stm32H5(Stm32H5SpiRegs),
@@ -1716,6 +1716,15 @@ impl crate::Peripheral for Spi {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
 
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        crate::peripherals::restore_fields!(
+            state,
+            self,
+            [regs, frame, tx_queue, loopback, cr2_mask, cr1_mask]
+        );
+        Ok(())
+    }
+
     fn as_any(&self) -> Option<&dyn std::any::Any> {
         Some(self)
     }
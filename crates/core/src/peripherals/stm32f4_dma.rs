@@ -367,7 +367,6 @@ impl StreamDma {
         let request = DmaRequest {
             src_addr: src as u64,
             addr: dst as u64,
-            val: 0,
             direction,
             transform: None,
         };
@@ -44,8 +44,9 @@ use std::cell::Cell;
 /// **byte-identical** to the walk-driven reference. At interval N > 1 the
 /// exception pend and lazy reads are quantised to the batch grid — at most one
 /// interval late/stale, the same documented bound the write-path `sync_to`
-/// ships (and strictly better than the legacy walk at interval N, which under
-/// the default `tick_elapsed` slows the countdown by a factor of N).
+/// ships. The legacy walk at interval N replays all N cycles per call
+/// (`tick_elapsed`), so its countdown also runs at core speed; its pend is
+/// quantised to the same grid.
 ///
 /// ### Preserved semantics (differentially pinned)
 ///
@@ -148,6 +149,12 @@ impl Systick {
         self.clock = None;
     }
 
+    /// True when the counter runs with TICKINT set, i.e. it will pend the
+    /// SysTick exception on its next wrap.
+    pub fn interrupt_armed(&self) -> bool {
+        self.csr & 0x3 == 0x3
+    }
+
     /// Exact closed-form replay of `e` walk ticks from counter value `v` with
     /// reload `r`: returns `(new_value, zero_hits)`. Mirrors `tick()`'s
     /// edge-triggered sequence — from 0 the next tick reloads `r` (no fire);
@@ -389,6 +396,28 @@ impl crate::Peripheral for Systick {
         }
     }
 
+    fn tick_elapsed(&mut self, cycles: u64) -> crate::PeripheralTickResult {
+        // Legacy walk at `peripheral_tick_interval` N hands over N elapsed
+        // cycles per call. Replay all of them with the closed form (identical
+        // to N `tick()`s, see `advance_counter_matches_iterated_walk_exactly`)
+        // so the countdown keeps core speed at any interval; wraps inside one
+        // window merge into a single exception-15 pend, as in silicon.
+        if self.scheduler_mode() || (self.csr & 0x1) == 0 {
+            return crate::PeripheralTickResult::default();
+        }
+        let (val, wraps) = Self::advance_counter(self.cvr.get() as u64, self.rvr as u64, cycles);
+        self.cvr.set(val as u32);
+        if wraps == 0 {
+            return crate::PeripheralTickResult::default();
+        }
+        self.countflag.set(true);
+        let fire = (self.csr & 0x2) != 0;
+        crate::PeripheralTickResult {
+            system_exception: if fire { Some(15) } else { None },
+            ..Default::default()
+        }
+    }
+
     fn uses_scheduler(&self) -> bool {
         // True once the bus attached its cycle clock (event-scheduler builds):
         // reads stay fresh through the lazy `advance_to` path and the periodic
@@ -556,6 +585,39 @@ mod tests {
         assert_eq!(csr2 & 0x1_0000, 0, "COUNTFLAG cleared by the read");
     }
 
+    /// At `peripheral_tick_interval` N the walk calls `tick_elapsed(N)`: the
+    /// counter must still drop one per cycle from RVR, wrap, set COUNTFLAG and
+    /// pend exception 15 exactly where N single ticks would.
+    #[test]
+    fn tick_elapsed_counts_every_cycle_of_the_interval() {
+        let mut batched = Systick::new();
+        batched.write_u32(0x04, 99).unwrap(); // RVR
+        batched.write_u32(0x08, 0).unwrap(); // CVR ← 0
+        batched.write_u32(0x00, 0x7).unwrap(); // ENABLE | TICKINT | CLKSOURCE
+        let mut reference = armed(99);
+
+        // Reload tick + 63 count-down ticks: 99 → 36, no wrap yet.
+        assert_eq!(batched.tick_elapsed(64).system_exception, None);
+        assert_eq!(batched.read_u32(0x08).unwrap(), 36);
+        assert_eq!(batched.read_u32(0x00).unwrap() & 0x1_0000, 0);
+
+        // 36 more ticks reach zero (wrap), then reload + 27: 99 → 72.
+        assert_eq!(batched.tick_elapsed(64).system_exception, Some(15));
+        assert_eq!(batched.read_u32(0x08).unwrap(), 72);
+        assert_eq!(
+            batched.read_u32(0x00).unwrap() & 0x1_0000,
+            0x1_0000,
+            "COUNTFLAG set by the wrap"
+        );
+        assert_eq!(batched.read_u32(0x00).unwrap() & 0x1_0000, 0);
+
+        let fires = (0..128)
+            .filter(|_| reference.tick().system_exception == Some(15))
+            .count();
+        assert_eq!(fires, 1);
+        assert_eq!(reference.read_u32(0x08).unwrap(), 72);
+    }
+
     /// A disabled SysTick (ENABLE=0) never ticks down or fires.
     #[test]
     fn disabled_systick_is_inert() {
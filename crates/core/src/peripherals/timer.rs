@@ -57,9 +57,9 @@ use std::cell::Cell;
 /// `crates/core/tests/stm32_timer_walk_differential.rs` and the in-module
 /// `scheduler_matches_walk_*` property gates). At interval N > 1 IRQ pends and
 /// lazy reads are quantised to the batch grid — at most one interval
-/// late/stale, the same documented bound the write-path `sync_to` ships (and
-/// strictly better than the legacy walk at interval N, which under the default
-/// `tick_elapsed` slows the count by a factor of N).
+/// late/stale, the same documented bound the write-path `sync_to` ships. The
+/// legacy walk at interval N advances by all N elapsed cycles per
+/// `tick_elapsed` call, so its count matches interval 1 at every walk boundary.
 ///
 /// ### Preserved semantics (differentially pinned — including the model's
 /// known limitations, kept deliberately so the two drive modes are identical)
@@ -741,6 +741,25 @@ impl crate::Peripheral for Timer {
         }
     }
 
+    fn tick_elapsed(&mut self, cycles: u64) -> crate::PeripheralTickResult {
+        if cycles <= 1 || self.scheduler_mode() {
+            return self.tick();
+        }
+        // A wide walk interval advances the counter by every elapsed cycle
+        // through the same closed-form replay the scheduler path uses, so
+        // overflow timing no longer depends on `peripheral_tick_interval`.
+        // The legacy walk never reads `anchor`; borrow it for the window.
+        let saved = self.anchor.replace(0);
+        self.advance_to(cycles);
+        self.anchor.set(saved);
+        crate::PeripheralTickResult {
+            irq: self.irq_level_held(),
+            cycles: 0,
+            dma_signals: None,
+            ..Default::default()
+        }
+    }
+
     fn uses_scheduler(&self) -> bool {
         // True once the bus attached its cycle clock (event-scheduler builds):
         // reads stay fresh through the lazy `advance_to` path and update/
@@ -841,6 +860,38 @@ impl crate::Peripheral for Timer {
         self.sync_from_clock();
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    fn reset(&mut self) {
+        let mut power_on = Self::new_with_layout(self.width, self.advanced);
+        power_on.basic = self.basic;
+        // Keep the bus wiring, start counting from now, and kill any chain
+        // armed for the pre-reset configuration.
+        power_on.arm_seq = self.arm_seq.wrapping_add(1);
+        power_on.clock = self.clock.take();
+        if let Some(clock) = &power_on.clock {
+            power_on.anchor.set(clock.now());
+        }
+        *self = power_on;
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        crate::peripherals::restore_fields!(
+            state,
+            self,
+            [
+                cr1, cr2, smcr, dier, sr, egr, ccmr1, ccmr2, ccer, cnt, psc, arr, rcr, ccr1, ccr2,
+                ccr3, ccr4, bdtr, dcr, dmar, or1, ccmr3, ccr5, ccr6, or2, width, advanced, basic,
+                psc_cnt,
+            ]
+        );
+        // Count on from the restored CNT rather than replaying the window
+        // before the restore, and kill any chain armed for the old state.
+        if let Some(clock) = &self.clock {
+            self.anchor.set(clock.now());
+        }
+        self.arm_seq = self.arm_seq.wrapping_add(1);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -974,6 +1025,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn overflow_cycle_is_independent_of_tick_interval() {
+        // PSC=1, ARR=63: the counter wraps every 128 cycles. Walking the
+        // timer at any interval must latch UIF on the same cycle and leave
+        // the same counter state at every shared walk boundary.
+        let armed = || {
+            let mut tim = Timer::new();
+            tim.write_reg(0x28, 1); // PSC
+            tim.write_reg(0x2C, 63); // ARR
+            tim.write_reg(0x00, 1); // CR1 = CEN
+            tim
+        };
+        let mut reference = armed();
+        let mut states = vec![(0, 0, 0)];
+        for _ in 0..256 {
+            reference.tick();
+            states.push((
+                reference.cnt.get(),
+                reference.sr.get(),
+                reference.psc_cnt.get(),
+            ));
+        }
+        let first_uif = states.iter().position(|s| s.1 & 1 != 0);
+        assert_eq!(first_uif, Some(128));
+
+        for interval in [2u64, 8, 64] {
+            let mut tim = armed();
+            for cycle in (interval..=256).step_by(interval as usize) {
+                tim.tick_elapsed(interval);
+                assert_eq!(
+                    (tim.cnt.get(), tim.sr.get(), tim.psc_cnt.get()),
+                    states[cycle as usize],
+                    "interval {interval}: state diverged at cycle {cycle}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tick_elapsed_reports_the_held_update_irq() {
+        let mut tim = Timer::new();
+        tim.write_reg(0x2C, 9); // ARR
+        tim.write_reg(0x0C, 1); // DIER = UIE
+        tim.write_reg(0x00, 1); // CR1 = CEN
+        assert!(!tim.tick_elapsed(8).irq);
+        assert!(tim.tick_elapsed(8).irq, "overflow at cycle 10 must pend");
+        // The held level freezes the counter until firmware clears UIF.
+        assert_eq!(tim.cnt.get(), 0);
+        assert!(tim.tick_elapsed(8).irq);
+        tim.write_reg(0x10, 0);
+        assert!(!tim.tick_elapsed(8).irq);
+        assert_eq!(tim.cnt.get(), 8);
+    }
+
     #[cfg(feature = "event-scheduler")]
     mod scheduler_mode {
         use super::*;
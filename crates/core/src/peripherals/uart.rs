@@ -1236,6 +1236,37 @@ impl crate::Peripheral for Uart {
     fn snapshot(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
     }
+
+    fn reset(&mut self) {
+        // Registers only: sinks, streams and pending host RX input stay.
+        self.cr1 = 0;
+        self.cr2 = 0;
+        self.cr3 = 0;
+        self.brr = 0;
+        self.gtpr = 0;
+        self.dma_tx_pending = false;
+        self.elapsed_us = 0;
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> SimResult<()> {
+        // `layout` and `echo_stdout` are wiring, not register state; the sink
+        // and attached streams stay with the live instance.
+        crate::peripherals::restore_fields!(
+            state,
+            self,
+            [
+                cr1,
+                cr3,
+                cr2,
+                brr,
+                gtpr,
+                cr3_mask,
+                dma_tx_pending,
+                elapsed_us
+            ]
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
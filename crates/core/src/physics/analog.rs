@@ -0,0 +1,103 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! Time-varying analog stimulus: a level in millivolts as a function of the
+//! simulated cycle count. The bus samples each waveform at every peripheral
+//! tick and feeds it to an ADC channel (see `SystemBus::set_analog_signal`),
+//! so a test can model a sensor ramp or a mains-hum ripple without scripting
+//! one injection per conversion.
+
+/// An analog level over simulated time, sampled in millivolts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Waveform {
+    /// A fixed level.
+    Constant(u16),
+    /// Straight lines between `(cycle, millivolts)` points, which must be in
+    /// ascending cycle order. Held flat before the first point and after the
+    /// last.
+    PiecewiseLinear(Vec<(u64, u16)>),
+    /// `offset_mv + amplitude_mv * sin(2π · cycle / period_cycles)`, clamped
+    /// to the representable range.
+    Sine {
+        offset_mv: f64,
+        amplitude_mv: f64,
+        period_cycles: u64,
+    },
+}
+
+impl Waveform {
+    /// A linear ramp from `from_mv` at `start_cycle` to `to_mv` at
+    /// `end_cycle`, held at the end level afterwards.
+    pub fn ramp(from_mv: u16, to_mv: u16, start_cycle: u64, end_cycle: u64) -> Self {
+        Self::PiecewiseLinear(vec![(start_cycle, from_mv), (end_cycle, to_mv)])
+    }
+
+    /// The level at `cycle`.
+    pub fn sample(&self, cycle: u64) -> u16 {
+        match self {
+            Self::Constant(mv) => *mv,
+            Self::PiecewiseLinear(points) => {
+                let Some(&(first_cycle, first_mv)) = points.first() else {
+                    return 0;
+                };
+                if cycle <= first_cycle {
+                    return first_mv;
+                }
+                for pair in points.windows(2) {
+                    let ((c0, v0), (c1, v1)) = (pair[0], pair[1]);
+                    if cycle <= c1 {
+                        if c1 == c0 {
+                            return v1;
+                        }
+                        let t = (cycle - c0) as f64 / (c1 - c0) as f64;
+                        return (v0 as f64 + t * (v1 as f64 - v0 as f64)).round() as u16;
+                    }
+                }
+                points.last().map_or(first_mv, |&(_, mv)| mv)
+            }
+            Self::Sine {
+                offset_mv,
+                amplitude_mv,
+                period_cycles,
+            } => {
+                if *period_cycles == 0 {
+                    return offset_mv.clamp(0.0, u16::MAX as f64) as u16;
+                }
+                let phase = (cycle % period_cycles) as f64 / *period_cycles as f64;
+                let mv = offset_mv + amplitude_mv * (std::f64::consts::TAU * phase).sin();
+                mv.round().clamp(0.0, u16::MAX as f64) as u16
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn piecewise_linear_interpolates_and_holds_ends() {
+        let w = Waveform::PiecewiseLinear(vec![(100, 0), (200, 1000), (300, 500)]);
+        assert_eq!(w.sample(0), 0);
+        assert_eq!(w.sample(150), 500);
+        assert_eq!(w.sample(200), 1000);
+        assert_eq!(w.sample(250), 750);
+        assert_eq!(w.sample(10_000), 500);
+    }
+
+    #[test]
+    fn sine_swings_around_offset() {
+        let w = Waveform::Sine {
+            offset_mv: 1650.0,
+            amplitude_mv: 1000.0,
+            period_cycles: 400,
+        };
+        assert_eq!(w.sample(0), 1650);
+        assert_eq!(w.sample(100), 2650);
+        assert_eq!(w.sample(300), 650);
+        assert_eq!(w.sample(400), 1650);
+    }
+}
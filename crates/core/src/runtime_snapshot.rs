@@ -98,6 +98,8 @@ pub struct MachineRuntimeSnapshot {
     /// for Xtensa/Arm snapshots, whose RAM is peripheral-backed (captured in
     /// `peripherals`) and whose linear windows are firmware mirrors re-derived
     /// on load. Untouched (all-zero) windows are skipped to stay compact.
+    /// Every arch also lists each backed page of the `bus.sparse_mem`
+    /// windows, keyed by the page's address.
     #[serde(default)]
     pub memories: Vec<(u64, Vec<u8>)>,
 }
@@ -4,8 +4,8 @@
 // This software is released under the MIT License.
 // See the LICENSE file in the project root for full license information.
 
-use serde::{Deserialize, Serialize, Serializer};
-use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// On-disk schema version for `MachineSnapshot`. Bump whenever the wire
 /// format changes in a non-backwards-compatible way (new required fields,
@@ -21,24 +21,14 @@ fn default_schema_version() -> u32 {
     0
 }
 
-fn serialize_peripherals<S>(
-    peripherals: &HashMap<String, serde_json::Value>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    let ordered = peripherals.iter().collect::<BTreeMap<_, _>>();
-    ordered.serialize(serializer)
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MachineSnapshot {
     #[serde(default = "default_schema_version")]
     pub schema_version: u32,
     pub cpu: CpuSnapshot,
-    #[serde(serialize_with = "serialize_peripherals")]
-    pub peripherals: HashMap<String, serde_json::Value>,
+    /// Keyed by peripheral name. Ordered so that two snapshots of the same
+    /// state serialize to identical bytes.
+    pub peripherals: BTreeMap<String, serde_json::Value>,
     // Future: metrics
 }
 
@@ -50,7 +40,7 @@ pub enum CpuSnapshot {
     XtensaLx7(XtensaLx7CpuSnapshot),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ArmCpuSnapshot {
     pub registers: Vec<u32>,
     pub pc: u32,
@@ -62,6 +52,23 @@ pub struct ArmCpuSnapshot {
     #[serde(default)]
     pub pending_exceptions_hi: Vec<u64>,
     pub vtor: u32,
+    /// Both banked stack pointers; `registers[13]` is whichever is live.
+    /// These and the fields below are `None` in snapshots taken before they
+    /// existed; restoring such a snapshot leaves the core's values alone
+    /// rather than zeroing PSP and CONTROL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msp: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psp: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faultmask: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basepri: Option<u8>,
+    /// Exception being serviced; 0 means Thread mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_exception: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -97,6 +104,210 @@ pub struct XtensaLx7CpuSnapshot {
     pub vecbase: u32,
 }
 
+/// One CPU register whose value differs between two snapshots. A side is
+/// `None` when that snapshot's CPU has no register of this name (the two
+/// snapshots come from different architectures).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegisterChange {
+    pub name: String,
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+/// One differing leaf inside a peripheral's JSON state, addressed by JSON
+/// pointer (`""` is the whole value). `None` marks a missing key.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct JsonChange {
+    pub path: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+/// Per-peripheral state differences, keyed by peripheral name.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PeripheralChange {
+    pub name: String,
+    pub changes: Vec<JsonChange>,
+}
+
+/// Result of [`MachineSnapshot::diff`]. Registers keep the CPU's register
+/// order; peripherals are sorted by name.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    pub registers: Vec<RegisterChange>,
+    pub peripherals: Vec<PeripheralChange>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty() && self.peripherals.is_empty()
+    }
+}
+
+impl MachineSnapshot {
+    /// Compare against `other` (the "new" side): changed CPU registers by
+    /// name, and a JSON diff for every peripheral whose state differs or
+    /// that exists on only one side.
+    pub fn diff(&self, other: &MachineSnapshot) -> SnapshotDiff {
+        let old_regs = self.cpu.named_registers();
+        let new_regs = other.cpu.named_registers();
+        let mut registers = Vec::new();
+        for (name, old) in &old_regs {
+            let new = new_regs.iter().find(|(n, _)| n == name).map(|(_, v)| *v);
+            if new != Some(*old) {
+                registers.push(RegisterChange {
+                    name: name.clone(),
+                    old: Some(*old),
+                    new,
+                });
+            }
+        }
+        for (name, new) in &new_regs {
+            if !old_regs.iter().any(|(n, _)| n == name) {
+                registers.push(RegisterChange {
+                    name: name.clone(),
+                    old: None,
+                    new: Some(*new),
+                });
+            }
+        }
+
+        let names: std::collections::BTreeSet<&String> = self
+            .peripherals
+            .keys()
+            .chain(other.peripherals.keys())
+            .collect();
+        let mut peripherals = Vec::new();
+        for name in names {
+            let mut changes = Vec::new();
+            diff_json(
+                String::new(),
+                self.peripherals.get(name),
+                other.peripherals.get(name),
+                &mut changes,
+            );
+            if !changes.is_empty() {
+                peripherals.push(PeripheralChange {
+                    name: name.clone(),
+                    changes,
+                });
+            }
+        }
+
+        SnapshotDiff {
+            registers,
+            peripherals,
+        }
+    }
+}
+
+impl CpuSnapshot {
+    /// Flatten into `(name, value)` pairs: general-purpose registers under
+    /// their architectural names, then the special registers by field name.
+    pub fn named_registers(&self) -> Vec<(String, u64)> {
+        let mut out = Vec::new();
+        match self {
+            CpuSnapshot::Arm(s) => {
+                const NAMES: [&str; 16] = [
+                    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11",
+                    "r12", "sp", "lr", "pc",
+                ];
+                for (i, v) in s.registers.iter().enumerate() {
+                    let name = NAMES
+                        .get(i)
+                        .map_or_else(|| format!("r{i}"), |n| n.to_string());
+                    out.push((name, *v as u64));
+                }
+                if s.registers.len() < 16 {
+                    out.push(("pc".to_string(), s.pc as u64));
+                }
+                out.push(("xpsr".to_string(), s.xpsr as u64));
+                out.push(("primask".to_string(), s.primask as u64));
+                out.push(("pending_exceptions".to_string(), s.pending_exceptions));
+                for (i, v) in s.pending_exceptions_hi.iter().enumerate() {
+                    out.push((format!("pending_exceptions_hi[{i}]"), *v));
+                }
+                out.push(("vtor".to_string(), s.vtor as u64));
+                let banked = [
+                    ("msp", s.msp.map(u64::from)),
+                    ("psp", s.psp.map(u64::from)),
+                    ("control", s.control.map(u64::from)),
+                    ("faultmask", s.faultmask.map(u64::from)),
+                    ("basepri", s.basepri.map(u64::from)),
+                    ("active_exception", s.active_exception.map(u64::from)),
+                ];
+                for (name, value) in banked {
+                    if let Some(value) = value {
+                        out.push((name.to_string(), value));
+                    }
+                }
+            }
+            CpuSnapshot::RiscV(s) => {
+                for (i, v) in s.registers.iter().enumerate() {
+                    out.push((format!("x{i}"), *v as u64));
+                }
+                out.extend([
+                    ("pc".to_string(), s.pc as u64),
+                    ("mstatus".to_string(), s.mstatus as u64),
+                    ("mie".to_string(), s.mie as u64),
+                    ("mip".to_string(), s.mip as u64),
+                    ("mtvec".to_string(), s.mtvec as u64),
+                    ("mscratch".to_string(), s.mscratch as u64),
+                    ("mepc".to_string(), s.mepc as u64),
+                    ("mcause".to_string(), s.mcause as u64),
+                    ("mtval".to_string(), s.mtval as u64),
+                    ("mtime".to_string(), s.mtime),
+                    ("mtimecmp".to_string(), s.mtimecmp),
+                ]);
+            }
+            CpuSnapshot::XtensaLx7(s) => {
+                for (i, v) in s.registers.iter().enumerate() {
+                    out.push((format!("a{i}"), *v as u64));
+                }
+                out.extend([
+                    ("pc".to_string(), s.pc as u64),
+                    ("ps".to_string(), s.ps as u64),
+                    ("window_base".to_string(), s.window_base as u64),
+                    ("window_start".to_string(), s.window_start as u64),
+                    ("vecbase".to_string(), s.vecbase as u64),
+                ]);
+            }
+        }
+        out
+    }
+}
+
+/// Recursive JSON diff: objects compare key by key, arrays index by index,
+/// anything else (including a type change) is reported as a whole leaf.
+fn diff_json(
+    path: String,
+    old: Option<&serde_json::Value>,
+    new: Option<&serde_json::Value>,
+    out: &mut Vec<JsonChange>,
+) {
+    use serde_json::Value;
+    match (old, new) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                diff_json(format!("{path}/{escaped}"), a.get(key), b.get(key), out);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_json(format!("{path}/{i}"), a.get(i), b.get(i), out);
+            }
+        }
+        (a, b) if a != b => out.push(JsonChange {
+            path,
+            old: a.cloned(),
+            new: b.cloned(),
+        }),
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,12 +321,13 @@ mod tests {
             pending_exceptions: 0,
             pending_exceptions_hi: Vec::new(),
             vtor: 0,
+            ..Default::default()
         })
     }
 
     #[test]
     fn snapshot_json_orders_peripherals_by_name() {
-        let mut peripherals = HashMap::new();
+        let mut peripherals = BTreeMap::new();
         for name in ["zeta", "beta", "alpha", "gamma"] {
             peripherals.insert(name.to_owned(), serde_json::json!({ "name": name }));
         }
@@ -133,4 +345,65 @@ mod tests {
         let zeta = json.find("\"zeta\"").expect("zeta key");
         assert!(alpha < beta && beta < gamma && gamma < zeta, "{json}");
     }
+
+    #[test]
+    fn diff_reports_only_the_changed_register() {
+        let regs: Vec<u32> = (0..16).collect();
+        let snapshot = |registers: Vec<u32>| MachineSnapshot {
+            schema_version: SCHEMA_VERSION,
+            cpu: CpuSnapshot::Arm(ArmCpuSnapshot {
+                registers,
+                pc: 15,
+                xpsr: 0x0100_0000,
+                primask: false,
+                pending_exceptions: 0,
+                pending_exceptions_hi: Vec::new(),
+                vtor: 0,
+                ..Default::default()
+            }),
+            peripherals: BTreeMap::from([(
+                "uart1".to_owned(),
+                serde_json::json!({ "sr": 0xC0, "fifo": [1, 2] }),
+            )]),
+        };
+        let a = snapshot(regs.clone());
+        let mut changed = regs;
+        changed[3] = 0xDEAD_BEEF;
+        let b = snapshot(changed);
+
+        assert!(a.diff(&a).is_empty());
+        let diff = a.diff(&b);
+        assert_eq!(
+            diff.registers,
+            vec![RegisterChange {
+                name: "r3".to_owned(),
+                old: Some(3),
+                new: Some(0xDEAD_BEEF),
+            }]
+        );
+        assert!(diff.peripherals.is_empty());
+    }
+
+    #[test]
+    fn diff_walks_peripheral_json_by_pointer() {
+        let snapshot = |uart: serde_json::Value| MachineSnapshot {
+            schema_version: SCHEMA_VERSION,
+            cpu: arm_cpu(),
+            peripherals: BTreeMap::from([("uart1".to_owned(), uart)]),
+        };
+        let a = snapshot(serde_json::json!({ "sr": 0xC0, "fifo": [1, 2] }));
+        let b = snapshot(serde_json::json!({ "sr": 0xE0, "fifo": [1, 2, 3] }));
+
+        let diff = a.diff(&b);
+        assert!(diff.registers.is_empty());
+        assert_eq!(diff.peripherals.len(), 1);
+        assert_eq!(diff.peripherals[0].name, "uart1");
+        let paths: Vec<&str> = diff.peripherals[0]
+            .changes
+            .iter()
+            .map(|c| c.path.as_str())
+            .collect();
+        assert_eq!(paths, ["/fifo/2", "/sr"]);
+        assert_eq!(diff.peripherals[0].changes[0].old, None);
+    }
 }
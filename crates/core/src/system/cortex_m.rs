@@ -19,7 +19,9 @@ pub fn configure_cortex_m(bus: &mut SystemBus) -> (CortexM, Arc<NvicState>) {
     let shpr1 = Arc::new(AtomicU32::new(0));
     let shpr2 = Arc::new(AtomicU32::new(0));
     let shpr3 = Arc::new(AtomicU32::new(0));
-    let nvic_state = Arc::new(NvicState::default());
+    // Reuse the state of an NVIC the chip config already declared (see
+    // `SystemBus::from_config`) so its register window and the CPU agree.
+    let nvic_state: Arc<NvicState> = bus.nvic.clone().unwrap_or_default();
 
     let mut cpu = CortexM::default();
     cpu.set_shared_vtor(vtor.clone());
@@ -333,6 +333,97 @@ pub mod integration_tests {
         assert_eq!(irqs, vec![16]);
     }
 
+    #[test]
+    fn test_from_config_declared_nvic_pends_timer_overflow() {
+        const TIM2: u64 = 0x4000_0000;
+        const TIM2_IRQ: u32 = 28;
+        const NVIC_ISPR0: u64 = 0xE000_E200;
+        let chip = ChipDescriptor {
+            schema_version: "1.0".to_string(),
+            name: "test-chip-nvic".to_string(),
+            arch: Arch::Arm,
+            core: None,
+            flash: MemoryRange {
+                base: 0x0,
+                size: "128KB".parse().unwrap(),
+            },
+            ram: MemoryRange {
+                base: 0x2000_0000,
+                size: "20KB".parse().unwrap(),
+            },
+            reset_vector_offset: 0,
+            vtor: None,
+            atomic_register_aliases: false,
+            flash_writable: true,
+            memory_regions: Vec::new(),
+            peripherals: vec![
+                PeripheralConfig {
+                    id: "tim2".to_string(),
+                    r#type: "timer".to_string(),
+                    base_address: TIM2,
+                    size: Some("1KB".to_string()),
+                    irq: Some(TIM2_IRQ),
+                    clock: None,
+                    config: HashMap::new(),
+                },
+                PeripheralConfig {
+                    id: "nvic".to_string(),
+                    r#type: "nvic".to_string(),
+                    base_address: 0xE000_E100,
+                    size: Some("1KB".to_string()),
+                    irq: None,
+                    clock: None,
+                    config: HashMap::new(),
+                },
+            ],
+            pins: Default::default(),
+        };
+        let manifest = SystemManifest {
+            walk_deleted: Some(false),
+            schema_version: "1.0".to_string(),
+            name: "test-system-nvic".to_string(),
+            chip: "test-chip-nvic".to_string(),
+            memory_overrides: HashMap::new(),
+            external_devices: Vec::new(),
+            cosim_models: Vec::new(),
+            board_io: Vec::new(),
+            debug_uart: None,
+            peripherals: Vec::new(),
+        };
+
+        let mut bus = crate::bus::SystemBus::from_config(&chip, &manifest).unwrap();
+        let declared = bus.nvic.clone().expect("declared NVIC must attach state");
+        let (cpu, nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        assert!(
+            Arc::ptr_eq(&declared, &nvic),
+            "configure_cortex_m must reuse the config-built NVIC state"
+        );
+
+        // `b .` in RAM; the timer overflows every 4 counts (PSC=0, ARR=3).
+        bus.write_u16(0x2000_0000, 0xE7FE).unwrap();
+        bus.write_u32(TIM2 + 0x2C, 3).unwrap(); // ARR
+        bus.write_u32(TIM2 + 0x0C, 1).unwrap(); // DIER.UIE
+        bus.write_u32(TIM2, 1).unwrap(); // CR1.CEN
+        let mut machine = Machine::new(cpu, bus);
+        machine.cpu.pc = 0x2000_0000;
+        machine.cpu.sp = 0x2000_1000;
+        for _ in 0..64 {
+            machine.step().unwrap();
+        }
+
+        assert_ne!(machine.bus.read_u32(TIM2 + 0x10).unwrap() & 1, 0, "UIF");
+        assert_eq!(
+            nvic.ispr[0].load(Ordering::SeqCst) & (1 << TIM2_IRQ),
+            1 << TIM2_IRQ,
+            "TIM2 overflow must pend its NVIC line"
+        );
+        assert_eq!(
+            machine.bus.read_u32(NVIC_ISPR0).unwrap() & (1 << TIM2_IRQ),
+            1 << TIM2_IRQ,
+            "the declared NVIC window must expose the shared ISPR"
+        );
+    }
+
     #[test]
     fn test_machine_step_error_does_not_tick_peripherals() {
         #[derive(Debug)]
@@ -389,6 +480,151 @@ pub mod integration_tests {
         );
     }
 
+    #[derive(Debug, Default)]
+    struct FaultSink {
+        faults: Mutex<Vec<crate::FaultInfo>>,
+    }
+
+    impl crate::SimulationObserver for FaultSink {
+        fn on_fault(&self, fault: crate::FaultInfo) {
+            self.faults.lock().unwrap().push(fault);
+        }
+    }
+
+    #[test]
+    fn test_memory_violation_fires_on_fault() {
+        let mut machine = create_machine();
+        let sink = Arc::new(FaultSink::default());
+        machine.observers.push(sink.clone());
+
+        machine.cpu.pc = 0xDEAD_BEEE;
+        assert!(machine.step().is_err());
+
+        let expected = crate::FaultInfo {
+            kind: crate::FaultCause::MemoryViolation,
+            pc: 0xDEAD_BEEE,
+            address: Some(0xDEAD_BEEE),
+        };
+        assert_eq!(*sink.faults.lock().unwrap(), vec![expected]);
+        assert_eq!(machine.last_fault, Some(expected));
+
+        // A clean step clears the recorded fault and fires nothing new.
+        machine.bus.write_u16(0x0, 0xBF00).unwrap(); // NOP
+        machine.cpu.pc = 0x0;
+        machine.step().unwrap();
+        assert_eq!(machine.last_fault, None);
+        assert_eq!(sink.faults.lock().unwrap().len(), 1);
+    }
+
+    #[derive(Debug, Default)]
+    struct ProgressSink {
+        reports: Mutex<Vec<u64>>,
+    }
+
+    impl crate::SimulationObserver for ProgressSink {
+        fn on_progress(&self, steps: u64, ips: f64) {
+            assert!(ips >= 0.0);
+            self.reports.lock().unwrap().push(steps);
+        }
+    }
+
+    #[test]
+    fn test_progress_callback_fires_every_interval() {
+        let mut machine = create_machine();
+        machine.bus.write_u16(0x0, 0xE7FE).unwrap(); // B .
+        machine.cpu.pc = 0x0;
+        let sink = Arc::new(ProgressSink::default());
+        machine.observers.push(sink.clone());
+
+        // Off by default.
+        machine.run(Some(100)).unwrap();
+        assert!(sink.reports.lock().unwrap().is_empty());
+
+        machine.set_progress_interval(Some(250));
+        machine.run(Some(600)).unwrap();
+        machine.run(Some(400)).unwrap();
+        assert_eq!(*sink.reports.lock().unwrap(), vec![250, 500, 750, 1000]);
+
+        machine.set_progress_interval(None);
+        machine.run(Some(500)).unwrap();
+        assert_eq!(sink.reports.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_strict_alignment_faults_unaligned_str() {
+        let mut machine = create_machine();
+        // STR R1, [R0, #0]
+        machine.bus.write_u16(0x0, 0x6001).unwrap();
+        machine.bus.config.strict_alignment = true;
+        machine.cpu.pc = 0x0;
+        machine.cpu.r0 = 0x2000_0001;
+        machine.cpu.r1 = 0xDEAD_BEEF;
+
+        let step = machine.step();
+        assert!(
+            matches!(
+                step,
+                Err(crate::SimulationError::MemoryViolation(0x2000_0001))
+            ),
+            "expected unaligned STR to fault, got {:?}",
+            step
+        );
+        assert_eq!(machine.bus.read_u8(0x2000_0001).unwrap(), 0);
+
+        // Permissive (default) mode keeps the byte-wise behavior.
+        machine.bus.config.strict_alignment = false;
+        machine.cpu.pc = 0x0;
+        machine.step().unwrap();
+        assert_eq!(machine.bus.read_u32(0x2000_0001).unwrap(), 0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_armv6m_profile_faults_on_m4_dsp_instruction() {
+        let mut machine = create_machine();
+        // UADD8 R2, R2, R12 (Cortex-M4 DSP extension)
+        machine.bus.write_u16(0x0, 0xFA82).unwrap();
+        machine.bus.write_u16(0x2, 0xF24C).unwrap();
+        machine.bus.config.arm_profile = crate::ArmProfile::V6M;
+        machine.cpu.pc = 0x0;
+        machine.cpu.r2 = 0x0102_0304;
+        machine.cpu.r12 = 0x0101_0101;
+
+        machine.step().unwrap();
+        assert_eq!(machine.cpu.r2, 0x0102_0304);
+        assert_eq!(machine.cpu.pc, 0x0, "the faulting instruction must not retire");
+        assert_ne!(
+            machine.cpu.pending_exceptions[0] & (1 << 3),
+            0,
+            "UADD8 is UNDEFINED on ARMv6-M and must pend HardFault"
+        );
+
+        machine.cpu.pending_exceptions[0] = 0;
+        machine.bus.config.arm_profile = crate::ArmProfile::V7M;
+        machine.cpu.pc = 0x0;
+        machine.step().unwrap();
+        assert_eq!(machine.cpu.r2, 0x0203_0405);
+    }
+
+    #[test]
+    fn test_armv6m_profile_faults_on_cbz_and_it() {
+        // CBZ R0, +4 and ITE EQ are ARMv7-M 16-bit encodings.
+        for opcode in [0xB110u16, 0xBF0C] {
+            let mut machine = create_machine();
+            machine.bus.write_u16(0x0, opcode).unwrap();
+            machine.bus.config.arm_profile = crate::ArmProfile::V6M;
+            machine.cpu.pc = 0x0;
+
+            machine.step().unwrap();
+            assert_eq!(machine.cpu.pc, 0x0, "{opcode:#06x} must not retire");
+            assert_eq!(machine.cpu.it_state, 0, "{opcode:#06x}");
+            assert_ne!(
+                machine.cpu.pending_exceptions[0] & (1 << 3),
+                0,
+                "{opcode:#06x} must pend HardFault on ARMv6-M"
+            );
+        }
+    }
+
     #[test]
     fn test_from_config_maps_unknown_peripherals_to_stub() {
         let chip = ChipDescriptor {
@@ -398,14 +634,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![
                 PeripheralConfig {
@@ -567,14 +805,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![
                 PeripheralConfig {
@@ -649,14 +889,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "uart1".to_string(),
@@ -708,14 +950,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "gpioa".to_string(),
@@ -773,14 +1017,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "uart3".to_string(),
@@ -915,11 +1161,11 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             memory_regions: Vec::new(),
             peripherals: vec![
@@ -944,7 +1190,9 @@ pub mod integration_tests {
             ],
             pins: Default::default(),
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
         };
 
         let manifest = SystemManifest {
@@ -986,14 +1234,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "rcc".to_string(),
@@ -1048,14 +1298,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "rcc".to_string(),
@@ -1110,14 +1362,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x0,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x2000_0000,
-                size: "20KB".to_string(),
+                size: "20KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "gpioa".to_string(),
@@ -1616,6 +1870,116 @@ pub mod integration_tests {
         assert_eq!(machine.cpu.pc, isr_addr);
     }
 
+    #[test]
+    fn test_configured_vtor_boots_from_ram_table_and_vectors_irqs() {
+        let mut machine: Machine<CortexM> = create_machine();
+        machine.bus.vtor = Some(0x2000_0000);
+
+        // Bootloader vectors at the flash base are valid, but the configured
+        // VTOR must win.
+        let mut boot = vec![0u8; 8];
+        boot[0..4].copy_from_slice(&0x2000_4000u32.to_le_bytes());
+        boot[4..8].copy_from_slice(&0x0000_0101u32.to_le_bytes());
+        // Application table in RAM: SP, reset, and IRQ0 (exception 16).
+        let mut table = vec![0u8; 0x44];
+        table[0..4].copy_from_slice(&0x2000_3000u32.to_le_bytes());
+        table[4..8].copy_from_slice(&0x0000_0201u32.to_le_bytes());
+        table[0x40..0x44].copy_from_slice(&0x0000_0301u32.to_le_bytes());
+
+        let mut image = crate::memory::ProgramImage::new(0x201, crate::Arch::Arm);
+        image.add_segment(0x0, boot);
+        image.add_segment(0x2000_0000, table);
+        machine.load_firmware(&image).unwrap();
+
+        assert_eq!(machine.cpu.pc, 0x200);
+        assert_eq!(machine.cpu.sp, 0x2000_3000);
+        assert_eq!(machine.bus.read_u32(0xE000_ED08).unwrap(), 0x2000_0000);
+
+        machine.bus.write_u16(0x200, 0xBF00).unwrap(); // NOP
+        machine.bus.write_u8(0xE000_E100, 1).unwrap(); // ISER0 bit 0
+        machine.bus.write_u8(0xE000_E200, 1).unwrap(); // ISPR0 bit 0
+        machine.step().unwrap(); // Step instruction, collect interrupt
+        machine.step().unwrap(); // Handle interrupt
+        assert_eq!(machine.cpu.pc, 0x300, "IRQ0 vectors through the RAM table");
+    }
+
+    #[test]
+    fn test_load_firmware_by_region_routes_segment_to_named_external_ram() {
+        let mut machine: Machine<CortexM> = create_machine();
+        machine
+            .bus
+            .extra_mem
+            .push(crate::memory::LinearMemory::named(
+                "ext_ram",
+                0x1000,
+                0x6000_0000,
+            ));
+        let names: Vec<&str> = machine
+            .bus
+            .named_memory_regions()
+            .iter()
+            .map(|r| r.0)
+            .collect();
+        assert_eq!(names, ["flash", "ram", "ext_ram"]);
+
+        let mut vectors = vec![0u8; 8];
+        vectors[0..4].copy_from_slice(&0x2000_1000u32.to_le_bytes());
+        vectors[4..8].copy_from_slice(&0x0000_0101u32.to_le_bytes());
+        let mut image = crate::memory::ProgramImage::new(0x101, crate::Arch::Arm);
+        image.add_segment(0x0, vectors);
+        image.add_segment(0x6000_0010, vec![1, 2, 3, 4]);
+        machine.load_firmware_by_region(&image).unwrap();
+
+        assert_eq!(machine.bus.read_u32(0x6000_0010).unwrap(), 0x0403_0201);
+        assert_eq!(machine.cpu.pc, 0x100);
+
+        // The routable segment sorts first, yet nothing is written when a
+        // later one has no region.
+        let mut stray = crate::memory::ProgramImage::new(0x101, crate::Arch::Arm);
+        stray.add_segment(0x6000_0010, vec![0xEE; 4]);
+        stray.add_segment(0x7000_0000, vec![0xAA]);
+        let err = machine.load_firmware_by_region(&stray).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("segment 0x70000000..0x70000001 is not inside any named memory region"),
+            "{err}"
+        );
+        assert_eq!(machine.bus.read_u32(0x6000_0010).unwrap(), 0x0403_0201);
+    }
+
+    #[test]
+    fn test_boot_vector_issues_flags_sp_outside_ram() {
+        let mut machine: Machine<CortexM> = create_machine();
+        machine.bus.write_u32(0x0, 0x2000_1000).unwrap();
+        machine.bus.write_u32(0x4, 0x0000_0101).unwrap();
+        assert!(machine.boot_vector_issues().is_empty());
+
+        machine.bus.write_u32(0x0, 0x1000_0000).unwrap(); // not RAM
+        let issues = machine.boot_vector_issues();
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].contains("initial SP 0x10000000"), "{issues:?}");
+
+        machine.bus.write_u32(0x4, 0x2000_0001).unwrap(); // RAM, not flash
+        assert_eq!(machine.boot_vector_issues().len(), 2);
+    }
+
+    #[test]
+    fn test_boot_vector_issues_accepts_named_memory_regions() {
+        let mut machine: Machine<CortexM> = create_machine();
+        let mut ccm = crate::memory::LinearMemory::new(0x1_0000, 0x1000_0000);
+        ccm.name = "ccm".to_string();
+        machine.bus.extra_mem.push(ccm);
+
+        machine.bus.write_u32(0x0, 0x1001_0000).unwrap(); // top of CCM
+        machine.bus.write_u32(0x4, 0x1000_0101).unwrap(); // handler in CCM
+        assert!(machine.boot_vector_issues().is_empty());
+
+        machine.bus.write_u32(0x0, 0x3000_0000).unwrap(); // no region
+        let issues = machine.boot_vector_issues();
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].contains("ccm [0x10000000, 0x10010000]"), "{issues:?}");
+    }
+
     #[test]
     fn test_mov_w_instruction() {
         let mut machine: Machine<CortexM> = create_machine();
@@ -2183,6 +2547,133 @@ pub mod integration_tests {
         );
     }
 
+    #[test]
+    fn test_energy_grows_monotonically_with_run_length() {
+        let mut bus = crate::bus::SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        machine.bus.write_u16(0x0, 0x6808).unwrap(); // ldr r0, [r1]
+        machine.bus.write_u16(0x2, 0x3201).unwrap(); // adds r2, #1
+        machine.bus.write_u16(0x4, 0xE7FC).unwrap(); // b 0x0
+        machine.cpu.pc = 0x0;
+        machine.cpu.r1 = 0x2000_0000;
+        assert_eq!(machine.energy_consumption(), 0.0);
+
+        let mut last = 0.0;
+        for steps in [3, 30, 300] {
+            for _ in 0..steps {
+                machine.step().unwrap();
+            }
+            let energy = machine.energy_consumption();
+            assert!(energy > last, "{steps} more steps: {energy} J <= {last} J");
+            last = energy;
+        }
+
+        // 333 instructions, a third each of load, ALU and branch.
+        let model = machine.config.energy_model;
+        let expected_pj = 111.0 * (model.memory_pj + model.alu_pj + model.branch_pj);
+        assert!((last - expected_pj * 1e-12).abs() < 1e-15, "{last} J");
+    }
+
+    #[test]
+    fn test_debug_control_injects_bitflip_and_skip() {
+        let mut bus = crate::bus::SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        machine.bus.write_u16(0x0, 0x2101).unwrap(); // movs r1, #1
+        machine.bus.write_u16(0x2, 0x2202).unwrap(); // movs r2, #2
+        machine.cpu.pc = 0x0;
+
+        machine.write_core_reg(0, 0x10);
+        machine.inject_fault("bitflip:r0:3").unwrap();
+        assert_eq!(machine.read_core_reg(0), 0x18);
+
+        machine.inject_fault("skip_instruction").unwrap();
+        machine.step_single().unwrap();
+        machine.step_single().unwrap();
+        assert_eq!(
+            (machine.cpu.r1, machine.cpu.r2),
+            (0, 2),
+            "movs r1 was skipped"
+        );
+
+        machine.inject_fault("memflip:0x20000000:7").unwrap();
+        assert_eq!(machine.bus.read_u8(0x2000_0000).unwrap(), 0x80);
+
+        let err = machine.inject_fault("bitflip:r99:0").unwrap_err();
+        assert!(err.to_string().contains("unknown register"), "{err}");
+    }
+
+    #[test]
+    fn test_skipped_instruction_still_retires_through_it_block() {
+        let mut bus = crate::bus::SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        machine.bus.write_u16(0x0, 0xBF0C).unwrap(); // ite eq
+        machine.bus.write_u16(0x2, 0x2101).unwrap(); // movs r1, #1 (then)
+        machine.bus.write_u16(0x4, 0x2202).unwrap(); // movs r2, #2 (else)
+        machine.cpu.pc = 0x0;
+        machine.cpu.xpsr |= 1 << 30; // Z: EQ holds
+
+        machine.step_single().unwrap();
+        machine.inject_fault("skip_instruction").unwrap();
+        let energy = machine.cpu.energy_pj;
+        machine.step_single().unwrap();
+        assert_eq!(machine.cpu.pc, 0x4);
+        assert!(machine.cpu.energy_pj > energy, "the skipped slot costs energy");
+
+        // ITSTATE advanced past the skipped THEN slot, so the ELSE slot
+        // is still conditioned on NE and does not execute.
+        machine.step_single().unwrap();
+        assert_eq!((machine.cpu.r1, machine.cpu.r2), (0, 0));
+        assert_eq!(machine.cpu.it_state, 0);
+    }
+
+    #[test]
+    fn test_adc_follows_analog_ramp() {
+        use crate::peripherals::adc::Adc;
+        use crate::physics::analog::Waveform;
+
+        let mut bus = crate::bus::SystemBus::new();
+        bus.peripherals.push(crate::bus::PeripheralEntry {
+            name: "adc1".to_string(),
+            base: 0x4001_2400,
+            size: 0x400,
+            irq: Some(18),
+            dev: Box::new(Adc::new()),
+            ticks_remaining: 0,
+            clock_gate: None,
+        });
+        // 0 V → 3.3 V over the first 1000 cycles.
+        bus.set_analog_signal("adc1", 0, Waveform::ramp(0, 3300, 0, 1000))
+            .unwrap();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        machine.bus.write_u16(0x0, 0xBF00).unwrap();
+        machine.cpu.pc = 0x0;
+
+        let cr2 = 0x4001_2408;
+        machine.bus.write_u32(cr2, 1).unwrap(); // ADON, channel 0
+        let mut last = 0;
+        for _ in 0..3 {
+            for _ in 0..200 {
+                machine.step().unwrap();
+            }
+            machine.bus.write_u32(cr2, (1 << 30) | 1).unwrap(); // SWSTART
+            for _ in 0..20 {
+                machine.step().unwrap();
+            }
+            let dr = machine.bus.read_u32(0x4001_244C).unwrap();
+            // The ramp's count at "now", give or take the conversion time.
+            let expected = machine.total_cycles * 3300 / 1000 * 4095 / 3300;
+            assert!(
+                dr > last && dr.abs_diff(expected as u32) < 100,
+                "DR {dr} should track the ramp (~{expected}) and rise past {last}"
+            );
+            last = dr;
+        }
+    }
+
     #[test]
     fn test_state_snapshot() {
         use crate::snapshot::MachineSnapshot;
@@ -2215,7 +2706,7 @@ pub mod integration_tests {
         }
 
         // Verify Peripheral via JSON Value inspection
-        // snap.peripherals is HashMap<String, serde_json::Value>
+        // snap.peripherals is BTreeMap<String, serde_json::Value>
         let gpioa = snap.peripherals.get("gpioa").expect("gpioa missing");
         let odr = gpioa
             .get("odr")
@@ -2232,6 +2723,28 @@ pub mod integration_tests {
         let _snap_restored: MachineSnapshot = serde_json::from_str(&json_str).unwrap();
     }
 
+    #[test]
+    fn test_snapshot_json_is_byte_identical_for_identical_state() {
+        let build = || {
+            let mut bus = crate::bus::SystemBus::new();
+            let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+            let mut machine = Machine::new(cpu, bus);
+            machine.cpu.r0 = 7;
+            machine.bus.write_u32(0x4001_080C, 0x55).unwrap();
+            machine
+        };
+
+        let machine = build();
+        let first = serde_json::to_vec(&machine.snapshot()).unwrap();
+        let second = serde_json::to_vec(&machine.snapshot()).unwrap();
+        assert_eq!(first, second);
+
+        // A separately built machine has its own hasher seeds; the bytes must
+        // still match.
+        let other = serde_json::to_vec(&build().snapshot()).unwrap();
+        assert_eq!(first, other);
+    }
+
     #[test]
     fn test_snapshot_schema_mismatch_rejected() {
         use crate::snapshot::{MachineSnapshot, SCHEMA_VERSION};
@@ -2252,7 +2765,7 @@ pub mod integration_tests {
         let bad = MachineSnapshot {
             schema_version: 999,
             cpu: machine.snapshot().cpu,
-            peripherals: std::collections::HashMap::new(),
+            peripherals: std::collections::BTreeMap::new(),
         };
         assert!(matches!(
             machine.apply_snapshot(bad),
@@ -2301,6 +2814,33 @@ pub mod integration_tests {
         assert_eq!(p.read(0x04).unwrap(), 0x00, "COUNT should be RO");
     }
 
+    #[test]
+    fn test_machine_reset_restores_declarative_reset_values() {
+        use crate::peripherals::declarative::GenericPeripheral;
+        use labwired_config::PeripheralDescriptor;
+
+        let desc =
+            PeripheralDescriptor::from_file("../../tests/fixtures/descriptors/mock_timer.yaml")
+                .expect("Failed to load YAML");
+        let mut machine = create_machine();
+        machine.bus.add_peripheral(
+            "mock_timer",
+            0x5000_4000,
+            0x100,
+            None,
+            Box::new(GenericPeripheral::new(desc)),
+        );
+        machine.bus.write_u32(0x5000_4008, 0x1234).unwrap(); // ARR
+        assert_eq!(machine.bus.read_u32(0x5000_4008).unwrap(), 0x1234);
+
+        machine.reset().unwrap();
+        assert_eq!(
+            machine.bus.read_u32(0x5000_4008).unwrap(),
+            0xFFFF_FFFF,
+            "ARR must read its reset value again after a machine reset"
+        );
+    }
+
     // ── esp32c3 timg0 counter test ─────────────────────────────────────────────
     //
     // Builds the ESP32-C3 system from its chip config (which registers timg0 at
@@ -2325,14 +2865,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x4200_0000,
-                size: "4MB".to_string(),
+                size: "4MB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x3FC8_0000,
-                size: "400KB".to_string(),
+                size: "400KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "timg0".to_string(),
@@ -2414,14 +2956,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x4200_0000,
-                size: "4MB".to_string(),
+                size: "4MB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x3FC8_0000,
-                size: "400KB".to_string(),
+                size: "400KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![PeripheralConfig {
                 id: "gpio".to_string(),
@@ -2476,14 +3020,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x4200_0000,
-                size: "4MB".to_string(),
+                size: "4MB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x3FC8_0000,
-                size: "400KB".to_string(),
+                size: "400KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![
                 PeripheralConfig {
@@ -2641,6 +3187,52 @@ pub mod integration_tests {
         );
     }
 
+    #[test]
+    fn test_timer_overflow_cycle_is_independent_of_tick_interval() {
+        // PSC=1, ARR=63: TIM2 wraps every 128 cycles. Driven by a stepping
+        // Machine, its state at each 64-cycle boundary must not depend on
+        // how often the peripheral walk runs.
+        const TIM2: u64 = 0x4000_0000;
+        let run = |interval: u32| {
+            let mut machine = create_machine();
+            machine.config.peripheral_tick_interval = interval;
+            machine.bus.config.peripheral_tick_interval = interval;
+            machine.bus.add_peripheral(
+                "tim2",
+                TIM2,
+                0x400,
+                None,
+                Box::new(crate::peripherals::timer::Timer::new()),
+            );
+            let pc = 0x2000_0000u32;
+            machine.cpu.set_pc(pc);
+            for i in 0..256u64 {
+                machine.bus.write_u16(pc as u64 + i * 2, 0x4600).unwrap(); // nop
+            }
+            machine.bus.write_u32(TIM2 + 0x28, 1).unwrap(); // PSC
+            machine.bus.write_u32(TIM2 + 0x2C, 63).unwrap(); // ARR
+            machine.bus.write_u32(TIM2 + 0x00, 1).unwrap(); // CR1 = CEN
+            (0..3)
+                .map(|_| {
+                    machine.run(Some(64)).unwrap();
+                    let sr = machine.bus.read_u32(TIM2 + 0x10).unwrap();
+                    let cnt = machine.bus.read_u32(TIM2 + 0x24).unwrap();
+                    (machine.total_cycles, sr & 1, cnt)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let reference = run(1);
+        assert_eq!(
+            reference.iter().map(|s| s.1).collect::<Vec<_>>(),
+            [0, 1, 1],
+            "UIF latches at the 128-cycle overflow"
+        );
+        for interval in [8, 64] {
+            assert_eq!(run(interval), reference, "interval {interval}");
+        }
+    }
+
     #[test]
     fn pc_coverage_observer_records_executed_addresses() {
         use crate::pc_coverage::PcCoverageObserver;
@@ -2683,14 +3275,16 @@ pub mod integration_tests {
             core: None,
             flash: MemoryRange {
                 base: 0x4200_0000,
-                size: "4MB".to_string(),
+                size: "4MB".parse().unwrap(),
             },
             ram: MemoryRange {
                 base: 0x3FC8_0000,
-                size: "400KB".to_string(),
+                size: "400KB".parse().unwrap(),
             },
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             peripherals: vec![
                 PeripheralConfig {
@@ -2841,14 +3435,16 @@ pub mod integration_tests {
                 core: None,
                 flash: MemoryRange {
                     base: 0x4200_0000,
-                    size: "4MB".to_string(),
+                    size: "4MB".parse().unwrap(),
                 },
                 ram: MemoryRange {
                     base: 0x3FC8_0000,
-                    size: "400KB".to_string(),
+                    size: "400KB".parse().unwrap(),
                 },
                 reset_vector_offset: 0,
+                vtor: None,
                 atomic_register_aliases: false,
+                flash_writable: true,
                 memory_regions: Vec::new(),
                 peripherals,
                 pins: Default::default(),
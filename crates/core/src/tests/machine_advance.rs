@@ -143,6 +143,7 @@ impl Cpu for CountingCpu {
             pending_exceptions: self.pending.first().copied().unwrap_or(0),
             pending_exceptions_hi: self.pending.iter().skip(1).copied().collect(),
             vtor: 0,
+            ..Default::default()
         })
     }
 
@@ -294,6 +295,43 @@ fn step_adapter_advances_both_cores_once() {
     assert_eq!(machine.total_cycles, 1);
 }
 
+#[test]
+fn with_config_applies_builder_settings_to_machine_and_bus() {
+    let config = SimulationConfig::builder()
+        .peripheral_tick_interval(16)
+        .batch_mode_enabled(false)
+        .build();
+    let mut bus = crate::bus::SystemBus::new();
+    bus.config.strict_alignment = true;
+
+    let machine = Machine::with_config(CountingCpu::default(), bus, config);
+
+    assert_eq!(machine.config.peripheral_tick_interval, 16);
+    assert!(!machine.config.batch_mode_enabled);
+    assert_eq!(machine.bus.config.peripheral_tick_interval, 16);
+    assert!(machine.bus.config.strict_alignment);
+}
+
+#[test]
+fn with_config_strict_alignment_reaches_the_bus() {
+    let config = SimulationConfig::builder().strict_alignment(true).build();
+    let mut machine = Machine::with_config(
+        CountingCpu::default(),
+        crate::bus::SystemBus::new(),
+        config,
+    );
+
+    assert!(machine.config.strict_alignment);
+    assert!(machine.bus.config.strict_alignment);
+    assert!(machine.bus.write_u32(0x2000_0001, 0).is_err());
+
+    // Leaving it off keeps a chip that requires alignment strict.
+    let mut bus = crate::bus::SystemBus::new();
+    bus.config.strict_alignment = true;
+    let machine = Machine::with_config(CountingCpu::default(), bus, SimulationConfig::default());
+    assert!(machine.bus.config.strict_alignment);
+}
+
 #[test]
 fn step_adapter_publishes_and_profiles_one_cycle() {
     let mut machine = Machine::new(CountingCpu::default(), crate::bus::SystemBus::new());
@@ -376,6 +414,24 @@ fn counting_cpu_register_names_match_cortex_m() {
     assert_eq!(cpu.index_of_register("R13"), None);
 }
 
+#[test]
+fn registers_are_accessible_by_name() {
+    let mut machine = Machine::new(CountingCpu::default(), crate::bus::SystemBus::new());
+    machine.cpu.pc = 0x100;
+
+    assert_eq!(
+        machine.read_register_by_name("pc").unwrap(),
+        machine.get_pc()
+    );
+    machine.write_register_by_name("SP", 0x2000_1000).unwrap();
+    assert_eq!(machine.read_core_reg(13), 0x2000_1000);
+    assert!(matches!(
+        machine.read_register_by_name("r13"),
+        Err(SimulationError::Other(_))
+    ));
+    assert!(machine.write_register_by_name("bogus", 0).is_err());
+}
+
 #[test]
 fn step_adapter_with_halted_primary_still_consumes_one_scheduling_quantum() {
     let mut machine = counting_dual_core_machine();
@@ -557,6 +613,27 @@ fn breakpoint_wins_when_reached_exactly_at_fuel_limit() {
     assert_eq!(adapter.run(Some(2)).unwrap(), StopReason::Breakpoint(4));
 }
 
+#[test]
+fn stop_request_from_another_thread_interrupts_unbounded_run() {
+    let mut machine = Machine::new(CountingCpu::default(), SystemBus::new());
+    let stop = machine.stop_handle();
+    let requester = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        stop.store(true, std::sync::atomic::Ordering::Release);
+    });
+
+    let started = std::time::Instant::now();
+    assert_eq!(machine.run(None).unwrap(), StopReason::ManualStop);
+    requester.join().unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert!(machine.cpu.steps > 0);
+
+    // The request is consumed: the next advance runs normally.
+    let report = machine.advance(AdvanceRequest::run(Some(3))).unwrap();
+    assert_eq!(report.stop, AdvanceStop::FuelLimit);
+    assert_eq!(report.primary_steps, 3);
+}
+
 #[test]
 fn honor_breakpoint_stops_before_current_pc_and_inside_wide_window() {
     let mut current = Machine::new(CountingCpu::default(), SystemBus::new());
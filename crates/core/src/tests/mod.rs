@@ -19,6 +19,8 @@ pub mod machine_advance;
 #[cfg(test)]
 pub mod nrf52;
 #[cfg(test)]
+pub mod peripheral_snapshot;
+#[cfg(test)]
 pub mod rp2040;
 #[cfg(test)]
 pub mod scb_reset;
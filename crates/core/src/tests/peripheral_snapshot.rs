@@ -0,0 +1,139 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+//
+// This software is released under the MIT License.
+// See the LICENSE file in the project root for full license information.
+
+//! JSON `snapshot`/`restore` round trips for the core STM32 peripherals: a
+//! state change must survive into a freshly constructed instance.
+
+use crate::peripherals::{
+    adc::Adc,
+    dma::Dma1,
+    gpio::{GpioPort, GpioRegisterLayout},
+    i2c::I2c,
+    spi::Spi,
+    timer::Timer,
+    uart::Uart,
+};
+use crate::{Peripheral, SimulationError};
+
+/// Mutate a fresh peripheral, snapshot it, restore the snapshot into another
+/// fresh instance and require identical snapshots. Also requires the mutation
+/// to be visible in the snapshot, so a peripheral that snapshots nothing
+/// cannot pass.
+fn assert_snapshot_round_trip<P: Peripheral>(new: impl Fn() -> P, mutate: impl FnOnce(&mut P)) {
+    let pristine = new().snapshot();
+    let mut original = new();
+    mutate(&mut original);
+    let state = original.snapshot();
+    assert_ne!(state, pristine, "mutation is not visible in the snapshot");
+
+    let mut restored = new();
+    restored.restore(state.clone()).unwrap();
+    assert_eq!(restored.snapshot(), state);
+}
+
+#[test]
+fn gpio_round_trips() {
+    assert_snapshot_round_trip(GpioPort::new, |gpio| {
+        gpio.write_u32(0x00, 0x3333_3333).unwrap(); // CRL
+        gpio.write_u32(0x0C, 0x00A5).unwrap(); // ODR
+    });
+}
+
+#[test]
+fn uart_round_trips() {
+    assert_snapshot_round_trip(Uart::new, |uart| {
+        uart.write_u32(0x08, 0x1D4C).unwrap(); // BRR
+        uart.write_u32(0x0C, 0x200C).unwrap(); // CR1: UE | TE | RE
+    });
+}
+
+#[test]
+fn timer_round_trips() {
+    assert_snapshot_round_trip(Timer::new, |timer| {
+        timer.write_u32(0x28, 7).unwrap(); // PSC
+        timer.write_u32(0x2C, 999).unwrap(); // ARR
+        timer.write_u32(0x24, 123).unwrap(); // CNT
+        timer.write_u32(0x34, 50).unwrap(); // CCR1
+    });
+}
+
+#[test]
+fn i2c_round_trips() {
+    assert_snapshot_round_trip(I2c::new, |i2c| {
+        i2c.write_u32(0x04, 0x0008).unwrap(); // CR2: 8 MHz
+        i2c.write_u32(0x1C, 0x0028).unwrap(); // CCR
+        i2c.write_u32(0x20, 0x0009).unwrap(); // TRISE
+    });
+}
+
+#[test]
+fn spi_round_trips() {
+    assert_snapshot_round_trip(Spi::new, |spi| {
+        spi.write_u32(0x00, 0x0304).unwrap(); // CR1: MSTR | SSI | SSM
+        spi.write_u32(0x10, 0x0011).unwrap(); // CRCPR
+    });
+}
+
+#[test]
+fn dma_round_trips() {
+    assert_snapshot_round_trip(Dma1::new, |dma| {
+        dma.write_u32(0x0C, 16).unwrap(); // CNDTR1
+        dma.write_u32(0x10, 0x4001_3804).unwrap(); // CPAR1
+        dma.write_u32(0x14, 0x2000_0000).unwrap(); // CMAR1
+    });
+}
+
+#[test]
+fn adc_round_trips() {
+    assert_snapshot_round_trip(Adc::new, |adc| {
+        adc.write_u32(0x04, 0x0100).unwrap(); // CR1: SCAN
+        adc.write_u32(0x08, 0x0002).unwrap(); // CR2: CONT
+    });
+}
+
+/// The error message of a failed `restore`, which must name `needle`.
+fn restore_error<P: Peripheral>(mut p: P, state: serde_json::Value, needle: &str) {
+    match p.restore(state) {
+        Err(SimulationError::Other(msg)) => assert!(msg.contains(needle), "{msg}"),
+        other => panic!("expected a restore error naming `{needle}`, got {other:?}"),
+    }
+}
+
+#[test]
+fn corrupted_snapshots_fail_to_restore() {
+    let mut state = Uart::new().snapshot();
+    state.as_object_mut().unwrap().remove("brr");
+    restore_error(Uart::new(), state, "brr");
+
+    let mut state = Timer::new().snapshot();
+    state["arr"] = serde_json::json!("not a number");
+    restore_error(Timer::new(), state, "arr");
+
+    let mut state = Dma1::new().snapshot();
+    state["channels"].as_array_mut().unwrap().pop();
+    restore_error(Dma1::new(), state, "channels");
+}
+
+#[test]
+fn gpio_snapshot_from_another_family_fails_to_restore() {
+    let f1 = GpioPort::new().snapshot();
+    restore_error(
+        GpioPort::new_with_layout(GpioRegisterLayout::Stm32V2),
+        f1,
+        "Stm32V2",
+    );
+}
+
+#[test]
+fn failed_restore_leaves_earlier_fields_untouched() {
+    let mut uart = Uart::new();
+    uart.write_u32(0x08, 0x1D4C).unwrap(); // BRR
+    let before = uart.snapshot();
+    let mut state = Uart::new().snapshot();
+    state.as_object_mut().unwrap().remove("elapsed_us");
+    assert!(uart.restore(state).is_err());
+    assert_eq!(uart.snapshot(), before);
+}
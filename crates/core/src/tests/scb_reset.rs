@@ -129,6 +129,38 @@ mod scb_reset_tests {
         );
     }
 
+    /// Hot-detaching a peripheral registered ahead of the SCB must not
+    /// disturb the machine's cached SCB index: SYSRESETREQ still reboots.
+    #[test]
+    fn sysresetreq_still_reboots_after_detaching_an_earlier_peripheral() {
+        let mut bus = crate::bus::SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut m = Machine::new(cpu, bus);
+        let gpioa = m.bus.find_peripheral_index_by_name("gpioa").unwrap();
+        let scb = m.bus.find_peripheral_index_by_name("scb").unwrap();
+        assert!(gpioa < scb, "the test needs a peripheral ahead of the SCB");
+
+        m.bus.detach_peripheral("gpioa").unwrap();
+        assert_eq!(m.bus.find_peripheral_index_by_name("scb"), Some(scb));
+
+        const MSP: u32 = 0x2000_1000;
+        const RESET_ADDR: u32 = 0x0800_0100;
+        m.bus.write_u32(0x0000_0000, MSP).unwrap();
+        m.bus.write_u32(0x0000_0004, RESET_ADDR | 1).unwrap();
+        const PC: u32 = 0x2000_0000;
+        m.bus.write_u16(PC as u64, 0xBF00).unwrap(); // NOP
+        m.cpu.set_pc(PC);
+        m.cpu.set_sp(0x2000_8000);
+
+        m.bus
+            .write_u32(SCB_AIRCR, (0x05FA << 16) | (1 << 2))
+            .unwrap();
+        m.step().unwrap();
+
+        assert_eq!(m.cpu.get_pc() & !1, RESET_ADDR);
+        assert_eq!(m.cpu.get_register(13), MSP);
+    }
+
     /// An AIRCR write missing the VECTKEY must NOT reset the CPU: the latch is
     /// never set, so `step()` leaves PC/SP advancing normally.
     #[test]
@@ -163,4 +195,27 @@ mod scb_reset_tests {
             "SP must be untouched without a valid reset request"
         );
     }
+
+    /// A machine reset is a chip reset: peripheral registers return to their
+    /// power-on values along with the core.
+    #[test]
+    fn machine_reset_restores_gpio_odr() {
+        const GPIOA_CRL: u64 = 0x4001_0800;
+        const GPIOA_ODR: u64 = 0x4001_080C;
+
+        let mut bus = crate::bus::SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut m = Machine::new(cpu, bus);
+        m.bus.write_u32(0x0000_0000, 0x2000_1000).unwrap();
+        m.bus.write_u32(0x0000_0004, 0x0800_0101).unwrap();
+
+        m.bus.write_u32(GPIOA_CRL, 0x3333_3333).unwrap();
+        m.bus.write_u32(GPIOA_ODR, 0x0020).unwrap();
+        assert_eq!(m.bus.read_u32(GPIOA_ODR).unwrap(), 0x0020);
+
+        m.reset().unwrap();
+
+        assert_eq!(m.bus.read_u32(GPIOA_ODR).unwrap(), 0);
+        assert_eq!(m.bus.read_u32(GPIOA_CRL).unwrap(), 0x4444_4444);
+    }
 }
@@ -41,6 +41,7 @@ impl Cpu for MockCpu {
             pending_exceptions: 0,
             pending_exceptions_hi: Vec::new(),
             vtor: 0,
+            ..Default::default()
         })
     } // Dummy
     fn apply_snapshot(&mut self, _snapshot: &crate::snapshot::CpuSnapshot) {}
@@ -229,11 +230,13 @@ fn machine_inspect_enumerates_and_filters() {
                 name: "ENABLE".to_string(),
                 bit_range: [0, 0],
                 description: None,
+                access: None,
             }],
             side_effects: None,
         }],
         interrupts: None,
         timing: None,
+        interrupt_descriptors: Vec::new(),
     };
 
     let cpu = MockCpu::default();
@@ -264,3 +267,62 @@ fn machine_inspect_enumerates_and_filters() {
     assert_eq!(ctrl.fields[0].name, "ENABLE");
     assert_eq!(ctrl.fields[0].value, 1);
 }
+
+/// Counts its ticks and reports a fixed busy cost per tick.
+#[derive(Debug)]
+struct CostlyTicker {
+    cost: u32,
+    ticks: Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl crate::Peripheral for CostlyTicker {
+    fn read(&self, _offset: u64) -> SimResult<u8> {
+        Ok(0)
+    }
+    fn write(&mut self, _offset: u64, _value: u8) -> SimResult<()> {
+        Ok(())
+    }
+    fn tick(&mut self) -> crate::PeripheralTickResult {
+        self.ticks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        crate::PeripheralTickResult {
+            cycles: self.cost,
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn peripheral_tick_cadence_ignores_tick_costs() {
+    let run = |cost: u32| {
+        let ticks = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let mut bus = crate::bus::SystemBus::new();
+        bus.add_peripheral(
+            "ticker",
+            0x5000_0000,
+            0x10,
+            None,
+            Box::new(CostlyTicker {
+                cost,
+                ticks: ticks.clone(),
+            }),
+        );
+        let config = crate::SimulationConfig::builder()
+            .peripheral_tick_interval(10)
+            .build();
+        let mut machine = Machine::with_config(MockCpu::default(), bus, config);
+        let mut ticks_at = Vec::new();
+        for _ in 0..10 {
+            machine.run(Some(10)).unwrap();
+            ticks_at.push(ticks.load(std::sync::atomic::Ordering::SeqCst));
+        }
+        (ticks_at, machine.cpu_cycles, machine.total_cycles)
+    };
+
+    let (free, free_cpu, free_total) = run(0);
+    let (costly, costly_cpu, costly_total) = run(7);
+    assert_eq!(free, (1..=10).collect::<Vec<u64>>());
+    assert_eq!(costly, free, "tick boundaries must not move with tick cost");
+    assert_eq!((free_cpu, costly_cpu), (100, 100));
+    assert_eq!(free_total, 100);
+    assert_eq!(costly_total, 100 + 10 * 7);
+}
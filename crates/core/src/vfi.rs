@@ -19,6 +19,53 @@ pub enum Fault {
     MemoryBitFlip { address: u64, bit: u8 },
 }
 
+impl Fault {
+    /// Parse a `Cpu::inject_fault` / `DebugControl::inject_fault` target:
+    ///
+    /// - `skip_instruction` — the next instruction is fetched but not executed.
+    /// - `bitflip:<register>:<bit>` — flip one bit of a core register, named
+    ///   as the core lists it (e.g. `bitflip:r0:3`, `bitflip:sp:31`).
+    /// - `memflip:<address>:<bit>` — flip one bit of the byte at `address`
+    ///   (decimal or `0x` hex); needs the bus, so only the machine applies it.
+    pub fn parse(target: &str, cpu: &dyn Cpu) -> SimResult<Self> {
+        let invalid =
+            |why: &str| SimulationError::Other(format!("invalid fault target '{target}': {why}"));
+        let bit = |text: &str, width: u32| -> SimResult<u8> {
+            match text.parse::<u8>() {
+                Ok(bit) if u32::from(bit) < width => Ok(bit),
+                _ => Err(invalid(&format!("bit must be 0..{width}"))),
+            }
+        };
+        let parts: Vec<&str> = target.trim().split(':').collect();
+        match parts.as_slice() {
+            ["skip_instruction"] => Ok(Self::InstructionSkip),
+            ["bitflip", register, b] => {
+                let register_id = cpu
+                    .index_of_register(register)
+                    .ok_or_else(|| invalid(&format!("unknown register '{register}'")))?;
+                Ok(Self::RegisterBitFlip {
+                    register_id,
+                    bit: bit(b, 32)?,
+                })
+            }
+            ["memflip", address, b] => {
+                let address = match address.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => address.parse(),
+                }
+                .map_err(|_| invalid("bad address"))?;
+                Ok(Self::MemoryBitFlip {
+                    address,
+                    bit: bit(b, 8)?,
+                })
+            }
+            _ => Err(invalid(
+                "expected skip_instruction, bitflip:<register>:<bit> or memflip:<address>:<bit>",
+            )),
+        }
+    }
+}
+
 /// The Shadow Engine provides lockstep execution of two CPU instances
 /// to detect and analyze the effects of fault injection.
 pub struct ShadowEngine {
@@ -369,23 +369,16 @@ fn validate_cortex_m_firmware(
         );
     }
 
-    let flash_size = labwired_config::parse_size(&chip.flash.size).with_context(|| {
-        format!(
-            "node '{}': invalid flash size for chip '{}'",
-            node_id, chip.name
-        )
-    })?;
-    let ram_size = labwired_config::parse_size(&chip.ram.size).with_context(|| {
-        format!(
-            "node '{}': invalid RAM size for chip '{}'",
-            node_id, chip.name
-        )
-    })?;
-    let vector_base = chip
-        .flash
-        .base
-        .checked_add(chip.reset_vector_offset)
-        .context("Cortex-M reset vector address overflow")?;
+    let flash_size = chip.flash.size.bytes();
+    let ram_size = chip.ram.size.bytes();
+    let vector_base = match chip.vtor {
+        Some(vtor) => vtor,
+        None => chip
+            .flash
+            .base
+            .checked_add(chip.reset_vector_offset)
+            .context("Cortex-M reset vector address overflow")?,
+    };
     let stack_pointer = image_u32_at(image, vector_base);
     let reset_handler = image_u32_at(image, vector_base.saturating_add(4));
     let reset_target = reset_handler.map(|handler| u64::from(handler & !1));
@@ -449,6 +442,9 @@ fn load_elf_image(path: &std::path::Path) -> anyhow::Result<crate::memory::Progr
             image.add_segment(ph.p_paddr, bytes[off..off + n].to_vec());
         }
     }
+    if let Some(&(start, end)) = image.overlaps().first() {
+        anyhow::bail!("ELF {path:?} load segments overlap at {start:#x}..{end:#x}");
+    }
     Ok(image)
 }
 
@@ -517,6 +513,178 @@ fn parse_mqtt_url(url: &str) -> anyhow::Result<(String, u16)> {
     Ok((host.to_string(), port.parse()?))
 }
 
+/// A timed external event applied to a running [`Machine`] by a [`Scenario`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ScenarioEvent {
+    /// Drive `gpio` pin `pin` to its pressed level (high, or low when
+    /// `active_low`); the edge reaches EXTI like a real button.
+    ButtonPress {
+        gpio: String,
+        pin: u8,
+        #[serde(default)]
+        active_low: bool,
+    },
+    /// Return `gpio` pin `pin` to its released level.
+    ButtonRelease {
+        gpio: String,
+        pin: u8,
+        #[serde(default)]
+        active_low: bool,
+    },
+    /// Queue `data` on the receive side of the UART peripheral named `uart`.
+    UartRx { uart: String, data: String },
+    /// Pend the interrupt line of the peripheral named `peripheral`.
+    Irq { peripheral: String },
+    /// Write `value` at `address` as a `width`-byte (1, 2 or 4) store.
+    Poke {
+        address: u64,
+        value: u32,
+        #[serde(default = "default_poke_width")]
+        width: u8,
+    },
+}
+
+fn default_poke_width() -> u8 {
+    4
+}
+
+impl ScenarioEvent {
+    /// Apply this event to `machine` now.
+    pub fn apply<C: Cpu>(&self, machine: &mut Machine<C>) -> anyhow::Result<()> {
+        match self {
+            Self::ButtonPress {
+                gpio,
+                pin,
+                active_low,
+            } => machine
+                .set_board_input(gpio, *pin, !active_low)
+                .map_err(anyhow::Error::msg),
+            Self::ButtonRelease {
+                gpio,
+                pin,
+                active_low,
+            } => machine
+                .set_board_input(gpio, *pin, *active_low)
+                .map_err(anyhow::Error::msg),
+            Self::UartRx { uart, data } => {
+                let idx = machine
+                    .bus
+                    .find_peripheral_index_by_name(uart)
+                    .ok_or_else(|| anyhow::anyhow!("UART peripheral '{uart}' not found"))?;
+                let Some(dev) = machine.bus.peripherals[idx]
+                    .dev
+                    .as_any()
+                    .and_then(|a| a.downcast_ref::<crate::peripherals::uart::Uart>())
+                else {
+                    anyhow::bail!("peripheral '{uart}' is not a UART that accepts injected RX");
+                };
+                dev.rx_buffer().lock().unwrap().extend(data.bytes());
+                Ok(())
+            }
+            Self::Irq { peripheral } => {
+                let idx = machine
+                    .bus
+                    .find_peripheral_index_by_name(peripheral)
+                    .ok_or_else(|| anyhow::anyhow!("peripheral '{peripheral}' not found"))?;
+                let irq = machine.bus.peripherals[idx]
+                    .irq
+                    .ok_or_else(|| anyhow::anyhow!("peripheral '{peripheral}' has no irq line"))?;
+                let mut fallthrough = Vec::new();
+                machine.bus.pend_irq_for_event(irq, &mut fallthrough);
+                for exc in fallthrough {
+                    machine.cpu.set_exception_pending(exc);
+                }
+                Ok(())
+            }
+            Self::Poke {
+                address,
+                value,
+                width,
+            } => {
+                match width {
+                    1 => machine.bus.write_u8(*address, *value as u8)?,
+                    2 => machine.bus.write_u16(*address, *value as u16)?,
+                    4 => machine.bus.write_u32(*address, *value)?,
+                    other => anyhow::bail!("poke width must be 1, 2 or 4 (got {other})"),
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// One scenario entry: `event` fires once the machine reaches `at_cycle`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledEvent {
+    pub at_cycle: u64,
+    pub event: ScenarioEvent,
+}
+
+/// A script of timed external events — press a button at cycle X, feed a
+/// UART line at cycle Y — replayed against a running machine. The driver
+/// calls [`Scenario::fire_due`] between steps and may clamp its step budget
+/// to [`Scenario::next_cycle`] so events land on their exact cycle.
+///
+/// ```yaml
+/// events:
+///   - at_cycle: 5000
+///     event: { type: button_press, gpio: gpioa, pin: 0 }
+///   - at_cycle: 9000
+///     event: { type: uart_rx, uart: uart1, data: "ping\n" }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub events: Vec<ScheduledEvent>,
+    /// Index of the next event to fire; events before it have fired.
+    #[serde(skip)]
+    next: usize,
+}
+
+impl Scenario {
+    pub fn new(mut events: Vec<ScheduledEvent>) -> Self {
+        // Stable, so same-cycle events keep their listed order.
+        events.sort_by_key(|e| e.at_cycle);
+        Self { events, next: 0 }
+    }
+
+    pub fn from_yaml(text: &str) -> anyhow::Result<Self> {
+        let parsed: Self = serde_yaml::from_str(text)?;
+        Ok(Self::new(parsed.events))
+    }
+
+    pub fn from_file(path: &std::path::Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("failed to read scenario {}: {e}", path.display()))?;
+        Self::from_yaml(&text)
+            .map_err(|e| anyhow::anyhow!("failed to parse scenario {}: {e}", path.display()))
+    }
+
+    /// Cycle of the next event still to fire, if any.
+    pub fn next_cycle(&self) -> Option<u64> {
+        self.events.get(self.next).map(|e| e.at_cycle)
+    }
+
+    /// Apply every unfired event scheduled at or before the machine's current
+    /// cycle, in order. An event that fails is consumed and its error
+    /// returned; the rest stay pending for the next call.
+    pub fn fire_due<C: Cpu>(&mut self, machine: &mut Machine<C>) -> anyhow::Result<()> {
+        while let Some(entry) = self.events.get(self.next) {
+            if entry.at_cycle > machine.total_cycles {
+                break;
+            }
+            self.next += 1;
+            entry
+                .event
+                .apply(machine)
+                .map_err(|e| anyhow::anyhow!("scenario event at cycle {}: {e}", entry.at_cycle))?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod egress_manifest_tests {
     use super::*;
@@ -663,4 +831,41 @@ mod tests {
         let rx_ch = radio2.read(0x10).unwrap();
         assert_eq!(rx_ch, 10);
     }
+
+    #[test]
+    fn scenario_button_press_is_seen_by_polling_firmware() {
+        let mut bus = SystemBus::new();
+        let (cpu, _nvic) = crate::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        // Spin on GPIOA_IDR bit 0, then set r2 and park.
+        for (addr, insn) in [
+            (0x0, 0x4802), // ldr r0, [pc, #8]
+            (0x2, 0x6801), // ldr r1, [r0]
+            (0x4, 0x07C9), // lsls r1, r1, #31
+            (0x6, 0xD0FC), // beq 0x2
+            (0x8, 0x2201), // movs r2, #1
+            (0xA, 0xE7FE), // b .
+        ] {
+            machine.bus.write_u16(addr, insn).unwrap();
+        }
+        machine.bus.write_u32(0xC, 0x4001_0808).unwrap();
+        machine.cpu.pc = 0x0;
+
+        let mut scenario = Scenario::from_yaml(
+            "events:\n  - at_cycle: 200\n    event: { type: button_press, gpio: gpioa, pin: 0 }\n",
+        )
+        .unwrap();
+        assert_eq!(scenario.next_cycle(), Some(200));
+
+        while machine.total_cycles < 400 {
+            scenario.fire_due(&mut machine).unwrap();
+            if machine.total_cycles < 200 {
+                assert_eq!(machine.cpu.r2, 0, "pressed before cycle 200");
+            }
+            machine.step().unwrap();
+        }
+        assert_eq!(scenario.next_cycle(), None);
+        assert_eq!(machine.cpu.r2, 1, "firmware never saw the press");
+        assert_eq!(machine.cpu.pc, 0xA);
+    }
 }
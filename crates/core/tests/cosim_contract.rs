@@ -323,3 +323,87 @@ fn manifest_builds_runner_with_manifest_relative_model_path() {
     assert_eq!(signals["plant.output.voltage"], CosimSignalValue::F64(10.0));
     assert_eq!(signals["plant.output.current"], CosimSignalValue::F64(10.0));
 }
+
+/// In-process co-sim endpoint on one end of a socket pair: answers the
+/// handshake, then every read with `read_value` (or not at all when `None`)
+/// and reports each request it saw.
+#[cfg(unix)]
+fn spawn_mock_cosim_endpoint(
+    read_value: Option<u64>,
+) -> (
+    labwired_core::cosim::CosimLink,
+    std::sync::mpsc::Receiver<serde_json::Value>,
+) {
+    use labwired_core::cosim::CosimLink;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let (ours, mut theirs) = UnixStream::pair().unwrap();
+    let (seen_tx, seen) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let reader = BufReader::new(theirs.try_clone().unwrap());
+        for line in reader.lines() {
+            let request: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+            let reply = match request["op"].as_str() {
+                Some("hello") => serde_json::json!({ "op": "hello", "version": 1 }),
+                Some("read") => match read_value {
+                    Some(value) => serde_json::json!({ "value": value }),
+                    None => {
+                        let _ = seen_tx.send(request);
+                        continue;
+                    }
+                },
+                _ => serde_json::json!({ "ok": true }),
+            };
+            let _ = seen_tx.send(request);
+            writeln!(theirs, "{reply}").unwrap();
+        }
+    });
+
+    let reader = ours.try_clone().unwrap();
+    let link = CosimLink::new(reader, ours, Duration::from_millis(200));
+    (link, seen)
+}
+
+#[cfg(unix)]
+#[test]
+fn cosim_peripheral_reads_value_from_external_endpoint() {
+    use labwired_core::bus::SystemBus;
+    use labwired_core::cosim::CosimPeripheral;
+    use labwired_core::Bus;
+
+    let (link, seen) = spawn_mock_cosim_endpoint(Some(0xDEAD_BEEF));
+    let fpga = CosimPeripheral::connect("fpga", 0x5000_0000, 0x100, link).unwrap();
+    let hello = seen.recv().unwrap();
+    assert_eq!(hello["peripheral"], "fpga");
+    assert_eq!(hello["base"], 0x5000_0000u64);
+
+    let mut bus = SystemBus::new();
+    bus.add_peripheral("fpga", 0x5000_0000, 0x100, None, Box::new(fpga));
+
+    assert_eq!(bus.read_u32(0x5000_0010).unwrap(), 0xDEAD_BEEF);
+    let read = seen.recv().unwrap();
+    assert_eq!(read["offset"], 0x10);
+    assert_eq!(read["width"], 4);
+
+    bus.write_u8(0x5000_0000, 0x01).unwrap();
+    let write = seen.recv().unwrap();
+    assert_eq!(write["op"], "write");
+    assert_eq!(write["value"], 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn cosim_peripheral_read_times_out_when_endpoint_is_silent() {
+    use labwired_core::cosim::CosimPeripheral;
+    use labwired_core::Peripheral;
+
+    let (link, _seen) = spawn_mock_cosim_endpoint(None);
+    let fpga = CosimPeripheral::connect("fpga", 0x5000_0000, 0x100, link).unwrap();
+
+    let err = fpga.read_u32(0).unwrap_err().to_string();
+    assert!(err.contains("did not answer"), "{err}");
+    let err = fpga.read(0).unwrap_err().to_string();
+    assert!(err.contains("unusable"), "{err}");
+}
@@ -0,0 +1,77 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+// SPDX-License-Identifier: MIT
+
+//! Timing harness for the bus address → peripheral lookup.
+//!
+//! Not a pass/fail benchmark — it prints wall-clock numbers for a linear scan
+//! of `SystemBus::peripherals` (the pre-index routing) against the sorted
+//! range index behind `SystemBus::resolve_window`, on a bus with as many
+//! peripheral windows as a large chip profile. Addresses hop between windows
+//! on every lookup so the last-route/gap caches cannot hide the search.
+//!
+//! Run manually with:
+//!
+//! ```sh
+//! cargo test --release -p labwired-core --test peripheral_lookup_bench -- --ignored --nocapture
+//! ```
+
+use labwired_core::bus::SystemBus;
+use labwired_core::peripherals::stub::StubPeripheral;
+use std::hint::black_box;
+use std::time::Instant;
+
+const WINDOWS: u64 = 256;
+const LOOKUPS: usize = 4_000_000;
+
+fn scan_window(bus: &SystemBus, addr: u64) -> Option<(u64, u64)> {
+    bus.peripherals
+        .iter()
+        .filter(|p| addr >= p.base && addr < p.base + p.size)
+        .max_by_key(|p| p.base)
+        .map(|p| (p.base, p.size))
+}
+
+#[test]
+#[ignore = "manual timing harness, run with --release --nocapture"]
+fn peripheral_lookup_scan_vs_indexed() {
+    let mut bus = SystemBus::empty();
+    for i in 0..WINDOWS {
+        bus.add_peripheral(
+            &format!("p{i}"),
+            0x4000_0000 + i * 0x400,
+            0x400,
+            None,
+            Box::new(StubPeripheral::new(0)),
+        );
+    }
+    // Stride coprime with the window count: every lookup lands in a
+    // different window than the previous one.
+    let addrs: Vec<u64> = (0..1024u64)
+        .map(|i| 0x4000_0000 + ((i * 97) % WINDOWS) * 0x400 + (i % 0x100) * 4)
+        .collect();
+    for &addr in &addrs {
+        assert_eq!(bus.resolve_window(addr), scan_window(&bus, addr));
+    }
+
+    let start = Instant::now();
+    for i in 0..LOOKUPS {
+        black_box(scan_window(&bus, black_box(addrs[i % addrs.len()])));
+    }
+    let scan = start.elapsed();
+
+    let start = Instant::now();
+    for i in 0..LOOKUPS {
+        black_box(bus.resolve_window(black_box(addrs[i % addrs.len()])));
+    }
+    let indexed = start.elapsed();
+
+    println!(
+        "{WINDOWS} windows, {LOOKUPS} lookups: scan {:?} ({:.1} ns/lookup), indexed {:?} ({:.1} ns/lookup), {:.1}x",
+        scan,
+        scan.as_nanos() as f64 / LOOKUPS as f64,
+        indexed,
+        indexed.as_nanos() as f64 / LOOKUPS as f64,
+        scan.as_secs_f64() / indexed.as_secs_f64().max(f64::EPSILON)
+    );
+}
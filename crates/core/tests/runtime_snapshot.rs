@@ -8,6 +8,8 @@
 // the heavy end-to-end resume test lives in `e2e_external_arduino_esp32_in_sim.rs`.
 
 use labwired_core::bus::SystemBus;
+use labwired_core::cpu::RiscV;
+use labwired_core::memory::SparseMemory;
 use labwired_core::peripherals::components::Ssd1680Tricolor290;
 use labwired_core::peripherals::esp32::spi::Esp32Spi;
 use labwired_core::runtime_snapshot::{CpuKind, MachineRuntimeSnapshot};
@@ -181,6 +183,27 @@ fn machine_runtime_snapshot_roundtrips_through_serialization() {
     assert_eq!(machine.bus.read_u32(0x3FFB_0200).unwrap(), 0xDEAD_BEEF);
 }
 
+#[test]
+fn machine_runtime_snapshot_restores_sparse_memory_pages() {
+    let build = || {
+        let mut bus = SystemBus::new();
+        bus.sparse_mem.push(SparseMemory::new(0x100_0000, 0x9000_0000));
+        Machine::new(RiscV::new(), bus)
+    };
+    let mut machine = build();
+    machine.bus.write_u32(0x9000_0010, 0xDEAD_BEEF).unwrap();
+    machine.bus.write_u8(0x90F0_0000, 0x5A).unwrap();
+
+    let bytes = machine.take_runtime_snapshot().to_bytes();
+    let decoded = MachineRuntimeSnapshot::from_bytes(&bytes).expect("from_bytes");
+
+    let mut fresh = build();
+    fresh.apply_runtime_snapshot(&decoded).expect("apply");
+    assert_eq!(fresh.bus.read_u32(0x9000_0010).unwrap(), 0xDEAD_BEEF);
+    assert_eq!(fresh.bus.read_u8(0x90F0_0000).unwrap(), 0x5A);
+    assert_eq!(fresh.bus.sparse_mem[0].allocated_pages(), 2);
+}
+
 /// Verifies that the offline-captured the reference firmware snapshot file produced by
 /// `labwired-cli snapshot capture` decodes cleanly and restores onto a
 /// freshly-built machine with the panel in its post-paint state.
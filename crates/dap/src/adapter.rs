@@ -416,7 +416,7 @@ impl LabwiredAdapter {
             // Disassemble mnemonic (for the trace log)
             let mnemonic = {
                 let instr = labwired_core::decoder::decode_thumb_16(opcode as u16);
-                Some(format!("{:?}", instr))
+                Some(instr.to_string())
             };
 
             // Resolve function name from symbols
@@ -499,7 +499,15 @@ impl LabwiredAdapter {
         if let Some(machine) = machine_guard.as_ref() {
             for (name, base, size) in machine.get_peripherals() {
                 let mut registers = Vec::new();
+                let mut interrupts = Vec::new();
                 if let Some(desc) = machine.get_peripheral_descriptor(&name) {
+                    for irq in desc.interrupt_list() {
+                        interrupts.push(json!({
+                            "name": irq.name,
+                            "value": irq.value,
+                            "description": irq.description
+                        }));
+                    }
                     for reg in desc.registers {
                         let mut val = 0u32;
                         if let Ok(data) = machine
@@ -547,7 +555,8 @@ impl LabwiredAdapter {
                     "name": name,
                     "base": base,
                     "size": size,
-                    "registers": registers
+                    "registers": registers,
+                    "interrupts": interrupts
                 }));
             }
         }
@@ -899,6 +908,11 @@ impl LabwiredAdapter {
         })
     }
 
+    pub fn symbol_at(&self, addr: u32) -> Option<String> {
+        let symbols = self.symbols.lock().unwrap();
+        symbols.as_ref()?.symbol_at(addr as u64).map(str::to_string)
+    }
+
     pub fn read_memory(&self, addr: u64, len: usize) -> Result<Vec<u8>> {
         let machine_guard = self.machine.lock().unwrap();
         if let Some(machine) = machine_guard.as_ref() {
@@ -1004,7 +1018,8 @@ fn gpio_offsets_for_peripheral(
 
     match layout {
         labwired_core::peripherals::gpio::GpioRegisterLayout::Stm32F1 => Some(default_offsets),
-        labwired_core::peripherals::gpio::GpioRegisterLayout::Stm32V2 => Some(GpioOffsets {
+        labwired_core::peripherals::gpio::GpioRegisterLayout::Stm32V2
+        | labwired_core::peripherals::gpio::GpioRegisterLayout::Stm32F4 => Some(GpioOffsets {
             idr_offset: GPIO_V2_IDR_OFFSET,
             odr_offset: GPIO_V2_ODR_OFFSET,
         }),
@@ -1106,18 +1121,20 @@ mod tests {
         let chip = labwired_config::ChipDescriptor {
             schema_version: "1.0".to_string(),
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             name: "test".to_string(),
             arch: labwired_config::Arch::Arm,
             core: None,
             flash: labwired_config::MemoryRange {
                 base: 0x0800_0000,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: labwired_config::MemoryRange {
                 base: 0x2000_0000,
-                size: "32KB".to_string(),
+                size: "32KB".parse().unwrap(),
             },
             peripherals: vec![labwired_config::PeripheralConfig {
                 id: "gpioa".to_string(),
@@ -1165,18 +1182,20 @@ mod tests {
         let chip = labwired_config::ChipDescriptor {
             schema_version: "1.0".to_string(),
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             name: "test".to_string(),
             arch: labwired_config::Arch::Arm,
             core: None,
             flash: labwired_config::MemoryRange {
                 base: 0x0800_0000,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: labwired_config::MemoryRange {
                 base: 0x2000_0000,
-                size: "32KB".to_string(),
+                size: "32KB".parse().unwrap(),
             },
             peripherals: vec![labwired_config::PeripheralConfig {
                 id: "gpiob".to_string(),
@@ -1237,18 +1256,20 @@ mod tests {
         let chip = labwired_config::ChipDescriptor {
             schema_version: "1.0".to_string(),
             reset_vector_offset: 0,
+            vtor: None,
             atomic_register_aliases: false,
+            flash_writable: true,
             memory_regions: Vec::new(),
             name: "test".to_string(),
             arch: labwired_config::Arch::Arm,
             core: None,
             flash: labwired_config::MemoryRange {
                 base: 0x0800_0000,
-                size: "128KB".to_string(),
+                size: "128KB".parse().unwrap(),
             },
             ram: labwired_config::MemoryRange {
                 base: 0x2000_0000,
-                size: "32KB".to_string(),
+                size: "32KB".parse().unwrap(),
             },
             peripherals: vec![labwired_config::PeripheralConfig {
                 id: "gpiob".to_string(),
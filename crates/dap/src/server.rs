@@ -1174,7 +1174,8 @@ impl DapServer {
                         };
 
                         // Format instruction with Ozone-like mnemonic style
-                        let instr_str = format!("{:?}", instr);
+                        let symbolize = |target: u32| self.adapter.symbol_at(target);
+                        let instr_str = instr.to_asm_at(curr_addr as u32, Some(&symbolize));
                         let display_instr = instr_str
                             .split_whitespace()
                             .next()
@@ -1689,6 +1690,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_handle_disassemble_annotates_call_target_symbol() -> Result<()> {
+        let server = DapServer::new();
+        let adapter = server.adapter.clone();
+
+        let mut bus = labwired_core::bus::SystemBus::new();
+        let (cpu, _) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = labwired_core::Machine::new(cpu, bus);
+        // bl 0x20000100
+        machine
+            .write_memory(0x20000000, &[0x00, 0xF0, 0x7E, 0xF8])
+            .unwrap();
+        *adapter.machine.lock().unwrap() = Some(Box::new(machine));
+
+        let mut symbols = labwired_loader::SymbolProvider::new_empty();
+        symbols.add_test_symbol("main", 0x20000101);
+        *adapter.symbols.lock().unwrap() = Some(symbols);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let sender = MessageSender {
+            output: output.clone(),
+            seq: Arc::new(AtomicI64::new(1)),
+        };
+        let args = json!({ "memoryReference": "0x20000000", "instructionCount": 1 });
+        server.handle_request(1, "disassemble", Some(&args), &sender)?;
+
+        let out_str = String::from_utf8(output.lock().unwrap().clone())?;
+        assert!(
+            out_str.contains("\"instruction\":\"BL 0x20000100 <main>\""),
+            "{out_str}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_handle_set_breakpoints_missing_arguments_returns_error() -> Result<()> {
         let server = DapServer::new();
@@ -6,11 +6,16 @@
 
 use core::convert::Infallible;
 use gdbstub::stub::{BaseStopReason, GdbStub};
+use gdbstub::target::ext::base::single_register_access::{
+    SingleRegisterAccess, SingleRegisterAccessOps,
+};
 use gdbstub::target::ext::base::singlethread::{
     SingleThreadBase, SingleThreadResume, SingleThreadSingleStep,
 };
 use gdbstub::target::ext::base::BaseOps;
+use gdbstub::target::ext::memory_map::{MemoryMap, MemoryMapOps};
 use gdbstub::target::{Target, TargetError, TargetResult};
+use labwired_core::bus::SystemBus;
 use labwired_core::cpu::{CortexM, RiscV};
 use labwired_core::{Cpu, DebugControl, Machine, StopReason};
 use std::marker::PhantomData;
@@ -23,6 +28,23 @@ pub struct LabwiredTarget<C: Cpu> {
     pub single_step: bool,
 }
 
+/// Instructions retired per `Machine::run` call while the target is continuing.
+const CONTINUE_CHUNK_STEPS: u32 = 10_000;
+
+/// How often a continuing target checks the client connection (for Ctrl-C or
+/// other packets). Polling by time instead of per chunk keeps the socket
+/// syscalls off the hot path.
+const CONNECTION_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Why [`LabwiredTarget::resume_until_stop`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeOutcome {
+    /// The target stopped (breakpoint, finished step or simulation error).
+    Stopped(BaseStopReason<(), u32>),
+    /// The client sent a byte while the target was running.
+    Incoming(u8),
+}
+
 impl<C: Cpu> LabwiredTarget<C> {
     pub fn new(machine: Machine<C>) -> Self {
         Self {
@@ -31,6 +53,100 @@ impl<C: Cpu> LabwiredTarget<C> {
             single_step: false,
         }
     }
+
+    /// Runs a resumed target until it stops or `poll` yields client data.
+    ///
+    /// A single-step request retires one instruction. A continue runs in
+    /// chunks of `CONTINUE_CHUNK_STEPS` until a breakpoint is hit, calling
+    /// `poll` at most once per `CONNECTION_POLL_INTERVAL` (and once up front),
+    /// so one GDB `continue` is one call here however far the breakpoint is.
+    pub fn resume_until_stop(&mut self, mut poll: impl FnMut() -> Option<u8>) -> ResumeOutcome {
+        let mut next_poll = std::time::Instant::now();
+        loop {
+            if std::time::Instant::now() >= next_poll {
+                if let Some(byte) = poll() {
+                    return ResumeOutcome::Incoming(byte);
+                }
+                next_poll = std::time::Instant::now() + CONNECTION_POLL_INTERVAL;
+            }
+
+            let result = if self.single_step {
+                self.machine.step_single()
+            } else {
+                self.machine.run(Some(CONTINUE_CHUNK_STEPS))
+            };
+
+            match result {
+                Ok(StopReason::MaxStepsReached) if !self.single_step => {}
+                Ok(_) => {
+                    self.running = false;
+                    return ResumeOutcome::Stopped(BaseStopReason::Signal(
+                        gdbstub::common::Signal::SIGTRAP,
+                    ));
+                }
+                Err(e) => {
+                    self.running = false;
+                    tracing::error!("GDB Simulation Error: {}", e);
+                    return ResumeOutcome::Stopped(BaseStopReason::Signal(
+                        gdbstub::common::Signal::SIGSEGV,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Reads the register the CPU calls `name` (resolved through
+    /// `Cpu::index_of_register`) into `buf` as little-endian bytes. Returns the
+    /// byte count, 0 when the CPU has no such register.
+    fn read_named_register(&self, name: &str, buf: &mut [u8]) -> usize {
+        let Some(index) = self.machine.cpu.index_of_register(name) else {
+            return 0;
+        };
+        let bytes = self.machine.read_core_reg(index).to_le_bytes();
+        let len = bytes.len().min(buf.len());
+        buf[..len].copy_from_slice(&bytes[..len]);
+        len
+    }
+
+    /// Writes little-endian `val` to the register the CPU calls `name`.
+    fn write_named_register(&mut self, name: &str, val: &[u8]) -> TargetResult<(), Self>
+    where
+        Self: Target,
+    {
+        let index = self
+            .machine
+            .cpu
+            .index_of_register(name)
+            .ok_or(TargetError::NonFatal)?;
+        let bytes: [u8; 4] = val.try_into().map_err(|_| TargetError::NonFatal)?;
+        self.machine
+            .write_core_reg(index, u32::from_le_bytes(bytes));
+        Ok(())
+    }
+}
+
+/// CPU register name for an ARM GDB register id, as `Cpu::index_of_register`
+/// understands it.
+fn arm_register_name(id: gdbstub_arch::arm::reg::id::ArmCoreRegId) -> Option<String> {
+    use gdbstub_arch::arm::reg::id::ArmCoreRegId;
+    match id {
+        ArmCoreRegId::Gpr(n) => Some(format!("R{}", n)),
+        ArmCoreRegId::Sp => Some("SP".to_string()),
+        ArmCoreRegId::Lr => Some("LR".to_string()),
+        ArmCoreRegId::Pc => Some("PC".to_string()),
+        ArmCoreRegId::Cpsr => Some("XPSR".to_string()),
+        _ => None,
+    }
+}
+
+/// CPU register name for a RISC-V GDB register id.
+fn riscv_register_name(id: gdbstub_arch::riscv::reg::id::RiscvRegId<u32>) -> Option<String> {
+    use gdbstub_arch::riscv::reg::id::RiscvRegId;
+    match id {
+        RiscvRegId::Gpr(n) => Some(format!("x{}", n)),
+        RiscvRegId::Pc => Some("pc".to_string()),
+        _ => None,
+    }
 }
 
 impl Target for LabwiredTarget<CortexM> {
@@ -46,6 +162,10 @@ impl Target for LabwiredTarget<CortexM> {
     ) -> Option<gdbstub::target::ext::breakpoints::BreakpointsOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_memory_map(&mut self) -> Option<MemoryMapOps<'_, Self>> {
+        Some(self)
+    }
 }
 
 impl SingleThreadBase for LabwiredTarget<CortexM> {
@@ -99,6 +219,10 @@ impl SingleThreadBase for LabwiredTarget<CortexM> {
     ) -> Option<gdbstub::target::ext::base::singlethread::SingleThreadResumeOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_single_register_access(&mut self) -> Option<SingleRegisterAccessOps<'_, (), Self>> {
+        Some(self)
+    }
 }
 
 impl Target for LabwiredTarget<RiscV> {
@@ -114,6 +238,10 @@ impl Target for LabwiredTarget<RiscV> {
     ) -> Option<gdbstub::target::ext::breakpoints::BreakpointsOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_memory_map(&mut self) -> Option<MemoryMapOps<'_, Self>> {
+        Some(self)
+    }
 }
 
 impl SingleThreadBase for LabwiredTarget<RiscV> {
@@ -169,6 +297,52 @@ impl SingleThreadBase for LabwiredTarget<RiscV> {
     ) -> Option<gdbstub::target::ext::base::singlethread::SingleThreadResumeOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_single_register_access(&mut self) -> Option<SingleRegisterAccessOps<'_, (), Self>> {
+        Some(self)
+    }
+}
+
+impl SingleRegisterAccess<()> for LabwiredTarget<CortexM> {
+    fn read_register(
+        &mut self,
+        _tid: (),
+        reg_id: gdbstub_arch::arm::reg::id::ArmCoreRegId,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(arm_register_name(reg_id).map_or(0, |name| self.read_named_register(&name, buf)))
+    }
+
+    fn write_register(
+        &mut self,
+        _tid: (),
+        reg_id: gdbstub_arch::arm::reg::id::ArmCoreRegId,
+        val: &[u8],
+    ) -> TargetResult<(), Self> {
+        let name = arm_register_name(reg_id).ok_or(TargetError::NonFatal)?;
+        self.write_named_register(&name, val)
+    }
+}
+
+impl SingleRegisterAccess<()> for LabwiredTarget<RiscV> {
+    fn read_register(
+        &mut self,
+        _tid: (),
+        reg_id: gdbstub_arch::riscv::reg::id::RiscvRegId<u32>,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        Ok(riscv_register_name(reg_id).map_or(0, |name| self.read_named_register(&name, buf)))
+    }
+
+    fn write_register(
+        &mut self,
+        _tid: (),
+        reg_id: gdbstub_arch::riscv::reg::id::RiscvRegId<u32>,
+        val: &[u8],
+    ) -> TargetResult<(), Self> {
+        let name = riscv_register_name(reg_id).ok_or(TargetError::NonFatal)?;
+        self.write_named_register(&name, val)
+    }
 }
 
 impl<C: Cpu> SingleThreadResume for LabwiredTarget<C>
@@ -192,6 +366,8 @@ impl<C: Cpu> SingleThreadSingleStep for LabwiredTarget<C>
 where
     LabwiredTarget<C>: Target<Arch: gdbstub::arch::Arch<Usize = u32>>,
 {
+    /// Arms a single step; the event loop (`resume_until_stop`) then retires
+    /// exactly one instruction and reports the stop.
     fn step(&mut self, _signal: Option<gdbstub::common::Signal>) -> Result<(), Self::Error> {
         self.running = true;
         self.single_step = true;
@@ -233,6 +409,72 @@ where
     }
 }
 
+/// GDB memory-map XML (`qXfer:memory-map:read`) for `bus`: flash, RAM, the
+/// extra and sparse memory regions and every peripheral window, so `info mem`
+/// shows the real layout and GDB does not probe unmapped addresses.
+///
+/// Flash is advertised as `ram` (or `rom` when not writable) rather than
+/// `flash`: the latter makes GDB program it through `vFlash*` packets, which
+/// the stub does not implement. GDB discards a map with overlapping regions,
+/// so a region overlapping an earlier one (by start address) is left out.
+pub fn memory_map_xml(bus: &SystemBus) -> String {
+    let mut regions: Vec<(u64, u64, &str)> = Vec::new();
+    for mem in std::iter::once(&bus.flash)
+        .chain(std::iter::once(&bus.ram))
+        .chain(&bus.extra_mem)
+    {
+        let kind = if mem.writable { "ram" } else { "rom" };
+        regions.push((mem.base_addr, mem.data.len() as u64, kind));
+    }
+    for mem in &bus.sparse_mem {
+        regions.push((mem.base_addr, mem.size, "ram"));
+    }
+    for p in &bus.peripherals {
+        regions.push((p.base, p.size, "ram"));
+    }
+    regions.retain(|&(_, len, _)| len > 0);
+    regions.sort_by_key(|&(start, _, _)| start);
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?>\n\
+         <!DOCTYPE memory-map PUBLIC \"+//IDN gnu.org//DTD GDB Memory Map V1.0//EN\" \
+         \"http://sourceware.org/gdb/gdb-memory-map.dtd\">\n\
+         <memory-map>\n",
+    );
+    let mut end = 0u64;
+    for (start, len, kind) in regions {
+        if start < end {
+            continue;
+        }
+        end = start + len;
+        xml.push_str(&format!(
+            "  <memory type=\"{}\" start=\"0x{:x}\" length=\"0x{:x}\"/>\n",
+            kind, start, len
+        ));
+    }
+    xml.push_str("</memory-map>\n");
+    xml
+}
+
+impl<C: Cpu> MemoryMap for LabwiredTarget<C>
+where
+    LabwiredTarget<C>: Target<Arch: gdbstub::arch::Arch<Usize = u32>>,
+{
+    fn memory_map_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let xml = memory_map_xml(&self.machine.bus);
+        let data = xml.as_bytes();
+        let start = (offset as usize).min(data.len());
+        let end = start.saturating_add(length.min(buf.len())).min(data.len());
+        buf[..end - start].copy_from_slice(&data[start..end]);
+        Ok(end - start)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub struct GdbServer {
     port: u16,
@@ -301,57 +543,22 @@ where
             )));
         }
 
-        loop {
-            // Non-blocking peep at connection for interrupt
+        let outcome = target.resume_until_stop(|| {
+            // Non-blocking peek at the connection for an interrupt
             let mut byte = [0];
             conn.set_nonblocking(true).ok();
             let incoming = match conn.read(&mut byte) {
-                Ok(1) => {
-                    conn.set_nonblocking(false).ok();
-                    Some(byte[0])
-                }
-                _ => {
-                    conn.set_nonblocking(false).ok();
-                    None
-                }
-            };
-
-            if let Some(b) = incoming {
-                return Ok(Event::IncomingData(b));
-            }
-
-            // Run machine for a small chunk
-            let result = if target.single_step {
-                target.machine.step_single()
-            } else {
-                target.machine.run(Some(1000))
+                Ok(1) => Some(byte[0]),
+                _ => None,
             };
-
-            match result {
-                Ok(StopReason::Breakpoint(_)) | Ok(StopReason::StepDone) => {
-                    target.running = false;
-                    return Ok(Event::TargetStopped(BaseStopReason::Signal(
-                        gdbstub::common::Signal::SIGTRAP,
-                    )));
-                }
-                Ok(_) => {
-                    // MaxStepsReached
-                    if target.single_step {
-                        target.running = false;
-                        return Ok(Event::TargetStopped(BaseStopReason::Signal(
-                            gdbstub::common::Signal::SIGTRAP,
-                        )));
-                    }
-                }
-                Err(e) => {
-                    target.running = false;
-                    tracing::error!("GDB Simulation Error: {}", e);
-                    return Ok(Event::TargetStopped(BaseStopReason::Signal(
-                        gdbstub::common::Signal::SIGSEGV,
-                    )));
-                }
-            }
-        }
+            conn.set_nonblocking(false).ok();
+            incoming
+        });
+
+        Ok(match outcome {
+            ResumeOutcome::Stopped(reason) => Event::TargetStopped(reason),
+            ResumeOutcome::Incoming(byte) => Event::IncomingData(byte),
+        })
     }
 
     fn on_interrupt(
@@ -368,6 +575,7 @@ mod tests {
     use super::*;
     use labwired_core::bus::SystemBus;
     use labwired_core::cpu::CortexM;
+    use labwired_core::Bus;
 
     #[test]
     fn test_target_register_access() {
@@ -398,6 +606,40 @@ mod tests {
         assert_eq!(target.machine.read_core_reg(1), 0xdeadbeef);
     }
 
+    #[test]
+    fn test_single_register_access_by_name() {
+        use gdbstub_arch::arm::reg::id::ArmCoreRegId;
+
+        let mut bus = SystemBus::new();
+        let (cpu, _nvic) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut target = LabwiredTarget::<CortexM>::new(Machine::new(cpu, bus));
+        target.machine.write_core_reg(15, 0x0800_0100);
+        target.machine.write_core_reg(16, 0x6100_0000);
+
+        let mut buf = [0u8; 4];
+        let n = SingleRegisterAccess::read_register(&mut target, (), ArmCoreRegId::Pc, &mut buf)
+            .unwrap_or_else(|_| panic!("Failed to read PC"));
+        assert_eq!(&buf[..n], &0x0800_0100u32.to_le_bytes());
+
+        let n = SingleRegisterAccess::read_register(&mut target, (), ArmCoreRegId::Cpsr, &mut buf)
+            .unwrap_or_else(|_| panic!("Failed to read xPSR"));
+        assert_eq!(&buf[..n], &0x6100_0000u32.to_le_bytes());
+
+        SingleRegisterAccess::write_register(
+            &mut target,
+            (),
+            ArmCoreRegId::Gpr(3),
+            &0xCAFE_F00Du32.to_le_bytes(),
+        )
+        .unwrap_or_else(|_| panic!("Failed to write R3"));
+        assert_eq!(target.machine.read_core_reg(3), 0xCAFE_F00D);
+
+        // Registers the CPU does not expose read back as unavailable.
+        let n = SingleRegisterAccess::read_register(&mut target, (), ArmCoreRegId::Fps, &mut buf)
+            .unwrap_or_else(|_| panic!("Failed to read FPS"));
+        assert_eq!(n, 0);
+    }
+
     #[test]
     fn test_riscv_target_register_access() {
         let bus = SystemBus::new();
@@ -425,6 +667,123 @@ mod tests {
         assert_eq!(target.machine.read_core_reg(2), 0xdeadbeef);
     }
 
+    /// A Cortex-M target running a countdown of ~8000 instructions from RAM,
+    /// with the instruction after the loop at the returned address.
+    fn countdown_target() -> (LabwiredTarget<CortexM>, u32) {
+        let mut bus = SystemBus::new();
+        let (cpu, _nvic) = labwired_core::system::cortex_m::configure_cortex_m(&mut bus);
+        let mut machine = Machine::new(cpu, bus);
+        let base = 0x2000_0000u32;
+        let program: [u16; 5] = [
+            0x20FF, // movs r0, #0xFF
+            0x0100, // lsls r0, r0, #4   (r0 = 0xFF0)
+            0x3801, // loop: subs r0, #1
+            0xD1FD, // bne loop
+            0xBF00, // nop               (exit)
+        ];
+        for (i, insn) in program.iter().enumerate() {
+            machine
+                .bus
+                .write_u16(base as u64 + 2 * i as u64, *insn)
+                .unwrap();
+        }
+        machine.cpu.set_pc(base);
+        (LabwiredTarget::new(machine), base + 8)
+    }
+
+    #[test]
+    fn test_continue_reaches_breakpoint_in_one_resume() {
+        let (mut target, exit) = countdown_target();
+        target.machine.add_breakpoint(exit);
+
+        SingleThreadResume::resume(&mut target, None).unwrap();
+        let mut polls = 0;
+        let outcome = target.resume_until_stop(|| {
+            polls += 1;
+            None
+        });
+
+        assert_eq!(
+            outcome,
+            ResumeOutcome::Stopped(BaseStopReason::Signal(gdbstub::common::Signal::SIGTRAP))
+        );
+        assert!(!target.running);
+        assert_eq!(target.machine.read_core_reg(15), exit);
+        assert_eq!(target.machine.read_core_reg(0), 0);
+        assert!(polls >= 1);
+    }
+
+    #[test]
+    fn test_single_step_advances_one_instruction() {
+        let (mut target, _) = countdown_target();
+        let start = target.machine.read_core_reg(15);
+
+        SingleThreadSingleStep::step(&mut target, None).unwrap();
+        let outcome = target.resume_until_stop(|| None);
+
+        assert_eq!(
+            outcome,
+            ResumeOutcome::Stopped(BaseStopReason::Signal(gdbstub::common::Signal::SIGTRAP))
+        );
+        assert_eq!(target.machine.read_core_reg(15), start + 2);
+        assert_eq!(target.machine.read_core_reg(0), 0xFF);
+
+        // A following continue is not left in single-step mode.
+        SingleThreadResume::resume(&mut target, None).unwrap();
+        assert!(!target.single_step);
+    }
+
+    #[test]
+    fn test_continue_returns_client_data() {
+        let (mut target, _) = countdown_target();
+
+        SingleThreadResume::resume(&mut target, None).unwrap();
+        let outcome = target.resume_until_stop(|| Some(0x03));
+
+        assert_eq!(outcome, ResumeOutcome::Incoming(0x03));
+        assert!(target.running, "an interrupt byte does not stop the target");
+    }
+
+    #[test]
+    fn test_memory_map_xml_lists_flash_and_ram() {
+        let bus = SystemBus::new();
+        let xml = memory_map_xml(&bus);
+
+        assert!(xml.contains("<memory-map>"), "{xml}");
+        assert!(
+            xml.contains(r#"<memory type="ram" start="0x0" length="0x100000"/>"#),
+            "flash range missing: {xml}"
+        );
+        assert!(
+            xml.contains(r#"<memory type="ram" start="0x20000000" length="0x100000"/>"#),
+            "RAM range missing: {xml}"
+        );
+        assert!(
+            xml.contains(r#"start="0x4000c000" length="0x400""#),
+            "uart1 window missing: {xml}"
+        );
+
+        // The target serves the same document in chunks.
+        let machine = Machine::new(CortexM::default(), bus);
+        let target = LabwiredTarget::new(machine);
+        let mut buf = [0u8; 16];
+        let n = MemoryMap::memory_map_xml(&target, 0, buf.len(), &mut buf)
+            .unwrap_or_else(|_| panic!("memory map read failed"));
+        assert_eq!(&buf[..n], &xml.as_bytes()[..16]);
+    }
+
+    #[test]
+    fn test_memory_map_xml_lists_sparse_regions() {
+        let mut bus = SystemBus::new();
+        bus.sparse_mem.push(labwired_core::memory::SparseMemory::new(1 << 32, 0x1_0000_0000));
+        let xml = memory_map_xml(&bus);
+
+        assert!(
+            xml.contains(r#"<memory type="ram" start="0x100000000" length="0x100000000"/>"#),
+            "sparse range missing: {xml}"
+        );
+    }
+
     #[test]
     fn test_target_memory_access() {
         let mut bus = SystemBus::new();
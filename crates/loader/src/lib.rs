@@ -46,6 +46,21 @@ pub fn resolve_symbol_in_elf(buffer: &[u8], name: &str) -> Option<u32> {
     None
 }
 
+/// GNU build-id of an ELF (the `NT_GNU_BUILD_ID` note, normally in
+/// `.note.gnu.build-id`) as lowercase hex. Unlike a hash of the whole file it
+/// is stable across rebuilds that only change timestamps or debug paths.
+/// Returns `None` for non-ELF input or when the linker emitted no build-id
+/// (`--build-id` is off by default for most bare-metal toolchains).
+pub fn gnu_build_id(buffer: &[u8]) -> Option<String> {
+    use object::Object;
+    let object = object::File::parse(buffer).ok()?;
+    let id = object.build_id().ok().flatten()?;
+    if id.is_empty() {
+        return None;
+    }
+    Some(id.iter().map(|b| format!("{b:02x}")).collect())
+}
+
 /// Extract every Arduino-ESP32 / ESP-IDF / Arduino-core symbol the LabWired
 /// sim cares about for an Arduino-ESP32 firmware. Includes:
 ///   * flash-thunk targets (heap_caps_*, esp_timer_init, locks, …),
@@ -402,6 +417,10 @@ pub fn load_elf_bytes(buffer: &[u8]) -> Result<ProgramImage> {
     };
 
     let mut program_image = ProgramImage::new(elf.entry, arch);
+    // EI_DATA: ELFDATA2MSB images (PowerPC, ARM BE8) are big-endian.
+    if !elf.little_endian {
+        program_image.endianness = labwired_core::Endianness::Big;
+    }
 
     for ph in elf.program_headers {
         if ph.p_type == PT_LOAD {
@@ -431,6 +450,9 @@ pub fn load_elf_bytes(buffer: &[u8]) -> Result<ProgramImage> {
     if program_image.segments.is_empty() {
         warn!("No loadable segments found in ELF file");
     }
+    if let Some(&(start, end)) = program_image.overlaps().first() {
+        return Err(anyhow!("ELF load segments overlap at {start:#x}..{end:#x}"));
+    }
 
     Ok(program_image)
 }
@@ -501,6 +523,13 @@ impl SymbolProvider {
         let mut line_map = std::collections::HashMap::new();
         let mut stmt_rows: Vec<StmtRow> = Vec::new();
 
+        // DWARF is encoded in the image's byte order (big-endian targets too).
+        let endian = if object.is_little_endian() {
+            gimli::RunTimeEndian::Little
+        } else {
+            gimli::RunTimeEndian::Big
+        };
+
         // Build line map using gimli for reverse lookup
         let load_section = |id: gimli::SectionId| -> std::result::Result<
             addr2line::gimli::EndianReader<gimli::RunTimeEndian, Arc<[u8]>>,
@@ -512,7 +541,7 @@ impl SymbolProvider {
                 .and_then(|s| s.uncompressed_data().ok())
                 .map(|d| Arc::from(&d[..]))
                 .unwrap_or_else(|| Arc::from(&[][..]));
-            Ok(gimli::EndianReader::new(data, gimli::RunTimeEndian::Little))
+            Ok(gimli::EndianReader::new(data, endian))
         };
 
         let dwarf = gimli::Dwarf::load(&load_section).context("Failed to load DWARF")?;
@@ -683,6 +712,17 @@ impl SymbolProvider {
         self.symbol_map.get(name).copied()
     }
 
+    /// Name of the symbol at exactly `addr`, ignoring the Thumb bit. ARM
+    /// mapping symbols (`$t`, `$d`, …) are skipped; ties go to the
+    /// alphabetically first name so output is stable.
+    pub fn symbol_at(&self, addr: u64) -> Option<&str> {
+        self.symbol_map
+            .iter()
+            .filter(|(name, a)| **a & !1 == addr & !1 && !name.starts_with('$'))
+            .map(|(name, _)| name.as_str())
+            .min()
+    }
+
     pub fn find_locals(&self, pc: u64) -> Vec<LocalVariable> {
         let mut locals = Vec::new();
 
@@ -828,6 +868,11 @@ impl SymbolProvider {
         }
     }
 
+    /// Add a mock symbol for testing
+    pub fn add_test_symbol(&mut self, name: &str, addr: u64) {
+        self.symbol_map.insert(name.to_string(), addr);
+    }
+
     /// Add a mock local variable for testing
     pub fn add_test_local(&mut self, name: &str, location: DwarfLocation) {
         // We use PC 0 as the default for test locals if not specified
@@ -907,6 +952,44 @@ mod tests {
         assert_eq!(loc.line, Some(26));
     }
 
+    /// A minimal little-endian ARM ELF32 with one PT_LOAD per `paddr`, all
+    /// backed by the same 16 file bytes.
+    fn elf_with_loads(paddrs: &[u32]) -> Vec<u8> {
+        let phoff = 52u32;
+        let data_off = phoff + 32 * paddrs.len() as u32;
+        let mut elf = vec![0x7f, b'E', b'L', b'F', 1, 1, 1];
+        elf.resize(16, 0);
+        elf.extend(2u16.to_le_bytes()); // ET_EXEC
+        elf.extend(40u16.to_le_bytes()); // EM_ARM
+        elf.extend(1u32.to_le_bytes());
+        elf.extend(paddrs[0].to_le_bytes()); // e_entry
+        elf.extend(phoff.to_le_bytes());
+        elf.extend(0u32.to_le_bytes()); // e_shoff
+        elf.extend(0u32.to_le_bytes()); // e_flags
+        elf.extend(52u16.to_le_bytes());
+        elf.extend(32u16.to_le_bytes());
+        elf.extend((paddrs.len() as u16).to_le_bytes());
+        elf.extend(40u16.to_le_bytes());
+        elf.extend(0u16.to_le_bytes());
+        elf.extend(0u16.to_le_bytes());
+        for &paddr in paddrs {
+            for word in [PT_LOAD, data_off, paddr, paddr, 16, 16, 5, 4] {
+                elf.extend(word.to_le_bytes());
+            }
+        }
+        elf.extend([0xAA; 16]);
+        elf
+    }
+
+    #[test]
+    fn test_overlapping_load_segments_are_rejected() {
+        let image = load_elf_bytes(&elf_with_loads(&[0x0800_0000, 0x0800_0010])).unwrap();
+        assert_eq!(image.segments.len(), 2);
+
+        let err = load_elf_bytes(&elf_with_loads(&[0x0800_0000, 0x0800_0008])).unwrap_err();
+        assert!(err.to_string().contains("overlap at 0x8000008..0x8000010"), "{err}");
+    }
+
     #[test]
     fn test_statement_rows_full_not_deduped() {
         let elf_path =
@@ -971,4 +1054,69 @@ mod tests {
         );
         assert_eq!(resolved, Some((0x0800_00FC, 125)));
     }
+
+    #[test]
+    fn test_big_endian_elf_header_sets_image_endianness() {
+        // Bare ELF32 header (no segments): ARM, ELFDATA2MSB, entry 0x100.
+        let mut elf = vec![0x7f, b'E', b'L', b'F', 1, 2, 1, 0];
+        elf.resize(16, 0);
+        elf.extend_from_slice(&2u16.to_be_bytes()); // e_type = ET_EXEC
+        elf.extend_from_slice(&40u16.to_be_bytes()); // e_machine = EM_ARM
+        elf.extend_from_slice(&1u32.to_be_bytes()); // e_version
+        elf.extend_from_slice(&0x100u32.to_be_bytes()); // e_entry
+        elf.extend_from_slice(&[0; 12]); // e_phoff, e_shoff, e_flags
+        for half in [52u16, 32, 0, 40, 0, 0] {
+            elf.extend_from_slice(&half.to_be_bytes());
+        }
+
+        let image = load_elf_bytes(&elf).expect("parse big-endian header");
+        assert_eq!(image.entry_point, 0x100);
+        assert_eq!(image.endianness, labwired_core::Endianness::Big);
+    }
+
+    #[test]
+    fn test_gnu_build_id_reads_note_section() {
+        // ELF32 LE: header, one SHT_NOTE section holding an NT_GNU_BUILD_ID
+        // note, and a section-name string table.
+        let build_id = [0xde, 0xad, 0xbe, 0xef, 0x01, 0x23, 0x45, 0x67];
+        let shstrtab = b"\0.note.gnu.build-id\0.shstrtab\0";
+        let le32 = |v: u32| v.to_le_bytes();
+
+        let mut elf = vec![0x7f, b'E', b'L', b'F', 1, 1, 1, 0];
+        elf.resize(16, 0);
+        elf.extend_from_slice(&2u16.to_le_bytes()); // e_type = ET_EXEC
+        elf.extend_from_slice(&40u16.to_le_bytes()); // e_machine = EM_ARM
+        elf.extend_from_slice(&le32(1)); // e_version
+        elf.extend_from_slice(&le32(0)); // e_entry
+        elf.extend_from_slice(&le32(0)); // e_phoff
+        elf.extend_from_slice(&le32(108)); // e_shoff
+        elf.extend_from_slice(&le32(0)); // e_flags
+        for half in [52u16, 32, 0, 40, 3, 2] {
+            elf.extend_from_slice(&half.to_le_bytes());
+        }
+        // Note at 52: namesz, descsz, type, "GNU\0", desc.
+        for word in [4u32, build_id.len() as u32, 3] {
+            elf.extend_from_slice(&le32(word));
+        }
+        elf.extend_from_slice(b"GNU\0");
+        elf.extend_from_slice(&build_id);
+        // .shstrtab at 76, padded to the section headers at 108.
+        elf.extend_from_slice(shstrtab);
+        elf.resize(108, 0);
+        elf.extend_from_slice(&[0; 40]);
+        let section = |name: u32, kind: u32, flags: u32, offset: u32, size: u32, align: u32| {
+            [name, kind, flags, 0, offset, size, 0, 0, align, 0]
+        };
+        for sh in [
+            section(1, 7, 2, 52, 24, 4),
+            section(20, 3, 0, 76, shstrtab.len() as u32, 1),
+        ] {
+            for word in sh {
+                elf.extend_from_slice(&le32(word));
+            }
+        }
+
+        assert_eq!(gnu_build_id(&elf).as_deref(), Some("deadbeef01234567"));
+        assert_eq!(gnu_build_id(b"not an elf"), None);
+    }
 }
@@ -3,11 +3,15 @@
 use labwired_core::{
     bus::SystemBus,
     system::{cortex_m, riscv},
-    Arch, DebugControl, SimulationError, StopReason,
+    Arch, DebugControl, FaultCause, FaultInfo, ResetKind, SimulationConfig, SimulationError,
+    SimulationObserver, StopReason,
 };
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use std::cell::Cell;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 // Wrapper for StopReason to expose to Python
 #[derive(Clone, Debug, PartialEq)]
@@ -78,29 +82,185 @@ fn build_bus(system_path: Option<PathBuf>) -> anyhow::Result<SystemBus> {
     Ok(bus)
 }
 
+/// Build a [`SimulationConfig`] from the `config` dict passed to `Machine()`.
+fn build_config(config: Option<&PyDict>) -> PyResult<SimulationConfig> {
+    let mut builder = SimulationConfig::builder();
+    let Some(config) = config else {
+        return Ok(builder.build());
+    };
+    for (key, value) in config.iter() {
+        let key: String = key.extract()?;
+        builder = match key.as_str() {
+            "peripheral_tick_interval" => builder.peripheral_tick_interval(value.extract()?),
+            "decode_cache_enabled" => builder.decode_cache_enabled(value.extract()?),
+            "optimized_bus_access" => builder.optimized_bus_access(value.extract()?),
+            "batch_mode_enabled" => builder.batch_mode_enabled(value.extract()?),
+            "idle_fast_forward_enabled" => builder.idle_fast_forward_enabled(value.extract()?),
+            "riscv_jit_enabled" => builder.riscv_jit_enabled(value.extract()?),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown simulation config key '{}'",
+                    key
+                )))
+            }
+        };
+    }
+    Ok(builder.build())
+}
+
+type SharedMachine = Arc<Mutex<Box<dyn DebugControl + Send>>>;
+
+thread_local! {
+    /// Set while a progress or event callback runs on this thread. Callbacks
+    /// are invoked from inside `step`, which holds the machine lock, so a
+    /// callback touching that machine must fail instead of deadlocking.
+    static IN_CALLBACK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Call a Python callback with [`IN_CALLBACK`] set.
+fn run_callback<R>(f: impl FnOnce() -> R) -> R {
+    let outer = IN_CALLBACK.with(|c| c.replace(true));
+    let result = f();
+    IN_CALLBACK.with(|c| c.set(outer));
+    result
+}
+
+/// Lock the machine. Other threads wait for a running `step()` as before; a
+/// callback fired by that `step()` gets a RuntimeError instead of a deadlock.
+fn lock_machine(inner: &SharedMachine) -> PyResult<MutexGuard<'_, Box<dyn DebugControl + Send>>> {
+    if IN_CALLBACK.with(Cell::get) {
+        return inner.try_lock().map_err(|_| {
+            pyo3::exceptions::PyRuntimeError::new_err(
+                "the Machine is running; it cannot be used from its own progress or event callback",
+            )
+        });
+    }
+    Ok(inner.lock().unwrap())
+}
+
+/// Forwards `on_progress` reports to a Python callable.
+#[derive(Debug)]
+struct PyProgressObserver {
+    callback: Mutex<Option<PyObject>>,
+}
+
+impl SimulationObserver for PyProgressObserver {
+    fn wants_step_events(&self) -> bool {
+        false
+    }
+
+    fn on_progress(&self, steps: u64, ips: f64) {
+        let Ok(callback) = self.callback.lock() else {
+            return;
+        };
+        if let Some(callback) = callback.as_ref() {
+            Python::with_gil(|py| {
+                // A failing callback must not abort the run; surface it the
+                // way Python reports an exception in a callback.
+                if let Err(e) = run_callback(|| callback.call1(py, (steps, ips))) {
+                    e.print(py);
+                }
+            });
+        }
+    }
+}
+
+/// Forwards fault and peripheral-activity notifications to a Python callable
+/// as `callback(event, details)`.
+#[derive(Debug)]
+struct PyEventObserver {
+    callback: Mutex<Option<PyObject>>,
+}
+
+impl PyEventObserver {
+    fn emit(
+        &self,
+        event: &str,
+        details: impl for<'py> FnOnce(Python<'py>) -> PyResult<&'py PyDict>,
+    ) {
+        let Ok(callback) = self.callback.lock() else {
+            return;
+        };
+        if let Some(callback) = callback.as_ref() {
+            Python::with_gil(|py| {
+                let result =
+                    details(py).and_then(|d| run_callback(|| callback.call1(py, (event, d))));
+                // Same policy as progress callbacks: report, keep running.
+                if let Err(e) = result {
+                    e.print(py);
+                }
+            });
+        }
+    }
+}
+
+impl SimulationObserver for PyEventObserver {
+    fn wants_step_events(&self) -> bool {
+        false
+    }
+
+    fn on_peripheral_tick(&self, name: &str, cycles: u32) {
+        self.emit("peripheral_tick", |py| {
+            let details = PyDict::new(py);
+            details.set_item("peripheral", name)?;
+            details.set_item("cycles", cycles)?;
+            Ok(details)
+        });
+    }
+
+    fn on_fault(&self, fault: FaultInfo) {
+        self.emit("fault", |py| {
+            let kind = match fault.kind {
+                FaultCause::MemoryViolation => "memory_violation",
+                FaultCause::DecodeError => "decode_error",
+                FaultCause::Exception => "exception",
+            };
+            let details = PyDict::new(py);
+            details.set_item("kind", kind)?;
+            details.set_item("pc", fault.pc)?;
+            details.set_item("address", fault.address)?;
+            Ok(details)
+        });
+    }
+}
+
 #[pyclass]
 /// The core LabWired machine simulator.
 ///
 /// This class provides a direct interface to the Rust simulation core, allowing
 /// for loading firmware, stepping execution, inspecting state, and time-travel debugging.
 struct Machine {
-    inner: Arc<Mutex<Box<dyn DebugControl + Send>>>,
+    inner: SharedMachine,
+    progress: Option<Arc<PyProgressObserver>>,
+    events: Option<Arc<PyEventObserver>>,
+    /// Console UART bytes captured by the most recent `run_test`.
+    uart: Arc<Mutex<Vec<u8>>>,
+    /// Held outside `inner` so `request_stop` never waits on a running `step`.
+    stop: Arc<AtomicBool>,
 }
 
 #[allow(non_local_definitions)]
 #[pymethods]
 impl Machine {
     #[new]
-    #[pyo3(signature = (firmware, system_config=None))]
+    #[pyo3(signature = (firmware, system_config=None, config=None))]
     /// Create a new Machine instance.
     ///
     /// Args:
     ///     firmware (str): Path to the ELF firmware file to load.
     ///     system_config (Optional[str]): Path to the system configuration YAML.
     ///         If None, defaults to a standard Cortex-M layout (1MB Flash @ 0x08000000, 128KB RAM @ 0x20000000).
-    fn new(firmware: String, system_config: Option<String>) -> PyResult<Self> {
+    ///     config (Optional[dict]): Simulation settings, e.g.
+    ///         ``{"peripheral_tick_interval": 10, "batch_mode_enabled": False}``.
+    ///         Unknown keys raise ValueError.
+    fn new(
+        firmware: String,
+        system_config: Option<String>,
+        config: Option<&PyDict>,
+    ) -> PyResult<Self> {
         let firmware_path = PathBuf::from(firmware);
         let system_path = system_config.map(PathBuf::from);
+        let sim_config = build_config(config)?;
 
         // Load firmware to check arch
         let program = labwired_loader::load_elf(&firmware_path)
@@ -114,19 +274,19 @@ impl Machine {
         let machine: Box<dyn DebugControl + Send> = match program.arch {
             Arch::Arm => {
                 let (cpu, _nvic) = cortex_m::configure_cortex_m(&mut bus);
-                let mut m = labwired_core::Machine::new(cpu, bus);
+                let mut m = labwired_core::Machine::with_config(cpu, bus, sim_config);
                 m.load_firmware(&program).map_err(PySimulationError)?;
                 Box::new(m)
             }
             Arch::RiscV => {
                 let cpu = riscv::configure_riscv(&mut bus);
-                let mut m = labwired_core::Machine::new(cpu, bus);
+                let mut m = labwired_core::Machine::with_config(cpu, bus, sim_config);
                 m.load_firmware(&program).map_err(PySimulationError)?;
                 Box::new(m)
             }
             Arch::XtensaLx7 => {
                 let cpu = labwired_core::system::xtensa::configure_xtensa(&mut bus);
-                let mut m = labwired_core::Machine::new(cpu, bus);
+                let mut m = labwired_core::Machine::with_config(cpu, bus, sim_config);
                 m.load_firmware(&program).map_err(PySimulationError)?;
                 Box::new(m)
             }
@@ -137,8 +297,13 @@ impl Machine {
             }
         };
 
+        let stop = machine.stop_handle();
         Ok(Machine {
             inner: Arc::new(Mutex::new(machine)),
+            progress: None,
+            events: None,
+            uart: Arc::new(Mutex::new(Vec::new())),
+            stop,
         })
     }
 
@@ -150,10 +315,126 @@ impl Machine {
     ///
     /// Returns:
     ///     StopReason: The reason why the simulation stopped (e.g., Breakpoint, MaxStepsReached).
-    fn step(&mut self, max_steps: Option<u32>) -> PyResult<PyStopReason> {
-        let mut guard = self.inner.lock().unwrap();
-        let reason = guard.run(max_steps).map_err(PySimulationError)?;
-        Ok(reason.into())
+    ///
+    /// The GIL is released while running, so another thread can call `request_stop()`.
+    fn step(&self, py: Python, max_steps: Option<u32>) -> PyResult<PyStopReason> {
+        let inner = self.inner.clone();
+        let reason = py.allow_threads(move || -> PyResult<_> {
+            lock_machine(&inner)?
+                .run(max_steps)
+                .map_err(|e| PySimulationError(e).into())
+        })?;
+        Ok(PyStopReason::from(reason))
+    }
+
+    /// Run the firmware headlessly and collect its console output.
+    ///
+    /// Mirrors ``labwired test``: runs until a breakpoint, a manual stop or
+    /// `max_steps` instructions, with the GIL released. Console output is
+    /// captured only while this call runs, so plain `step()` calls never
+    /// accumulate it.
+    ///
+    /// Args:
+    ///     max_steps (int): Maximum number of instructions to execute.
+    ///
+    /// Returns:
+    ///     dict: ``uart`` (str, UART text sent during this run), ``stop_reason``
+    ///     (StopReason) and ``steps`` (int, instructions executed).
+    fn run_test(&self, py: Python, max_steps: u32) -> PyResult<PyObject> {
+        self.uart.lock().unwrap().clear();
+        let inner = self.inner.clone();
+        let sink = self.uart.clone();
+        let (reason, steps) = py
+            .allow_threads(move || {
+                let mut guard = lock_machine(&inner)?;
+                guard.set_uart_tx_sink(Some(sink));
+                let start = guard.get_instruction_count();
+                let reason = guard.run(Some(max_steps));
+                guard.set_uart_tx_sink(None);
+                let reason = reason.map_err(PySimulationError)?;
+                Ok::<_, PyErr>((reason, guard.get_instruction_count() - start))
+            })?;
+        let uart = String::from_utf8_lossy(&self.uart.lock().unwrap()).into_owned();
+
+        let result = PyDict::new(py);
+        result.set_item("uart", uart)?;
+        result.set_item("stop_reason", PyStopReason::from(reason).into_py(py))?;
+        result.set_item("steps", steps)?;
+        Ok(result.into())
+    }
+
+    /// Bytes captured from the console UART(s) by the most recent `run_test`.
+    ///
+    /// Returns:
+    ///     bytes: The raw UART output.
+    fn uart_output<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.uart.lock().unwrap())
+    }
+
+    /// Ask a running (or the next) `step()` to return with a `manual_stop` reason.
+    ///
+    /// Safe to call from another thread, e.g. to interrupt `step(None)`.
+    fn request_stop(&self) {
+        self.stop.store(true, Ordering::Release);
+    }
+
+    /// Report progress during `step()` every `interval` executed instructions.
+    ///
+    /// Args:
+    ///     callback (Optional[Callable[[int, float], None]]): Called with the
+    ///         instructions executed since registration and the average
+    ///         instructions per second, e.g. to drive a notebook progress bar.
+    ///         Replaces any earlier callback; None stops reporting. The
+    ///         callback runs inside `step()`, so calling this machine's
+    ///         methods from it raises RuntimeError.
+    ///     interval (int): Instructions between calls (default 10000).
+    #[pyo3(signature = (callback, interval=10_000))]
+    fn set_progress_callback(&mut self, callback: Option<PyObject>, interval: u64) -> PyResult<()> {
+        if interval == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "interval must be greater than zero",
+            ));
+        }
+        let mut guard = lock_machine(&self.inner)?;
+        let enabled = callback.is_some();
+        match &self.progress {
+            Some(observer) => *observer.callback.lock().unwrap() = callback,
+            None if enabled => {
+                let observer = Arc::new(PyProgressObserver {
+                    callback: Mutex::new(callback),
+                });
+                guard.add_observer(observer.clone());
+                self.progress = Some(observer);
+            }
+            None => {}
+        }
+        guard.set_progress_interval(enabled.then_some(interval));
+        Ok(())
+    }
+
+    /// Receive simulation events while `step()` runs.
+    ///
+    /// Args:
+    ///     callback (Optional[Callable[[str, dict], None]]): Called as
+    ///         ``callback(event, details)``. Events are ``"fault"`` (details
+    ///         ``kind``, ``pc``, ``address``) when a CPU fault stops the run,
+    ///         and ``"peripheral_tick"`` (details ``peripheral``, ``cycles``)
+    ///         when a peripheral reports busy cycles. Replaces any earlier
+    ///         callback; None stops delivery. Like progress callbacks, it
+    ///         must not call back into this machine.
+    fn set_event_callback(&mut self, callback: Option<PyObject>) -> PyResult<()> {
+        match &self.events {
+            Some(observer) => *observer.callback.lock().unwrap() = callback,
+            None if callback.is_some() => {
+                let observer = Arc::new(PyEventObserver {
+                    callback: Mutex::new(callback),
+                });
+                lock_machine(&self.inner)?.add_observer(observer.clone());
+                self.events = Some(observer);
+            }
+            None => {}
+        }
+        Ok(())
     }
 
     /// Read a core register by its ID.
@@ -163,9 +444,9 @@ impl Machine {
     ///
     /// Returns:
     ///     int: The 32-bit value of the register.
-    fn read_register(&self, id: u8) -> u32 {
-        let guard = self.inner.lock().unwrap();
-        guard.read_core_reg(id)
+    fn read_register(&self, id: u8) -> PyResult<u32> {
+        let guard = lock_machine(&self.inner)?;
+        Ok(guard.read_core_reg(id))
     }
 
     /// Write a value to a core register.
@@ -173,9 +454,55 @@ impl Machine {
     /// Args:
     ///     id (int): The register ID.
     ///     val (int): The 32-bit value to write.
-    fn write_register(&mut self, id: u8, val: u32) {
-        let mut guard = self.inner.lock().unwrap();
+    fn write_register(&mut self, id: u8, val: u32) -> PyResult<()> {
+        let mut guard = lock_machine(&self.inner)?;
         guard.write_core_reg(id, val);
+        Ok(())
+    }
+
+    /// Read a core register by name.
+    ///
+    /// Args:
+    ///     name (str): The register name, e.g. ``"pc"``, ``"r0"``, ``"sp"``,
+    ///         or a RISC-V ABI name such as ``"a0"``.
+    ///
+    /// Returns:
+    ///     int: The 32-bit value of the register.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the CPU has no register with that name.
+    fn read_register_by_name(&self, name: &str) -> PyResult<u32> {
+        let guard = lock_machine(&self.inner)?;
+        guard
+            .read_register_by_name(name)
+            .map_err(PySimulationError)
+            .map_err(Into::into)
+    }
+
+    /// Write a value to a core register by name.
+    ///
+    /// Args:
+    ///     name (str): The register name (see `read_register_by_name`).
+    ///     val (int): The 32-bit value to write.
+    fn write_register_by_name(&mut self, name: &str, val: u32) -> PyResult<()> {
+        let mut guard = lock_machine(&self.inner)?;
+        guard
+            .write_register_by_name(name, val)
+            .map_err(PySimulationError)
+            .map_err(Into::into)
+    }
+
+    /// Inject a fault into the running machine.
+    ///
+    /// Args:
+    ///     target (str): ``"bitflip:<register>:<bit>"`` (e.g. ``"bitflip:r0:3"``),
+    ///         ``"skip_instruction"`` or ``"memflip:<address>:<bit>"``.
+    fn inject_fault(&mut self, target: &str) -> PyResult<()> {
+        let mut guard = lock_machine(&self.inner)?;
+        guard
+            .inject_fault(target)
+            .map_err(PySimulationError)
+            .map_err(Into::into)
     }
 
     /// Read a block of memory.
@@ -187,7 +514,7 @@ impl Machine {
     /// Returns:
     ///     List[int]: The bytes read from memory.
     fn read_memory(&self, addr: u32, len: usize) -> PyResult<Vec<u8>> {
-        let guard = self.inner.lock().unwrap();
+        let guard = lock_machine(&self.inner)?;
         guard
             .read_memory(addr, len)
             .map_err(PySimulationError)
@@ -200,19 +527,48 @@ impl Machine {
     ///     addr (int): The start address.
     ///     data (List[int]): The bytes to write.
     fn write_memory(&mut self, addr: u32, data: Vec<u8>) -> PyResult<()> {
-        let mut guard = self.inner.lock().unwrap();
+        let mut guard = lock_machine(&self.inner)?;
         guard
             .write_memory(addr, &data)
             .map_err(PySimulationError)
             .map_err(Into::into)
     }
 
+    /// Read a block of memory as ``bytes``.
+    ///
+    /// Cheaper than `read_memory` for large dumps: no per-byte ``int`` objects.
+    ///
+    /// Args:
+    ///     addr (int): The start address.
+    ///     len (int): The number of bytes to read.
+    ///
+    /// Returns:
+    ///     bytes: The bytes read from memory.
+    fn read_bytes<'py>(&self, py: Python<'py>, addr: u32, len: usize) -> PyResult<&'py PyBytes> {
+        let guard = lock_machine(&self.inner)?;
+        let data = guard.read_memory(addr, len).map_err(PySimulationError)?;
+        Ok(PyBytes::new(py, &data))
+    }
+
+    /// Write a ``bytes`` buffer to memory.
+    ///
+    /// Args:
+    ///     addr (int): The start address.
+    ///     data (bytes): The bytes to write.
+    fn write_bytes(&mut self, addr: u32, data: &[u8]) -> PyResult<()> {
+        let mut guard = lock_machine(&self.inner)?;
+        guard
+            .write_memory(addr, data)
+            .map_err(PySimulationError)
+            .map_err(Into::into)
+    }
+
     /// Take a snapshot of the current machine state.
     ///
     /// Returns:
     ///     str: A JSON string containing the full state of the CPU and peripherals.
     fn snapshot(&self) -> PyResult<String> {
-        let guard = self.inner.lock().unwrap();
+        let guard = lock_machine(&self.inner)?;
         let snap = guard.snapshot();
         serde_json::to_string(&snap)
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
@@ -223,7 +579,7 @@ impl Machine {
     /// Args:
     ///     json_snapshot (str): The JSON string from a previous `snapshot()` call.
     fn restore(&mut self, json_snapshot: String) -> PyResult<()> {
-        let mut guard = self.inner.lock().unwrap();
+        let mut guard = lock_machine(&self.inner)?;
         let snap: labwired_core::snapshot::MachineSnapshot =
             serde_json::from_str(&json_snapshot)
                 .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
@@ -233,10 +589,86 @@ impl Machine {
             .map_err(Into::into)
     }
 
+    /// Create a checkpoint for speculative execution.
+    ///
+    /// Use as ``with machine.checkpoint() as cp:``; the state is snapshotted
+    /// on entry and restored on exit unless ``cp.commit()`` was called.
+    ///
+    /// Returns:
+    ///     Checkpoint: The context manager.
+    fn checkpoint(slf: Py<Self>) -> Checkpoint {
+        Checkpoint {
+            machine: slf,
+            snapshot: None,
+            committed: false,
+        }
+    }
+
+    /// Reset the machine.
+    ///
+    /// Args:
+    ///     kind (str): ``"power_on"`` (default) clears every register;
+    ///         ``"warm"`` (system reset) and ``"pin"`` (NRST) keep
+    ///         backup-domain registers such as the RTC backup registers.
+    #[pyo3(signature = (kind="power_on"))]
+    fn reset(&mut self, kind: &str) -> PyResult<()> {
+        let kind: ResetKind = kind
+            .parse()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let mut guard = lock_machine(&self.inner)?;
+        guard
+            .reset_with(kind)
+            .map_err(PySimulationError)
+            .map_err(Into::into)
+    }
+
     /// Get the current Program Counter (PC).
-    fn get_pc(&self) -> u32 {
-        let guard = self.inner.lock().unwrap();
-        guard.get_pc()
+    fn get_pc(&self) -> PyResult<u32> {
+        let guard = lock_machine(&self.inner)?;
+        Ok(guard.get_pc())
+    }
+}
+
+#[pyclass]
+/// Context manager returned by `Machine.checkpoint()`.
+///
+/// Holds the JSON snapshot taken on ``__enter__`` and restores it on
+/// ``__exit__`` unless `commit()` was called inside the block.
+struct Checkpoint {
+    machine: Py<Machine>,
+    snapshot: Option<String>,
+    committed: bool,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl Checkpoint {
+    fn __enter__(mut slf: PyRefMut<'_, Self>) -> PyResult<PyRefMut<'_, Self>> {
+        let snapshot = slf.machine.borrow(slf.py()).snapshot()?;
+        slf.snapshot = Some(snapshot);
+        slf.committed = false;
+        Ok(slf)
+    }
+
+    /// Keep the state reached inside the block instead of rewinding on exit.
+    fn commit(&mut self) {
+        self.committed = true;
+    }
+
+    fn __exit__(
+        &mut self,
+        py: Python,
+        _exc_type: Option<&PyAny>,
+        _exc_value: Option<&PyAny>,
+        _traceback: Option<&PyAny>,
+    ) -> PyResult<bool> {
+        if let Some(snapshot) = self.snapshot.take() {
+            if !self.committed {
+                self.machine.borrow_mut(py).restore(snapshot)?;
+            }
+        }
+        // Never swallow an exception raised inside the block.
+        Ok(false)
     }
 }
 
@@ -244,5 +676,6 @@ impl Machine {
 fn labwired(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Machine>()?;
     m.add_class::<PyStopReason>()?;
+    m.add_class::<Checkpoint>()?;
     Ok(())
 }
@@ -8,6 +8,8 @@ import json
 # For now, we expect the user to provide FIRMWARE_PATH env var or we look in default places.
 FIRMWARE_PATH = os.environ.get("LABWIRED_FIRMWARE", "../../examples/demo-blinky/target/thumbv7em-none-eabihf/debug/demo-blinky")
 
+HELLO_DIR = os.environ.get("LABWIRED_HELLO_DIR", "../../examples/arm-c-hello")
+
 @pytest.fixture
 def machine():
     if not os.path.exists(FIRMWARE_PATH):
@@ -29,6 +31,20 @@ def test_register_access(machine):
     machine.write_register(1, 0xCAFEBABE)
     assert machine.read_register(1) == 0xCAFEBABE
 
+def test_register_access_by_name(machine):
+    assert machine.read_register_by_name("pc") == machine.get_pc()
+
+    machine.write_register_by_name("r2", 0x1234)
+    assert machine.read_register(2) == 0x1234
+
+    with pytest.raises(RuntimeError):
+        machine.read_register_by_name("no_such_reg")
+
+def test_inject_register_bitflip(machine):
+    machine.write_register(0, 0x10)
+    machine.inject_fault("bitflip:r0:3")
+    assert machine.read_register(0) == 0x18
+
 def test_memory_access(machine):
     # Write to RAM (usually 0x20000000)
     ram_addr = 0x20000000
@@ -39,6 +55,17 @@ def test_memory_access(machine):
     
     assert list(read_back) == data
 
+def test_bytes_memory_access(machine):
+    ram_addr = 0x20000000
+    data = bytes(i & 0xFF for i in range(4096))
+
+    machine.write_bytes(ram_addr, data)
+    read_back = machine.read_bytes(ram_addr, 4096)
+
+    assert isinstance(read_back, bytes)
+    assert read_back == data
+    assert machine.read_memory(ram_addr, 4) == [0, 1, 2, 3]
+
 def test_execution_steps(machine):
     start_pc = machine.get_pc()
     reason = machine.step(10)
@@ -74,6 +101,130 @@ def test_snapshot_restore(machine):
     assert machine.get_pc() == pc_before
     assert machine.read_register(0) == 0x12345678
 
+def test_checkpoint_rewinds_unless_committed(machine):
+    machine.step(20)
+    pc_before = machine.get_pc()
+
+    with machine.checkpoint():
+        machine.step(10)
+        assert machine.get_pc() != pc_before
+    assert machine.get_pc() == pc_before
+
+    with machine.checkpoint() as cp:
+        machine.step(10)
+        pc_kept = machine.get_pc()
+        cp.commit()
+    assert machine.get_pc() == pc_kept
+
+def test_progress_callback(machine):
+    calls = []
+    machine.set_progress_callback(lambda steps, ips: calls.append((steps, ips)), 10)
+    machine.step(45)
+
+    assert [steps for steps, _ in calls] == [10, 20, 30, 40]
+    assert all(ips >= 0 for _, ips in calls)
+
+    machine.set_progress_callback(None)
+    machine.step(20)
+    assert len(calls) == 4
+
+def test_progress_callback_cannot_reenter_machine(machine):
+    errors = []
+
+    def callback(steps, ips):
+        try:
+            machine.get_pc()
+        except RuntimeError as e:
+            errors.append(str(e))
+
+    machine.set_progress_callback(callback, 10)
+    machine.step(25)
+    machine.set_progress_callback(None)
+
+    assert len(errors) == 2
+    assert "callback" in errors[0]
+    # The lock is released once step() returns.
+    assert machine.get_pc() > 0
+
+def test_event_callback_reports_fault(machine):
+    events = []
+    machine.set_event_callback(lambda event, details: events.append((event, details)))
+
+    # Jump into unmapped space so the next fetch faults.
+    machine.write_register_by_name("pc", 0x60000000)
+    with pytest.raises(RuntimeError):
+        machine.step(1)
+
+    faults = [details for event, details in events if event == "fault"]
+    assert len(faults) == 1
+    assert faults[0]["kind"] == "memory_violation"
+    assert faults[0]["pc"] == 0x60000000
+
+    machine.set_event_callback(None)
+
+def test_event_callback_reports_peripheral_tick(tmp_path):
+    if not os.path.exists(FIRMWARE_PATH):
+        pytest.skip(f"Firmware not found at {FIRMWARE_PATH}")
+    (tmp_path / "chip.yaml").write_text(
+        "name: tick-chip\n"
+        "arch: arm\n"
+        "flash: {base: 0x08000000, size: 1MB}\n"
+        "ram: {base: 0x20000000, size: 128KB}\n"
+        "peripherals:\n"
+        "  - {id: rng, type: nrf52_rng, base_address: 0x4000D000}\n"
+    )
+    (tmp_path / "system.yaml").write_text("name: tick-system\nchip: chip.yaml\n")
+    m = labwired.Machine(FIRMWARE_PATH, str(tmp_path / "system.yaml"))
+
+    events = []
+    reentry = []
+
+    def callback(event, details):
+        events.append((event, details))
+        try:
+            m.get_pc()
+        except RuntimeError:
+            reentry.append(event)
+
+    m.set_event_callback(callback)
+    m.write_memory(0x4000D000, [1, 0, 0, 0])  # TASKS_START: the RNG runs
+    m.step(20)
+    m.set_event_callback(None)
+
+    ticks = [details for event, details in events if event == "peripheral_tick"]
+    assert ticks, "a running RNG reports busy cycles every tick"
+    assert ticks[0]["peripheral"] == "rng"
+    assert ticks[0]["cycles"] >= 1
+    assert len(reentry) == len(events)
+
+def test_request_stop_interrupts_unbounded_step(machine):
+    import threading
+
+    timer = threading.Timer(0.05, machine.request_stop)
+    timer.start()
+    reason = machine.step(None)
+    timer.join()
+
+    assert reason.kind == "manual_stop"
+
+def test_run_test_captures_uart_banner():
+    firmware = os.path.join(HELLO_DIR, "target/firmware")
+    if not os.path.exists(firmware):
+        pytest.skip(f"Firmware not found at {firmware}")
+    m = labwired.Machine(firmware, os.path.join(HELLO_DIR, "system.yaml"))
+
+    result = m.run_test(20_000)
+
+    assert "Hello from LabWired C Example!" in result["uart"]
+    assert result["stop_reason"].kind == "max_steps_reached"
+    assert result["steps"] == 20_000
+    assert m.uart_output().decode().startswith("Hello from LabWired C Example!")
+
+    # Capture stops with run_test: later plain steps do not buffer output.
+    captured = m.uart_output()
+    m.step(20_000)
+    assert m.uart_output() == captured
+
 def test_performance_benchmark(machine):
     """
     Test raw stepping performance. In release builds, this should be fast.
@@ -91,3 +242,27 @@ def test_performance_benchmark(machine):
     
     # Very loose assertion just to ensure it runs, real performance depends on host
     assert elapsed < 10.0, "Should handle 100k steps reasonably fast"
+
+def test_custom_peripheral_tick_interval():
+    if not os.path.exists(FIRMWARE_PATH):
+        pytest.skip(f"Firmware not found at {FIRMWARE_PATH}")
+
+    def systick_after(config, steps):
+        m = labwired.Machine(FIRMWARE_PATH, config=config)
+        # SysTick: reload 0xFFFFFF, clear the counter, enable on the core clock.
+        m.write_memory(0xE000E014, [0xFF, 0xFF, 0xFF, 0x00])
+        m.write_memory(0xE000E018, [0, 0, 0, 0])
+        m.write_memory(0xE000E010, [0x05, 0, 0, 0])
+        m.step(steps)
+        return m.read_memory(0xE000E018, 4)
+
+    # With ticks every instruction SysTick has reloaded and started counting;
+    # with a 1000-instruction interval no tick has happened yet.
+    assert list(systick_after(None, 10)) != [0, 0, 0, 0]
+    assert list(systick_after({"peripheral_tick_interval": 1000}, 10)) == [0, 0, 0, 0]
+
+def test_unknown_config_key_is_rejected():
+    if not os.path.exists(FIRMWARE_PATH):
+        pytest.skip(f"Firmware not found at {FIRMWARE_PATH}")
+    with pytest.raises(ValueError):
+        labwired.Machine(FIRMWARE_PATH, config={"no_such_knob": 1})
@@ -31,6 +31,39 @@ pub enum IngestError {
 /// Result type for SVD ingestor operations.
 pub type Result<T> = std::result::Result<T, IngestError>;
 
+/// Naming options for flattening SVD clusters into register ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestOptions {
+    /// Separator between a cluster name and the names nested inside it.
+    /// Defaults to `_` (`CH0_CR`).
+    pub name_separator: String,
+    /// Keep the SVD cluster hierarchy as a structured path: clusters are joined
+    /// with `.` and cluster-array instances keep their `[n]` index
+    /// (`CH[0].CR`). Overrides `name_separator`.
+    pub keep_cluster_hierarchy: bool,
+}
+
+impl Default for IngestOptions {
+    fn default() -> Self {
+        Self {
+            name_separator: "_".to_string(),
+            keep_cluster_hierarchy: false,
+        }
+    }
+}
+
+impl IngestOptions {
+    fn join(&self, prefix: &str, name: &str) -> String {
+        if prefix.is_empty() {
+            name.to_string()
+        } else if self.keep_cluster_hierarchy {
+            format!("{}.{}", prefix, name)
+        } else {
+            format!("{}{}{}", prefix, self.name_separator, name)
+        }
+    }
+}
+
 /// Processes a single peripheral from the SVD device and converts it into a `PeripheralDescriptor`.
 ///
 /// This function handles:
@@ -45,6 +78,15 @@ pub type Result<T> = std::result::Result<T, IngestError>;
 pub fn process_peripheral(
     device: &Device,
     peripheral: &Peripheral,
+) -> Result<PeripheralDescriptor> {
+    process_peripheral_with_options(device, peripheral, &IngestOptions::default())
+}
+
+/// Like [`process_peripheral`], with explicit cluster naming [`IngestOptions`].
+pub fn process_peripheral_with_options(
+    device: &Device,
+    peripheral: &Peripheral,
+    options: &IngestOptions,
 ) -> Result<PeripheralDescriptor> {
     let mut registers = Vec::new();
     let mut interrupts = HashMap::new();
@@ -91,7 +133,7 @@ pub fn process_peripheral(
     for p in peripherals_to_process.iter().rev() {
         if let Some(children) = &p.registers {
             for cluster in children {
-                process_register_cluster(cluster, &mut registers, 0, "", &reg_map)?;
+                process_register_cluster(cluster, &mut registers, 0, "", &reg_map, options)?;
             }
         }
     }
@@ -105,11 +147,23 @@ pub fn process_peripheral(
 
     registers.sort_by_key(|r| r.address_offset);
 
+    let mut interrupt_descriptors: HashMap<String, labwired_config::InterruptDescriptor> =
+        HashMap::new();
     for p in peripherals_to_process.iter().rev() {
         for interrupt in &p.interrupt {
             interrupts.insert(interrupt.name.clone(), interrupt.value);
+            interrupt_descriptors.insert(
+                interrupt.name.clone(),
+                labwired_config::InterruptDescriptor {
+                    name: interrupt.name.clone(),
+                    value: interrupt.value,
+                    description: interrupt.description.clone(),
+                },
+            );
         }
     }
+    let mut interrupt_descriptors: Vec<_> = interrupt_descriptors.into_values().collect();
+    interrupt_descriptors.sort_by(|a, b| a.value.cmp(&b.value).then_with(|| a.name.cmp(&b.name)));
 
     Ok(PeripheralDescriptor {
         peripheral: p_info.name.clone(),
@@ -121,9 +175,59 @@ pub fn process_peripheral(
             Some(interrupts)
         },
         timing: None,
+        interrupt_descriptors,
     })
 }
 
+/// One concrete peripheral instance produced by [`expand_peripheral`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeripheralInstance {
+    /// Absolute base address of this instance.
+    pub base_address: u64,
+    /// Register layout of this instance, named after the instance.
+    pub descriptor: PeripheralDescriptor,
+}
+
+/// Expands an SVD peripheral into one [`PeripheralInstance`] per concrete instance.
+///
+/// A `Single` peripheral yields exactly one instance at its own base address. An
+/// `Array` peripheral (e.g. `GPIO%s` with `dimIndex` `A,B,C`) yields `dim` instances,
+/// each at `base_address + i * dimIncrement` with `%s` substituted in its name,
+/// mirroring how register arrays are unrolled.
+pub fn expand_peripheral(
+    device: &Device,
+    peripheral: &Peripheral,
+) -> Result<Vec<PeripheralInstance>> {
+    expand_peripheral_with_options(device, peripheral, &IngestOptions::default())
+}
+
+/// Like [`expand_peripheral`], with explicit cluster naming [`IngestOptions`].
+pub fn expand_peripheral_with_options(
+    device: &Device,
+    peripheral: &Peripheral,
+    options: &IngestOptions,
+) -> Result<Vec<PeripheralInstance>> {
+    let descriptor = process_peripheral_with_options(device, peripheral, options)?;
+    match peripheral {
+        Peripheral::Single(info) => Ok(vec![PeripheralInstance {
+            base_address: info.base_address,
+            descriptor,
+        }]),
+        Peripheral::Array(info, dim) => Ok(dim_indexes(dim)?
+            .iter()
+            .enumerate()
+            .map(|(i, index)| {
+                let mut descriptor = descriptor.clone();
+                descriptor.peripheral = replace_dim_name(&info.name, index);
+                PeripheralInstance {
+                    base_address: info.base_address + i as u64 * dim.dim_increment as u64,
+                    descriptor,
+                }
+            })
+            .collect()),
+    }
+}
+
 fn collect_all_registers<'a>(
     cluster: &'a RegisterCluster,
     map: &mut HashMap<String, &'a svd_parser::svd::RegisterInfo>,
@@ -158,13 +262,21 @@ fn process_register_cluster(
     parent_offset: u64,
     name_prefix: &str,
     reg_map: &HashMap<String, &svd_parser::svd::RegisterInfo>,
+    options: &IngestOptions,
 ) -> Result<()> {
     match cluster {
         RegisterCluster::Register(reg) => {
-            expand_register(reg, registers, parent_offset, name_prefix, reg_map)?;
+            expand_register(reg, registers, parent_offset, name_prefix, reg_map, options)?;
         }
         RegisterCluster::Cluster(cluster) => {
-            expand_cluster(cluster, registers, parent_offset, name_prefix, reg_map)?;
+            expand_cluster(
+                cluster,
+                registers,
+                parent_offset,
+                name_prefix,
+                reg_map,
+                options,
+            )?;
         }
     }
     Ok(())
@@ -176,22 +288,18 @@ fn expand_register(
     parent_offset: u64,
     name_prefix: &str,
     reg_map: &HashMap<String, &svd_parser::svd::RegisterInfo>,
+    options: &IngestOptions,
 ) -> Result<()> {
     match reg_array {
         Register::Single(reg) => {
             if let Some(mut desc) = convert_register(reg, parent_offset, reg_map)? {
-                if !name_prefix.is_empty() {
-                    desc.id = format!("{}_{}", name_prefix, desc.id);
-                }
+                desc.id = options.join(name_prefix, &desc.id);
                 registers.push(desc);
             }
         }
         Register::Array(reg, dim) => {
-            for i in 0..dim.dim {
-                let mut name = replace_dim_name(&reg.name, i, dim);
-                if !name_prefix.is_empty() {
-                    name = format!("{}_{}", name_prefix, name);
-                }
+            for (i, index) in dim_indexes(dim)?.iter().enumerate() {
+                let name = options.join(name_prefix, &replace_dim_name(&reg.name, index));
                 let offset = parent_offset
                     + (reg.address_offset as u64)
                     + (i as u64 * dim.dim_increment as u64);
@@ -213,32 +321,37 @@ fn expand_cluster(
     parent_offset: u64,
     name_prefix: &str,
     reg_map: &HashMap<String, &svd_parser::svd::RegisterInfo>,
+    options: &IngestOptions,
 ) -> Result<()> {
     match cluster_array {
         svd_parser::svd::Cluster::Single(cluster) => {
             let current_offset = parent_offset + cluster.address_offset as u64;
-            let cluster_name = if !name_prefix.is_empty() {
-                format!("{}_{}", name_prefix, cluster.name)
-            } else {
-                cluster.name.clone()
-            };
+            let cluster_name = options.join(name_prefix, &cluster.name);
 
             for child in &cluster.children {
-                process_register_cluster(child, registers, current_offset, &cluster_name, reg_map)?;
+                process_register_cluster(
+                    child,
+                    registers,
+                    current_offset,
+                    &cluster_name,
+                    reg_map,
+                    options,
+                )?;
             }
         }
         svd_parser::svd::Cluster::Array(cluster, dim) => {
-            for i in 0..dim.dim {
+            for (i, index) in dim_indexes(dim)?.iter().enumerate() {
                 let current_offset = parent_offset
                     + (cluster.address_offset as u64)
                     + (i as u64 * dim.dim_increment as u64);
 
-                let instance_name = replace_dim_name(&cluster.name, i, dim);
-                let cluster_name = if !name_prefix.is_empty() {
-                    format!("{}_{}", name_prefix, instance_name)
+                let instance_name = if options.keep_cluster_hierarchy {
+                    let base = cluster.name.replace("[%s]", "").replace("%s", "");
+                    format!("{}[{}]", base, index)
                 } else {
-                    instance_name
+                    replace_dim_name(&cluster.name, index)
                 };
+                let cluster_name = options.join(name_prefix, &instance_name);
 
                 for child in &cluster.children {
                     process_register_cluster(
@@ -247,6 +360,7 @@ fn expand_cluster(
                         current_offset,
                         &cluster_name,
                         reg_map,
+                        options,
                     )?;
                 }
             }
@@ -255,23 +369,65 @@ fn expand_cluster(
     Ok(())
 }
 
-fn replace_dim_name(name: &str, index: u32, dim: &svd_parser::svd::DimElement) -> String {
-    let index_str = if let Some(dim_index) = &dim.dim_index {
-        // Handle dimIndex array/list (n/a for simple MVP, usually just use 'i')
-        // svd-parser returns valid Vec<String> for indexes().
-        // Let's rely on simple string formatting if index list is complex.
-        // Actually svd-rs has helper for this.
-        // But implementing simple "%s" replacement is safer for now.
-        if index < dim_index.len() as u32 {
-            dim_index[index as usize].clone()
-        } else {
-            index.to_string()
-        }
-    } else {
-        index.to_string()
+/// Resolves the per-element index strings of a `dim` array, following CMSIS
+/// `dimIndex` semantics (and svd-rs's `indexes()`): an absent `dimIndex` means
+/// `0..dim`, otherwise each entry may be a single index, a comma list (`A,B,C`)
+/// or an inclusive numeric or single-letter range (`0-3`, `A-D`).
+fn dim_indexes(dim: &svd_parser::svd::DimElement) -> Result<Vec<String>> {
+    let Some(dim_index) = &dim.dim_index else {
+        return Ok((0..dim.dim).map(|i| i.to_string()).collect());
     };
 
-    name.replace("[%s]", &index_str).replace("%s", &index_str)
+    let mut indexes = Vec::new();
+    for entry in dim_index.iter().flat_map(|e| e.split(',')) {
+        let entry = entry.trim();
+        match entry.split_once('-') {
+            Some((start, end)) => indexes.extend(parse_dim_range(start.trim(), end.trim())?),
+            None => indexes.push(entry.to_string()),
+        }
+    }
+
+    if indexes.len() != dim.dim as usize {
+        return Err(IngestError::InvalidSvd(format!(
+            "dimIndex {:?} yields {} indexes but dim is {}",
+            dim_index,
+            indexes.len(),
+            dim.dim
+        )));
+    }
+    Ok(indexes)
+}
+
+fn parse_dim_range(start: &str, end: &str) -> Result<Vec<String>> {
+    if let (Ok(start), Ok(end)) = (start.parse::<u32>(), end.parse::<u32>()) {
+        if start <= end {
+            return Ok((start..=end).map(|i| i.to_string()).collect());
+        }
+    }
+    let mut letters = (start.chars(), end.chars());
+    if let ((Some(a), None), (Some(b), None)) = (
+        (letters.0.next(), letters.0.next()),
+        (letters.1.next(), letters.1.next()),
+    ) {
+        if a.is_ascii_uppercase() && b.is_ascii_uppercase() && a <= b {
+            return Ok((a..=b).map(|c| c.to_string()).collect());
+        }
+    }
+    Err(IngestError::InvalidSvd(format!(
+        "Invalid dimIndex range {}-{}",
+        start, end
+    )))
+}
+
+/// Substitutes one array index into an SVD name. `[%s]` and `%s` are replaced in
+/// place; a name with no placeholder gets the index appended so array elements
+/// stay distinct.
+fn replace_dim_name(name: &str, index: &str) -> String {
+    if name.contains("%s") {
+        name.replace("[%s]", index).replace("%s", index)
+    } else {
+        format!("{}{}", name, index)
+    }
 }
 
 fn convert_register(
@@ -299,13 +455,11 @@ fn convert_register(
         .or(current_reg.properties.size)
         .unwrap_or(32) as u8;
 
-    let access = match reg.properties.access.or(current_reg.properties.access) {
-        Some(SvdAccess::ReadWrite) => Access::ReadWrite,
-        Some(SvdAccess::ReadOnly) => Access::ReadOnly,
-        Some(SvdAccess::WriteOnly) => Access::WriteOnly,
-        None => Access::ReadWrite,
-        _ => Access::ReadWrite,
-    };
+    let access = reg
+        .properties
+        .access
+        .or(current_reg.properties.access)
+        .map_or(Access::ReadWrite, convert_access);
 
     let reset_value = reg
         .properties
@@ -323,9 +477,9 @@ fn convert_register(
                     field_map.insert(f.name.clone(), f.clone());
                 }
                 Field::Array(f, dim) => {
-                    for i in 0..dim.dim {
+                    for (i, index) in dim_indexes(dim)?.iter().enumerate() {
                         let mut f_clone = f.clone();
-                        f_clone.name = replace_dim_name(&f.name, i, dim);
+                        f_clone.name = replace_dim_name(&f.name, index);
                         let shift = i * dim.dim_increment;
                         f_clone.bit_range.offset = f.bit_range.offset + shift;
                         field_map.insert(f_clone.name.clone(), f_clone);
@@ -342,9 +496,9 @@ fn convert_register(
                     field_map.insert(f.name.clone(), f.clone());
                 }
                 Field::Array(f, dim) => {
-                    for i in 0..dim.dim {
+                    for (i, index) in dim_indexes(dim)?.iter().enumerate() {
                         let mut f_clone = f.clone();
-                        f_clone.name = replace_dim_name(&f.name, i, dim);
+                        f_clone.name = replace_dim_name(&f.name, index);
                         let shift = i * dim.dim_increment;
                         f_clone.bit_range.offset = f.bit_range.offset + shift;
                         field_map.insert(f_clone.name.clone(), f_clone);
@@ -368,23 +522,30 @@ fn convert_register(
     };
 
     let reg_read_action = reg.read_action.or(current_reg.read_action);
-    if let Some(svd_parser::svd::ReadAction::Clear) = reg_read_action {
-        side_effects.read_action = Some(labwired_config::ReadAction::Clear);
-    }
+    side_effects.read_action = reg_read_action.map(|action| match action {
+        svd_parser::svd::ReadAction::Clear => labwired_config::ReadAction::Clear,
+        svd_parser::svd::ReadAction::Set => labwired_config::ReadAction::Set,
+        svd_parser::svd::ReadAction::Modify => labwired_config::ReadAction::Modify,
+        svd_parser::svd::ReadAction::ModifyExternal => labwired_config::ReadAction::ModifyExternal,
+    });
 
     let reg_modified_write = reg
         .modified_write_values
         .or(current_reg.modified_write_values);
     if let Some(svd_write) = reg_modified_write {
-        match svd_write {
-            svd_parser::svd::ModifiedWriteValues::OneToClear => {
-                side_effects.write_action = Some(labwired_config::WriteAction::WriteOneToClear);
-            }
-            svd_parser::svd::ModifiedWriteValues::ZeroToClear => {
-                side_effects.write_action = Some(labwired_config::WriteAction::WriteZeroToClear);
-            }
-            _ => {}
-        }
+        use labwired_config::WriteAction;
+        use svd_parser::svd::ModifiedWriteValues as Mwv;
+        side_effects.write_action = match svd_write {
+            Mwv::OneToClear => Some(WriteAction::WriteOneToClear),
+            Mwv::ZeroToClear => Some(WriteAction::WriteZeroToClear),
+            Mwv::OneToSet => Some(WriteAction::WriteOneToSet),
+            Mwv::ZeroToSet => Some(WriteAction::WriteZeroToSet),
+            Mwv::OneToToggle => Some(WriteAction::WriteOneToToggle),
+            Mwv::ZeroToToggle => Some(WriteAction::WriteZeroToToggle),
+            Mwv::Clear => Some(WriteAction::Clear),
+            Mwv::Set => Some(WriteAction::Set),
+            Mwv::Modify => None,
+        };
     }
 
     let side_effects = if side_effects.read_action.is_some() || side_effects.write_action.is_some()
@@ -405,6 +566,14 @@ fn convert_register(
     }))
 }
 
+fn convert_access(access: SvdAccess) -> Access {
+    match access {
+        SvdAccess::ReadOnly => Access::ReadOnly,
+        SvdAccess::WriteOnly => Access::WriteOnly,
+        _ => Access::ReadWrite,
+    }
+}
+
 fn convert_field(field: &svd_parser::svd::FieldInfo) -> Result<FieldDescriptor> {
     let msb = field.bit_range.offset + field.bit_range.width - 1;
     let lsb = field.bit_range.offset;
@@ -413,6 +582,7 @@ fn convert_field(field: &svd_parser::svd::FieldInfo) -> Result<FieldDescriptor>
         name: field.name.clone(),
         bit_range: [msb as u8, lsb as u8],
         description: field.description.clone(),
+        access: field.access.map(convert_access),
     })
 }
 
@@ -430,9 +600,64 @@ pub fn save_descriptor(descriptor: &PeripheralDescriptor, output_dir: &Path) ->
     Ok(())
 }
 
+/// Merges a freshly ingested descriptor into a previously saved one.
+///
+/// Registers, fields, reset values and interrupts come from `fresh` (the SVD).
+/// Hand-written behaviour in `existing` is kept: the peripheral-level `timing`
+/// hooks, and each register's `on_read`/`on_write` side-effect hooks, matched by
+/// register id. Hooks on registers that no longer exist in the SVD are dropped.
+pub fn merge_descriptor(
+    fresh: PeripheralDescriptor,
+    existing: &PeripheralDescriptor,
+) -> PeripheralDescriptor {
+    let hooks: HashMap<&str, &labwired_config::SideEffectsDescriptor> = existing
+        .registers
+        .iter()
+        .filter_map(|r| r.side_effects.as_ref().map(|se| (r.id.as_str(), se)))
+        .filter(|(_, se)| se.on_read.is_some() || se.on_write.is_some())
+        .collect();
+
+    let mut merged = fresh;
+    for reg in &mut merged.registers {
+        let Some(old) = hooks.get(reg.id.as_str()) else {
+            continue;
+        };
+        let se = reg
+            .side_effects
+            .get_or_insert_with(|| labwired_config::SideEffectsDescriptor {
+                read_action: None,
+                write_action: None,
+                on_read: None,
+                on_write: None,
+            });
+        se.on_read = old.on_read.clone();
+        se.on_write = old.on_write.clone();
+    }
+    if existing.timing.is_some() {
+        merged.timing = existing.timing.clone();
+    }
+    merged
+}
+
+/// Like [`save_descriptor`], but when `{peripheral_name}.yaml` already exists it is
+/// loaded and combined with `descriptor` via [`merge_descriptor`], so re-running the
+/// ingestor does not discard hand-added hooks.
+pub fn save_descriptor_merged(descriptor: &PeripheralDescriptor, output_dir: &Path) -> Result<()> {
+    let filename = output_dir.join(format!("{}.yaml", descriptor.peripheral.to_lowercase()));
+    if !filename.exists() {
+        return save_descriptor(descriptor, output_dir);
+    }
+    let existing: PeripheralDescriptor =
+        serde_yaml::from_str(&std::fs::read_to_string(&filename)?)?;
+    let merged = merge_descriptor(descriptor.clone(), &existing);
+    std::fs::write(&filename, serde_yaml::to_string(&merged)?)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use labwired_config::WriteAction;
     use svd_parser::svd::BitRangeType;
     use svd_parser::svd::{BitRange, FieldInfo, PeripheralInfo, RegisterCluster};
 
@@ -462,6 +687,42 @@ mod tests {
         }
     }
 
+    fn write_action_for(value: svd_parser::svd::ModifiedWriteValues) -> Option<WriteAction> {
+        let Register::Single(mut reg) = make_register("SR", 0x00) else {
+            unreachable!()
+        };
+        reg.modified_write_values = Some(value);
+        let desc = convert_register(&reg, 0, &HashMap::new()).unwrap().unwrap();
+        desc.side_effects.and_then(|se| se.write_action)
+    }
+
+    #[test]
+    fn test_toggle_and_one_to_set_write_values() {
+        use svd_parser::svd::ModifiedWriteValues;
+        assert_eq!(
+            write_action_for(ModifiedWriteValues::OneToToggle),
+            Some(WriteAction::WriteOneToToggle)
+        );
+        assert_eq!(
+            write_action_for(ModifiedWriteValues::OneToSet),
+            Some(WriteAction::WriteOneToSet)
+        );
+        assert_eq!(write_action_for(ModifiedWriteValues::Modify), None);
+    }
+
+    #[test]
+    fn test_read_action_set_is_kept() {
+        let Register::Single(mut reg) = make_register("DR", 0x00) else {
+            unreachable!()
+        };
+        reg.read_action = Some(svd_parser::svd::ReadAction::Set);
+        let desc = convert_register(&reg, 0, &HashMap::new()).unwrap().unwrap();
+        assert_eq!(
+            desc.side_effects.and_then(|se| se.read_action),
+            Some(labwired_config::ReadAction::Set)
+        );
+    }
+
     #[test]
     fn test_field_conversion() {
         let mut field = FieldInfo::builder().name("TEST_FIELD".to_string());
@@ -477,6 +738,25 @@ mod tests {
 
         assert_eq!(desc.name, "TEST_FIELD");
         assert_eq!(desc.bit_range, [7, 4]); // [msb, lsb]
+        assert_eq!(desc.access, None);
+    }
+
+    #[test]
+    fn test_field_access_is_kept() {
+        let field = FieldInfo::builder()
+            .name("BUSY".to_string())
+            .bit_range(BitRange {
+                offset: 7,
+                width: 1,
+                range_type: BitRangeType::BitRange,
+            })
+            .access(Some(SvdAccess::ReadOnly))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        assert_eq!(
+            convert_field(&field).unwrap().access,
+            Some(Access::ReadOnly)
+        );
     }
 
     #[test]
@@ -582,6 +862,265 @@ mod tests {
         assert_eq!(desc.registers[3].id, "P3");
         assert_eq!(desc.registers[3].address_offset, 0x0C);
     }
+    #[test]
+    fn test_peripheral_array_expands_into_instances() {
+        let info = PeripheralInfo::builder()
+            .name("GPIO%s".to_string())
+            .base_address(0x4001_0800)
+            .registers(Some(vec![RegisterCluster::Register(make_register(
+                "IDR", 0x08,
+            ))]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let dim = svd_parser::svd::DimElement::builder()
+            .dim(3)
+            .dim_increment(0x400)
+            .dim_index(Some(vec!["A".into(), "B".into(), "C".into()]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let peripheral_enum = Peripheral::Array(info, dim);
+        let device = Device::builder()
+            .name("STM32".to_string())
+            .peripherals(vec![])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+
+        let instances = expand_peripheral(&device, &peripheral_enum).unwrap();
+
+        assert_eq!(instances.len(), 3);
+        assert_eq!(instances[0].descriptor.peripheral, "GPIOA");
+        assert_eq!(instances[0].base_address, 0x4001_0800);
+        assert_eq!(instances[1].descriptor.peripheral, "GPIOB");
+        assert_eq!(instances[1].base_address, 0x4001_0C00);
+        assert_eq!(instances[2].descriptor.peripheral, "GPIOC");
+        assert_eq!(instances[2].base_address, 0x4001_1000);
+        assert!(instances
+            .iter()
+            .all(|i| i.descriptor.registers.len() == 1 && i.descriptor.registers[0].id == "IDR"));
+    }
+
+    fn dim_array_register_names(dim_index: &str, dim: u32) -> Vec<String> {
+        let reg_info = svd_parser::svd::RegisterInfo::builder()
+            .name("CCR%s".to_string())
+            .address_offset(0x00)
+            .size(Some(32))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let dim = svd_parser::svd::DimElement::builder()
+            .dim(dim)
+            .dim_increment(4)
+            .dim_index(Some(vec![dim_index.to_string()]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let mut registers = Vec::new();
+        expand_register(
+            &Register::Array(reg_info, dim),
+            &mut registers,
+            0,
+            "",
+            &HashMap::new(),
+            &IngestOptions::default(),
+        )
+        .unwrap();
+        registers.into_iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_dim_index_comma_list() {
+        assert_eq!(
+            dim_array_register_names("A,B,C", 3),
+            ["CCRA", "CCRB", "CCRC"]
+        );
+    }
+
+    #[test]
+    fn test_dim_index_range() {
+        assert_eq!(
+            dim_array_register_names("1-4", 4),
+            ["CCR1", "CCR2", "CCR3", "CCR4"]
+        );
+        assert_eq!(dim_array_register_names("X-Z", 3), ["CCRX", "CCRY", "CCRZ"]);
+    }
+
+    #[test]
+    fn test_dim_index_length_mismatch_is_rejected() {
+        let dim = svd_parser::svd::DimElement::builder()
+            .dim(4)
+            .dim_increment(4)
+            .dim_index(Some(vec!["0-2".to_string()]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        assert!(dim_indexes(&dim).is_err());
+    }
+
+    #[test]
+    fn test_dim_name_without_placeholder_gets_index_appended() {
+        assert_eq!(replace_dim_name("DR", "2"), "DR2");
+        assert_eq!(replace_dim_name("BUF[%s]", "2"), "BUF2");
+    }
+
+    #[test]
+    fn test_merge_keeps_hand_added_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut reg = make_register("SR", 0x00);
+        if let Register::Single(ref mut info) = reg {
+            info.properties.reset_value = Some(0xC0);
+        }
+        let peripheral = PeripheralInfo::builder()
+            .name("UART".to_string())
+            .base_address(0x4000_0000)
+            .registers(Some(vec![RegisterCluster::Register(reg)]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let device = Device::builder()
+            .name("STM32".to_string())
+            .peripherals(vec![])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let fresh = process_peripheral(&device, &Peripheral::Single(peripheral)).unwrap();
+
+        let mut existing = fresh.clone();
+        existing.registers[0].reset_value = 0;
+        existing.registers[0].side_effects = Some(labwired_config::SideEffectsDescriptor {
+            read_action: None,
+            write_action: None,
+            on_read: Some("clear_rxne".to_string()),
+            on_write: None,
+        });
+        existing.timing = Some(vec![labwired_config::TimingDescriptor {
+            id: "tx_done".to_string(),
+            trigger: labwired_config::TimingTrigger::Periodic { period_cycles: 100 },
+            delay_cycles: 0,
+            action: labwired_config::TimingAction::SetBits {
+                register: "SR".to_string(),
+                bits: 0x40,
+            },
+            interrupt: None,
+        }]);
+        save_descriptor(&existing, dir.path()).unwrap();
+
+        save_descriptor_merged(&fresh, dir.path()).unwrap();
+
+        let merged: PeripheralDescriptor =
+            serde_yaml::from_str(&std::fs::read_to_string(dir.path().join("uart.yaml")).unwrap())
+                .unwrap();
+        assert_eq!(merged.registers[0].reset_value, 0xC0);
+        assert_eq!(merged.timing, existing.timing);
+        assert_eq!(
+            merged.registers[0]
+                .side_effects
+                .as_ref()
+                .unwrap()
+                .on_read
+                .as_deref(),
+            Some("clear_rxne")
+        );
+    }
+
+    fn nested_cluster_register_names(options: &IngestOptions) -> Vec<String> {
+        let inner = svd_parser::svd::ClusterInfo::builder()
+            .name("CFG".to_string())
+            .address_offset(0x08)
+            .children(vec![RegisterCluster::Register(make_register("MODE", 0x00))])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let outer = svd_parser::svd::ClusterInfo::builder()
+            .name("CH%s".to_string())
+            .address_offset(0x100)
+            .children(vec![
+                RegisterCluster::Register(make_register("CR", 0x00)),
+                RegisterCluster::Cluster(svd_parser::svd::Cluster::Single(inner)),
+            ])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let dim = svd_parser::svd::DimElement::builder()
+            .dim(2)
+            .dim_increment(0x20)
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let peripheral = PeripheralInfo::builder()
+            .name("DMA".to_string())
+            .base_address(0x4002_0000)
+            .registers(Some(vec![RegisterCluster::Cluster(
+                svd_parser::svd::Cluster::Array(outer, dim),
+            )]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let device = Device::builder()
+            .name("STM32".to_string())
+            .peripherals(vec![])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+
+        let desc =
+            process_peripheral_with_options(&device, &Peripheral::Single(peripheral), options)
+                .unwrap();
+        desc.registers.into_iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_nested_cluster_naming_options() {
+        assert_eq!(
+            nested_cluster_register_names(&IngestOptions::default()),
+            ["CH0_CR", "CH0_CFG_MODE", "CH1_CR", "CH1_CFG_MODE"]
+        );
+
+        let custom = IngestOptions {
+            name_separator: "__".to_string(),
+            ..IngestOptions::default()
+        };
+        assert_eq!(
+            nested_cluster_register_names(&custom),
+            ["CH0__CR", "CH0__CFG__MODE", "CH1__CR", "CH1__CFG__MODE"]
+        );
+
+        let hierarchy = IngestOptions {
+            keep_cluster_hierarchy: true,
+            ..IngestOptions::default()
+        };
+        assert_eq!(
+            nested_cluster_register_names(&hierarchy),
+            ["CH[0].CR", "CH[0].CFG.MODE", "CH[1].CR", "CH[1].CFG.MODE"]
+        );
+    }
+
+    #[test]
+    fn test_interrupt_description_survives_ingestion() {
+        let interrupt = svd_parser::svd::Interrupt::builder()
+            .name("USART1".to_string())
+            .description(Some("USART1 global interrupt".to_string()))
+            .value(37)
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let peripheral = PeripheralInfo::builder()
+            .name("USART1".to_string())
+            .base_address(0x4001_3800)
+            .interrupt(Some(vec![interrupt]))
+            .registers(Some(vec![RegisterCluster::Register(make_register(
+                "SR", 0x00,
+            ))]))
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+        let device = Device::builder()
+            .name("STM32".to_string())
+            .peripherals(vec![])
+            .build(svd_parser::ValidateLevel::Disabled)
+            .unwrap();
+
+        let desc = process_peripheral(&device, &Peripheral::Single(peripheral)).unwrap();
+
+        assert_eq!(
+            desc.interrupt_descriptors,
+            vec![labwired_config::InterruptDescriptor {
+                name: "USART1".to_string(),
+                value: 37,
+                description: Some("USART1 global interrupt".to_string()),
+            }]
+        );
+        assert_eq!(desc.interrupts.as_ref().unwrap()["USART1"], 37);
+        assert_eq!(desc.interrupt_list(), desc.interrupt_descriptors);
+    }
+
     #[test]
     fn test_round_trip() {
         let mut p = PeripheralInfo::builder()
@@ -618,18 +1157,6 @@ mod tests {
         // Deserialize back
         let deserialized: PeripheralDescriptor = serde_yaml::from_str(&yaml).unwrap();
 
-        // Verify equality
-        // PeripheralDescriptor derives PartialEq (checked in config crate? assuming yes or I check fields)
-        // labwired_config doesn't explicitly derive PartialEq in my memory (viewed file was lib.rs but didn't check derive).
-        // Let's assert on fields to be safe or check if I can derive it.
-        // Actually, checking standard derive.
-
-        assert_eq!(desc.peripheral, deserialized.peripheral);
-        assert_eq!(desc.registers.len(), deserialized.registers.len());
-        assert_eq!(desc.registers[0].id, deserialized.registers[0].id);
-        assert_eq!(
-            desc.registers[0].fields[0].name,
-            deserialized.registers[0].fields[0].name
-        );
+        assert_eq!(desc, deserialized);
     }
 }
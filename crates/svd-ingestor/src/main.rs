@@ -2,7 +2,9 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use std::fs;
 use std::path::PathBuf;
-use svd_ingestor::{process_peripheral, save_descriptor};
+use svd_ingestor::{
+    expand_peripheral_with_options, save_descriptor, save_descriptor_merged, IngestOptions,
+};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -22,6 +24,18 @@ struct Args {
     /// Filter specific peripherals (comma separated)
     #[arg(long)]
     filter: Option<String>,
+
+    /// Merge into existing YAML files, keeping hand-added timing and on_read/on_write hooks
+    #[arg(long)]
+    merge: bool,
+
+    /// Separator between cluster and register names in flattened register ids
+    #[arg(long, default_value = "_")]
+    name_separator: String,
+
+    /// Keep the cluster hierarchy as a dotted path with array indexes (e.g. `CH[0].CR`)
+    #[arg(long)]
+    keep_cluster_hierarchy: bool,
 }
 
 fn main() -> Result<()> {
@@ -36,6 +50,11 @@ fn main() -> Result<()> {
         .filter
         .map(|s| s.split(',').map(|s| s.trim().to_string()).collect());
 
+    let options = IngestOptions {
+        name_separator: args.name_separator.clone(),
+        keep_cluster_hierarchy: args.keep_cluster_hierarchy,
+    };
+
     for peripheral in &device.peripherals {
         if let Some(ref filters) = filter_list {
             if !filters.contains(&peripheral.name) {
@@ -44,9 +63,15 @@ fn main() -> Result<()> {
         }
 
         println!("Processing peripheral: {}", peripheral.name);
-        match process_peripheral(&device, peripheral) {
-            Ok(descriptor) => {
-                save_descriptor(&descriptor, &args.output_dir)?;
+        match expand_peripheral_with_options(&device, peripheral, &options) {
+            Ok(instances) => {
+                for instance in &instances {
+                    if args.merge {
+                        save_descriptor_merged(&instance.descriptor, &args.output_dir)?;
+                    } else {
+                        save_descriptor(&instance.descriptor, &args.output_dir)?;
+                    }
+                }
             }
             Err(e) => {
                 eprintln!("Failed to process peripheral {}: {}", peripheral.name, e);
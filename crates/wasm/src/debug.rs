@@ -0,0 +1,79 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+// SPDX-License-Identifier: MIT
+
+//! WasmSimulator run control for browser debuggers: PC breakpoints and a
+//! bounded `run` that reports why it stopped, delegating to `DebugControl`
+//! on the held `Machine` so no GDB stub is needed. A second #[wasm_bindgen]
+//! impl block, split out of lib.rs.
+
+use crate::*;
+use labwired_core::{DebugControl, StopReason};
+
+/// Public shape returned by `run`; mirrors the Python binding's StopReason.
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub(crate) struct WasmStopReason {
+    kind: &'static str,
+    pc: Option<u32>,
+}
+
+impl From<StopReason> for WasmStopReason {
+    fn from(reason: StopReason) -> Self {
+        match reason {
+            StopReason::Breakpoint(pc) => Self {
+                kind: "breakpoint",
+                pc: Some(pc),
+            },
+            StopReason::StepDone => Self {
+                kind: "step_done",
+                pc: None,
+            },
+            StopReason::MaxStepsReached => Self {
+                kind: "max_steps_reached",
+                pc: None,
+            },
+            StopReason::ManualStop => Self {
+                kind: "manual_stop",
+                pc: None,
+            },
+        }
+    }
+}
+
+impl WasmSimulator {
+    pub(crate) fn run_until_stop(&mut self, max_steps: u32) -> Result<WasmStopReason, String> {
+        self.record_history();
+        DebugControl::run(self.machine(), Some(max_steps))
+            .map(WasmStopReason::from)
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[wasm_bindgen]
+impl WasmSimulator {
+    #[wasm_bindgen]
+    pub fn add_breakpoint(&mut self, addr: u32) {
+        DebugControl::add_breakpoint(self.machine(), addr);
+    }
+
+    #[wasm_bindgen]
+    pub fn remove_breakpoint(&mut self, addr: u32) {
+        DebugControl::remove_breakpoint(self.machine(), addr);
+    }
+
+    #[wasm_bindgen]
+    pub fn clear_breakpoints(&mut self) {
+        DebugControl::clear_breakpoints(self.machine());
+    }
+
+    /// Run up to `max_steps` instructions, stopping early at a breakpoint.
+    /// Returns `{ kind, pc }` where `kind` is `"breakpoint"` (with `pc`),
+    /// `"max_steps_reached"`, `"step_done"` or `"manual_stop"`.
+    #[wasm_bindgen]
+    pub fn run(&mut self, max_steps: u32) -> Result<JsValue, JsValue> {
+        let reason = self
+            .run_until_stop(max_steps)
+            .map_err(|e| JsValue::from_str(&format!("Run Error: {}", e)))?;
+        serde_wasm_bindgen::to_value(&reason).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
@@ -92,7 +92,8 @@ impl WasmSimulator {
     }
 
     /// Set an input board_io binding (e.g. button press).
-    /// Writes to the GPIO IDR register bit for the specified binding.
+    /// Writes to the GPIO IDR register bit for the specified binding and
+    /// presents the level change to EXTI.
     #[wasm_bindgen]
     pub fn set_board_io_input(&mut self, id: &str, active: bool) -> Result<(), JsValue> {
         let binding = self
@@ -103,25 +104,12 @@ impl WasmSimulator {
             .ok_or_else(|| JsValue::from_str(&format!("No input board_io binding '{}'", id)))?;
 
         let machine = self.machine.as_mut().unwrap();
-        let idx = machine
-            .bus
-            .find_peripheral_index_by_name(&binding.peripheral)
-            .ok_or_else(|| {
-                JsValue::from_str(&format!("Peripheral '{}' not found", binding.peripheral))
-            })?;
-
         let pin_high = if binding.active_high { active } else { !active };
-        if !machine.bus.peripherals[idx]
-            .dev
-            .set_gpio_input(binding.pin, pin_high)
-        {
-            return Err(JsValue::from_str(&format!(
-                "Peripheral '{}' does not expose GPIO input control",
-                binding.peripheral
-            )));
-        }
-
-        Ok(())
+        // Through the bus so the edge also reaches EXTI (button interrupts).
+        machine
+            .bus
+            .set_board_input(&binding.peripheral, binding.pin, pin_high)
+            .map_err(|e| JsValue::from_str(&e))
     }
 
     /// Read back the current sensor data from each I2C sensor declared in `board_io`.
@@ -421,6 +409,8 @@ board_io:
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         }
     }
 
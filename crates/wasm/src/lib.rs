@@ -6,11 +6,14 @@ use labwired_core::bus::SystemBus;
 // #124 Phase 4: browser-side JIT prototype. Runs the dominant
 // `0x400829cc` hot block through `js_sys::WebAssembly` instead of the
 // interpreter when `jit_enabled()` has been toggled on from JS.
+mod debug;
 mod inputs;
 mod inspect;
 mod install;
 mod jit_browser;
+mod time_travel;
 mod traces;
+mod watch;
 // CortexM and XtensaLx7 are used via Box<dyn Cpu>; the concrete types are
 // only constructed inside the configure_* fns and immediately boxed.
 use labwired_core::decoder::arm::{decode_thumb_16, decode_thumb_32};
@@ -75,6 +78,11 @@ pub struct WasmSimulator {
     /// Lazy-init at first JIT-able step. Boxed so the typical "JIT off"
     /// path pays no per-instance allocation.
     jit_browser_cache: Option<Box<jit_browser::BrowserJitCache>>,
+    /// Pre-step snapshots for `step_back`; empty and unrecorded until
+    /// `set_history_depth` enables it.
+    history: time_travel::SnapshotHistory,
+    /// Bus observer behind `watch_memory`; installed on first use.
+    memory_watch: Option<Arc<watch::MemoryWatchObserver>>,
 }
 
 /// Public shape returned by `step_batch_profile`.
@@ -221,6 +229,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -241,10 +251,10 @@ impl WasmSimulator {
         firmware: &[u8],
         blobs: JsValue,
     ) -> Result<WasmSimulator, JsValue> {
-        let manifest: SystemManifest = serde_yaml::from_str(system_yaml)
-            .map_err(|e| JsValue::from_str(&format!("System YAML error: {}", e)))?;
-        let chip: ChipDescriptor = serde_yaml::from_str(chip_yaml)
-            .map_err(|e| JsValue::from_str(&format!("Chip YAML error: {}", e)))?;
+        let manifest = SystemManifest::from_yaml(system_yaml)
+            .map_err(|e| JsValue::from_str(&format!("System YAML error: {:#}", e)))?;
+        let chip = ChipDescriptor::from_yaml(chip_yaml)
+            .map_err(|e| JsValue::from_str(&format!("Chip YAML error: {:#}", e)))?;
 
         match chip.arch {
             Arch::Arm | Arch::Unknown => Self::new_from_config_arm(&chip, &manifest, firmware),
@@ -315,6 +325,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -418,8 +430,7 @@ impl WasmSimulator {
         load_program_segments_without_reset(&mut machine, &bootloader_image)
             .map_err(|e| JsValue::from_str(&format!("C3 flash fast-start load: {e}")))?;
 
-        let sp_top =
-            (chip.ram.base + labwired_config::parse_size(&chip.ram.size).unwrap_or(0)) as u32;
+        let sp_top = (chip.ram.base + chip.ram.size.bytes()) as u32;
         machine.cpu.set_sp(sp_top & !0xF);
         machine.cpu.set_pc(bootloader_image.entry_point as u32);
 
@@ -434,6 +445,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -541,8 +554,7 @@ impl WasmSimulator {
             .load_firmware(program_image)
             .map_err(|e| JsValue::from_str(&format!("Simulation Error: {}", e)))?;
 
-        let sp_top =
-            (chip.ram.base + labwired_config::parse_size(&chip.ram.size).unwrap_or(0)) as u32;
+        let sp_top = (chip.ram.base + chip.ram.size.bytes()) as u32;
         machine.cpu.set_sp(sp_top & !0xF);
         machine.cpu.set_pc(program_image.entry_point as u32);
 
@@ -557,6 +569,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -660,6 +674,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -720,6 +736,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -813,6 +831,8 @@ impl WasmSimulator {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         })
     }
 
@@ -875,6 +895,7 @@ impl WasmSimulator {
     #[wasm_bindgen]
     pub fn step(&mut self, cycles: u32) -> Result<(), JsValue> {
         for _ in 0..cycles {
+            self.record_history();
             self.machine()
                 .advance(AdvanceRequest::single())
                 .map_err(|e| JsValue::from_str(&format!("Step Error: {}", e)))?;
@@ -884,6 +905,7 @@ impl WasmSimulator {
 
     #[wasm_bindgen]
     pub fn step_single(&mut self) -> Result<(), JsValue> {
+        self.record_history();
         self.machine()
             .advance(AdvanceRequest::single())
             .map(|_| ())
@@ -998,11 +1020,11 @@ impl WasmSimulator {
                         let is_32bit = (h1 & 0xE000) == 0xE000 && (h1 & 0x1800) != 0;
                         if is_32bit {
                             match machine.bus.read_u16(pc as u64 + 2) {
-                                Ok(h2) => format!("{:?}", decode_thumb_32(h1, h2)),
+                                Ok(h2) => decode_thumb_32(h1, h2).to_asm_at(pc, None),
                                 Err(_) => "?? (Error reading h2)".to_string(),
                             }
                         } else {
-                            format!("{:?}", decode_thumb_16(h1))
+                            decode_thumb_16(h1).to_asm_at(pc, None)
                         }
                     }
                     Err(_) => "?? (Error reading h1)".to_string(),
@@ -1014,6 +1036,7 @@ impl WasmSimulator {
     /// Execute up to max_cycles steps, returning the number actually executed.
     #[wasm_bindgen]
     pub fn step_batch(&mut self, max_cycles: u32) -> Result<u32, JsValue> {
+        self.record_history();
         let machine = self.machine();
         let before = machine.total_cycles;
         match machine.advance(AdvanceRequest::run(Some(u64::from(max_cycles)))) {
@@ -1407,6 +1430,8 @@ mod machine_advance_tests {
             esp32_ipi: None,
             jit_browser_enabled: false,
             jit_browser_cache: None,
+            history: Default::default(),
+            memory_watch: None,
         }
     }
 
@@ -1540,6 +1565,88 @@ mod machine_advance_tests {
         assert_batch_matches_32_singles(xtensa_simulator, 1, false);
     }
 
+    #[test]
+    fn step_back_restores_earlier_pc() {
+        let mut sim = arm_simulator();
+        sim.step_single().expect("unrecorded step");
+        assert_eq!(sim.history_len(), 0, "recording is off by default");
+
+        sim.set_history_depth(2);
+        let earlier = sim.get_pc();
+        for _ in 0..3 {
+            sim.step_single().expect("recorded step");
+        }
+        assert_eq!(sim.history_len(), 2, "ring keeps the last `depth` states");
+        assert_ne!(sim.get_pc(), earlier);
+
+        assert_eq!(sim.step_back(1).expect("step back"), 1);
+        assert_eq!(sim.get_pc(), earlier + 4);
+        // Asking for more than is recorded undoes what is there.
+        assert_eq!(sim.step_back(5).expect("step back"), 1);
+        assert_eq!(sim.get_pc(), earlier + 2);
+        assert_eq!(sim.step_back(1).expect("empty history"), 0);
+    }
+
+    #[test]
+    fn step_back_restores_memory_and_cycles() {
+        let mut sim = arm_simulator();
+        sim.set_history_depth(1);
+        let cycles = sim.machine().total_cycles;
+        let cpu_cycles = sim.machine().cpu_cycles;
+        sim.step_single().expect("recorded step");
+        // Stands in for a store the stepped instruction made.
+        sim.machine().bus.write_u32(0x2000_0000, 0xDEAD_BEEF).unwrap();
+        assert_ne!(sim.machine().total_cycles, cycles);
+        assert_ne!(sim.machine().cpu_cycles, cpu_cycles);
+
+        assert_eq!(sim.step_back(1).expect("step back"), 1);
+        assert_eq!(sim.machine().bus.read_u32(0x2000_0000).unwrap(), 0);
+        assert_eq!(sim.machine().total_cycles, cycles);
+        assert_eq!(sim.machine().cpu_cycles, cpu_cycles);
+    }
+
+    #[test]
+    fn watched_odr_store_is_reported() {
+        const GPIOA_ODR: u32 = 0x4001_080C;
+
+        let mut bus = SystemBus::new();
+        let (mut cpu, _) = configure_cortex_m(&mut bus);
+        bus.write_u16(0, 0x4801).unwrap(); // ldr r0, [pc, #4]
+        bus.write_u16(2, 0x2120).unwrap(); // movs r1, #0x20
+        bus.write_u16(4, 0x6001).unwrap(); // str r1, [r0]
+        bus.write_u16(6, 0xBF00).unwrap(); // nop
+        bus.write_u32(8, GPIOA_ODR).unwrap();
+        cpu.set_pc(0);
+        let mut sim = wrap_test_machine(cpu, bus, Arch::Arm);
+
+        sim.watch_memory(GPIOA_ODR, 4);
+        sim.step(2).expect("load address and value");
+        assert!(sim.drain_memory_writes().is_empty());
+
+        sim.step(1).expect("store to ODR");
+        let writes = sim.drain_memory_writes();
+        assert_eq!(writes.len(), 1, "only the changed byte is reported");
+        assert_eq!((writes[0].addr, writes[0].value), (GPIOA_ODR, 0x20));
+        assert!(sim.drain_memory_writes().is_empty(), "drained");
+    }
+
+    #[test]
+    fn run_stops_at_added_breakpoint() {
+        use crate::debug::WasmStopReason;
+        use labwired_core::StopReason;
+
+        let mut sim = arm_simulator();
+        sim.add_breakpoint(8);
+        let reason = sim.run_until_stop(100).expect("run");
+        assert_eq!(reason, WasmStopReason::from(StopReason::Breakpoint(8)));
+        assert_eq!(sim.get_pc(), 8);
+
+        sim.remove_breakpoint(8);
+        let reason = sim.run_until_stop(4).expect("run");
+        assert_eq!(reason, WasmStopReason::from(StopReason::MaxStepsReached));
+        assert_eq!(sim.get_pc(), 16);
+    }
+
     #[test]
     fn step_batch_profile_schema_is_exact() {
         let value = serde_json::to_value(WasmStepBatchProfile {
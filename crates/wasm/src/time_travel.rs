@@ -0,0 +1,150 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+// SPDX-License-Identifier: MIT
+
+//! WasmSimulator time travel: whole-machine `snapshot` / `restore` for JS and
+//! an optional ring of pre-step snapshots behind `step_back`. A second
+//! #[wasm_bindgen] impl block, split out of lib.rs.
+//!
+//! `snapshot` / `restore` carry `Machine::snapshot` — CPU registers and
+//! peripheral state only. `step_back` entries also keep the flash, RAM and
+//! extra memory windows and the cycle counters, so a rewound store is undone.
+
+use crate::*;
+use labwired_core::snapshot::MachineSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Bounded history of the states before each recorded step. `depth == 0`
+/// (the default) disables recording, so plain stepping pays nothing.
+#[derive(Default)]
+pub(crate) struct SnapshotHistory {
+    depth: usize,
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// One `step_back` target: the machine snapshot plus the memory contents
+/// and cycle counters it leaves out.
+struct HistoryEntry {
+    state: MachineSnapshot,
+    /// Flash, RAM, then each `extra_mem` window, in bus order.
+    memories: Vec<Vec<u8>>,
+    total_cycles: u64,
+    /// Drives the peripheral tick cadence, so it rewinds with `total_cycles`.
+    cpu_cycles: u64,
+}
+
+/// The linear memory windows a history entry captures, in capture order.
+fn memory_windows(bus: &mut SystemBus) -> impl Iterator<Item = &mut LinearMemory> {
+    [&mut bus.flash, &mut bus.ram]
+        .into_iter()
+        .chain(bus.extra_mem.iter_mut())
+}
+
+impl SnapshotHistory {
+    fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.entries.len() > depth {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// 64-bit fields (pending-exception masks, cycle counters) become BigInt so
+/// they survive the trip through JS unrounded; BTreeMaps become objects.
+fn js_serializer() -> serde_wasm_bindgen::Serializer {
+    serde_wasm_bindgen::Serializer::new()
+        .serialize_large_number_types_as_bigints(true)
+        .serialize_maps_as_objects(true)
+}
+
+impl WasmSimulator {
+    /// Push the current state onto the history ring when recording is on.
+    /// Called before every stepping entry point advances the machine.
+    pub(crate) fn record_history(&mut self) {
+        if self.history.depth == 0 {
+            return;
+        }
+        let machine = self.machine();
+        let entry = HistoryEntry {
+            state: machine.snapshot(),
+            memories: memory_windows(&mut machine.bus)
+                .map(|m| m.data.clone())
+                .collect(),
+            total_cycles: machine.total_cycles,
+            cpu_cycles: machine.cpu_cycles,
+        };
+        if self.history.entries.len() == self.history.depth {
+            self.history.entries.pop_front();
+        }
+        self.history.entries.push_back(entry);
+    }
+
+    /// Undo up to `n` recorded steps; returns how many were undone.
+    pub(crate) fn rewind(&mut self, n: usize) -> Result<usize, String> {
+        let n = n.min(self.history.entries.len());
+        let keep = self.history.entries.len() - n;
+        let Some(target) = self.history.entries.drain(keep..).next() else {
+            return Ok(0);
+        };
+        let machine = self.machine();
+        machine
+            .apply_snapshot(target.state)
+            .map_err(|e| e.to_string())?;
+        for (window, bytes) in memory_windows(&mut machine.bus).zip(&target.memories) {
+            window.data.clone_from(bytes);
+        }
+        machine.total_cycles = target.total_cycles;
+        machine.cpu_cycles = target.cpu_cycles;
+        Ok(n)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmSimulator {
+    /// Capture CPU and peripheral state as a plain JS object that `restore`
+    /// accepts back. Treat it as opaque; 64-bit fields are BigInts.
+    #[wasm_bindgen]
+    pub fn snapshot(&mut self) -> Result<JsValue, JsValue> {
+        self.machine()
+            .snapshot()
+            .serialize(&js_serializer())
+            .map_err(|e| JsValue::from_str(&format!("Snapshot Error: {}", e)))
+    }
+
+    /// Restore a state captured by `snapshot`. Clears the `step_back`
+    /// history, whose entries no longer lead to the restored state.
+    #[wasm_bindgen]
+    pub fn restore(&mut self, state: JsValue) -> Result<(), JsValue> {
+        let snapshot = MachineSnapshot::deserialize(serde_wasm_bindgen::Deserializer::from(state))
+            .map_err(|e| JsValue::from_str(&format!("Restore Error: {}", e)))?;
+        self.machine()
+            .apply_snapshot(snapshot)
+            .map_err(|e| JsValue::from_str(&format!("Restore Error: {}", e)))?;
+        self.history.entries.clear();
+        Ok(())
+    }
+
+    /// Keep the last `depth` pre-step states for `step_back`; 0 turns
+    /// recording off and drops the history. Each `step` cycle and each
+    /// `step_single`, `step_batch` or `run` call records one entry;
+    /// `step_with_esp32_aids` does not record.
+    #[wasm_bindgen]
+    pub fn set_history_depth(&mut self, depth: u32) {
+        self.history.set_depth(depth as usize);
+    }
+
+    /// Number of recorded states available to `step_back`.
+    #[wasm_bindgen]
+    pub fn history_len(&self) -> u32 {
+        self.history.entries.len() as u32
+    }
+
+    /// Rewind `n` recorded steps (fewer if the history is shorter) and return
+    /// how many were undone.
+    #[wasm_bindgen]
+    pub fn step_back(&mut self, n: u32) -> Result<u32, JsValue> {
+        self.rewind(n as usize)
+            .map(|undone| undone as u32)
+            .map_err(|e| JsValue::from_str(&format!("Step Back Error: {}", e)))
+    }
+}
@@ -0,0 +1,107 @@
+// LabWired - Firmware Simulation Platform
+// Copyright (C) 2026 Andrii Shylenko
+// SPDX-License-Identifier: MIT
+
+//! WasmSimulator memory watches: the UI registers addresses (an LED's ODR, a
+//! status flag) and drains the writes that changed them after each `step`,
+//! instead of polling. Built on the bus `on_memory_write` observer hook. A
+//! second #[wasm_bindgen] impl block, split out of lib.rs.
+
+use crate::*;
+use labwired_core::SimulationObserver;
+use std::collections::BTreeSet;
+
+/// Events kept between drains; the oldest are dropped past this so a UI that
+/// stops draining cannot grow the heap without bound.
+const MAX_PENDING_WRITES: usize = 4096;
+
+/// One watched byte whose value changed.
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MemoryWriteEvent {
+    pub addr: u32,
+    pub value: u8,
+}
+
+#[derive(Debug, Default)]
+struct WatchState {
+    addrs: BTreeSet<u64>,
+    writes: VecDeque<MemoryWriteEvent>,
+}
+
+/// Bus observer filtering `on_memory_write` down to the watched bytes.
+/// Stores that leave a byte unchanged are not reported.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryWatchObserver {
+    state: Mutex<WatchState>,
+}
+
+impl SimulationObserver for MemoryWatchObserver {
+    fn on_memory_write(&self, addr: u64, old: u8, new: u8) {
+        if old == new {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if !state.addrs.contains(&addr) {
+            return;
+        }
+        if state.writes.len() == MAX_PENDING_WRITES {
+            state.writes.pop_front();
+        }
+        state.writes.push_back(MemoryWriteEvent {
+            addr: addr as u32,
+            value: new,
+        });
+    }
+}
+
+impl WasmSimulator {
+    /// The watch observer, attached to the bus on first use so unwatched
+    /// simulations keep an observer-free write path.
+    fn memory_watch(&mut self) -> Arc<MemoryWatchObserver> {
+        if let Some(watch) = &self.memory_watch {
+            return Arc::clone(watch);
+        }
+        let watch = Arc::new(MemoryWatchObserver::default());
+        self.machine().bus.observers.push(watch.clone());
+        self.memory_watch = Some(Arc::clone(&watch));
+        watch
+    }
+
+    pub(crate) fn drain_memory_writes(&mut self) -> Vec<MemoryWriteEvent> {
+        match &self.memory_watch {
+            Some(watch) => watch.state.lock().unwrap().writes.drain(..).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl WasmSimulator {
+    /// Watch the `len` bytes starting at `addr` (e.g. `watch_memory(odr, 4)`
+    /// for a 32-bit register).
+    #[wasm_bindgen]
+    pub fn watch_memory(&mut self, addr: u32, len: u32) {
+        let watch = self.memory_watch();
+        let mut state = watch.state.lock().unwrap();
+        state
+            .addrs
+            .extend((0..u64::from(len)).map(|i| u64::from(addr) + i));
+    }
+
+    #[wasm_bindgen]
+    pub fn unwatch_memory(&mut self, addr: u32, len: u32) {
+        if let Some(watch) = &self.memory_watch {
+            let mut state = watch.state.lock().unwrap();
+            for i in 0..u64::from(len) {
+                state.addrs.remove(&(u64::from(addr) + i));
+            }
+        }
+    }
+
+    /// Writes that changed a watched byte since the last call, oldest first,
+    /// as `[{ addr, value }]` with one entry per byte.
+    #[wasm_bindgen]
+    pub fn take_memory_writes(&mut self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.drain_memory_writes()).unwrap_or(JsValue::NULL)
+    }
+}